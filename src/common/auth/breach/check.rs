@@ -0,0 +1,191 @@
+use std::{collections::HashSet, str::FromStr};
+
+use sha1::{Digest, Sha1};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::common::{auth::password::Password, fallible::Fallible};
+
+// k-匿名性を保つため、ハッシュ値の先頭5文字のみを問い合わせ、残りは手元で照合する
+const PREFIX_LENGTH: usize = 5;
+
+pub(crate) trait PasswordBreachChecker {
+    async fn is_breached(&self, password: &Password) -> Fallible<bool, CheckPasswordBreachError> {
+        let digest = PasswordDigest::of(password);
+
+        match self.fetch_range(digest.prefix()).await {
+            Ok(range) => Ok(range.contains(digest.suffix())),
+            Err(e) => match self.failure_policy() {
+                BreachCheckFailurePolicy::FailOpen => {
+                    warn!(error = %e, "漏洩パスワードの確認に失敗したため、漏洩していないとみなしました。");
+                    Ok(false)
+                },
+                BreachCheckFailurePolicy::FailClosed => Err(e),
+            },
+        }
+    }
+
+    async fn fetch_range(&self, prefix: &str) -> Fallible<BreachRange, CheckPasswordBreachError>;
+
+    fn failure_policy(&self) -> BreachCheckFailurePolicy;
+}
+
+#[derive(Debug, Error)]
+pub enum CheckPasswordBreachError {
+    #[error("漏洩パスワードの範囲の取得に失敗しました")]
+    FetchRangeFailed(#[source] anyhow::Error),
+}
+
+// 漏洩パスワードの確認に失敗した場合の扱い
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BreachCheckFailurePolicy {
+    // 漏洩していないとみなし、アカウント作成を妨げない
+    FailOpen,
+    // 確認できるまでパスワードを受け付けない
+    FailClosed,
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("有効な漏洩パスワードの確認失敗時の方針ではありません")]
+pub struct ParseBreachCheckFailurePolicyError;
+
+impl FromStr for BreachCheckFailurePolicy {
+    type Err = ParseBreachCheckFailurePolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(BreachCheckFailurePolicy::FailOpen),
+            "closed" => Ok(BreachCheckFailurePolicy::FailClosed),
+            _ => Err(ParseBreachCheckFailurePolicyError)
+        }
+    }
+}
+
+// パスワードのSHA-1ハッシュ値(大文字の16進数)
+pub struct PasswordDigest(String);
+
+impl PasswordDigest {
+    pub fn of(password: &Password) -> Self {
+        Self(format!("{:X}", Sha1::digest(password.value().as_bytes())))
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.0[..PREFIX_LENGTH]
+    }
+
+    pub fn suffix(&self) -> &str {
+        &self.0[PREFIX_LENGTH..]
+    }
+}
+
+// 同じ先頭5文字を持つ漏洩パスワードのハッシュ値の残りの部分
+// `{suffix}:{count}`の形式の行からなり、水増しのための出現回数0の行は含めない
+pub struct BreachRange(HashSet<String>);
+
+impl BreachRange {
+    pub fn empty() -> Self {
+        Self(HashSet::new())
+    }
+
+    pub fn parse(range: &str) -> Self {
+        let suffixes = range
+            .lines()
+            .filter_map(|line| line.trim().split_once(':'))
+            .filter(|(_, count)| u64::from_str(count).is_ok_and(|count| count > 0))
+            .map(|(suffix, _)| suffix.to_ascii_uppercase())
+            .collect();
+
+        Self(suffixes)
+    }
+
+    pub fn contains(&self, suffix: &str) -> bool {
+        self.0.contains(suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::LazyLock};
+
+    use thiserror::Error;
+
+    use crate::common::{auth::password::Password, fallible::Fallible};
+
+    use super::{BreachCheckFailurePolicy, BreachRange, CheckPasswordBreachError, PasswordBreachChecker, PasswordDigest};
+
+    static BREACHED: LazyLock<Password> = LazyLock::new(|| Password::new_unchecked("correct horse battery staple"));
+    static NOT_BREACHED: LazyLock<Password> = LazyLock::new(|| Password::new_unchecked("SCBGpks6FfnCb6R"));
+
+    struct MockPasswordBreachChecker {
+        available: bool,
+        failure_policy: BreachCheckFailurePolicy,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("疑似エラー")]
+    struct MockError;
+
+    impl PasswordBreachChecker for MockPasswordBreachChecker {
+        async fn fetch_range(&self, prefix: &str) -> Fallible<BreachRange, CheckPasswordBreachError> {
+            if !self.available {
+                return Err(CheckPasswordBreachError::FetchRangeFailed(MockError.into()));
+            }
+
+            let digest = PasswordDigest::of(&BREACHED);
+
+            if prefix == digest.prefix() {
+                Ok(BreachRange::parse(&format!("{}:3\r\n0018A45C4D1DEF81644B54AB7F969B88D65:0", digest.suffix())))
+            } else {
+                Ok(BreachRange::empty())
+            }
+        }
+
+        fn failure_policy(&self) -> BreachCheckFailurePolicy {
+            self.failure_policy
+        }
+    }
+
+    fn checker(available: bool, failure_policy: BreachCheckFailurePolicy) -> MockPasswordBreachChecker {
+        MockPasswordBreachChecker { available, failure_policy }
+    }
+
+    #[test]
+    fn digest() {
+        let digest = PasswordDigest::of(&Password::new_unchecked("password"));
+        assert_eq!((digest.prefix(), digest.suffix()), ("5BAA6", "1E4C9B93F3F0682250B6CF8331B7EE68FD8"));
+    }
+
+    #[test]
+    fn padding_excluded() {
+        let range = BreachRange::parse("0018A45C4D1DEF81644B54AB7F969B88D65:0\r\n00D4F6E8FA6EECAD2A3AA415EEC418D38EC:2");
+        assert!(!range.contains("0018A45C4D1DEF81644B54AB7F969B88D65"));
+        assert!(range.contains("00D4F6E8FA6EECAD2A3AA415EEC418D38EC"));
+    }
+
+    #[tokio::test]
+    async fn breached() {
+        assert!(checker(true, BreachCheckFailurePolicy::FailClosed).is_breached(&BREACHED).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn not_breached() {
+        assert!(!checker(true, BreachCheckFailurePolicy::FailClosed).is_breached(&NOT_BREACHED).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn fail_open() {
+        assert!(!checker(false, BreachCheckFailurePolicy::FailOpen).is_breached(&BREACHED).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn fail_closed() {
+        assert!(checker(false, BreachCheckFailurePolicy::FailClosed).is_breached(&BREACHED).await.is_err());
+    }
+
+    #[test]
+    fn parse_failure_policy() {
+        assert_eq!(BreachCheckFailurePolicy::from_str("open"), Ok(BreachCheckFailurePolicy::FailOpen));
+        assert_eq!(BreachCheckFailurePolicy::from_str("closed"), Ok(BreachCheckFailurePolicy::FailClosed));
+        assert!(BreachCheckFailurePolicy::from_str("ajar").is_err());
+    }
+}
\ No newline at end of file
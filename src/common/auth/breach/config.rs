@@ -0,0 +1,153 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use redis::cmd;
+use reqwest::Client;
+use thiserror::Error;
+
+use crate::{common::fallible::Fallible, helper::redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}}};
+
+use super::{check::{BreachCheckFailurePolicy, BreachRange, CheckPasswordBreachError, ParseBreachCheckFailurePolicyError, PasswordBreachChecker}, hibp::HibpRangeSource, offline::OfflineRangeDatabase};
+
+const BREACH_RANGE_CACHE_NAMESPACE: Namespace = Namespace::of("pwnrg");
+
+// HIBPの範囲は頻繁には更新されないため、同じ範囲への問い合わせを1日の間まとめる
+const BREACH_RANGE_CACHE_EXPIRATION_SECONDS: u32 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BreachRangeProvider {
+    Hibp,
+    Offline,
+    Disabled,
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("有効な漏洩パスワードの提供元ではありません")]
+pub struct ParseBreachRangeProviderError;
+
+impl FromStr for BreachRangeProvider {
+    type Err = ParseBreachRangeProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hibp" => Ok(BreachRangeProvider::Hibp),
+            "offline" => Ok(BreachRangeProvider::Offline),
+            "disabled" => Ok(BreachRangeProvider::Disabled),
+            _ => Err(ParseBreachRangeProviderError)
+        }
+    }
+}
+
+pub enum BreachRangeSource {
+    Hibp(HibpRangeSource),
+    Offline(OfflineRangeDatabase),
+    Disabled,
+}
+
+pub struct ConfiguredPasswordBreachChecker {
+    source: BreachRangeSource,
+    cache: Arc<Pool>,
+    failure_policy: BreachCheckFailurePolicy,
+}
+
+impl ConfiguredPasswordBreachChecker {
+    pub fn new(source: BreachRangeSource, cache: Arc<Pool>, failure_policy: BreachCheckFailurePolicy) -> Self {
+        Self { source, cache, failure_policy }
+    }
+
+    // PASSWORD_BREACH_PROVIDER: hibp | offline | disabled
+    // PASSWORD_BREACH_OFFLINE_PATH: 範囲のファイルを格納したディレクトリ(offlineのみ)
+    // PASSWORD_BREACH_FAILURE_POLICY: open | closed(任意、既定はopen)
+    pub fn from_env(client: Arc<Client>, cache: Arc<Pool>) -> Result<Self, LoadPasswordBreachCheckerConfigError> {
+        fn var(key: &'static str) -> Result<String, LoadPasswordBreachCheckerConfigError> {
+            dotenvy::var(key).map_err(|_| LoadPasswordBreachCheckerConfigError::MissingVariable(key))
+        }
+
+        let provider = BreachRangeProvider::from_str(&var("PASSWORD_BREACH_PROVIDER")?)
+            .map_err(LoadPasswordBreachCheckerConfigError::InvalidProvider)?;
+
+        let source = match provider {
+            BreachRangeProvider::Hibp => BreachRangeSource::Hibp(HibpRangeSource::new(client)),
+            BreachRangeProvider::Offline => BreachRangeSource::Offline(OfflineRangeDatabase::new(PathBuf::from(var("PASSWORD_BREACH_OFFLINE_PATH")?))),
+            BreachRangeProvider::Disabled => BreachRangeSource::Disabled,
+        };
+
+        let failure_policy = match dotenvy::var("PASSWORD_BREACH_FAILURE_POLICY") {
+            Ok(policy) => BreachCheckFailurePolicy::from_str(&policy).map_err(LoadPasswordBreachCheckerConfigError::InvalidFailurePolicy)?,
+            Err(_) => BreachCheckFailurePolicy::FailOpen,
+        };
+
+        Ok(Self::new(source, cache, failure_policy))
+    }
+
+    async fn fetch_cached_range(&self, source: &HibpRangeSource, prefix: &str) -> Fallible<BreachRange, CheckPasswordBreachError> {
+        let key = format!("{}{}{}", BREACH_RANGE_CACHE_NAMESPACE, NAMESPACE_SEPARATOR, prefix);
+
+        let mut conn = conn(&self.cache, |e| CheckPasswordBreachError::FetchRangeFailed(e.into())).await?;
+
+        let cached = cmd("GET")
+            .arg(&key)
+            .query_async::<Option<String>>(&mut *conn)
+            .await
+            .map_err(|e| CheckPasswordBreachError::FetchRangeFailed(e.into()))?;
+
+        if let Some(range) = cached {
+            return Ok(BreachRange::parse(&range));
+        }
+
+        let range = source.fetch_range(prefix).await?;
+
+        // 失敗しても続行
+        let _ = cmd("SET")
+            .arg(&key)
+            .arg(&range)
+            .arg("EX")
+            .arg(BREACH_RANGE_CACHE_EXPIRATION_SECONDS)
+            .exec_async(&mut *conn)
+            .await;
+
+        Ok(BreachRange::parse(&range))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadPasswordBreachCheckerConfigError {
+    #[error("環境変数{0}が設定されていません")]
+    MissingVariable(&'static str),
+    #[error("漏洩パスワードの提供元の解析に失敗しました")]
+    InvalidProvider(#[source] ParseBreachRangeProviderError),
+    #[error("漏洩パスワードの確認失敗時の方針の解析に失敗しました")]
+    InvalidFailurePolicy(#[source] ParseBreachCheckFailurePolicyError),
+}
+
+impl PasswordBreachChecker for ConfiguredPasswordBreachChecker {
+    async fn fetch_range(&self, prefix: &str) -> Fallible<BreachRange, CheckPasswordBreachError> {
+        match &self.source {
+            BreachRangeSource::Hibp(source) => self.fetch_cached_range(source, prefix).await,
+            BreachRangeSource::Offline(database) => database.fetch_range(prefix).await,
+            BreachRangeSource::Disabled => Ok(BreachRange::empty()),
+        }
+    }
+
+    fn failure_policy(&self) -> BreachCheckFailurePolicy {
+        self.failure_policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{BreachRangeProvider, ParseBreachRangeProviderError};
+
+    #[test]
+    fn parse_provider() {
+        assert_eq!(BreachRangeProvider::from_str("hibp"), Ok(BreachRangeProvider::Hibp));
+        assert_eq!(BreachRangeProvider::from_str("offline"), Ok(BreachRangeProvider::Offline));
+        assert_eq!(BreachRangeProvider::from_str("disabled"), Ok(BreachRangeProvider::Disabled));
+    }
+
+    #[test]
+    fn parse_unknown_provider() {
+        assert_eq!(BreachRangeProvider::from_str("pwned"), Err(ParseBreachRangeProviderError));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use reqwest::Client;
+
+use crate::common::fallible::Fallible;
+
+use super::check::CheckPasswordBreachError;
+
+// https://haveibeenpwned.com/API/v3#SearchingPwnedPasswordsByRange
+const HIBP_RANGE_URL: &str = "https://api.pwnedpasswords.com/range/";
+
+pub struct HibpRangeSource {
+    client: Arc<Client>,
+}
+
+impl HibpRangeSource {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    // 応答の大きさから問い合わせたパスワードを推測されないよう、水増しを要求する
+    pub async fn fetch_range(&self, prefix: &str) -> Fallible<String, CheckPasswordBreachError> {
+        self.client.get(&format!("{}{}", HIBP_RANGE_URL, prefix))
+            .header("Add-Padding", "true")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| CheckPasswordBreachError::FetchRangeFailed(e.into()))?
+            .text()
+            .await
+            .map_err(|e| CheckPasswordBreachError::FetchRangeFailed(e.into()))
+    }
+}
\ No newline at end of file
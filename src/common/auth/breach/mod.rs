@@ -0,0 +1,4 @@
+pub mod check;
+pub mod config;
+pub mod hibp;
+pub mod offline;
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::{fs, path::PathBuf};
+
+use tokio::task;
+
+use crate::common::fallible::Fallible;
+
+use super::check::{BreachRange, CheckPasswordBreachError};
+
+// 外部に問い合わせずに済むよう、HIBPの範囲をあらかじめ手元に複製したもの
+// PwnedPasswordsDownloaderの出力と同じく、`{prefix}.txt`というファイルに範囲ごとに格納する
+pub struct OfflineRangeDatabase {
+    directory: PathBuf,
+}
+
+impl OfflineRangeDatabase {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    pub async fn fetch_range(&self, prefix: &str) -> Fallible<BreachRange, CheckPasswordBreachError> {
+        let path = self.directory.join(format!("{}.txt", prefix));
+
+        // 複製が欠けている場合に漏洩していないと誤認しないよう、ファイルが無い場合も失敗とする
+        task::spawn_blocking(move || fs::read_to_string(path))
+            .await
+            .map_err(|e| CheckPasswordBreachError::FetchRangeFailed(e.into()))?
+            .map(|range| BreachRange::parse(&range))
+            .map_err(|e| CheckPasswordBreachError::FetchRangeFailed(e.into()))
+    }
+}
\ No newline at end of file
@@ -1,2 +1,4 @@
+pub mod breach;
 pub mod one_time_token;
-pub mod password;
\ No newline at end of file
+pub mod password;
+pub mod pepper;
\ No newline at end of file
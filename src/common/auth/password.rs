@@ -1,18 +1,20 @@
 use std::{collections::HashSet, fmt::{self, Display}, fs::File, io::{BufRead, BufReader}, str::FromStr, sync::LazyLock};
 
-use argon2::{password_hash::{self, PasswordHasher, SaltString}, Algorithm, Argon2, ParamsBuilder, PasswordVerifier, Version};
-use base64::{engine::general_purpose, Engine};
+use argon2::{password_hash::{self, PasswordHasher, SaltString}, Algorithm, Argon2, KeyId, Params, ParamsBuilder, PasswordVerifier, Version};
 use rand::rngs::OsRng;
 use regex::Regex;
 use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
 use serde::{de::{self}, Deserialize, Deserializer};
 use thiserror::Error;
 
-static ARGON2_CONTEXT: LazyLock<Argon2> = LazyLock::new(|| {
-    const MEMORY: u32 = 19 * 1024;
-    const ITERATIONS: u32 = 2;
-    const DEGREE_OF_PARALLELISM: u32 = 1;
+use super::pepper::{Pepper, Peppers};
 
+// パラメータを引き上げた場合は、古いパラメータのハッシュをサインイン時に更新する
+const MEMORY: u32 = 19 * 1024;
+const ITERATIONS: u32 = 2;
+const DEGREE_OF_PARALLELISM: u32 = 1;
+
+static PASSWORD_HASHER: LazyLock<PasswordHasherContext> = LazyLock::new(|| {
     let params = ParamsBuilder::new()
         .m_cost(MEMORY)
         .t_cost(ITERATIONS)
@@ -20,23 +22,116 @@ static ARGON2_CONTEXT: LazyLock<Argon2> = LazyLock::new(|| {
         .build()
         .unwrap();
 
-    Argon2::new_with_secret(&*PEPPER, Algorithm::Argon2id, Version::V0x13, params).unwrap()
+    let peppers = Peppers::from_env().unwrap();
+    println!("ペッパーを読み込みました。");
+
+    PasswordHasherContext::new(params, peppers)
 });
 
+// 現在のパラメータとペッパーでハッシュ化し、古いパラメータやペッパーによるハッシュも検証する
+pub struct PasswordHasherContext {
+    params: Params,
+    peppers: Peppers,
+}
+
+impl PasswordHasherContext {
+    pub fn new(params: Params, peppers: Peppers) -> Self {
+        Self { params, peppers }
+    }
+
+    pub fn hash(&self, password: &Password) -> PasswordHash {
+        let salt = SaltString::generate(&mut OsRng);
+
+        let phc_format_hash = self.argon2(self.peppers.current(), self.current_params())
+            .hash_password(password.value().as_bytes(), &salt)
+            .unwrap()
+            .to_string();
+
+        PasswordHash(phc_format_hash)
+    }
+
+    pub fn verify(&self, hash: &PasswordHash, password: &Password) -> PasswordVerification {
+        // PHCフォーマットを満たしたもののみがインスタンス化されるため`unwrap`は安全
+        let parsed_hash = password_hash::PasswordHash::new(&hash.0).unwrap();
+        let params = Params::try_from(&parsed_hash).ok();
+        let keyid = params.as_ref().map_or(&[][..], Params::keyid);
+
+        // 未知のペッパーの場合も処理時間を揃えるため、現在のペッパーで検証した上で不一致とする
+        let pepper = self.peppers.find(keyid);
+        let matched = self.argon2(pepper.unwrap_or(self.peppers.current()), self.params.clone())
+            .verify_password(password.value().as_bytes(), &parsed_hash)
+            .is_ok();
+
+        match (matched && pepper.is_some(), self.is_outdated(&parsed_hash, params.as_ref())) {
+            (false, _) => PasswordVerification::Mismatched,
+            (true, false) => PasswordVerification::Matched,
+            (true, true) => PasswordVerification::MatchedButOutdated,
+        }
+    }
+
+    fn is_outdated(&self, parsed_hash: &password_hash::PasswordHash, params: Option<&Params>) -> bool {
+        parsed_hash.algorithm != Algorithm::Argon2id.ident()
+            || parsed_hash.version != Some(Version::V0x13.into())
+            || params.is_none_or(|params| {
+                params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+                    || params.keyid() != self.peppers.current().id()
+            })
+    }
+
+    fn current_params(&self) -> Params {
+        let mut builder = ParamsBuilder::new();
+
+        builder
+            .m_cost(self.params.m_cost())
+            .t_cost(self.params.t_cost())
+            .p_cost(self.params.p_cost());
+
+        if !self.peppers.current().id().is_empty() {
+            // IDの長さは`Pepper`の生成時に検証済み
+            builder.keyid(KeyId::new(self.peppers.current().id()).unwrap());
+        }
+
+        builder.build().unwrap()
+    }
+
+    // 検証時のパラメータはハッシュから読み取られるため、ペッパーのみが意味を持つ
+    fn argon2<'a>(&self, pepper: &'a Pepper, params: Params) -> Argon2<'a> {
+        // ペッパーの長さは固定のため`unwrap`は安全
+        Argon2::new_with_secret(pepper.secret(), Algorithm::Argon2id, Version::V0x13, params).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PasswordVerification {
+    Mismatched,
+    Matched,
+    // 一致したが、古いパラメータかペッパーでハッシュ化されているため再ハッシュ化が望ましい
+    MatchedButOutdated,
+}
+
+impl PasswordVerification {
+    pub fn is_matched(&self) -> bool {
+        !matches!(self, Self::Mismatched)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Password(String);
 
 impl Password {
+    #[cfg(debug_assertions)]
+    pub fn new_unchecked(s: &str) -> Self {
+        Self(String::from(s))
+    }
+
     pub fn value(&self) -> &String {
         &self.0
     }
 
     pub fn hashed(&self) -> PasswordHash {
-        let salt = SaltString::generate(&mut OsRng);
-
-        let phc_format_hash = ARGON2_CONTEXT.hash_password(self.value().as_bytes(), &salt).unwrap().to_string();
-    
-        PasswordHash(phc_format_hash)
+        PASSWORD_HASHER.hash(self)
     }
 }
 
@@ -89,11 +184,8 @@ impl PasswordHash {
         &self.0
     }
 
-    pub fn verify(&self, password: &Password) -> bool {
-        // PHCフォーマットを満たしたもののみがインスタンス化されるため`unwrap`は安全
-        let parsed_hash = password_hash::PasswordHash::new(&self.0).unwrap();
-
-        ARGON2_CONTEXT.verify_password(password.value().as_bytes(), &parsed_hash).is_ok()
+    pub fn verify(&self, password: &Password) -> PasswordVerification {
+        PASSWORD_HASHER.verify(self, password)
     }
 }
 
@@ -131,16 +223,6 @@ impl FromCqlVal<Option<CqlValue>> for PasswordHash {
     }
 }
 
-// 以下はリファクタリングが必要
-static PEPPER: LazyLock<[u8; 32]> = LazyLock::new(load_pepper);
-
-fn load_pepper() -> [u8; 32] {
-    let base64 = dotenvy::var("PEPPER").unwrap();
-    let decoded = general_purpose::STANDARD.decode(base64).unwrap();
-    println!("ペッパーを読み込みました。");
-    decoded.as_slice().try_into().unwrap()
-}
-
 const UNSAFE_PASSWORDS_FILE_PATH: &str = "xato-net-10-million-passwords-filtered-min-10-chars.txt";
 static UNSAFE_PASSWORDS: LazyLock<HashSet<String>> = LazyLock::new(|| load_unsafe_passwords(UNSAFE_PASSWORDS_FILE_PATH).unwrap());
 
@@ -175,7 +257,18 @@ impl<'de> Deserialize<'de> for Password {
 mod tests {
     use std::str::FromStr;
 
-    use super::{ParsePasswordError, Password, PasswordHash, MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
+    use argon2::ParamsBuilder;
+
+    use crate::common::auth::pepper::{Pepper, Peppers};
+
+    use super::{ParsePasswordError, Password, PasswordHash, PasswordHasherContext, PasswordVerification, MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
+
+    // テストを高速に行うため、最小限のパラメータを用いる
+    fn context(t_cost: u32, current: (&[u8], u8), previous: &[(&[u8], u8)]) -> PasswordHasherContext {
+        let params = ParamsBuilder::new().m_cost(8).t_cost(t_cost).p_cost(1).build().unwrap();
+        let pepper = |(id, secret): (&[u8], u8)| Pepper::new(id, [secret; 32]).unwrap();
+        PasswordHasherContext::new(params, Peppers::new(pepper(current), previous.iter().copied().map(pepper).collect()))
+    }
 
     #[test]
     fn password_too_short() {
@@ -223,4 +316,46 @@ mod tests {
         let hash = "SCBGpks6FfnCb6R";
         assert!(PasswordHash::from_str(hash).is_err());
     }
+
+    #[test]
+    fn matched() {
+        let context = context(1, (b"new", 1), &[]);
+        let password = Password::new_unchecked("SCBGpks6FfnCb6R");
+        assert_eq!(context.verify(&context.hash(&password), &password), PasswordVerification::Matched);
+    }
+
+    #[test]
+    fn mismatched() {
+        let context = context(1, (b"new", 1), &[]);
+        let hash = context.hash(&Password::new_unchecked("SCBGpks6FfnCb6R"));
+        assert_eq!(context.verify(&hash, &Password::new_unchecked("vK,tOiHyLsehvnv")), PasswordVerification::Mismatched);
+    }
+
+    #[test]
+    fn keyid_embedded() {
+        let hash = context(1, (b"new", 1), &[]).hash(&Password::new_unchecked("SCBGpks6FfnCb6R"));
+        // `new`のB64表現
+        assert!(hash.value().contains(",keyid=bmV3$"), "{}", hash.value());
+    }
+
+    #[test]
+    fn outdated_params() {
+        let password = Password::new_unchecked("SCBGpks6FfnCb6R");
+        let hash = context(1, (b"new", 1), &[]).hash(&password);
+        assert_eq!(context(2, (b"new", 1), &[]).verify(&hash, &password), PasswordVerification::MatchedButOutdated);
+    }
+
+    #[test]
+    fn previous_pepper() {
+        let password = Password::new_unchecked("SCBGpks6FfnCb6R");
+        let hash = context(1, (b"", 0), &[]).hash(&password);
+        assert_eq!(context(1, (b"new", 1), &[(b"", 0)]).verify(&hash, &password), PasswordVerification::MatchedButOutdated);
+    }
+
+    #[test]
+    fn retired_pepper() {
+        let password = Password::new_unchecked("SCBGpks6FfnCb6R");
+        let hash = context(1, (b"old", 0), &[]).hash(&password);
+        assert_eq!(context(1, (b"new", 1), &[]).verify(&hash, &password), PasswordVerification::Mismatched);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::str::FromStr;
+
+use argon2::Params;
+use base64::{engine::general_purpose, Engine};
+use thiserror::Error;
+
+const PEPPER_LENGTH: usize = 32;
+
+// パスワードハッシュに混ぜる秘密鍵
+// PHC文字列の`keyid`でどのペッパーを用いたかを識別する(空の場合は`keyid`を付けない)
+#[derive(Clone)]
+pub struct Pepper {
+    id: Vec<u8>,
+    secret: [u8; PEPPER_LENGTH],
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ParsePepperError {
+    #[error("ペッパーのIDが長すぎます")]
+    IdTooLong,
+    #[error("ペッパーの形式を満たしませんでした")]
+    InvalidSecret,
+}
+
+impl Pepper {
+    pub fn new(id: &[u8], secret: [u8; PEPPER_LENGTH]) -> Result<Self, ParsePepperError> {
+        if id.len() > Params::MAX_KEYID_LEN {
+            return Err(ParsePepperError::IdTooLong);
+        }
+
+        Ok(Self { id: id.to_vec(), secret })
+    }
+
+    pub fn id(&self) -> &[u8] {
+        &self.id
+    }
+
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    fn decode(id: &str, base64: &str) -> Result<Self, ParsePepperError> {
+        let secret = general_purpose::STANDARD.decode(base64)
+            .ok()
+            .and_then(|decoded| <[u8; PEPPER_LENGTH]>::try_from(decoded.as_slice()).ok())
+            .ok_or(ParsePepperError::InvalidSecret)?;
+
+        Self::new(id.as_bytes(), secret)
+    }
+}
+
+// `{id}:{base64}`の形式
+impl FromStr for Pepper {
+    type Err = ParsePepperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, base64) = s.split_once(':').ok_or(ParsePepperError::InvalidSecret)?;
+        Self::decode(id, base64)
+    }
+}
+
+// ペッパーの更新中は、新しいペッパーでハッシュ化しつつ古いペッパーによるハッシュも検証できるようにする
+#[derive(Clone)]
+pub struct Peppers {
+    current: Pepper,
+    previous: Vec<Pepper>,
+}
+
+#[derive(Debug, Error)]
+pub enum LoadPeppersError {
+    #[error("環境変数{0}が設定されていません")]
+    MissingVariable(&'static str),
+    #[error("環境変数{0}のペッパーの解析に失敗しました")]
+    InvalidPepper(&'static str, #[source] ParsePepperError),
+}
+
+impl Peppers {
+    pub fn new(current: Pepper, previous: Vec<Pepper>) -> Self {
+        Self { current, previous }
+    }
+
+    // PEPPER: 現在のペッパー(32バイトのbase64)
+    // PEPPER_ID: 現在のペッパーのID(任意、8バイト以下)
+    // PREVIOUS_PEPPERS: 検証にのみ用いる古いペッパー(任意、`{id}:{base64}`のカンマ区切り)
+    //   IDを付けずに運用していた頃のペッパーは、IDを空にして`:{base64}`と記す
+    pub fn from_env() -> Result<Self, LoadPeppersError> {
+        let secret = dotenvy::var("PEPPER").map_err(|_| LoadPeppersError::MissingVariable("PEPPER"))?;
+        let id = dotenvy::var("PEPPER_ID").unwrap_or_default();
+
+        let current = Pepper::decode(&id, &secret).map_err(|e| LoadPeppersError::InvalidPepper("PEPPER", e))?;
+
+        let previous = dotenvy::var("PREVIOUS_PEPPERS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|pepper| !pepper.is_empty())
+            .map(|pepper| Pepper::from_str(pepper).map_err(|e| LoadPeppersError::InvalidPepper("PREVIOUS_PEPPERS", e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(current, previous))
+    }
+
+    pub fn current(&self) -> &Pepper {
+        &self.current
+    }
+
+    pub fn find(&self, id: &[u8]) -> Option<&Pepper> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .find(|pepper| pepper.id() == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{ParsePepperError, Pepper, Peppers};
+
+    const SECRET: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    #[test]
+    fn parse() {
+        let pepper = Pepper::from_str(&format!("2024:{}", SECRET)).unwrap();
+        assert_eq!(pepper.id(), b"2024");
+        assert_eq!(pepper.secret()[31], 31);
+    }
+
+    #[test]
+    fn parse_without_id() {
+        assert!(Pepper::from_str(&format!(":{}", SECRET)).unwrap().id().is_empty());
+    }
+
+    #[test]
+    fn id_too_long() {
+        assert!(matches!(Pepper::from_str(&format!("202401011:{}", SECRET)), Err(ParsePepperError::IdTooLong)));
+    }
+
+    #[test]
+    fn invalid_secret() {
+        assert!(matches!(Pepper::from_str("2024:AAEC"), Err(ParsePepperError::InvalidSecret)));
+        assert!(matches!(Pepper::from_str(SECRET), Err(ParsePepperError::InvalidSecret)));
+    }
+
+    #[test]
+    fn find() {
+        let peppers = Peppers::new(Pepper::new(b"new", [1; 32]).unwrap(), vec![Pepper::new(b"", [0; 32]).unwrap()]);
+        assert_eq!(peppers.find(b"new").map(Pepper::secret), Some([1; 32].as_slice()));
+        assert_eq!(peppers.find(b"").map(Pepper::secret), Some([0; 32].as_slice()));
+        assert!(peppers.find(b"old").is_none());
+    }
+}
\ No newline at end of file
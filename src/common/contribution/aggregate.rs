@@ -0,0 +1,257 @@
+use std::{collections::{hash_map::Entry, HashMap}, sync::Arc};
+
+use thiserror::Error;
+
+use crate::common::{consensus::{is_unstable_proposal, proposal::IsProposal, stability::Stability}, fallible::Fallible, profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, proposal_operation::ProposalOperation, relation::TagRelation}};
+
+use super::Contribution;
+
+pub type RelationKey = (NonTopTagId, NonTopTagId, TagRelation);
+
+// タグ関係の最終的な合意
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum FinalConsensus {
+    Accepted,
+    Rejected,
+}
+
+impl FinalConsensus {
+    // ステータスが未計算の関係と、投票中の提案はまだ合意に至っていない
+    pub fn of(is_status_calculated: bool, is_proposal: IsProposal, is_stable: Stability) -> Option<Self> {
+        if !is_status_calculated || is_unstable_proposal(is_proposal, is_stable) {
+            None
+        } else if is_stable == Stability::Stable {
+            Some(Self::Accepted)
+        } else {
+            Some(Self::Rejected)
+        }
+    }
+
+    // 高評価は採用と、低評価は不採用と一致したものとし、中評価は判定しない
+    pub fn agrees_with(&self, operation: ProposalOperation) -> Option<bool> {
+        match operation {
+            ProposalOperation::HighRated => Some(*self == Self::Accepted),
+            ProposalOperation::LowRated => Some(*self == Self::Rejected),
+            ProposalOperation::Rated | ProposalOperation::Proposed => None,
+        }
+    }
+}
+
+pub(crate) trait AggregateContributions {
+    // 全ての提案と評価を最終的な合意と照合し、アカウントごとの貢献を記録し直す
+    // 毎回全件から再計算するため、複数のインスタンスで同時に実行しても結果は変わらない
+    async fn aggregate_contributions(&self) -> Fallible<AggregationSummary, AggregateContributionsError> {
+        let mut contributions = HashMap::<AccountId, Contribution>::new();
+        let mut consensuses = HashMap::<RelationKey, Option<FinalConsensus>>::new();
+        let mut summary = AggregationSummary::default();
+
+        let mut cursor = ScanCursor::START;
+        loop {
+            let (proposals, next_cursor) = self.fetch_proposals(cursor).await?;
+
+            for (proposer_id, relation) in proposals {
+                let consensus = self.cached_final_consensus(&mut consensuses, relation).await?;
+
+                // 採用された提案が無いアカウントも、貢献が無いものとして記録し直す
+                let contribution = contributions.entry(proposer_id).or_default();
+                if consensus == Some(FinalConsensus::Accepted) {
+                    contribution.add_accepted_proposal();
+                }
+
+                summary.scanned += 1;
+            }
+
+            match next_cursor {
+                Some(next_cursor) => cursor = next_cursor,
+                None => break,
+            }
+        }
+
+        let mut cursor = ScanCursor::START;
+        loop {
+            let (ratings, next_cursor) = self.fetch_ratings(cursor).await?;
+
+            for (account_id, relation, operation) in ratings {
+                let consensus = self.cached_final_consensus(&mut consensuses, relation).await?;
+
+                let contribution = contributions.entry(account_id).or_default();
+                if let Some(agreed) = consensus.and_then(|c| c.agrees_with(operation)) {
+                    contribution.add_judged_rating(agreed);
+                }
+
+                summary.scanned += 1;
+            }
+
+            match next_cursor {
+                Some(next_cursor) => cursor = next_cursor,
+                None => break,
+            }
+        }
+
+        for (account_id, contribution) in contributions {
+            self.store_contribution(account_id, contribution).await?;
+            summary.stored += 1;
+        }
+
+        Ok(summary)
+    }
+
+    async fn cached_final_consensus(&self, consensuses: &mut HashMap<RelationKey, Option<FinalConsensus>>, relation: RelationKey) -> Fallible<Option<FinalConsensus>, AggregateContributionsError> {
+        match consensuses.entry(relation) {
+            Entry::Occupied(entry) => Ok(*entry.get()),
+            Entry::Vacant(entry) => {
+                let (subtag_id, supertag_id, relation) = relation;
+                let consensus = self.fetch_final_consensus(subtag_id, supertag_id, relation).await?;
+                Ok(*entry.insert(consensus))
+            },
+        }
+    }
+
+    // `cursor`から1ページ分の提案を取得し、続きがあれば次のカーソルを返す
+    async fn fetch_proposals(&self, cursor: ScanCursor) -> Fallible<(Vec<(AccountId, RelationKey)>, Option<ScanCursor>), AggregateContributionsError>;
+
+    // `cursor`から1ページ分の評価を取得し、続きがあれば次のカーソルを返す
+    async fn fetch_ratings(&self, cursor: ScanCursor) -> Fallible<(Vec<(AccountId, RelationKey, ProposalOperation)>, Option<ScanCursor>), AggregateContributionsError>;
+
+    // 関係がタグ階層に存在しない場合は`None`を返す
+    async fn fetch_final_consensus(&self, subtag_id: NonTopTagId, supertag_id: NonTopTagId, relation: TagRelation) -> Fallible<Option<FinalConsensus>, AggregateContributionsError>;
+
+    async fn store_contribution(&self, account_id: AccountId, contribution: Contribution) -> Fallible<(), AggregateContributionsError>;
+}
+
+#[derive(Debug, Error)]
+pub enum AggregateContributionsError {
+    #[error("提案の取得に失敗しました")]
+    FetchProposalsFailed(#[source] anyhow::Error),
+    #[error("評価の取得に失敗しました")]
+    FetchRatingsFailed(#[source] anyhow::Error),
+    #[error("最終的な合意の取得に失敗しました")]
+    FetchFinalConsensusFailed(#[source] anyhow::Error),
+    #[error("貢献の記録に失敗しました")]
+    StoreContributionFailed(#[source] anyhow::Error),
+}
+
+// 全件走査の続きの位置
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ScanCursor(Option<Arc<[u8]>>);
+
+impl ScanCursor {
+    pub const START: ScanCursor = ScanCursor(None);
+
+    pub fn of(position: Arc<[u8]>) -> Self {
+        Self(Some(position))
+    }
+
+    pub fn value(&self) -> Option<&Arc<[u8]>> {
+        self.0.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct AggregationSummary {
+    scanned: u32,
+    stored: u32,
+}
+
+impl AggregationSummary {
+    pub fn scanned(&self) -> u32 {
+        self.scanned
+    }
+
+    pub fn stored(&self) -> u32 {
+        self.stored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::{Arc, LazyLock, Mutex}};
+
+    use crate::{common::{consensus::{proposal::IsProposal, stability::Stability}, contribution::Contribution, fallible::Fallible, profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, proposal_operation::ProposalOperation, relation::TagRelation}}, helper::test::mock_non_top_tag_id};
+
+    use super::{AggregateContributions, AggregateContributionsError, FinalConsensus, RelationKey, ScanCursor};
+
+    static PROPOSER: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static RATER: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    static ACCEPTED: LazyLock<RelationKey> = LazyLock::new(|| (mock_non_top_tag_id(0), mock_non_top_tag_id(1), TagRelation::Inclusion));
+    static REJECTED: LazyLock<RelationKey> = LazyLock::new(|| (mock_non_top_tag_id(2), mock_non_top_tag_id(3), TagRelation::Inclusion));
+    static UNDECIDED: LazyLock<RelationKey> = LazyLock::new(|| (mock_non_top_tag_id(4), mock_non_top_tag_id(5), TagRelation::Inclusion));
+
+    struct MockAggregateContributions {
+        consensus_lookups: Mutex<u32>,
+        stored: Mutex<HashMap<AccountId, Contribution>>,
+    }
+
+    impl AggregateContributions for MockAggregateContributions {
+        async fn fetch_proposals(&self, cursor: ScanCursor) -> Fallible<(Vec<(AccountId, RelationKey)>, Option<ScanCursor>), AggregateContributionsError> {
+            // 2ページに分けて返す
+            if cursor == ScanCursor::START {
+                Ok((vec![(*PROPOSER, *ACCEPTED), (*PROPOSER, *REJECTED)], Some(ScanCursor::of(Arc::from([1u8])))))
+            } else {
+                Ok((vec![(*PROPOSER, *UNDECIDED)], None))
+            }
+        }
+
+        async fn fetch_ratings(&self, _: ScanCursor) -> Fallible<(Vec<(AccountId, RelationKey, ProposalOperation)>, Option<ScanCursor>), AggregateContributionsError> {
+            Ok((vec![
+                (*RATER, *ACCEPTED, ProposalOperation::HighRated),
+                (*RATER, *REJECTED, ProposalOperation::HighRated),
+                (*RATER, *UNDECIDED, ProposalOperation::LowRated),
+                (*RATER, *ACCEPTED, ProposalOperation::Rated),
+                (*PROPOSER, *REJECTED, ProposalOperation::LowRated),
+            ], None))
+        }
+
+        async fn fetch_final_consensus(&self, subtag_id: NonTopTagId, _: NonTopTagId, _: TagRelation) -> Fallible<Option<FinalConsensus>, AggregateContributionsError> {
+            *self.consensus_lookups.lock().unwrap() += 1;
+
+            if subtag_id == ACCEPTED.0 {
+                Ok(Some(FinalConsensus::Accepted))
+            } else if subtag_id == REJECTED.0 {
+                Ok(Some(FinalConsensus::Rejected))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn store_contribution(&self, account_id: AccountId, contribution: Contribution) -> Fallible<(), AggregateContributionsError> {
+            self.stored.lock().unwrap().insert(account_id, contribution);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn aggregate() {
+        let mock = MockAggregateContributions { consensus_lookups: Mutex::new(0), stored: Mutex::new(HashMap::new()) };
+
+        let summary = mock.aggregate_contributions().await.unwrap();
+        assert_eq!(summary.scanned(), 8);
+        assert_eq!(summary.stored(), 2);
+
+        let stored = mock.stored.lock().unwrap();
+        assert_eq!(stored[&PROPOSER], Contribution::new(1, 1, 0));
+        assert_eq!(stored[&RATER], Contribution::new(0, 1, 1));
+
+        // 同じ関係の合意は一度だけ取得する
+        assert_eq!(*mock.consensus_lookups.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn final_consensus() {
+        assert_eq!(FinalConsensus::of(false, IsProposal::NotProposal, Stability::Stable), None);
+        assert_eq!(FinalConsensus::of(true, IsProposal::Proposal, Stability::Unstable), None);
+        assert_eq!(FinalConsensus::of(true, IsProposal::Proposal, Stability::Stable), Some(FinalConsensus::Accepted));
+        assert_eq!(FinalConsensus::of(true, IsProposal::NotProposal, Stability::Stable), Some(FinalConsensus::Accepted));
+        assert_eq!(FinalConsensus::of(true, IsProposal::NotProposal, Stability::Unstable), Some(FinalConsensus::Rejected));
+    }
+
+    #[test]
+    fn agreement() {
+        assert_eq!(FinalConsensus::Accepted.agrees_with(ProposalOperation::HighRated), Some(true));
+        assert_eq!(FinalConsensus::Accepted.agrees_with(ProposalOperation::LowRated), Some(false));
+        assert_eq!(FinalConsensus::Rejected.agrees_with(ProposalOperation::LowRated), Some(true));
+        assert_eq!(FinalConsensus::Rejected.agrees_with(ProposalOperation::Rated), None);
+        assert_eq!(FinalConsensus::Accepted.agrees_with(ProposalOperation::Proposed), None);
+    }
+}
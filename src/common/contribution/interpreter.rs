@@ -0,0 +1,140 @@
+use std::{sync::Arc, time::Duration};
+
+use scylla::{prepared_statement::PreparedStatement, statement::{PagingState, PagingStateResponse}, Session};
+use tokio::{task::JoinHandle, time};
+use tracing::{info, warn};
+
+use crate::{common::{consensus::{proposal::IsProposal, stability::Stability}, fallible::Fallible, profile::account_id::AccountId, tag::{hierarchy::TagHierarchy, non_top_tag::NonTopTagId, proposal_operation::ProposalOperation, relation::TagRelation}}, helper::{error::InitError, scylla::prepare}};
+
+use super::{aggregate::{AggregateContributions, AggregateContributionsError, FinalConsensus, RelationKey, ScanCursor}, Contribution};
+
+// 合意は1サイクル(1時間)ごとに計算されるため、同じ間隔で集計する
+const AGGREGATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+// 提案や評価を全て取り消したアカウントの貢献が残り続けないよう、数回の集計で更新されなければ失効させる
+const CONTRIBUTION_TTL_SECONDS: i32 = 3 * 60 * 60;
+
+// 一度に読み込む提案や評価の数
+const SCAN_PAGE_SIZE: i32 = 1000;
+
+pub struct ContributionAggregatorImpl {
+    db: Arc<Session>,
+    select_proposals: Arc<PreparedStatement>,
+    select_ratings: Arc<PreparedStatement>,
+    select_final_consensus: Arc<PreparedStatement>,
+    insert_contribution: Arc<PreparedStatement>,
+}
+
+impl ContributionAggregatorImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let mut select_proposals = db.prepare("SELECT proposer_id, subtag_id, supertag_id, relation FROM tag_relation_proposals").await?;
+        select_proposals.set_page_size(SCAN_PAGE_SIZE);
+
+        let mut select_ratings = db.prepare("SELECT account_id, subtag_id, supertag_id, relation, operation_id FROM tag_relation_ratings_by_account").await?;
+        select_ratings.set_page_size(SCAN_PAGE_SIZE);
+
+        let select_final_consensus = prepare(&db, "SELECT is_status_calculated, is_proposal, is_stable FROM hierarchical_tag_lists WHERE tag_id = ? AND hierarchy = ? AND related_tag_id = ?").await?;
+
+        let insert_contribution = prepare(&db, "INSERT INTO account_contributions (account_id, accepted_proposals, agreed_ratings, disagreed_ratings) VALUES (?, ?, ?, ?) USING TTL ?").await?;
+
+        Ok(Self { db, select_proposals: Arc::new(select_proposals), select_ratings: Arc::new(select_ratings), select_final_consensus, insert_contribution })
+    }
+
+    // 貢献を定期的に集計するタスクを起動する
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = time::interval(AGGREGATION_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                match self.aggregate_contributions().await {
+                    Ok(summary) => info!(
+                        scanned = summary.scanned(),
+                        stored = summary.stored(),
+                        "貢献の集計が完了しました。"
+                    ),
+                    Err(e) => warn!(error = %e, "貢献の集計に失敗しました。"),
+                }
+            }
+        })
+    }
+}
+
+fn paging_state(cursor: &ScanCursor) -> PagingState {
+    cursor.value()
+        .map(|position| PagingState::new_from_raw_bytes(position.clone()))
+        .unwrap_or_else(PagingState::start)
+}
+
+fn next_cursor(response: PagingStateResponse) -> Option<ScanCursor> {
+    match response {
+        PagingStateResponse::HasMorePages { state } => state.as_bytes_slice().cloned().map(ScanCursor::of),
+        PagingStateResponse::NoMorePages => None,
+    }
+}
+
+impl AggregateContributions for ContributionAggregatorImpl {
+    async fn fetch_proposals(&self, cursor: ScanCursor) -> Fallible<(Vec<(AccountId, RelationKey)>, Option<ScanCursor>), AggregateContributionsError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> AggregateContributionsError {
+            AggregateContributionsError::FetchProposalsFailed(e.into())
+        }
+
+        let (result, response) = self.db
+            .execute_single_page(&self.select_proposals, (), paging_state(&cursor))
+            .await
+            .map_err(handle_error)?;
+
+        let proposals = result.rows_typed::<(AccountId, NonTopTagId, NonTopTagId, TagRelation)>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(proposer_id, subtag_id, supertag_id, relation)| (proposer_id, (subtag_id, supertag_id, relation))))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)?;
+
+        Ok((proposals, next_cursor(response)))
+    }
+
+    async fn fetch_ratings(&self, cursor: ScanCursor) -> Fallible<(Vec<(AccountId, RelationKey, ProposalOperation)>, Option<ScanCursor>), AggregateContributionsError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> AggregateContributionsError {
+            AggregateContributionsError::FetchRatingsFailed(e.into())
+        }
+
+        let (result, response) = self.db
+            .execute_single_page(&self.select_ratings, (), paging_state(&cursor))
+            .await
+            .map_err(handle_error)?;
+
+        let ratings = result.rows_typed::<(AccountId, NonTopTagId, NonTopTagId, TagRelation, ProposalOperation)>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(account_id, subtag_id, supertag_id, relation, operation)| (account_id, (subtag_id, supertag_id, relation), operation)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)?;
+
+        Ok((ratings, next_cursor(response)))
+    }
+
+    async fn fetch_final_consensus(&self, subtag_id: NonTopTagId, supertag_id: NonTopTagId, relation: TagRelation) -> Fallible<Option<FinalConsensus>, AggregateContributionsError> {
+        let hierarchy = match relation {
+            TagRelation::Inclusion => TagHierarchy::Super,
+            TagRelation::Equivalence => TagHierarchy::Equivalent
+        };
+
+        self.db
+            .execute_unpaged(&self.select_final_consensus, (subtag_id, hierarchy, supertag_id))
+            .await
+            .map_err(|e| AggregateContributionsError::FetchFinalConsensusFailed(e.into()))?
+            .maybe_first_row_typed::<(bool, IsProposal, Stability)>()
+            .map(|row| row.and_then(|(is_status_calculated, is_proposal, is_stable)| FinalConsensus::of(is_status_calculated, is_proposal, is_stable)))
+            .map_err(|e| AggregateContributionsError::FetchFinalConsensusFailed(e.into()))
+    }
+
+    async fn store_contribution(&self, account_id: AccountId, contribution: Contribution) -> Fallible<(), AggregateContributionsError> {
+        let (accepted_proposals, agreed_ratings, disagreed_ratings) = contribution.into();
+
+        self.db
+            .execute_unpaged(&self.insert_contribution, (account_id, accepted_proposals, agreed_ratings, disagreed_ratings, CONTRIBUTION_TTL_SECONDS))
+            .await
+            .map(|_| ())
+            .map_err(|e| AggregateContributionsError::StoreContributionFailed(e.into()))
+    }
+}
@@ -0,0 +1,60 @@
+pub mod aggregate;
+pub mod interpreter;
+
+// 集計処理によって更新される、アカウントのタグ関係への貢献
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Contribution {
+    accepted_proposals: u32,
+    agreed_ratings: u32,
+    disagreed_ratings: u32,
+}
+
+impl Contribution {
+    pub const fn new(accepted_proposals: u32, agreed_ratings: u32, disagreed_ratings: u32) -> Self {
+        Self { accepted_proposals, agreed_ratings, disagreed_ratings }
+    }
+
+    // 最終的な合意で安定化した提案の数
+    pub fn accepted_proposals(&self) -> u32 {
+        self.accepted_proposals
+    }
+
+    // 最終的な合意と一致した評価の数
+    pub fn agreed_ratings(&self) -> u32 {
+        self.agreed_ratings
+    }
+
+    // 最終的な合意と一致しなかった評価の数
+    pub fn disagreed_ratings(&self) -> u32 {
+        self.disagreed_ratings
+    }
+
+    pub fn judged_ratings(&self) -> u32 {
+        self.agreed_ratings.saturating_add(self.disagreed_ratings)
+    }
+
+    fn add_accepted_proposal(&mut self) {
+        self.accepted_proposals = self.accepted_proposals.saturating_add(1);
+    }
+
+    fn add_judged_rating(&mut self, agreed: bool) {
+        if agreed {
+            self.agreed_ratings = self.agreed_ratings.saturating_add(1);
+        } else {
+            self.disagreed_ratings = self.disagreed_ratings.saturating_add(1);
+        }
+    }
+}
+
+impl From<(i32, i32, i32)> for Contribution {
+    fn from((accepted_proposals, agreed_ratings, disagreed_ratings): (i32, i32, i32)) -> Self {
+        Self::new(accepted_proposals.max(0) as u32, agreed_ratings.max(0) as u32, disagreed_ratings.max(0) as u32)
+    }
+}
+
+impl From<Contribution> for (i32, i32, i32) {
+    fn from(value: Contribution) -> Self {
+        let to_i32 = |count: u32| count.min(i32::MAX as u32) as i32;
+        (to_i32(value.accepted_proposals), to_i32(value.agreed_ratings), to_i32(value.disagreed_ratings))
+    }
+}
@@ -6,13 +6,37 @@ use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result
 use serde::{de::{self}, Deserialize, Deserializer};
 use thiserror::Error;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Email(String);
 
 impl Email {
     pub fn value(&self) -> &String {
         &self.0
     }
+
+    pub fn local_part(&self) -> &str {
+        self.split().0
+    }
+
+    pub fn domain(&self) -> &str {
+        self.split().1
+    }
+
+    // ドメインの大文字小文字や国際化ドメインの表記揺れによる重複登録を防ぐため、比較用の表記に揃える
+    // ローカル部の大文字小文字を区別するかは受信側のサーバー次第であるため、そのまま残す
+    pub fn normalized(&self) -> Email {
+        let (local_part, domain) = self.split();
+
+        // 検証済みのドメインであるため、通常は失敗しない
+        let domain = domain_to_ascii(domain).unwrap_or_else(|_| domain.to_ascii_lowercase());
+
+        Email(format!("{}@{}", local_part, domain))
+    }
+
+    fn split(&self) -> (&str, &str) {
+        // 検証済みのため、必ず`@`を含む
+        self.0.rsplit_once('@').unwrap()
+    }
 }
 
 #[derive(Debug, Error)]
@@ -130,6 +154,25 @@ mod tests {
         assert!(!validate_email("メール@example.com"));
     }
 
+    #[test]
+    fn normalize_case() {
+        // ローカル部の大文字小文字は区別される
+        let email = Email::from_str("A.User@Example.COM").unwrap();
+        assert_eq!(email.normalized(), Email::from_str("A.User@example.com").unwrap());
+    }
+
+    #[test]
+    fn normalize_idn() {
+        let email = Email::from_str("email@日本語.JP").unwrap();
+        assert_eq!(email.normalized(), Email::from_str("email@xn--wgv71a119e.jp").unwrap());
+    }
+
+    #[test]
+    fn local_part_and_domain() {
+        let email = Email::from_str("email@example.com").unwrap();
+        assert_eq!((email.local_part(), email.domain()), ("email", "example.com"));
+    }
+
     #[test]
     fn deserialize_valid_json() {
         let json = r#""email@example.com""#;
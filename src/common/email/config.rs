@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use lettre::message::dkim::DkimSigningAlgorithm;
+use thiserror::Error;
+
+use super::{address::Email, resend::ResendEmailSender, send::{Body, EmailSendFailed, EmailSender, NetmateEmail, SenderName, Subject}, smtp::{dkim_config, BuildSmtpSenderError, ParseSmtpSecurityError, SmtpConfig, SmtpEmailSender, SmtpSecurity}};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EmailProvider {
+    Resend,
+    Smtp,
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("有効なメール送信プロバイダではありません")]
+pub struct ParseEmailProviderError;
+
+impl FromStr for EmailProvider {
+    type Err = ParseEmailProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "resend" => Ok(EmailProvider::Resend),
+            "smtp" => Ok(EmailProvider::Smtp),
+            _ => Err(ParseEmailProviderError)
+        }
+    }
+}
+
+pub enum ConfiguredEmailSender {
+    Resend(ResendEmailSender),
+    Smtp(Box<SmtpEmailSender>),
+}
+
+impl ConfiguredEmailSender {
+    // EMAIL_PROVIDER: resend | smtp
+    // RESEND_API_KEY: ResendのAPIキー(resendのみ)
+    // SMTP_HOST: SMTPサーバーのホスト名(smtpのみ)
+    // SMTP_PORT: SMTPサーバーのポート番号(任意)
+    // SMTP_SECURITY: starttls | tls | none(smtpのみ、既定はstarttls)
+    // SMTP_USERNAME, SMTP_PASSWORD: SMTP認証の資格情報(任意)
+    // DKIM_SELECTOR, DKIM_DOMAIN, DKIM_PRIVATE_KEY: DKIM署名の設定(任意、smtpのみ)
+    // DKIM_ALGORITHM: rsa | ed25519(任意、既定はrsa)
+    pub fn from_env() -> Result<Self, LoadEmailSenderConfigError> {
+        fn var(key: &'static str) -> Result<String, LoadEmailSenderConfigError> {
+            dotenvy::var(key).map_err(|_| LoadEmailSenderConfigError::MissingVariable(key))
+        }
+
+        let provider = EmailProvider::from_str(&var("EMAIL_PROVIDER")?)
+            .map_err(LoadEmailSenderConfigError::InvalidProvider)?;
+
+        match provider {
+            EmailProvider::Resend => Ok(Self::Resend(ResendEmailSender::new(&var("RESEND_API_KEY")?))),
+            EmailProvider::Smtp => {
+                let port = dotenvy::var("SMTP_PORT")
+                    .ok()
+                    .map(|port| u16::from_str(&port).map_err(|_| LoadEmailSenderConfigError::InvalidPort))
+                    .transpose()?;
+
+                let security = match dotenvy::var("SMTP_SECURITY") {
+                    Ok(security) => SmtpSecurity::from_str(&security).map_err(LoadEmailSenderConfigError::InvalidSecurity)?,
+                    Err(_) => SmtpSecurity::StartTls,
+                };
+
+                let credentials = match (dotenvy::var("SMTP_USERNAME"), dotenvy::var("SMTP_PASSWORD")) {
+                    (Ok(username), Ok(password)) => Some((username, password)),
+                    _ => None,
+                };
+
+                let dkim = match dotenvy::var("DKIM_PRIVATE_KEY") {
+                    Ok(private_key) => {
+                        let algorithm = match dotenvy::var("DKIM_ALGORITHM").as_deref() {
+                            Ok("ed25519") => DkimSigningAlgorithm::Ed25519,
+                            Ok("rsa") | Err(_) => DkimSigningAlgorithm::Rsa,
+                            Ok(_) => return Err(LoadEmailSenderConfigError::InvalidDkimAlgorithm),
+                        };
+
+                        Some(dkim_config(var("DKIM_SELECTOR")?, var("DKIM_DOMAIN")?, &private_key, algorithm)?)
+                    },
+                    Err(_) => None,
+                };
+
+                let config = SmtpConfig::new(var("SMTP_HOST")?, port, security, credentials, dkim);
+
+                Ok(Self::Smtp(Box::new(SmtpEmailSender::new(config)?)))
+            },
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadEmailSenderConfigError {
+    #[error("環境変数{0}が設定されていません")]
+    MissingVariable(&'static str),
+    #[error("メール送信プロバイダの解析に失敗しました")]
+    InvalidProvider(#[source] ParseEmailProviderError),
+    #[error("SMTPのポート番号が不正です")]
+    InvalidPort,
+    #[error("SMTPの暗号化方式の解析に失敗しました")]
+    InvalidSecurity(#[source] ParseSmtpSecurityError),
+    #[error("DKIMの署名アルゴリズムが不正です")]
+    InvalidDkimAlgorithm,
+    #[error("SMTPによる送信の準備に失敗しました")]
+    BuildSmtpSenderFailed(#[from] BuildSmtpSenderError),
+}
+
+impl EmailSender for ConfiguredEmailSender {
+    async fn send(&self, from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body) -> Result<(), EmailSendFailed> {
+        match self {
+            Self::Resend(sender) => sender.send(from, to, sender_name, subject, body).await,
+            Self::Smtp(sender) => sender.send(from, to, sender_name, subject, body).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{EmailProvider, ParseEmailProviderError};
+
+    #[test]
+    fn parse_provider() {
+        assert_eq!(EmailProvider::from_str("resend"), Ok(EmailProvider::Resend));
+        assert_eq!(EmailProvider::from_str("smtp"), Ok(EmailProvider::Smtp));
+    }
+
+    #[test]
+    fn parse_unknown_provider() {
+        assert_eq!(EmailProvider::from_str("sendgrid"), Err(ParseEmailProviderError));
+    }
+}
\ No newline at end of file
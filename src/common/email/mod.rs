@@ -1,3 +1,10 @@
 pub mod address;
+pub mod config;
+pub mod outbox;
+pub mod policy;
 pub mod resend;
-pub mod send;
\ No newline at end of file
+pub mod send;
+pub mod smtp;
+pub mod suppression;
+pub mod template;
+pub mod webhook;
\ No newline at end of file
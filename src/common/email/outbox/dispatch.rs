@@ -0,0 +1,375 @@
+use thiserror::Error;
+use tracing::warn;
+
+use crate::common::{email::{address::Email, send::EmailSendFailed}, fallible::Fallible, unixtime::UnixtimeMillis};
+
+use super::message::{Attempts, EmailMessageId, OutboxMessage, RawOutboxColumns};
+
+// 一度のディスパッチで送信するメールの最大数
+pub const DISPATCH_BATCH_SIZE: DispatchBatchSize = DispatchBatchSize::of(100);
+
+pub(crate) trait DispatchEmail {
+    // 送信時刻を迎えたメールを送信し、失敗したものは間隔を空けて再送するか、デッドレターとする
+    // 一通の処理に失敗しても、残りのメールの送信は続ける
+    async fn dispatch_due(&self, now: UnixtimeMillis) -> Fallible<DispatchSummary, DispatchEmailError> {
+        let mut summary = DispatchSummary::default();
+
+        for due_message in self.fetch_due_messages(now, DISPATCH_BATCH_SIZE).await? {
+            // ロックを失った場合は、他のインスタンスと同じメールを送信しないよう中断する
+            if !self.renew_dispatcher_lock().await? {
+                warn!("ディスパッチ中にロックを失ったため中断しました。");
+                break;
+            }
+
+            let res = match &due_message {
+                DueMessage::Valid(message) => self.dispatch_message(message, now, &mut summary).await,
+                DueMessage::Malformed(malformed) => self.dispatch_malformed_message(malformed, now, &mut summary).await,
+            };
+
+            if let Err(e) = res {
+                warn!(message_id = %due_message.message_id(), error = %e, "送信待ちのメールの処理に失敗しました。");
+                summary.failed += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn dispatch_message(&self, message: &OutboxMessage, now: UnixtimeMillis, summary: &mut DispatchSummary) -> Fallible<(), DispatchEmailError> {
+        // 不達や迷惑メール報告のあった宛先には送信せず、送信待ちから取り除く
+        if self.is_suppressed(message.to()).await? {
+            self.complete(message).await?;
+            summary.suppressed += 1;
+            return Ok(());
+        }
+
+        match self.send(message).await {
+            Ok(()) => {
+                self.complete(message).await?;
+                summary.sent += 1;
+            },
+            Err(e) => {
+                let attempts = message.attempts().incremented();
+                let last_error = format!("{:#}", e.0);
+
+                if attempts.is_exhausted() {
+                    self.dead_letter(message, attempts, now, &last_error).await?;
+                    summary.dead_lettered += 1;
+                } else {
+                    self.reschedule(message, attempts, attempts.next_attempt_at(now), &last_error).await?;
+                    summary.rescheduled += 1;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    // 復元できない行は再送しても送信できないため、送信待ちに残さずデッドレターとする
+    async fn dispatch_malformed_message(&self, malformed: &MalformedMessage, now: UnixtimeMillis, summary: &mut DispatchSummary) -> Fallible<(), DispatchEmailError> {
+        warn!(message_id = %malformed.message_id(), reason = malformed.reason(), "送信待ちのメールを復元できないため、デッドレターとします。");
+
+        self.dead_letter_malformed(malformed, now).await?;
+        summary.dead_lettered += 1;
+
+        Ok(())
+    }
+
+    async fn fetch_due_messages(&self, now: UnixtimeMillis, limit: DispatchBatchSize) -> Fallible<Vec<DueMessage>, DispatchEmailError>;
+
+    // ロックの有効期限を延長し、まだ自身がロックを保持しているかを返す
+    async fn renew_dispatcher_lock(&self) -> Fallible<bool, DispatchEmailError>;
+
+    async fn is_suppressed(&self, to: &Email) -> Fallible<bool, DispatchEmailError>;
+
+    async fn send(&self, message: &OutboxMessage) -> Result<(), EmailSendFailed>;
+
+    async fn complete(&self, message: &OutboxMessage) -> Fallible<(), DispatchEmailError>;
+
+    async fn reschedule(&self, message: &OutboxMessage, attempts: Attempts, next_attempt_at: UnixtimeMillis, last_error: &str) -> Fallible<(), DispatchEmailError>;
+
+    async fn dead_letter(&self, message: &OutboxMessage, attempts: Attempts, failed_at: UnixtimeMillis, last_error: &str) -> Fallible<(), DispatchEmailError>;
+
+    async fn dead_letter_malformed(&self, malformed: &MalformedMessage, failed_at: UnixtimeMillis) -> Fallible<(), DispatchEmailError>;
+}
+
+// 送信待ちから取り出した行
+pub enum DueMessage {
+    Valid(OutboxMessage),
+    Malformed(MalformedMessage),
+}
+
+impl DueMessage {
+    pub fn message_id(&self) -> EmailMessageId {
+        match self {
+            DueMessage::Valid(message) => message.message_id(),
+            DueMessage::Malformed(malformed) => malformed.message_id(),
+        }
+    }
+}
+
+// メッセージとして復元できなかった行
+// デッドレターから原因を調べられるよう、元の列の値をそのまま保持する
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MalformedMessage {
+    message_id: EmailMessageId,
+    next_attempt_at: UnixtimeMillis,
+    columns: RawOutboxColumns,
+    reason: String,
+}
+
+impl MalformedMessage {
+    pub fn new(message_id: EmailMessageId, next_attempt_at: UnixtimeMillis, columns: RawOutboxColumns, reason: String) -> Self {
+        Self { message_id, next_attempt_at, columns, reason }
+    }
+
+    pub fn message_id(&self) -> EmailMessageId {
+        self.message_id
+    }
+
+    pub fn next_attempt_at(&self) -> UnixtimeMillis {
+        self.next_attempt_at
+    }
+
+    pub fn columns(&self) -> &RawOutboxColumns {
+        &self.columns
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DispatchEmailError {
+    #[error("送信待ちのメールの取得に失敗しました")]
+    FetchDueMessagesFailed(#[source] anyhow::Error),
+    #[error("送信停止中の宛先であるかの確認に失敗しました")]
+    IsSuppressedFailed(#[source] anyhow::Error),
+    #[error("送信済みのメールの削除に失敗しました")]
+    CompleteFailed(#[source] anyhow::Error),
+    #[error("メールの再送の予約に失敗しました")]
+    RescheduleFailed(#[source] anyhow::Error),
+    #[error("メールのデッドレターへの移動に失敗しました")]
+    DeadLetterFailed(#[source] anyhow::Error),
+    #[error("ディスパッチのロックの延長に失敗しました")]
+    RenewDispatcherLockFailed(#[source] anyhow::Error),
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DispatchSummary {
+    sent: u32,
+    rescheduled: u32,
+    dead_lettered: u32,
+    suppressed: u32,
+    failed: u32,
+}
+
+impl DispatchSummary {
+    pub fn sent(&self) -> u32 {
+        self.sent
+    }
+
+    pub fn rescheduled(&self) -> u32 {
+        self.rescheduled
+    }
+
+    pub fn dead_lettered(&self) -> u32 {
+        self.dead_lettered
+    }
+
+    pub fn suppressed(&self) -> u32 {
+        self.suppressed
+    }
+
+    // 処理に失敗し、送信待ちに残ったメールの数
+    pub fn failed(&self) -> u32 {
+        self.failed
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DispatchBatchSize(i32);
+
+impl DispatchBatchSize {
+    pub const fn of(size: i32) -> Self {
+        Self(size)
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::{LazyLock, Mutex}};
+
+    use thiserror::Error;
+
+    use crate::common::{email::{address::Email, outbox::message::{Attempts, EmailMessageId, IdempotencyKey, OutboxMessage, RawOutboxColumns, MAX_ATTEMPTS}, send::{Body, EmailSendFailed, HtmlContent, NetmateEmail, PlainText, SenderName, Subject}}, fallible::Fallible, profile::language::Language, unixtime::UnixtimeMillis};
+
+    use super::{DispatchBatchSize, DispatchEmail, DispatchEmailError, DispatchSummary, DueMessage, MalformedMessage};
+
+    static FROM: LazyLock<NetmateEmail> = LazyLock::new(|| NetmateEmail::try_from(Email::from_str("verify-email@account.netmate.app").unwrap()).unwrap());
+    static DELIVERABLE: LazyLock<Email> = LazyLock::new(|| Email::from_str("deliverable@example.com").unwrap());
+    static UNDELIVERABLE: LazyLock<Email> = LazyLock::new(|| Email::from_str("undeliverable@example.com").unwrap());
+    static SUPPRESSED: LazyLock<Email> = LazyLock::new(|| Email::from_str("suppressed@example.com").unwrap());
+    static UNCOMPLETABLE: LazyLock<Email> = LazyLock::new(|| Email::from_str("uncompletable@example.com").unwrap());
+
+    const NOW: u64 = 1_000_000;
+
+    #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+    enum Outcome {
+        Completed,
+        Rescheduled(Attempts, UnixtimeMillis),
+        DeadLettered(Attempts),
+        MalformedDeadLettered(EmailMessageId),
+    }
+
+    struct MockDispatchEmail {
+        messages: Mutex<Vec<DueMessage>>,
+        outcomes: Mutex<Vec<Outcome>>,
+        // ロックを保持し続けられるメールの数
+        lock_renewals: Mutex<usize>,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("疑似エラー")]
+    struct MockError;
+
+    impl MockDispatchEmail {
+        fn new(messages: Vec<DueMessage>, lock_renewals: usize) -> Self {
+            Self { messages: Mutex::new(messages), outcomes: Mutex::new(Vec::new()), lock_renewals: Mutex::new(lock_renewals) }
+        }
+    }
+
+    impl DispatchEmail for MockDispatchEmail {
+        async fn fetch_due_messages(&self, _: UnixtimeMillis, _: DispatchBatchSize) -> Fallible<Vec<DueMessage>, DispatchEmailError> {
+            Ok(self.messages.lock().unwrap().drain(..).collect())
+        }
+
+        async fn renew_dispatcher_lock(&self) -> Fallible<bool, DispatchEmailError> {
+            let mut lock_renewals = self.lock_renewals.lock().unwrap();
+            let renewed = *lock_renewals > 0;
+            *lock_renewals = lock_renewals.saturating_sub(1);
+            Ok(renewed)
+        }
+
+        async fn is_suppressed(&self, to: &Email) -> Fallible<bool, DispatchEmailError> {
+            Ok(*to == *SUPPRESSED)
+        }
+
+        async fn send(&self, message: &OutboxMessage) -> Result<(), EmailSendFailed> {
+            if *message.to() == *UNDELIVERABLE || *message.to() == *SUPPRESSED {
+                Err(EmailSendFailed(MockError.into()))
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn complete(&self, message: &OutboxMessage) -> Fallible<(), DispatchEmailError> {
+            if *message.to() == *UNCOMPLETABLE {
+                return Err(DispatchEmailError::CompleteFailed(MockError.into()));
+            }
+
+            self.outcomes.lock().unwrap().push(Outcome::Completed);
+            Ok(())
+        }
+
+        async fn reschedule(&self, _: &OutboxMessage, attempts: Attempts, next_attempt_at: UnixtimeMillis, _: &str) -> Fallible<(), DispatchEmailError> {
+            self.outcomes.lock().unwrap().push(Outcome::Rescheduled(attempts, next_attempt_at));
+            Ok(())
+        }
+
+        async fn dead_letter(&self, _: &OutboxMessage, attempts: Attempts, _: UnixtimeMillis, _: &str) -> Fallible<(), DispatchEmailError> {
+            self.outcomes.lock().unwrap().push(Outcome::DeadLettered(attempts));
+            Ok(())
+        }
+
+        async fn dead_letter_malformed(&self, malformed: &MalformedMessage, _: UnixtimeMillis) -> Fallible<(), DispatchEmailError> {
+            self.outcomes.lock().unwrap().push(Outcome::MalformedDeadLettered(malformed.message_id()));
+            Ok(())
+        }
+    }
+
+    fn message(to: &Email, attempts: Attempts) -> DueMessage {
+        let body = Body::new(HtmlContent::new("<p>本文</p>"), PlainText::new("本文"));
+        DueMessage::Valid(OutboxMessage::new(EmailMessageId::gen(), IdempotencyKey::new(String::from("key")), FROM.clone(), to.clone(), SenderName::by(Language::Japanese), Subject::from_str("件名").unwrap(), body, attempts, UnixtimeMillis::of(NOW)))
+    }
+
+    fn malformed(message_id: EmailMessageId) -> DueMessage {
+        let columns: RawOutboxColumns = (Some(String::from("key")), Some(String::from("invalid")), None, None, None, None, None, Some(0));
+        DueMessage::Malformed(MalformedMessage::new(message_id, UnixtimeMillis::of(NOW), columns, String::from("to_addressがありません")))
+    }
+
+    async fn dispatch(messages: Vec<DueMessage>) -> (DispatchSummary, Vec<Outcome>) {
+        dispatch_with_lock(messages, usize::MAX).await
+    }
+
+    async fn dispatch_with_lock(messages: Vec<DueMessage>, lock_renewals: usize) -> (DispatchSummary, Vec<Outcome>) {
+        let dispatch = MockDispatchEmail::new(messages, lock_renewals);
+        let summary = dispatch.dispatch_due(UnixtimeMillis::of(NOW)).await.unwrap();
+        let outcomes = dispatch.outcomes.lock().unwrap().clone();
+        (summary, outcomes)
+    }
+
+    #[tokio::test]
+    async fn sent() {
+        let (summary, outcomes) = dispatch(vec![message(&DELIVERABLE, Attempts::of(0))]).await;
+        assert_eq!(summary.sent(), 1);
+        assert_eq!(outcomes, vec![Outcome::Completed]);
+    }
+
+    #[tokio::test]
+    async fn rescheduled() {
+        let (summary, outcomes) = dispatch(vec![message(&UNDELIVERABLE, Attempts::of(2))]).await;
+        let attempts = Attempts::of(3);
+        assert_eq!(summary.rescheduled(), 1);
+        assert_eq!(outcomes, vec![Outcome::Rescheduled(attempts, attempts.next_attempt_at(UnixtimeMillis::of(NOW)))]);
+    }
+
+    #[tokio::test]
+    async fn dead_lettered() {
+        let (summary, outcomes) = dispatch(vec![message(&UNDELIVERABLE, Attempts::of(MAX_ATTEMPTS.value() - 1))]).await;
+        assert_eq!(summary.dead_lettered(), 1);
+        assert_eq!(outcomes, vec![Outcome::DeadLettered(MAX_ATTEMPTS)]);
+    }
+
+    #[tokio::test]
+    async fn suppressed() {
+        let (summary, outcomes) = dispatch(vec![message(&SUPPRESSED, Attempts::of(0))]).await;
+        assert_eq!((summary.sent(), summary.suppressed()), (0, 1));
+        assert_eq!(outcomes, vec![Outcome::Completed]);
+    }
+
+    #[tokio::test]
+    async fn one_failure_does_not_block_others() {
+        let (summary, outcomes) = dispatch(vec![message(&UNDELIVERABLE, Attempts::of(0)), message(&DELIVERABLE, Attempts::of(0))]).await;
+        assert_eq!((summary.sent(), summary.rescheduled()), (1, 1));
+        assert_eq!(outcomes.last(), Some(&Outcome::Completed));
+    }
+
+    #[tokio::test]
+    async fn malformed_message_is_dead_lettered() {
+        // 復元できない行が送信待ちに残り続けず、後続のメールも送信される
+        let message_id = EmailMessageId::gen();
+        let (summary, outcomes) = dispatch(vec![malformed(message_id), message(&DELIVERABLE, Attempts::of(0))]).await;
+        assert_eq!((summary.sent(), summary.dead_lettered()), (1, 1));
+        assert_eq!(outcomes, vec![Outcome::MalformedDeadLettered(message_id), Outcome::Completed]);
+    }
+
+    #[tokio::test]
+    async fn failed_message_does_not_abort_batch() {
+        let (summary, outcomes) = dispatch(vec![message(&UNCOMPLETABLE, Attempts::of(0)), message(&DELIVERABLE, Attempts::of(0))]).await;
+        assert_eq!((summary.sent(), summary.failed()), (1, 1));
+        assert_eq!(outcomes, vec![Outcome::Completed]);
+    }
+
+    #[tokio::test]
+    async fn lost_lock_stops_dispatch() {
+        let (summary, outcomes) = dispatch_with_lock(vec![message(&DELIVERABLE, Attempts::of(0)), message(&DELIVERABLE, Attempts::of(0))], 1).await;
+        assert_eq!(summary.sent(), 1);
+        assert_eq!(outcomes, vec![Outcome::Completed]);
+    }
+}
@@ -0,0 +1,137 @@
+use thiserror::Error;
+
+use crate::common::fallible::Fallible;
+
+use super::message::{EmailMessageId, IdempotencyKey, OutboxMessage};
+
+pub(crate) trait EnqueueEmail {
+    // メールは直ちに送信せず、送信待ちとして永続化したうえでディスパッチャに送信させる
+    async fn enqueue(&self, message: OutboxMessage) -> Fallible<(), EnqueueEmailError> {
+        match self.reserve_idempotency_key(message.idempotency_key(), message.message_id()).await {
+            Ok(()) => (),
+            // 同じ冪等キーで既に登録されている場合は、重複して送信しないよう成功とみなす
+            Err(EnqueueEmailError::DuplicateMessage) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+
+        if let Err(e) = self.insert_pending_message(&message).await {
+            // 予約したままでは再試行が重複とみなされ、メールが失われるため予約を取り消す
+            self.release_idempotency_key(message.idempotency_key(), message.message_id()).await?;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    async fn reserve_idempotency_key(&self, idempotency_key: &IdempotencyKey, message_id: EmailMessageId) -> Fallible<(), EnqueueEmailError>;
+
+    async fn insert_pending_message(&self, message: &OutboxMessage) -> Fallible<(), EnqueueEmailError>;
+
+    async fn release_idempotency_key(&self, idempotency_key: &IdempotencyKey, message_id: EmailMessageId) -> Fallible<(), EnqueueEmailError>;
+}
+
+#[derive(Debug, Error)]
+pub enum EnqueueEmailError {
+    #[error("冪等キーの予約に失敗しました")]
+    ReserveIdempotencyKeyFailed(#[source] anyhow::Error),
+    #[error("同じ冪等キーのメールが既に登録されています")]
+    DuplicateMessage,
+    #[error("送信待ちのメールの登録に失敗しました")]
+    InsertPendingMessageFailed(#[source] anyhow::Error),
+    #[error("冪等キーの予約の取り消しに失敗しました")]
+    ReleaseIdempotencyKeyFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::{LazyLock, Mutex}};
+
+    use thiserror::Error;
+
+    use crate::common::{email::{address::Email, send::{Body, HtmlContent, NetmateEmail, PlainText, SenderName, Subject}}, fallible::Fallible, profile::language::Language};
+
+    use super::{EmailMessageId, EnqueueEmail, EnqueueEmailError, IdempotencyKey, OutboxMessage};
+
+    static FROM: LazyLock<NetmateEmail> = LazyLock::new(|| NetmateEmail::try_from(Email::from_str("verify-email@account.netmate.app").unwrap()).unwrap());
+    static TO: LazyLock<Email> = LazyLock::new(|| Email::from_str("user@example.com").unwrap());
+
+    const NEW_KEY: &str = "new";
+    const DUPLICATE_KEY: &str = "duplicate";
+    const UNRESERVABLE_KEY: &str = "unreservable";
+    const UNINSERTABLE_KEY: &str = "uninsertable";
+
+    #[derive(Default)]
+    struct MockEnqueueEmail {
+        inserted: Mutex<Vec<EmailMessageId>>,
+        released: Mutex<Vec<EmailMessageId>>,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("疑似エラー")]
+    struct MockError;
+
+    impl EnqueueEmail for MockEnqueueEmail {
+        async fn reserve_idempotency_key(&self, idempotency_key: &IdempotencyKey, _: EmailMessageId) -> Fallible<(), EnqueueEmailError> {
+            match idempotency_key.value().as_str() {
+                DUPLICATE_KEY => Err(EnqueueEmailError::DuplicateMessage),
+                UNRESERVABLE_KEY => Err(EnqueueEmailError::ReserveIdempotencyKeyFailed(MockError.into())),
+                _ => Ok(()),
+            }
+        }
+
+        async fn insert_pending_message(&self, message: &OutboxMessage) -> Fallible<(), EnqueueEmailError> {
+            if message.idempotency_key().value() == UNINSERTABLE_KEY {
+                return Err(EnqueueEmailError::InsertPendingMessageFailed(MockError.into()));
+            }
+
+            self.inserted.lock().unwrap().push(message.message_id());
+            Ok(())
+        }
+
+        async fn release_idempotency_key(&self, _: &IdempotencyKey, message_id: EmailMessageId) -> Fallible<(), EnqueueEmailError> {
+            self.released.lock().unwrap().push(message_id);
+            Ok(())
+        }
+    }
+
+    fn message(idempotency_key: &str) -> OutboxMessage {
+        let body = Body::new(HtmlContent::new("<p>本文</p>"), PlainText::new("本文"));
+        OutboxMessage::compose(IdempotencyKey::new(String::from(idempotency_key)), FROM.clone(), TO.clone(), SenderName::by(Language::Japanese), Subject::from_str("件名").unwrap(), body)
+    }
+
+    #[tokio::test]
+    async fn enqueue_new_message() {
+        let enqueue = MockEnqueueEmail::default();
+        let message = message(NEW_KEY);
+        let message_id = message.message_id();
+
+        assert!(enqueue.enqueue(message).await.is_ok());
+        assert_eq!(*enqueue.inserted.lock().unwrap(), vec![message_id]);
+    }
+
+    #[tokio::test]
+    async fn enqueue_duplicate_message() {
+        let enqueue = MockEnqueueEmail::default();
+
+        assert!(enqueue.enqueue(message(DUPLICATE_KEY)).await.is_ok());
+        assert!(enqueue.inserted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reserve_idempotency_key_failed() {
+        let enqueue = MockEnqueueEmail::default();
+
+        assert!(matches!(enqueue.enqueue(message(UNRESERVABLE_KEY)).await.err(), Some(EnqueueEmailError::ReserveIdempotencyKeyFailed(_))));
+        assert!(enqueue.inserted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn release_idempotency_key_on_insert_failure() {
+        let enqueue = MockEnqueueEmail::default();
+        let message = message(UNINSERTABLE_KEY);
+        let message_id = message.message_id();
+
+        assert!(matches!(enqueue.enqueue(message).await.err(), Some(EnqueueEmailError::InsertPendingMessageFailed(_))));
+        assert_eq!(*enqueue.released.lock().unwrap(), vec![message_id]);
+    }
+}
\ No newline at end of file
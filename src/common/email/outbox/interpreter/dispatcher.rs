@@ -0,0 +1,248 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use redis::{cmd, Script};
+use scylla::{prepared_statement::PreparedStatement, Session};
+use tokio::{task::JoinHandle, time};
+use tracing::{info, warn};
+
+use crate::{common::{email::{address::Email, config::ConfiguredEmailSender, outbox::{dispatch::{DispatchBatchSize, DispatchEmail, DispatchEmailError, DispatchSummary, DueMessage, MalformedMessage}, message::{Attempts, EmailMessageId, IdempotencyKey, OutboxMessage, OutboxStatus, RawOutboxColumns}}, send::{Body, EmailSendFailed, EmailSender, HtmlContent, NetmateEmail, PlainText, SenderName, Subject}}, fallible::Fallible, unixtime::UnixtimeMillis, uuid::uuid7::Uuid7}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::Namespace}, scylla::prepare}};
+
+use super::{insert_message, INSERT_MESSAGE};
+
+const DISPATCHER_LOCK_NAMESPACE: Namespace = Namespace::of("emdsp");
+
+// 複数のインスタンスが同じメールを同時に送信しないよう、ロックを取得したインスタンスのみがディスパッチする
+// 一通ごとに有効期限を延長するため、一通の処理に要する時間より十分に長くする
+const DISPATCHER_LOCK_EXPIRATION_SECONDS: u64 = 60;
+
+// 一通の送信がロックの有効期限を超えないよう、送信を打ち切る時間
+// 送信後の送信待ちからの削除などを含めても有効期限内に収まるようにする
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+const DISPATCH_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct EmailDispatcherImpl {
+    db: Arc<Session>,
+    cache: Arc<Pool>,
+    sender: ConfiguredEmailSender,
+    insert_message: Arc<PreparedStatement>,
+    select_due_messages: Arc<PreparedStatement>,
+    delete_message: Arc<PreparedStatement>,
+    select_suppression: Arc<PreparedStatement>,
+    renew_dispatcher_lock: Arc<Script>,
+    release_dispatcher_lock: Arc<Script>,
+}
+
+impl EmailDispatcherImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, sender: ConfiguredEmailSender) -> Result<Self, InitError<Self>> {
+        let insert_message = prepare(&db, INSERT_MESSAGE).await?;
+
+        let select_due_messages = prepare(&db, "SELECT message_id, next_attempt_at, idempotency_key, from_address, to_address, sender_name, subject, html_content, plain_text, attempts FROM email_outbox WHERE status = ? AND next_attempt_at <= ? LIMIT ?").await?;
+
+        let delete_message = prepare(&db, "DELETE FROM email_outbox WHERE status = ? AND next_attempt_at = ? AND message_id = ?").await?;
+
+        let select_suppression = prepare(&db, "SELECT email FROM email_suppressions WHERE email = ?").await?;
+
+        let renew_dispatcher_lock = Arc::new(Script::new(include_str!("renew_dispatcher_lock.lua")));
+
+        let release_dispatcher_lock = Arc::new(Script::new(include_str!("release_dispatcher_lock.lua")));
+
+        Ok(Self { db, cache, sender, insert_message, select_due_messages, delete_message, select_suppression, renew_dispatcher_lock, release_dispatcher_lock })
+    }
+
+    // 送信待ちのメールを定期的に送信するタスクを起動する
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = time::interval(DISPATCH_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                match self.dispatch_if_leader().await {
+                    Ok(Some(summary)) if summary != DispatchSummary::default() => info!(
+                        sent = summary.sent(),
+                        rescheduled = summary.rescheduled(),
+                        dead_lettered = summary.dead_lettered(),
+                        suppressed = summary.suppressed(),
+                        failed = summary.failed(),
+                        "送信待ちのメールを処理しました。"
+                    ),
+                    Ok(_) => (),
+                    Err(e) => warn!(error = %e, "送信待ちのメールの処理に失敗しました。"),
+                }
+            }
+        })
+    }
+
+    async fn dispatch_if_leader(&self) -> anyhow::Result<Option<DispatchSummary>> {
+        let lock_token = Uuid7::now();
+
+        // ディスパッチ中はRedisの接続を保持しない
+        let acquired = {
+            let mut conn = conn(&self.cache, anyhow::Error::from).await?;
+
+            cmd("SET")
+                .arg(DISPATCHER_LOCK_NAMESPACE.to_string())
+                .arg(lock_token)
+                .arg("NX")
+                .arg("EX")
+                .arg(DISPATCHER_LOCK_EXPIRATION_SECONDS)
+                .query_async::<Option<String>>(&mut *conn)
+                .await?
+                .is_some()
+        };
+
+        if !acquired {
+            return Ok(None);
+        }
+
+        let summary = LockedEmailDispatcher { dispatcher: self, lock_token }
+            .dispatch_due(UnixtimeMillis::now())
+            .await;
+
+        let mut conn = conn(&self.cache, anyhow::Error::from).await?;
+
+        self.release_dispatcher_lock
+            .key(DISPATCHER_LOCK_NAMESPACE.to_string())
+            .arg(lock_token)
+            .invoke_async::<()>(&mut *conn)
+            .await?;
+
+        Ok(Some(summary?))
+    }
+
+    async fn delete_message(&self, message_id: EmailMessageId, next_attempt_at: UnixtimeMillis) -> Result<(), anyhow::Error> {
+        self.db
+            .execute_unpaged(&self.delete_message, (OutboxStatus::Pending, next_attempt_at, message_id))
+            .await
+            .map(|_| ())
+            .map_err(anyhow::Error::from)
+    }
+}
+
+// ロックを取得した一度のディスパッチ
+struct LockedEmailDispatcher<'a> {
+    dispatcher: &'a EmailDispatcherImpl,
+    lock_token: Uuid7,
+}
+
+type OutboxRow = (EmailMessageId, UnixtimeMillis, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<i32>);
+
+// 登録時に検証済みのため通常は失敗しないが、失敗した行は理由とともにデッドレターとする
+fn restore_message((message_id, next_attempt_at, idempotency_key, from, to, sender_name, subject, html_content, plain_text, attempts): OutboxRow) -> DueMessage {
+    let columns: RawOutboxColumns = (idempotency_key, from, to, sender_name, subject, html_content, plain_text, attempts);
+
+    match restore_columns(message_id, next_attempt_at, &columns) {
+        Ok(message) => DueMessage::Valid(message),
+        Err(reason) => DueMessage::Malformed(MalformedMessage::new(message_id, next_attempt_at, columns, reason)),
+    }
+}
+
+fn restore_columns(message_id: EmailMessageId, next_attempt_at: UnixtimeMillis, (idempotency_key, from, to, sender_name, subject, html_content, plain_text, attempts): &RawOutboxColumns) -> Result<OutboxMessage, String> {
+    fn required<'a, T>(column: &'a Option<T>, name: &str) -> Result<&'a T, String> {
+        column.as_ref().ok_or_else(|| format!("{}がありません", name))
+    }
+
+    let from = Email::from_str(required(from, "from_address")?)
+        .map_err(|e| e.to_string())
+        .and_then(|email| NetmateEmail::try_from(email).map_err(|e| e.to_string()))?;
+    let to = Email::from_str(required(to, "to_address")?).map_err(|e| e.to_string())?;
+    let subject = Subject::from_str(required(subject, "subject")?).map_err(|e| e.to_string())?;
+    let body = Body::new(HtmlContent::new(required(html_content, "html_content")?), PlainText::new(required(plain_text, "plain_text")?));
+
+    Ok(OutboxMessage::new(message_id, IdempotencyKey::new(required(idempotency_key, "idempotency_key")?.clone()), from, to, SenderName::new(required(sender_name, "sender_name")?.clone()), subject, body, Attempts::from(attempts.unwrap_or_default()), next_attempt_at))
+}
+
+impl DispatchEmail for LockedEmailDispatcher<'_> {
+    async fn fetch_due_messages(&self, now: UnixtimeMillis, limit: DispatchBatchSize) -> Fallible<Vec<DueMessage>, DispatchEmailError> {
+        let rows = self.dispatcher.db
+            .execute_unpaged(&self.dispatcher.select_due_messages, (OutboxStatus::Pending, now, limit.value()))
+            .await
+            .map_err(|e| DispatchEmailError::FetchDueMessagesFailed(e.into()))?
+            .rows_typed::<OutboxRow>()
+            .map_err(|e| DispatchEmailError::FetchDueMessagesFailed(e.into()))?;
+
+        let mut due_messages = Vec::new();
+
+        for row in rows {
+            match row {
+                Ok(row) => due_messages.push(restore_message(row)),
+                // 主キーを読めない行は移動も削除もできないため、記録だけ残す
+                Err(e) => warn!(error = %e, "送信待ちのメールの行を読み込めませんでした。"),
+            }
+        }
+
+        Ok(due_messages)
+    }
+
+    async fn renew_dispatcher_lock(&self) -> Fallible<bool, DispatchEmailError> {
+        let mut conn = conn(&self.dispatcher.cache, |e| DispatchEmailError::RenewDispatcherLockFailed(e.into())).await?;
+
+        self.dispatcher.renew_dispatcher_lock
+            .key(DISPATCHER_LOCK_NAMESPACE.to_string())
+            .arg(self.lock_token)
+            .arg(DISPATCHER_LOCK_EXPIRATION_SECONDS)
+            .invoke_async::<bool>(&mut *conn)
+            .await
+            .map_err(|e| DispatchEmailError::RenewDispatcherLockFailed(e.into()))
+    }
+
+    async fn is_suppressed(&self, to: &Email) -> Fallible<bool, DispatchEmailError> {
+        self.dispatcher.db
+            .execute_unpaged(&self.dispatcher.select_suppression, (to, ))
+            .await
+            .map_err(|e| DispatchEmailError::IsSuppressedFailed(e.into()))?
+            .maybe_first_row_typed::<(Email, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| DispatchEmailError::IsSuppressedFailed(e.into()))
+    }
+
+    async fn send(&self, message: &OutboxMessage) -> Result<(), EmailSendFailed> {
+        let send = self.dispatcher.sender.send(message.from(), message.to(), message.sender_name(), message.subject(), message.body());
+
+        time::timeout(SEND_TIMEOUT, send)
+            .await
+            .unwrap_or_else(|_| Err(EmailSendFailed(anyhow::anyhow!("メールの送信がタイムアウトしました"))))
+    }
+
+    async fn complete(&self, message: &OutboxMessage) -> Fallible<(), DispatchEmailError> {
+        self.dispatcher.delete_message(message.message_id(), message.next_attempt_at())
+            .await
+            .map_err(DispatchEmailError::CompleteFailed)
+    }
+
+    // 削除に失敗しても再送されるだけでメールは失われないよう、新しい行の登録を先に行う
+    async fn reschedule(&self, message: &OutboxMessage, attempts: Attempts, next_attempt_at: UnixtimeMillis, last_error: &str) -> Fallible<(), DispatchEmailError> {
+        insert_message(&self.dispatcher.db, &self.dispatcher.insert_message, OutboxStatus::Pending, next_attempt_at, message, attempts, Some(last_error))
+            .await
+            .map_err(DispatchEmailError::RescheduleFailed)?;
+
+        self.dispatcher.delete_message(message.message_id(), message.next_attempt_at())
+            .await
+            .map_err(DispatchEmailError::RescheduleFailed)
+    }
+
+    async fn dead_letter(&self, message: &OutboxMessage, attempts: Attempts, failed_at: UnixtimeMillis, last_error: &str) -> Fallible<(), DispatchEmailError> {
+        insert_message(&self.dispatcher.db, &self.dispatcher.insert_message, OutboxStatus::Dead, failed_at, message, attempts, Some(last_error))
+            .await
+            .map_err(DispatchEmailError::DeadLetterFailed)?;
+
+        self.dispatcher.delete_message(message.message_id(), message.next_attempt_at())
+            .await
+            .map_err(DispatchEmailError::DeadLetterFailed)
+    }
+
+    async fn dead_letter_malformed(&self, malformed: &MalformedMessage, failed_at: UnixtimeMillis) -> Fallible<(), DispatchEmailError> {
+        // 検証を経ずに元の値のままデッドレターに移す
+        let (idempotency_key, from, to, sender_name, subject, html_content, plain_text, attempts) = malformed.columns();
+
+        self.dispatcher.db
+            .execute_unpaged(&self.dispatcher.insert_message, (OutboxStatus::Dead, failed_at, malformed.message_id(), idempotency_key, from, to, sender_name, subject, html_content, plain_text, attempts, malformed.reason()))
+            .await
+            .map_err(|e| DispatchEmailError::DeadLetterFailed(e.into()))?;
+
+        self.dispatcher.delete_message(malformed.message_id(), malformed.next_attempt_at())
+            .await
+            .map_err(DispatchEmailError::DeadLetterFailed)
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+use super::{enqueue::{EnqueueEmail, EnqueueEmailError}, message::{Attempts, EmailMessageId, IdempotencyKey, OutboxMessage, OutboxStatus}};
+
+pub mod dispatcher;
+
+const INSERT_MESSAGE: &str = "INSERT INTO email_outbox (status, next_attempt_at, message_id, idempotency_key, from_address, to_address, sender_name, subject, html_content, plain_text, attempts, last_error) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+
+// 再送が尽きるまでの期間より長く保持し、その間の重複した登録を防ぐ
+const IDEMPOTENCY_KEY_TTL_SECONDS: i32 = 7 * 24 * 60 * 60;
+
+// メールの登録のみを行い、送信は`EmailDispatcherImpl`が担う
+#[derive(Debug)]
+pub struct EmailOutboxImpl {
+    db: Arc<Session>,
+    insert_idempotency_key: Arc<PreparedStatement>,
+    delete_idempotency_key: Arc<PreparedStatement>,
+    insert_message: Arc<PreparedStatement>,
+}
+
+impl EmailOutboxImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let insert_idempotency_key = prepare(&db, "INSERT INTO email_outbox_idempotency_keys (idempotency_key, message_id) VALUES (?, ?) IF NOT EXISTS USING TTL ?").await?;
+
+        let delete_idempotency_key = prepare(&db, "DELETE FROM email_outbox_idempotency_keys WHERE idempotency_key = ? IF message_id = ?").await?;
+
+        let insert_message = prepare(&db, INSERT_MESSAGE).await?;
+
+        Ok(Self { db, insert_idempotency_key, delete_idempotency_key, insert_message })
+    }
+}
+
+async fn insert_message(db: &Session, statement: &PreparedStatement, status: OutboxStatus, next_attempt_at: UnixtimeMillis, message: &OutboxMessage, attempts: Attempts, last_error: Option<&str>) -> Result<(), anyhow::Error> {
+    let body = message.body();
+
+    db.execute_unpaged(statement, (status, next_attempt_at, message.message_id(), message.idempotency_key(), message.from().value(), message.to(), message.sender_name().value(), message.subject().value(), body.html_content().value(), body.plain_text().value(), attempts, last_error))
+        .await
+        .map(|_| ())
+        .map_err(anyhow::Error::from)
+}
+
+impl EnqueueEmail for EmailOutboxImpl {
+    async fn reserve_idempotency_key(&self, idempotency_key: &IdempotencyKey, message_id: EmailMessageId) -> Fallible<(), EnqueueEmailError> {
+        self.db
+            .execute_unpaged(&self.insert_idempotency_key, (idempotency_key, message_id, IDEMPOTENCY_KEY_TTL_SECONDS))
+            .await
+            .applied(EnqueueEmailError::ReserveIdempotencyKeyFailed, || EnqueueEmailError::DuplicateMessage)
+    }
+
+    async fn insert_pending_message(&self, message: &OutboxMessage) -> Fallible<(), EnqueueEmailError> {
+        insert_message(&self.db, &self.insert_message, OutboxStatus::Pending, message.next_attempt_at(), message, message.attempts(), None)
+            .await
+            .map_err(EnqueueEmailError::InsertPendingMessageFailed)
+    }
+
+    async fn release_idempotency_key(&self, idempotency_key: &IdempotencyKey, message_id: EmailMessageId) -> Fallible<(), EnqueueEmailError> {
+        self.db
+            .execute_unpaged(&self.delete_idempotency_key, (idempotency_key, message_id))
+            .await
+            .map(|_| ())
+            .map_err(|e| EnqueueEmailError::ReleaseIdempotencyKeyFailed(e.into()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- 有効期限切れ後に他のインスタンスが取得したロックを解放しないよう、自身のロックである場合のみ削除する
+if redis.call("get", KEYS[1]) == ARGV[1] then
+    return redis.call("del", KEYS[1])
+end
+return 0
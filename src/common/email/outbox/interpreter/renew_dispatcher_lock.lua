@@ -0,0 +1,5 @@
+-- 自身のロックである場合のみ有効期限を延長し、延長できたかを返す
+if redis.call("get", KEYS[1]) == ARGV[1] then
+    return redis.call("expire", KEYS[1], ARGV[2])
+end
+return 0
@@ -0,0 +1,236 @@
+use std::fmt::{self, Display};
+
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::Serialize;
+
+use crate::common::{email::{address::Email, send::{Body, NetmateEmail, SenderName, Subject}}, unixtime::UnixtimeMillis, uuid::uuid7::Uuid7};
+
+// この回数送信に失敗したメッセージは再送せず、デッドレターとして残す
+pub const MAX_ATTEMPTS: Attempts = Attempts::of(8);
+
+const BASE_RETRY_INTERVAL_MILLIS: u64 = 60 * 1000;
+const MAX_RETRY_INTERVAL_MILLIS: u64 = 6 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
+pub struct EmailMessageId(Uuid7);
+
+impl EmailMessageId {
+    pub fn gen() -> Self {
+        Self(Uuid7::now())
+    }
+
+    pub fn value(&self) -> Uuid7 {
+        self.0
+    }
+}
+
+impl Display for EmailMessageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl SerializeValue for EmailMessageId {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for EmailMessageId {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        Uuid7::from_cql(cql_val).map(EmailMessageId)
+    }
+}
+
+// 同じメールを二重に送信しないよう、呼び出し側が送信の意図ごとに一意な値を与える
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    pub fn new(key: String) -> Self {
+        Self(key)
+    }
+
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl SerializeValue for IdempotencyKey {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
+pub struct Attempts(u32);
+
+impl Attempts {
+    pub const fn of(attempts: u32) -> Self {
+        Self(attempts)
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    pub fn incremented(&self) -> Self {
+        Self(self.0.saturating_add(1))
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        *self >= MAX_ATTEMPTS
+    }
+
+    // 失敗するごとに再送までの間隔を倍にする
+    pub fn next_attempt_at(&self, failed_at: UnixtimeMillis) -> UnixtimeMillis {
+        let interval = 1u64.checked_shl(self.0.saturating_sub(1))
+            .and_then(|multiplier| BASE_RETRY_INTERVAL_MILLIS.checked_mul(multiplier))
+            .map_or(MAX_RETRY_INTERVAL_MILLIS, |interval| interval.min(MAX_RETRY_INTERVAL_MILLIS));
+
+        UnixtimeMillis::of(failed_at.value() + interval)
+    }
+}
+
+impl From<Attempts> for i32 {
+    fn from(value: Attempts) -> Self {
+        value.value() as i32
+    }
+}
+
+impl From<i32> for Attempts {
+    fn from(value: i32) -> Self {
+        Attempts::of(value.max(0) as u32)
+    }
+}
+
+impl SerializeValue for Attempts {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&i32::from(*self), typ, writer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    Pending,
+    Dead,
+}
+
+impl OutboxStatus {
+    pub fn value(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+impl SerializeValue for OutboxStatus {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+// 送信待ちの行の主キー以外の列の値
+// (idempotency_key, from_address, to_address, sender_name, subject, html_content, plain_text, attempts)
+pub type RawOutboxColumns = (Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<i32>);
+
+// 送信するメールを、送信時点の内容のまま保持する
+pub struct OutboxMessage {
+    message_id: EmailMessageId,
+    idempotency_key: IdempotencyKey,
+    from: NetmateEmail,
+    to: Email,
+    sender_name: SenderName,
+    subject: Subject,
+    body: Body,
+    attempts: Attempts,
+    next_attempt_at: UnixtimeMillis,
+}
+
+impl OutboxMessage {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(message_id: EmailMessageId, idempotency_key: IdempotencyKey, from: NetmateEmail, to: Email, sender_name: SenderName, subject: Subject, body: Body, attempts: Attempts, next_attempt_at: UnixtimeMillis) -> Self {
+        Self { message_id, idempotency_key, from, to, sender_name, subject, body, attempts, next_attempt_at }
+    }
+
+    // 新たに送信するメールは、即座に送信対象とする
+    pub fn compose(idempotency_key: IdempotencyKey, from: NetmateEmail, to: Email, sender_name: SenderName, subject: Subject, body: Body) -> Self {
+        Self::new(EmailMessageId::gen(), idempotency_key, from, to, sender_name, subject, body, Attempts::of(0), UnixtimeMillis::now())
+    }
+
+    pub fn message_id(&self) -> EmailMessageId {
+        self.message_id
+    }
+
+    pub fn idempotency_key(&self) -> &IdempotencyKey {
+        &self.idempotency_key
+    }
+
+    pub fn from(&self) -> &NetmateEmail {
+        &self.from
+    }
+
+    pub fn to(&self) -> &Email {
+        &self.to
+    }
+
+    pub fn sender_name(&self) -> &SenderName {
+        &self.sender_name
+    }
+
+    pub fn subject(&self) -> &Subject {
+        &self.subject
+    }
+
+    pub fn body(&self) -> &Body {
+        &self.body
+    }
+
+    pub fn attempts(&self) -> Attempts {
+        self.attempts
+    }
+
+    pub fn next_attempt_at(&self) -> UnixtimeMillis {
+        self.next_attempt_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::unixtime::UnixtimeMillis;
+
+    use super::{Attempts, MAX_ATTEMPTS, MAX_RETRY_INTERVAL_MILLIS};
+
+    const FAILED_AT: u64 = 1_000_000;
+
+    fn interval(attempts: u32) -> u64 {
+        Attempts::of(attempts).next_attempt_at(UnixtimeMillis::of(FAILED_AT)).value() - FAILED_AT
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        assert_eq!(interval(1), 60 * 1000);
+        assert_eq!(interval(2), 2 * 60 * 1000);
+        assert_eq!(interval(4), 8 * 60 * 1000);
+    }
+
+    #[test]
+    fn capped_backoff() {
+        assert_eq!(interval(10), MAX_RETRY_INTERVAL_MILLIS);
+        assert_eq!(interval(u32::MAX), MAX_RETRY_INTERVAL_MILLIS);
+    }
+
+    #[test]
+    fn exhausted() {
+        assert!(!Attempts::of(MAX_ATTEMPTS.value() - 1).is_exhausted());
+        assert!(MAX_ATTEMPTS.is_exhausted());
+    }
+}
\ No newline at end of file
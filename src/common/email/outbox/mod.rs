@@ -0,0 +1,4 @@
+pub mod dispatch;
+pub mod enqueue;
+pub mod interpreter;
+pub mod message;
\ No newline at end of file
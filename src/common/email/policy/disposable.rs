@@ -0,0 +1,87 @@
+use std::{collections::HashSet, fs, io};
+
+use idna::domain_to_ascii;
+use thiserror::Error;
+
+const BUNDLED_DOMAINS: &str = include_str!("disposable_domains.txt");
+
+// 使い捨てメールアドレスのドメインの一覧
+// 1行に1ドメインを記し、`#`から始まる行と空行は読み飛ばす
+pub struct DisposableDomains(HashSet<String>);
+
+#[derive(Debug, Error)]
+pub enum LoadDisposableDomainsError {
+    #[error("使い捨てメールアドレスのドメインの一覧を読み込めませんでした")]
+    ReadFailed(#[source] io::Error),
+}
+
+impl DisposableDomains {
+    // DISPOSABLE_EMAIL_DOMAINS_PATH: 使い捨てメールアドレスのドメインの一覧のパス(任意、既定は同梱の一覧)
+    pub fn from_env() -> Result<Self, LoadDisposableDomainsError> {
+        match dotenvy::var("DISPOSABLE_EMAIL_DOMAINS_PATH") {
+            Ok(path) => fs::read_to_string(path)
+                .map(|list| Self::parse(&list))
+                .map_err(LoadDisposableDomainsError::ReadFailed),
+            Err(_) => Ok(Self::bundled()),
+        }
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_DOMAINS)
+    }
+
+    pub fn parse(list: &str) -> Self {
+        let domains = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|domain| domain_to_ascii(domain).ok())
+            .collect();
+
+        Self(domains)
+    }
+
+    // `mail.mailinator.com`のようなサブドメインも使い捨てとみなす
+    // `domain`は正規化済みであること
+    pub fn contains(&self, domain: &str) -> bool {
+        let mut suffix = domain;
+
+        loop {
+            if self.0.contains(suffix) {
+                return true;
+            }
+
+            match suffix.split_once('.') {
+                Some((_, parent)) => suffix = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisposableDomains;
+
+    #[test]
+    fn parse() {
+        let domains = DisposableDomains::parse("# コメント\n\n Mailinator.com \nyopmail.com");
+        assert!(domains.contains("mailinator.com"));
+        assert!(domains.contains("yopmail.com"));
+        assert!(!domains.contains("# コメント"));
+    }
+
+    #[test]
+    fn subdomain() {
+        let domains = DisposableDomains::parse("mailinator.com");
+        assert!(domains.contains("mail.mailinator.com"));
+        assert!(!domains.contains("notmailinator.com"));
+        assert!(!domains.contains("com"));
+    }
+
+    #[test]
+    fn bundled() {
+        assert!(DisposableDomains::bundled().contains("guerrillamail.com"));
+        assert!(!DisposableDomains::bundled().contains("example.com"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+pub mod disposable;
+pub mod mx;
+
+use thiserror::Error;
+
+use super::address::Email;
+
+use self::{disposable::{DisposableDomains, LoadDisposableDomainsError}, mx::{DnsMxResolver, InitDnsMxResolverError, MxResolver}};
+
+// 個人ではなく役割を表すアドレス(RFC2142など)は、アカウントの連絡先として認めない
+const ROLE_LOCAL_PARTS: [&str; 11] = [
+    "abuse",
+    "do-not-reply",
+    "donotreply",
+    "hostmaster",
+    "mailer-daemon",
+    "no-reply",
+    "noc",
+    "noreply",
+    "postmaster",
+    "security",
+    "webmaster",
+];
+
+// アカウント作成に用いるメールアドレスの方針
+// MXレコードの確認は外部への問い合わせを伴うため、`mx_resolver`を与えた場合のみ行う
+pub(crate) struct EmailDomainPolicy<R> {
+    disposable_domains: DisposableDomains,
+    mx_resolver: Option<R>,
+}
+
+#[derive(Debug, Error)]
+pub enum CheckEmailPolicyError {
+    #[error("役割を表すメールアドレスは利用できません")]
+    RoleAddress,
+    #[error("使い捨てメールアドレスは利用できません")]
+    DisposableDomain,
+    #[error("メールを受信できるドメインではありません")]
+    NoMxRecord,
+    #[error("MXレコードの確認に失敗しました")]
+    ResolveMxFailed(#[source] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum LoadEmailDomainPolicyError {
+    #[error("使い捨てメールアドレスの一覧の読み込みに失敗しました")]
+    DisposableDomainsUnavailable(#[source] LoadDisposableDomainsError),
+    #[error("MXレコードの確認の設定が不正です")]
+    InvalidMxCheck,
+    #[error("DNSリゾルバの初期化に失敗しました")]
+    ResolverUnavailable(#[source] InitDnsMxResolverError),
+}
+
+impl<R: MxResolver> EmailDomainPolicy<R> {
+    pub fn new(disposable_domains: DisposableDomains, mx_resolver: Option<R>) -> Self {
+        Self { disposable_domains, mx_resolver }
+    }
+
+    // `email`は正規化済みであること
+    pub async fn check(&self, email: &Email) -> Result<(), CheckEmailPolicyError> {
+        if is_role_address(email) {
+            return Err(CheckEmailPolicyError::RoleAddress);
+        }
+
+        if self.disposable_domains.contains(email.domain()) {
+            return Err(CheckEmailPolicyError::DisposableDomain);
+        }
+
+        if let Some(mx_resolver) = &self.mx_resolver {
+            let has_mx_record = mx_resolver.has_mx_record(email.domain())
+                .await
+                .map_err(|e| CheckEmailPolicyError::ResolveMxFailed(e.into()))?;
+
+            if !has_mx_record {
+                return Err(CheckEmailPolicyError::NoMxRecord);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl EmailDomainPolicy<DnsMxResolver> {
+    // DISPOSABLE_EMAIL_DOMAINS_PATH: 使い捨てメールアドレスのドメインの一覧のパス(任意)
+    // EMAIL_MX_CHECK: enabled | disabled(任意、既定はdisabled)
+    pub fn from_env() -> Result<Self, LoadEmailDomainPolicyError> {
+        let disposable_domains = DisposableDomains::from_env()
+            .map_err(LoadEmailDomainPolicyError::DisposableDomainsUnavailable)?;
+
+        let mx_resolver = match dotenvy::var("EMAIL_MX_CHECK").as_deref() {
+            Ok("enabled") => Some(DnsMxResolver::from_system_conf().map_err(LoadEmailDomainPolicyError::ResolverUnavailable)?),
+            Ok("disabled") | Err(_) => None,
+            Ok(_) => return Err(LoadEmailDomainPolicyError::InvalidMxCheck),
+        };
+
+        Ok(Self::new(disposable_domains, mx_resolver))
+    }
+}
+
+// `noreply+tag@`のようなサブアドレスも役割アドレスとみなす
+fn is_role_address(email: &Email) -> bool {
+    let local_part = email.local_part().to_ascii_lowercase();
+    let local_part = local_part.split_once('+').map_or(local_part.as_str(), |(base, _)| base);
+
+    ROLE_LOCAL_PARTS.contains(&local_part)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::common::email::address::Email;
+
+    use super::{disposable::DisposableDomains, mx::OfflineMxResolver, CheckEmailPolicyError, EmailDomainPolicy};
+
+    fn policy(mx_check: bool) -> EmailDomainPolicy<OfflineMxResolver> {
+        let mx_resolver = mx_check.then(|| OfflineMxResolver::new(["example.com"]));
+        EmailDomainPolicy::new(DisposableDomains::parse("mailinator.com"), mx_resolver)
+    }
+
+    async fn check(email: &str, mx_check: bool) -> Result<(), CheckEmailPolicyError> {
+        policy(mx_check).check(&Email::from_str(email).unwrap().normalized()).await
+    }
+
+    #[tokio::test]
+    async fn acceptable() {
+        assert!(check("user@example.com", true).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn role_address() {
+        assert!(matches!(check("postmaster@example.com", false).await, Err(CheckEmailPolicyError::RoleAddress)));
+        assert!(matches!(check("NoReply+news@example.com", false).await, Err(CheckEmailPolicyError::RoleAddress)));
+    }
+
+    #[tokio::test]
+    async fn disposable_domain() {
+        assert!(matches!(check("user@Mail.Mailinator.com", false).await, Err(CheckEmailPolicyError::DisposableDomain)));
+    }
+
+    #[tokio::test]
+    async fn no_mx_record() {
+        assert!(matches!(check("user@example.net", true).await, Err(CheckEmailPolicyError::NoMxRecord)));
+    }
+
+    #[tokio::test]
+    async fn mx_check_disabled() {
+        assert!(check("user@example.net", false).await.is_ok());
+    }
+}
\ No newline at end of file
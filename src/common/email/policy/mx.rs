@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use hickory_resolver::{error::ResolveErrorKind, TokioAsyncResolver};
+use thiserror::Error;
+
+pub(crate) trait MxResolver {
+    // `domain`は正規化済みであること
+    async fn has_mx_record(&self, domain: &str) -> Result<bool, ResolveMxError>;
+}
+
+#[derive(Debug, Error)]
+#[error("MXレコードの解決に失敗しました")]
+pub struct ResolveMxError(#[source] pub anyhow::Error);
+
+// システムのDNS設定に従って問い合わせる
+pub struct DnsMxResolver(TokioAsyncResolver);
+
+#[derive(Debug, Error)]
+#[error("DNSリゾルバの初期化に失敗しました")]
+pub struct InitDnsMxResolverError(#[source] anyhow::Error);
+
+impl DnsMxResolver {
+    pub fn from_system_conf() -> Result<Self, InitDnsMxResolverError> {
+        TokioAsyncResolver::tokio_from_system_conf()
+            .map(Self)
+            .map_err(|e| InitDnsMxResolverError(e.into()))
+    }
+}
+
+impl MxResolver for DnsMxResolver {
+    async fn has_mx_record(&self, domain: &str) -> Result<bool, ResolveMxError> {
+        // 末尾に`.`を付け、検索ドメインが補完されないようにする
+        match self.0.mx_lookup(format!("{}.", domain)).await {
+            // RFC7505のNull MX(`.`のみ)は、メールを受け付けないことの表明
+            Ok(records) => Ok(records.iter().any(|record| !record.exchange().is_root())),
+            Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => Ok(false),
+            Err(e) => Err(ResolveMxError(e.into())),
+        }
+    }
+}
+
+// ネットワークに接続せず、与えられたドメインにのみMXレコードがあるとみなす
+pub struct OfflineMxResolver(HashSet<String>);
+
+impl OfflineMxResolver {
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(domains: I) -> Self {
+        Self(domains.into_iter().map(Into::into).collect())
+    }
+}
+
+impl MxResolver for OfflineMxResolver {
+    async fn has_mx_record(&self, domain: &str) -> Result<bool, ResolveMxError> {
+        Ok(self.0.contains(domain))
+    }
+}
\ No newline at end of file
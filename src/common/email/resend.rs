@@ -2,12 +2,18 @@ use resend_rs::{types::CreateEmailBaseOptions, Resend};
 
 use super::{address::Email, send::{Body, EmailSendFailed, EmailSender, NetmateEmail, SenderName, Subject}};
 
-pub struct ResendEmailSender;
+pub struct ResendEmailSender {
+    resend: Resend,
+}
 
-impl EmailSender for ResendEmailSender {
-    async fn send(from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body) -> Result<(), EmailSendFailed> {
-        let resend = Resend::new("");
+impl ResendEmailSender {
+    pub fn new(api_key: &str) -> Self {
+        Self { resend: Resend::new(api_key) }
+    }
+}
 
+impl EmailSender for ResendEmailSender {
+    async fn send(&self, from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body) -> Result<(), EmailSendFailed> {
         // ネットメイト <example@netmate.app>
         let from = format!("{} <{}>", sender_name, from);
         let to = [to.value()];
@@ -16,7 +22,7 @@ impl EmailSender for ResendEmailSender {
             .with_html(&body.html_content().to_string())
             .with_text(&body.plain_text().to_string());
 
-        resend.emails
+        self.resend.emails
             .send(email)
             .await
             .map(|_| ())
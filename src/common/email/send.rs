@@ -2,21 +2,29 @@ use std::{fmt::{self, Display}, str::FromStr};
 
 use thiserror::Error;
 
-use crate::{common::profile::language::Language, translation::{ja, us_en}};
+use crate::{common::profile::language::Language, translation::{ja, ko, us_en, zh_tw}};
 
 use super::address::Email;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SenderName(String);
 
 impl SenderName {
     pub fn by(language: Language) -> Self {
         let sender_name = match language {
             Language::Japanese => ja::email::SENDER_NAME,
-            _ => us_en::email::SENDER_NAME,
+            Language::Korean => ko::email::SENDER_NAME,
+            Language::TaiwaneseMandarin => zh_tw::email::SENDER_NAME,
+            Language::AmericanEnglish => us_en::email::SENDER_NAME,
         };
         Self(String::from(sender_name))
     }
 
+    // 送信待ちのメールとして保存された送信者名を復元する
+    pub fn new(sender_name: String) -> Self {
+        Self(sender_name)
+    }
+
     pub fn value(&self) -> &String {
         &self.0
     }
@@ -28,7 +36,7 @@ impl Display for SenderName {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NetmateEmail(Email);
 
 impl NetmateEmail {
@@ -61,7 +69,7 @@ impl TryFrom<Email> for NetmateEmail {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Subject(String);
 
 impl Subject {
@@ -96,6 +104,7 @@ impl FromStr for Subject {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct HtmlContent(String);
 
 impl HtmlContent {
@@ -114,6 +123,7 @@ impl Display for HtmlContent {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct PlainText(String);
 
 impl PlainText {
@@ -133,6 +143,7 @@ impl Display for PlainText {
 }
 
 // 長い行は折り返されるため、Bodyには制限を適用しない
+#[derive(Debug, Clone, PartialEq)]
 pub struct Body(HtmlContent, PlainText);
 
 impl Body {
@@ -150,7 +161,7 @@ impl Body {
 }
 
 pub(crate) trait EmailSender {
-    async fn send(from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body) -> Result<(), EmailSendFailed>;
+    async fn send(&self, from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body) -> Result<(), EmailSendFailed>;
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,198 @@
+use std::str::FromStr;
+
+use lettre::{message::{dkim::{DkimConfig, DkimSigningAlgorithm, DkimSigningKey}, Mailbox, MultiPart}, transport::smtp::{authentication::Credentials, client::{Tls, TlsParameters}}, Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use thiserror::Error;
+
+use super::{address::Email, send::{Body, EmailSendFailed, EmailSender, NetmateEmail, SenderName, Subject}};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SmtpSecurity {
+    // 接続後にSTARTTLSで暗号化する(既定のポートは587)
+    StartTls,
+    // 接続時点から暗号化する(既定のポートは465)
+    Tls,
+    // 開発環境のメールキャッチャー向け(既定のポートは25)
+    None,
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("有効なSMTPの暗号化方式ではありません")]
+pub struct ParseSmtpSecurityError;
+
+impl FromStr for SmtpSecurity {
+    type Err = ParseSmtpSecurityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "starttls" => Ok(SmtpSecurity::StartTls),
+            "tls" => Ok(SmtpSecurity::Tls),
+            // 平文で送信されるため、本番環境では選択できないようにする
+            #[cfg(debug_assertions)]
+            "none" => Ok(SmtpSecurity::None),
+            _ => Err(ParseSmtpSecurityError)
+        }
+    }
+}
+
+pub struct SmtpConfig {
+    host: String,
+    port: Option<u16>,
+    security: SmtpSecurity,
+    credentials: Option<(String, String)>,
+    dkim: Option<DkimConfig>,
+}
+
+impl SmtpConfig {
+    pub fn new(host: String, port: Option<u16>, security: SmtpSecurity, credentials: Option<(String, String)>, dkim: Option<DkimConfig>) -> Self {
+        Self { host, port, security, credentials, dkim }
+    }
+}
+
+// DKIMの秘密鍵は、RSAではPKCS#1のPEM形式、Ed25519ではBase64形式で与える
+pub fn dkim_config(selector: String, domain: String, private_key: &str, algorithm: DkimSigningAlgorithm) -> Result<DkimConfig, BuildSmtpSenderError> {
+    let private_key = DkimSigningKey::new(private_key, algorithm)
+        .map_err(|e| BuildSmtpSenderError::InvalidDkimKey(anyhow::anyhow!("{:?}", e)))?;
+
+    Ok(DkimConfig::default_config(selector, domain, private_key))
+}
+
+#[derive(Debug, Error)]
+pub enum BuildSmtpSenderError {
+    #[error("TLSの設定に失敗しました")]
+    TlsFailed(#[source] anyhow::Error),
+    #[error("DKIMの秘密鍵が不正です")]
+    InvalidDkimKey(#[source] anyhow::Error),
+}
+
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    dkim: Option<DkimConfig>,
+}
+
+impl SmtpEmailSender {
+    pub fn new(config: SmtpConfig) -> Result<Self, BuildSmtpSenderError> {
+        let tls = match config.security {
+            SmtpSecurity::StartTls => Tls::Required(TlsParameters::new(config.host.clone()).map_err(|e| BuildSmtpSenderError::TlsFailed(e.into()))?),
+            SmtpSecurity::Tls => Tls::Wrapper(TlsParameters::new(config.host.clone()).map_err(|e| BuildSmtpSenderError::TlsFailed(e.into()))?),
+            SmtpSecurity::None => Tls::None,
+        };
+
+        let port = config.port.unwrap_or(match config.security {
+            SmtpSecurity::StartTls => 587,
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::None => 25,
+        });
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(config.host)
+            .port(port)
+            .tls(tls);
+
+        if let Some((username, password)) = config.credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self { transport: builder.build(), dkim: config.dkim })
+    }
+}
+
+// 件名と本文からmultipart/alternativeのメッセージを組み立て、DKIMの設定があれば署名する
+pub fn build_message(from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body, dkim: Option<&DkimConfig>) -> anyhow::Result<Message> {
+    let from = Mailbox::new(Some(sender_name.value().clone()), Address::from_str(from.value().value())?);
+    let to = Mailbox::new(None, Address::from_str(to.value())?);
+
+    let mut message = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(subject.value())
+        .multipart(MultiPart::alternative_plain_html(body.plain_text().value().clone(), body.html_content().value().clone()))?;
+
+    if let Some(dkim) = dkim {
+        message.sign(dkim);
+    }
+
+    Ok(message)
+}
+
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, from: &NetmateEmail, to: &Email, sender_name: &SenderName, subject: &Subject, body: &Body) -> Result<(), EmailSendFailed> {
+        let message = build_message(from, to, sender_name, subject, body, self.dkim.as_ref())
+            .map_err(EmailSendFailed)?;
+
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| EmailSendFailed(e.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::LazyLock};
+
+    use lettre::message::dkim::DkimSigningAlgorithm;
+
+    use crate::common::{email::{address::Email, send::{Body, HtmlContent, NetmateEmail, PlainText, SenderName, Subject}}, profile::language::Language};
+
+    use super::{build_message, dkim_config, ParseSmtpSecurityError, SmtpSecurity};
+
+    static FROM: LazyLock<NetmateEmail> = LazyLock::new(|| NetmateEmail::try_from(Email::from_str("verify-email@account.netmate.app").unwrap()).unwrap());
+    static TO: LazyLock<Email> = LazyLock::new(|| Email::from_str("user@example.com").unwrap());
+
+    // テスト用のEd25519の秘密鍵(32バイトのゼロ)
+    const ED25519_PRIVATE_KEY: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+    fn formatted(signed: bool) -> String {
+        let dkim = signed.then(|| dkim_config(String::from("netmate"), String::from("account.netmate.app"), ED25519_PRIVATE_KEY, DkimSigningAlgorithm::Ed25519).unwrap());
+        let body = Body::new(HtmlContent::new("<p>本文</p>"), PlainText::new("本文"));
+        let message = build_message(&FROM, &TO, &SenderName::by(Language::Japanese), &Subject::from_str("Verify your email").unwrap(), &body, dkim.as_ref()).unwrap();
+        String::from_utf8(message.formatted()).unwrap()
+    }
+
+    #[test]
+    fn parse_security() {
+        assert_eq!(SmtpSecurity::from_str("starttls"), Ok(SmtpSecurity::StartTls));
+        assert_eq!(SmtpSecurity::from_str("tls"), Ok(SmtpSecurity::Tls));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn parse_plaintext_security() {
+        assert_eq!(SmtpSecurity::from_str("none"), Ok(SmtpSecurity::None));
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn reject_plaintext_security() {
+        assert_eq!(SmtpSecurity::from_str("none"), Err(ParseSmtpSecurityError));
+    }
+
+    #[test]
+    fn parse_unknown_security() {
+        assert_eq!(SmtpSecurity::from_str("ssl"), Err(ParseSmtpSecurityError));
+    }
+
+    #[test]
+    fn multipart_alternative() {
+        let message = formatted(false);
+        assert!(message.contains("To: user@example.com"));
+        assert!(message.contains("Subject: Verify your email"));
+        assert!(message.contains("multipart/alternative"));
+        assert!(message.contains("text/plain"));
+        assert!(message.contains("text/html"));
+        assert!(!message.contains("DKIM-Signature"));
+    }
+
+    #[test]
+    fn dkim_signed() {
+        let message = formatted(true);
+        assert!(message.contains("DKIM-Signature"));
+        assert!(message.contains("d=account.netmate.app"));
+        assert!(message.contains("s=netmate"));
+    }
+
+    #[test]
+    fn invalid_dkim_key() {
+        assert!(dkim_config(String::from("netmate"), String::from("account.netmate.app"), "invalid", DkimSigningAlgorithm::Ed25519).is_err());
+    }
+}
\ No newline at end of file
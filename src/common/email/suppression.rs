@@ -0,0 +1,24 @@
+use scylla::{frame::response::result::ColumnType, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+
+// 送信を停止した理由
+// ソフトバウンスは一時的な障害であるため、再送に任せ停止しない
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SuppressionReason {
+    HardBounce,
+    Complaint,
+}
+
+impl SuppressionReason {
+    pub fn value(&self) -> &'static str {
+        match self {
+            Self::HardBounce => "hard_bounce",
+            Self::Complaint => "complaint",
+        }
+    }
+}
+
+impl SerializeValue for SuppressionReason {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EmailTemplateKind {
+    EmailVerification,
+    SecurityNotification,
+}
+
+impl EmailTemplateKind {
+    pub const ALL: [EmailTemplateKind; 2] = [EmailTemplateKind::EmailVerification, EmailTemplateKind::SecurityNotification];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EmailVerification => "email_verification",
+            Self::SecurityNotification => "security_notification",
+        }
+    }
+
+    // テンプレートで参照できる変数
+    // 宣言されていない変数を参照するテンプレートは、読み込み時に拒否する
+    pub fn variables(&self) -> &'static [&'static str] {
+        match self {
+            Self::EmailVerification => &["verification_url", "expires_at"],
+            Self::SecurityNotification => &["email", "detected_at", "recovery_url"],
+        }
+    }
+}
\ No newline at end of file
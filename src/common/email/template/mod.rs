@@ -0,0 +1,4 @@
+pub mod kind;
+pub mod parse;
+pub mod registry;
+pub mod variable;
\ No newline at end of file
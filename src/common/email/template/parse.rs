@@ -0,0 +1,138 @@
+use thiserror::Error;
+
+use crate::common::profile::language::Language;
+
+use super::variable::{escape_html, TemplateVariables};
+
+const OPENING: &str = "{{";
+const CLOSING: &str = "}}";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Variable(String),
+}
+
+// `{{name}}`の形式で変数を埋め込んだテンプレート
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template(Vec<Segment>);
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseTemplateError {
+    #[error("変数が閉じられていません")]
+    UnclosedVariable,
+    #[error("変数名が不正です")]
+    InvalidVariableName(String),
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("変数{0}が与えられていません")]
+pub struct MissingVariableError(pub String);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Escape {
+    Html,
+    None,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, ParseTemplateError> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find(OPENING) {
+            if start > 0 {
+                segments.push(Segment::Literal(String::from(&rest[..start])));
+            }
+
+            let after_opening = &rest[start + OPENING.len()..];
+            let end = after_opening.find(CLOSING).ok_or(ParseTemplateError::UnclosedVariable)?;
+            let name = after_opening[..end].trim();
+
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+                return Err(ParseTemplateError::InvalidVariableName(String::from(name)));
+            }
+
+            segments.push(Segment::Variable(String::from(name)));
+            rest = &after_opening[end + CLOSING.len()..];
+        }
+
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(String::from(rest)));
+        }
+
+        Ok(Self(segments))
+    }
+
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    pub fn render(&self, variables: &TemplateVariables, language: Language, escape: Escape) -> Result<String, MissingVariableError> {
+        self.0.iter().try_fold(String::new(), |mut rendered, segment| {
+            match segment {
+                Segment::Literal(literal) => rendered.push_str(literal),
+                Segment::Variable(name) => {
+                    let value = variables.get(name)
+                        .ok_or_else(|| MissingVariableError(name.clone()))?
+                        .format(language);
+
+                    match escape {
+                        Escape::Html => rendered.push_str(&escape_html(&value)),
+                        Escape::None => rendered.push_str(&value),
+                    }
+                },
+            }
+            Ok(rendered)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{email::template::variable::TemplateVariables, profile::language::Language};
+
+    use super::{Escape, MissingVariableError, ParseTemplateError, Template};
+
+    #[test]
+    fn parse_and_render() {
+        let template = Template::parse("<a href=\"{{url}}\">{{ url }}</a>").unwrap();
+        let variables = TemplateVariables::new().text("url", "https://netmate.app/?a=1&b=2");
+        assert_eq!(template.variables().collect::<Vec<&str>>(), vec!["url", "url"]);
+        assert_eq!(template.render(&variables, Language::Japanese, Escape::Html).unwrap(), "<a href=\"https://netmate.app/?a=1&amp;b=2\">https://netmate.app/?a=1&amp;b=2</a>");
+        assert_eq!(template.render(&variables, Language::Japanese, Escape::None).unwrap(), "<a href=\"https://netmate.app/?a=1&b=2\">https://netmate.app/?a=1&b=2</a>");
+    }
+
+    #[test]
+    fn escape_injected_html() {
+        let template = Template::parse("<p>{{email}}</p>").unwrap();
+        let variables = TemplateVariables::new().text("email", "<script>\"'</script>");
+        assert_eq!(template.render(&variables, Language::Japanese, Escape::Html).unwrap(), "<p>&lt;script&gt;&quot;&#39;&lt;/script&gt;</p>");
+    }
+
+    #[test]
+    fn without_variables() {
+        let template = Template::parse("件名").unwrap();
+        assert_eq!(template.render(&TemplateVariables::new(), Language::Japanese, Escape::None).unwrap(), "件名");
+    }
+
+    #[test]
+    fn unclosed_variable() {
+        assert_eq!(Template::parse("{{url"), Err(ParseTemplateError::UnclosedVariable));
+    }
+
+    #[test]
+    fn invalid_variable_name() {
+        assert_eq!(Template::parse("{{Url}}"), Err(ParseTemplateError::InvalidVariableName(String::from("Url"))));
+        assert_eq!(Template::parse("{{}}"), Err(ParseTemplateError::InvalidVariableName(String::new())));
+    }
+
+    #[test]
+    fn missing_variable() {
+        let template = Template::parse("{{url}}").unwrap();
+        assert_eq!(template.render(&TemplateVariables::new(), Language::Japanese, Escape::None), Err(MissingVariableError(String::from("url"))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,205 @@
+use std::{collections::HashMap, str::FromStr};
+
+use thiserror::Error;
+
+use crate::common::{email::send::{Body, HtmlContent, PlainText, Subject}, profile::language::Language};
+
+use super::{kind::EmailTemplateKind, parse::{Escape, MissingVariableError, ParseTemplateError, Template}, variable::TemplateVariables};
+
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateSource {
+    subject: &'static str,
+    html: &'static str,
+    plain: &'static str,
+}
+
+macro_rules! template_source {
+    ($language:literal, $name:literal) => {
+        TemplateSource {
+            subject: include_str!(concat!("../../../translation/templates/", $language, "/", $name, ".subject.txt")),
+            html: include_str!(concat!("../../../translation/templates/", $language, "/", $name, ".html")),
+            plain: include_str!(concat!("../../../translation/templates/", $language, "/", $name, ".txt")),
+        }
+    };
+}
+
+macro_rules! template_sources {
+    ($language:literal) => {
+        [
+            (EmailTemplateKind::EmailVerification, template_source!($language, "email_verification")),
+            (EmailTemplateKind::SecurityNotification, template_source!($language, "security_notification")),
+        ]
+    };
+}
+
+const JA: [(EmailTemplateKind, TemplateSource); 2] = template_sources!("ja");
+const KO: [(EmailTemplateKind, TemplateSource); 2] = template_sources!("ko");
+const ZH_TW: [(EmailTemplateKind, TemplateSource); 2] = template_sources!("zh_tw");
+const US_EN: [(EmailTemplateKind, TemplateSource); 2] = template_sources!("us_en");
+
+// 言語を追加した際に翻訳の追加漏れがコンパイルエラーとなるよう、`_`を用いずに対応付ける
+fn template_sources(language: Language) -> &'static [(EmailTemplateKind, TemplateSource)] {
+    match language {
+        Language::Japanese => &JA,
+        Language::Korean => &KO,
+        Language::TaiwaneseMandarin => &ZH_TW,
+        Language::AmericanEnglish => &US_EN,
+    }
+}
+
+#[derive(Debug)]
+struct EmailTemplate {
+    subject: Template,
+    html: Template,
+    plain: Template,
+}
+
+#[derive(Debug, Error)]
+pub enum LoadEmailTemplatesError {
+    #[error("{language:?}の{kind:?}のテンプレートがありません")]
+    MissingTemplate { language: Language, kind: EmailTemplateKind },
+    #[error("{language:?}の{kind:?}のテンプレートの解析に失敗しました")]
+    ParseFailed { language: Language, kind: EmailTemplateKind, #[source] source: ParseTemplateError },
+    #[error("{language:?}の{kind:?}のテンプレートが宣言されていない変数{variable}を参照しています")]
+    UndeclaredVariable { language: Language, kind: EmailTemplateKind, variable: String },
+}
+
+#[derive(Debug, Error)]
+pub enum RenderEmailError {
+    #[error("テンプレートの描画に失敗しました")]
+    MissingVariable(#[from] MissingVariableError),
+    #[error("描画した件名が件名の形式を満たしませんでした")]
+    InvalidSubject,
+}
+
+// 全ての言語と種類の組み合わせについて、テンプレートが揃っていることを読み込み時に保証する
+#[derive(Debug)]
+pub struct EmailTemplates(HashMap<(Language, EmailTemplateKind), EmailTemplate>);
+
+impl EmailTemplates {
+    pub fn load() -> Result<Self, LoadEmailTemplatesError> {
+        let sources = Language::ALL.into_iter()
+            .flat_map(|language| template_sources(language).iter().map(move |(kind, source)| (language, *kind, *source)))
+            .collect::<Vec<_>>();
+
+        Self::load_from(&sources)
+    }
+
+    pub fn load_from(sources: &[(Language, EmailTemplateKind, TemplateSource)]) -> Result<Self, LoadEmailTemplatesError> {
+        let mut templates = HashMap::new();
+
+        for language in Language::ALL {
+            for kind in EmailTemplateKind::ALL {
+                let source = sources.iter()
+                    .find(|(l, k, _)| *l == language && *k == kind)
+                    .map(|(_, _, source)| source)
+                    .ok_or(LoadEmailTemplatesError::MissingTemplate { language, kind })?;
+
+                let parse = |source: &str| Template::parse(source.trim_end())
+                    .map_err(|source| LoadEmailTemplatesError::ParseFailed { language, kind, source });
+
+                let template = EmailTemplate { subject: parse(source.subject)?, html: parse(source.html)?, plain: parse(source.plain)? };
+
+                let undeclared = [&template.subject, &template.html, &template.plain]
+                    .into_iter()
+                    .flat_map(Template::variables)
+                    .find(|variable| !kind.variables().contains(variable));
+
+                if let Some(variable) = undeclared {
+                    return Err(LoadEmailTemplatesError::UndeclaredVariable { language, kind, variable: String::from(variable) });
+                }
+
+                templates.insert((language, kind), template);
+            }
+        }
+
+        Ok(Self(templates))
+    }
+
+    pub fn render(&self, kind: EmailTemplateKind, language: Language, variables: &TemplateVariables) -> Result<(Subject, Body), RenderEmailError> {
+        // 読み込み時に全ての組み合わせが揃っていることを検証しているため、`unwrap()`で問題ない
+        let template = self.0.get(&(language, kind)).unwrap();
+
+        let subject = template.subject.render(variables, language, Escape::None)?;
+        let subject = Subject::from_str(&subject).map_err(|_| RenderEmailError::InvalidSubject)?;
+
+        let html = template.html.render(variables, language, Escape::Html)?;
+        let plain = template.plain.render(variables, language, Escape::None)?;
+
+        Ok((subject, Body::new(HtmlContent::new(&html), PlainText::new(&plain))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::common::{email::template::{kind::EmailTemplateKind, variable::TemplateVariables}, profile::language::Language, unixtime::UnixtimeMillis};
+
+    use super::{EmailTemplates, LoadEmailTemplatesError, RenderEmailError, TemplateSource};
+
+    static TEMPLATES: LazyLock<EmailTemplates> = LazyLock::new(|| EmailTemplates::load().unwrap());
+
+    fn verification_variables() -> TemplateVariables {
+        TemplateVariables::new()
+            .text("verification_url", "https://netmate.app/verify-email/token")
+            .datetime("expires_at", UnixtimeMillis::of(0))
+    }
+
+    fn sources(subject: &'static str) -> Vec<(Language, EmailTemplateKind, TemplateSource)> {
+        Language::ALL.into_iter()
+            .flat_map(|language| EmailTemplateKind::ALL.into_iter().map(move |kind| (language, kind, TemplateSource { subject, html: "", plain: "" })))
+            .collect()
+    }
+
+    #[test]
+    fn all_templates_exist() {
+        LazyLock::force(&TEMPLATES);
+    }
+
+    #[test]
+    fn render_verification_email() {
+        let (subject, body) = TEMPLATES.render(EmailTemplateKind::EmailVerification, Language::Japanese, &verification_variables()).unwrap();
+        assert_eq!(subject.value(), "メールアドレスの認証をしてください");
+        assert!(body.html_content().value().contains("<a href=\"https://netmate.app/verify-email/token\">"));
+        assert!(body.plain_text().value().contains("https://netmate.app/verify-email/token"));
+        assert!(!body.plain_text().value().contains("{{"));
+    }
+
+    // 全ての言語について、各言語の翻訳で描画されることを保証する
+    #[test]
+    fn every_language_translated() {
+        let subjects = Language::ALL.map(|language| {
+            let (subject, _) = TEMPLATES.render(EmailTemplateKind::EmailVerification, language, &verification_variables()).unwrap();
+            subject.value().clone()
+        });
+
+        for (i, subject) in subjects.iter().enumerate() {
+            assert!(!subjects[i + 1..].contains(subject));
+        }
+    }
+
+    #[test]
+    fn missing_variable() {
+        let variables = TemplateVariables::new().text("verification_url", "https://netmate.app/verify-email/token");
+        let result = TEMPLATES.render(EmailTemplateKind::EmailVerification, Language::AmericanEnglish, &variables);
+        assert!(matches!(result, Err(RenderEmailError::MissingVariable(_))));
+    }
+
+    #[test]
+    fn missing_template() {
+        let mut sources = sources("件名");
+        sources.retain(|(language, _, _)| *language != Language::Korean);
+        assert!(matches!(EmailTemplates::load_from(&sources), Err(LoadEmailTemplatesError::MissingTemplate { language: Language::Korean, .. })));
+    }
+
+    #[test]
+    fn undeclared_variable() {
+        assert!(matches!(EmailTemplates::load_from(&sources("{{token}}")), Err(LoadEmailTemplatesError::UndeclaredVariable { .. })));
+    }
+
+    #[test]
+    fn unparsable_template() {
+        assert!(matches!(EmailTemplates::load_from(&sources("{{token")), Err(LoadEmailTemplatesError::ParseFailed { .. })));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::common::{profile::language::Language, unixtime::UnixtimeMillis};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateValue {
+    Text(String),
+    // 受信者の言語に応じた形式とタイムゾーンで表示する
+    DateTime(UnixtimeMillis),
+}
+
+impl TemplateValue {
+    pub fn format(&self, language: Language) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::DateTime(datetime) => format_datetime(*datetime, language),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateVariables(Vec<(&'static str, TemplateValue)>);
+
+impl TemplateVariables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, name: &'static str, text: &str) -> Self {
+        self.0.push((name, TemplateValue::Text(String::from(text))));
+        self
+    }
+
+    pub fn datetime(mut self, name: &'static str, datetime: UnixtimeMillis) -> Self {
+        self.0.push((name, TemplateValue::DateTime(datetime)));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TemplateValue> {
+        self.0.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value)
+    }
+}
+
+// 属性値に埋め込まれても安全なよう、引用符もエスケープする
+pub fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn format_datetime(datetime: UnixtimeMillis, language: Language) -> String {
+    let (offset_hours, suffix) = match language {
+        Language::Japanese => (9, "(日本時間)"),
+        Language::Korean => (9, "(KST)"),
+        Language::TaiwaneseMandarin => (8, "(UTC+8)"),
+        Language::AmericanEnglish => (0, "UTC"),
+    };
+
+    // `UnixtimeMillis`は常に表現可能な範囲にあるため、失敗した場合はUNIXエポックとして扱う
+    let datetime = OffsetDateTime::from_unix_timestamp_nanos(datetime.value() as i128 * 1_000_000)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+        .to_offset(UtcOffset::from_hms(offset_hours, 0, 0).unwrap_or(UtcOffset::UTC));
+
+    let (year, month, day, hour, minute) = (datetime.year(), u8::from(datetime.month()), datetime.day(), datetime.hour(), datetime.minute());
+
+    match language {
+        Language::Japanese | Language::TaiwaneseMandarin => format!("{}年{}月{}日 {:02}:{:02} {}", year, month, day, hour, minute, suffix),
+        Language::Korean => format!("{}년 {}월 {}일 {:02}:{:02} {}", year, month, day, hour, minute, suffix),
+        Language::AmericanEnglish => format!("{:04}-{:02}-{:02} {:02}:{:02} {}", year, month, day, hour, minute, suffix),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{profile::language::Language, unixtime::UnixtimeMillis};
+
+    use super::{escape_html, TemplateValue, TemplateVariables};
+
+    // 2024-08-01T15:04:00Z
+    const DATETIME: u64 = 1722524640000;
+
+    fn format(language: Language) -> String {
+        TemplateValue::DateTime(UnixtimeMillis::of(DATETIME)).format(language)
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_html("<a href=\"x\">&'</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;");
+    }
+
+    #[test]
+    fn datetime_per_language() {
+        assert_eq!(format(Language::Japanese), "2024年8月2日 00:04 (日本時間)");
+        assert_eq!(format(Language::Korean), "2024년 8월 2일 00:04 (KST)");
+        assert_eq!(format(Language::TaiwaneseMandarin), "2024年8月1日 23:04 (UTC+8)");
+        assert_eq!(format(Language::AmericanEnglish), "2024-08-01 15:04 UTC");
+    }
+
+    #[test]
+    fn get_variable() {
+        let variables = TemplateVariables::new().text("email", "user@example.com");
+        assert_eq!(variables.get("email"), Some(&TemplateValue::Text(String::from("user@example.com"))));
+        assert_eq!(variables.get("token"), None);
+    }
+}
\ No newline at end of file
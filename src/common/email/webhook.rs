@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::common::unixtime::UnixtimeMillis;
+
+// 再送攻撃を防ぐため、署名時刻との差がこれを超える通知は受け付けない
+const SIGNATURE_TOLERANCE_SECONDS: u64 = 5 * 60;
+
+const SECRET_PREFIX: &str = "whsec_";
+
+// Resendのwebhookは、Svix形式(`{id}.{timestamp}.{payload}`のHMAC-SHA256)で署名される
+pub struct WebhookSecret(Vec<u8>);
+
+#[derive(Debug, PartialEq, Error)]
+#[error("webhookの署名鍵の形式を満たしませんでした")]
+pub struct ParseWebhookSecretError;
+
+impl FromStr for WebhookSecret {
+    type Err = ParseWebhookSecretError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded = s.strip_prefix(SECRET_PREFIX).unwrap_or(s);
+
+        STANDARD.decode(encoded)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(Self)
+            .ok_or(ParseWebhookSecretError)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadWebhookSecretError {
+    #[error("環境変数RESEND_WEBHOOK_SECRETが設定されていません")]
+    MissingVariable,
+    #[error("webhookの署名鍵の解析に失敗しました")]
+    InvalidSecret(#[source] ParseWebhookSecretError),
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum VerifyWebhookSignatureError {
+    #[error("署名時刻の形式が不正です")]
+    InvalidTimestamp,
+    #[error("署名時刻が許容範囲外です")]
+    TimestampOutOfTolerance,
+    #[error("署名が一致しません")]
+    SignatureMismatch,
+}
+
+impl WebhookSecret {
+    // RESEND_WEBHOOK_SECRET: Resendのwebhookの署名鍵(whsec_から始まる)
+    pub fn from_env() -> Result<Self, LoadWebhookSecretError> {
+        let secret = dotenvy::var("RESEND_WEBHOOK_SECRET").map_err(|_| LoadWebhookSecretError::MissingVariable)?;
+        Self::from_str(&secret).map_err(LoadWebhookSecretError::InvalidSecret)
+    }
+
+    // 署名ヘッダには鍵の更新中に複数の署名が空白区切りで含まれ得るため、いずれかが一致すればよい
+    pub fn verify(&self, id: &str, timestamp: &str, signatures: &str, payload: &[u8], now: UnixtimeMillis) -> Result<(), VerifyWebhookSignatureError> {
+        let signed_at = u64::from_str(timestamp).map_err(|_| VerifyWebhookSignatureError::InvalidTimestamp)?;
+
+        if (now.value() / 1000).abs_diff(signed_at) > SIGNATURE_TOLERANCE_SECONDS {
+            return Err(VerifyWebhookSignatureError::TimestampOutOfTolerance);
+        }
+
+        let mac = {
+            // HMACは任意の長さの鍵を受け付けるため、`unwrap()`で問題ない
+            let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).unwrap();
+            mac.update(id.as_bytes());
+            mac.update(b".");
+            mac.update(timestamp.as_bytes());
+            mac.update(b".");
+            mac.update(payload);
+            mac
+        };
+
+        let matched = signatures
+            .split_whitespace()
+            .filter_map(|signature| signature.strip_prefix("v1,"))
+            .filter_map(|signature| STANDARD.decode(signature).ok())
+            // `verify_slice`は定数時間で比較する
+            .any(|signature| mac.clone().verify_slice(&signature).is_ok());
+
+        if matched {
+            Ok(())
+        } else {
+            Err(VerifyWebhookSignatureError::SignatureMismatch)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::common::unixtime::UnixtimeMillis;
+
+    use super::{ParseWebhookSecretError, VerifyWebhookSignatureError, WebhookSecret};
+
+    // Svixのドキュメントに記載されている検証用の値
+    const SECRET: &str = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+    const ID: &str = "msg_p5jXN8AQM9LWM0D4loKWxJek";
+    const TIMESTAMP: &str = "1614265330";
+    const PAYLOAD: &[u8] = br#"{"test": 2432232314}"#;
+    const SIGNATURE: &str = "v1,g0hM9SsE+OTPJTGt/tmIKtSyZlE3uFJELVlNIOLJ1OE=";
+
+    const SIGNED_AT_MILLIS: u64 = 1_614_265_330_000;
+
+    fn verify(signatures: &str, payload: &[u8], now: u64) -> Result<(), VerifyWebhookSignatureError> {
+        WebhookSecret::from_str(SECRET).unwrap().verify(ID, TIMESTAMP, signatures, payload, UnixtimeMillis::of(now))
+    }
+
+    #[test]
+    fn valid_signature() {
+        assert_eq!(verify(SIGNATURE, PAYLOAD, SIGNED_AT_MILLIS), Ok(()));
+    }
+
+    #[test]
+    fn one_of_multiple_signatures() {
+        let signatures = format!("v1,aW52YWxpZA== {}", SIGNATURE);
+        assert_eq!(verify(&signatures, PAYLOAD, SIGNED_AT_MILLIS), Ok(()));
+    }
+
+    #[test]
+    fn tampered_payload() {
+        assert_eq!(verify(SIGNATURE, br#"{"test": 2432232315}"#, SIGNED_AT_MILLIS), Err(VerifyWebhookSignatureError::SignatureMismatch));
+    }
+
+    #[test]
+    fn stale_timestamp() {
+        assert_eq!(verify(SIGNATURE, PAYLOAD, SIGNED_AT_MILLIS + 6 * 60 * 1000), Err(VerifyWebhookSignatureError::TimestampOutOfTolerance));
+    }
+
+    #[test]
+    fn invalid_secret() {
+        assert!(matches!(WebhookSecret::from_str("whsec_!!!"), Err(ParseWebhookSecretError)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,206 @@
+use std::{fmt::{self, Display}, str::FromStr};
+
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::common::{handle::id::HandleId, tag::{non_top_tag::NonTopTagId, tag_id::TagId}, uuid::uuid4::Uuid4};
+
+// フォローの対象
+// トップタグは範囲が広すぎるため、フォローできるのは非トップタグのみ
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum Followee {
+    Handle(HandleId),
+    Tag(NonTopTagId),
+}
+
+impl Followee {
+    pub fn kind(&self) -> FolloweeKind {
+        match self {
+            Followee::Handle(_) => FolloweeKind::Handle,
+            Followee::Tag(_) => FolloweeKind::Tag,
+        }
+    }
+
+    pub fn id(&self) -> Uuid4 {
+        match self {
+            Followee::Handle(handle_id) => handle_id.value(),
+            Followee::Tag(tag_id) => tag_id.value().value(),
+        }
+    }
+
+    pub fn of(kind: FolloweeKind, id: Uuid4) -> Result<Self, ParseFolloweeError> {
+        match kind {
+            FolloweeKind::Handle => Ok(Followee::Handle(HandleId::of(id))),
+            FolloweeKind::Tag => NonTopTagId::try_from(TagId::of(id))
+                .map(Followee::Tag)
+                .map_err(|_| ParseFolloweeError::TopTag),
+        }
+    }
+}
+
+// カーソルとして使うため、`<種類>:<ID>`の形式で文字列化する
+impl Display for Followee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind(), self.id())
+    }
+}
+
+impl FromStr for Followee {
+    type Err = ParseFolloweeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s.split_once(':').ok_or(ParseFolloweeError::InvalidFormat)?;
+
+        let kind = FolloweeKind::from_str(kind)?;
+        let id = Uuid::from_str(id)
+            .ok()
+            .and_then(|id| Uuid4::try_from(id).ok())
+            .ok_or(ParseFolloweeError::InvalidId)?;
+
+        Followee::of(kind, id)
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseFolloweeError {
+    #[error("フォロー対象の形式が不正です")]
+    InvalidFormat,
+    #[error("フォロー対象の種類が不正です")]
+    InvalidKind,
+    #[error("フォロー対象のIDが不正です")]
+    InvalidId,
+    #[error("トップタグはフォローできません")]
+    TopTag,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FolloweeKind {
+    Handle,
+    Tag,
+}
+
+impl FolloweeKind {
+    pub fn value(&self) -> i8 {
+        match self {
+            FolloweeKind::Handle => 0,
+            FolloweeKind::Tag => 1,
+        }
+    }
+}
+
+impl TryFrom<i8> for FolloweeKind {
+    type Error = ParseFolloweeError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FolloweeKind::Handle),
+            1 => Ok(FolloweeKind::Tag),
+            _ => Err(ParseFolloweeError::InvalidKind),
+        }
+    }
+}
+
+impl Display for FolloweeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FolloweeKind::Handle => write!(f, "handle"),
+            FolloweeKind::Tag => write!(f, "tag"),
+        }
+    }
+}
+
+impl FromStr for FolloweeKind {
+    type Err = ParseFolloweeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "handle" => Ok(FolloweeKind::Handle),
+            "tag" => Ok(FolloweeKind::Tag),
+            _ => Err(ParseFolloweeError::InvalidKind),
+        }
+    }
+}
+
+impl SerializeValue for FolloweeKind {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for FolloweeKind {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        i8::from_cql(cql_val).and_then(|v| FolloweeKind::try_from(v).map_err(|_| FromCqlValError::BadVal))
+    }
+}
+
+// 一覧の続きを取得するためのカーソル
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct FollowCursor(Followee);
+
+impl FollowCursor {
+    pub fn value(&self) -> Followee {
+        self.0
+    }
+}
+
+impl From<Followee> for FollowCursor {
+    fn from(value: Followee) -> Self {
+        Self(value)
+    }
+}
+
+impl Serialize for FollowCursor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for FollowCursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)
+            .and_then(|v| Followee::from_str(&v).map(FollowCursor).map_err(de::Error::custom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{common::{handle::id::HandleId, tag::top_tag}, helper::test::mock_non_top_tag_id};
+
+    use super::{FollowCursor, Followee, FolloweeKind, ParseFolloweeError};
+
+    #[test]
+    fn round_trip() {
+        for followee in [Followee::Handle(HandleId::gen()), Followee::Tag(mock_non_top_tag_id(1))] {
+            assert_eq!(Followee::from_str(&followee.to_string()), Ok(followee));
+            assert_eq!(Followee::of(followee.kind(), followee.id()), Ok(followee));
+        }
+    }
+
+    #[test]
+    fn invalid_string() {
+        assert_eq!(Followee::from_str("handle"), Err(ParseFolloweeError::InvalidFormat));
+        assert_eq!(Followee::from_str("user:0"), Err(ParseFolloweeError::InvalidKind));
+        assert_eq!(Followee::from_str("tag:not-a-uuid"), Err(ParseFolloweeError::InvalidId));
+    }
+
+    #[test]
+    fn top_tag() {
+        let top_tag_id = top_tag::JAPANESE.value().value();
+        assert_eq!(Followee::of(FolloweeKind::Tag, top_tag_id), Err(ParseFolloweeError::TopTag));
+    }
+
+    #[test]
+    fn serialize_as_tagged_json() {
+        let handle_id = HandleId::gen();
+        let json = serde_json::to_string(&Followee::Handle(handle_id)).unwrap();
+        assert_eq!(json, format!(r#"{{"type":"handle","id":"{}"}}"#, handle_id));
+
+        let cursor = serde_json::to_string(&FollowCursor::from(Followee::Handle(handle_id))).unwrap();
+        assert_eq!(cursor, format!(r#""handle:{}""#, handle_id));
+    }
+}
\ No newline at end of file
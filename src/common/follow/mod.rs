@@ -0,0 +1 @@
+pub mod followee;
\ No newline at end of file
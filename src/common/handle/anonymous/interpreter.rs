@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId}, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+use super::{provision::{ProvisionAnonymousHandle, ProvisionAnonymousHandleError}, repair::{AccountCursor, RepairAnonymousHandles, RepairAnonymousHandlesError}};
+
+pub struct AnonymousHandleProvisionerImpl {
+    db: Arc<Session>,
+    insert_anonymous_handle_claim: Arc<PreparedStatement>,
+    select_anonymous_handle_claim: Arc<PreparedStatement>,
+    select_handle: Arc<PreparedStatement>,
+    insert_anonymous_handle: Arc<PreparedStatement>,
+}
+
+impl AnonymousHandleProvisionerImpl {
+    pub async fn try_new<T>(db: Arc<Session>) -> Result<Self, InitError<T>> {
+        let insert_anonymous_handle_claim = prepare(&db, "INSERT INTO anonymous_handles (account_id, handle_id) VALUES (?, ?) IF NOT EXISTS").await?;
+
+        let select_anonymous_handle_claim = prepare(&db, "SELECT handle_id FROM anonymous_handles WHERE account_id = ?").await?;
+
+        let select_handle = prepare(&db, "SELECT handle_id FROM handles WHERE account_id = ? AND handle_id = ?").await?;
+
+        // ログ付きバッチで名義、共有数、所有者の索引をまとめて作成する
+        let insert_anonymous_handle = prepare(&db, "
+            BEGIN BATCH
+                INSERT INTO handles (account_id, handle_id, handle_name) VALUES (?, ?, '');
+                INSERT INTO handle_share_counts (account_id, handle_id, share_count) VALUES (?, ?, 0);
+                INSERT INTO handle_owners (handle_id, account_id) VALUES (?, ?);
+            APPLY BATCH
+        ").await?;
+
+        Ok(Self { db, insert_anonymous_handle_claim, select_anonymous_handle_claim, select_handle, insert_anonymous_handle })
+    }
+}
+
+impl ProvisionAnonymousHandle for AnonymousHandleProvisionerImpl {
+    async fn claim_anonymous_handle_id(&self, account_id: AccountId, candidate: HandleId) -> Fallible<Option<HandleId>, ProvisionAnonymousHandleError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ProvisionAnonymousHandleError {
+            ProvisionAnonymousHandleError::ClaimAnonymousHandleIdFailed(e.into())
+        }
+
+        let res = self.db
+            .execute_unpaged(&self.insert_anonymous_handle_claim, (account_id, candidate))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) => Ok(None),
+            Err(Some(e)) => Err(handle_error(e)),
+            // 既に確保されている場合は、そのIDを取得する
+            Err(None) => self.db
+                .execute_unpaged(&self.select_anonymous_handle_claim, (account_id, ))
+                .await
+                .map_err(handle_error)?
+                .first_row_typed::<(HandleId, )>()
+                .map(|(handle_id, )| Some(handle_id))
+                .map_err(handle_error),
+        }
+    }
+
+    async fn anonymous_handle_exists(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, ProvisionAnonymousHandleError> {
+        self.db
+            .execute_unpaged(&self.select_handle, (account_id, handle_id))
+            .await
+            .map_err(|e| ProvisionAnonymousHandleError::CheckAnonymousHandleExistsFailed(e.into()))?
+            .maybe_first_row_typed::<(HandleId, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| ProvisionAnonymousHandleError::CheckAnonymousHandleExistsFailed(e.into()))
+    }
+
+    async fn insert_anonymous_handle(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), ProvisionAnonymousHandleError> {
+        self.db
+            .execute_unpaged(&self.insert_anonymous_handle, (account_id, handle_id, account_id, handle_id, handle_id, account_id))
+            .await
+            .map(|_| ())
+            .map_err(|e| ProvisionAnonymousHandleError::InsertAnonymousHandleFailed(e.into()))
+    }
+}
+
+pub struct AnonymousHandleRepairImpl {
+    db: Arc<Session>,
+    provisioner: AnonymousHandleProvisionerImpl,
+    select_accounts: Arc<PreparedStatement>,
+    select_handles: Arc<PreparedStatement>,
+    insert_handle_owner: Arc<PreparedStatement>,
+    insert_missing_share_count: Arc<PreparedStatement>,
+}
+
+impl AnonymousHandleRepairImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let provisioner = AnonymousHandleProvisionerImpl::try_new(db.clone()).await?;
+
+        let select_accounts = prepare(&db, "SELECT id, token(id) FROM accounts WHERE token(id) > ? LIMIT ?").await?;
+
+        let select_handles = prepare(&db, "SELECT handle_id, handle_name FROM handles WHERE account_id = ?").await?;
+
+        let insert_handle_owner = prepare(&db, "INSERT INTO handle_owners (handle_id, account_id) VALUES (?, ?)").await?;
+
+        let insert_missing_share_count = prepare(&db, "INSERT INTO handle_share_counts (account_id, handle_id, share_count) VALUES (?, ?, 0) IF NOT EXISTS").await?;
+
+        Ok(Self { db, provisioner, select_accounts, select_handles, insert_handle_owner, insert_missing_share_count })
+    }
+
+    // 全てのアカウントを一度走査するタスクを起動する
+    // 匿名名義のIDの確保により、複数のインスタンスで同時に実行しても重複は生じない
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            match self.repair_anonymous_handles().await {
+                Ok(summary) => info!(
+                    scanned = summary.scanned(),
+                    repaired = summary.repaired(),
+                    indexed = summary.indexed(),
+                    "匿名名義の修復が完了しました。"
+                ),
+                Err(e) => warn!(error = %e, "匿名名義の修復に失敗しました。"),
+            }
+        })
+    }
+}
+
+impl ProvisionAnonymousHandle for AnonymousHandleRepairImpl {
+    async fn claim_anonymous_handle_id(&self, account_id: AccountId, candidate: HandleId) -> Fallible<Option<HandleId>, ProvisionAnonymousHandleError> {
+        self.provisioner.claim_anonymous_handle_id(account_id, candidate).await
+    }
+
+    async fn anonymous_handle_exists(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, ProvisionAnonymousHandleError> {
+        self.provisioner.anonymous_handle_exists(account_id, handle_id).await
+    }
+
+    async fn insert_anonymous_handle(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), ProvisionAnonymousHandleError> {
+        self.provisioner.insert_anonymous_handle(account_id, handle_id).await
+    }
+}
+
+impl RepairAnonymousHandles for AnonymousHandleRepairImpl {
+    async fn fetch_accounts(&self, cursor: AccountCursor, limit: i32) -> Fallible<(Vec<AccountId>, Option<AccountCursor>), RepairAnonymousHandlesError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> RepairAnonymousHandlesError {
+            RepairAnonymousHandlesError::FetchAccountsFailed(e.into())
+        }
+
+        let rows = self.db
+            .execute_unpaged(&self.select_accounts, (cursor.value(), limit))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(AccountId, i64)>()
+            .map_err(handle_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)?;
+
+        let next_cursor = match rows.last() {
+            Some((_, token)) if rows.len() as i32 == limit => Some(AccountCursor::of(*token)),
+            _ => None,
+        };
+
+        Ok((rows.into_iter().map(|(account_id, _)| account_id).collect(), next_cursor))
+    }
+
+    async fn fetch_handles(&self, account_id: AccountId) -> Fallible<Vec<(HandleId, bool)>, RepairAnonymousHandlesError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> RepairAnonymousHandlesError {
+            RepairAnonymousHandlesError::FetchHandlesFailed(e.into())
+        }
+
+        // 匿名名義は空の名前で作成されている
+        self.db
+            .execute_unpaged(&self.select_handles, (account_id, ))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(HandleId, String)>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(handle_id, handle_name)| (handle_id, handle_name.is_empty())))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)
+    }
+
+    async fn insert_handle_owner(&self, handle_id: HandleId, account_id: AccountId) -> Fallible<(), RepairAnonymousHandlesError> {
+        self.db
+            .execute_unpaged(&self.insert_handle_owner, (handle_id, account_id))
+            .await
+            .map(|_| ())
+            .map_err(|e| RepairAnonymousHandlesError::InsertHandleOwnerFailed(e.into()))
+    }
+
+    async fn insert_missing_share_count(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), RepairAnonymousHandlesError> {
+        // 既に存在する場合は適用されないが、それで問題ない
+        self.db
+            .execute_unpaged(&self.insert_missing_share_count, (account_id, handle_id))
+            .await
+            .map(|_| ())
+            .map_err(|e| RepairAnonymousHandlesError::InsertShareCountFailed(e.into()))
+    }
+}
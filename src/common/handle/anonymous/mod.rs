@@ -0,0 +1,3 @@
+pub mod interpreter;
+pub mod provision;
+pub mod repair;
\ No newline at end of file
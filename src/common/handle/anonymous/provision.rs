@@ -0,0 +1,103 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+pub(crate) trait ProvisionAnonymousHandle {
+    // アカウントごとに匿名名義のIDを一つだけ確保してから名義を作成する
+    // 確保済みの場合はそのIDを用いるため、アカウント作成と修復ジョブが並行しても重複しない
+    // 名義を作成した場合は`true`を返す
+    async fn provision_anonymous_handle(&self, account_id: AccountId, candidate: HandleId) -> Fallible<bool, ProvisionAnonymousHandleError> {
+        let handle_id = match self.claim_anonymous_handle_id(account_id, candidate).await? {
+            Some(handle_id) => handle_id,
+            None => candidate,
+        };
+
+        if self.anonymous_handle_exists(account_id, handle_id).await? {
+            return Ok(false);
+        }
+
+        self.insert_anonymous_handle(account_id, handle_id).await?;
+        Ok(true)
+    }
+
+    // 確保に成功した場合は`None`を、既に確保されていた場合はそのIDを返す
+    async fn claim_anonymous_handle_id(&self, account_id: AccountId, candidate: HandleId) -> Fallible<Option<HandleId>, ProvisionAnonymousHandleError>;
+
+    async fn anonymous_handle_exists(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, ProvisionAnonymousHandleError>;
+
+    // 名義と共有数を同時に作成する
+    async fn insert_anonymous_handle(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), ProvisionAnonymousHandleError>;
+}
+
+#[derive(Debug, Error)]
+pub enum ProvisionAnonymousHandleError {
+    #[error("匿名名義のIDの確保に失敗しました")]
+    ClaimAnonymousHandleIdFailed(#[source] anyhow::Error),
+    #[error("匿名名義の存在の確認に失敗しました")]
+    CheckAnonymousHandleExistsFailed(#[source] anyhow::Error),
+    #[error("匿名名義の作成に失敗しました")]
+    InsertAnonymousHandleFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::{LazyLock, Mutex}};
+
+    use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+    use super::{ProvisionAnonymousHandle, ProvisionAnonymousHandleError};
+
+    static CLAIMED_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    #[derive(Default)]
+    struct MockProvisionAnonymousHandle {
+        claims: Mutex<HashMap<AccountId, HandleId>>,
+        handles: Mutex<Vec<(AccountId, HandleId)>>,
+    }
+
+    impl ProvisionAnonymousHandle for MockProvisionAnonymousHandle {
+        async fn claim_anonymous_handle_id(&self, account_id: AccountId, candidate: HandleId) -> Fallible<Option<HandleId>, ProvisionAnonymousHandleError> {
+            let mut claims = self.claims.lock().unwrap();
+
+            match claims.get(&account_id) {
+                Some(handle_id) => Ok(Some(*handle_id)),
+                None => {
+                    claims.insert(account_id, candidate);
+                    Ok(None)
+                }
+            }
+        }
+
+        async fn anonymous_handle_exists(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, ProvisionAnonymousHandleError> {
+            Ok(self.handles.lock().unwrap().contains(&(account_id, handle_id)))
+        }
+
+        async fn insert_anonymous_handle(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), ProvisionAnonymousHandleError> {
+            self.handles.lock().unwrap().push((account_id, handle_id));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn provision() {
+        let mock = MockProvisionAnonymousHandle::default();
+        let account_id = AccountId::gen();
+
+        assert!(mock.provision_anonymous_handle(account_id, HandleId::gen()).await.unwrap());
+
+        // 二度目は作成しない
+        assert!(!mock.provision_anonymous_handle(account_id, HandleId::gen()).await.unwrap());
+        assert_eq!(mock.handles.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn use_claimed_handle_id() {
+        let mock = MockProvisionAnonymousHandle::default();
+        let account_id = AccountId::gen();
+        mock.claims.lock().unwrap().insert(account_id, *CLAIMED_HANDLE_ID);
+
+        // 確保後に名義の作成が中断された場合も、確保済みのIDで作成する
+        assert!(mock.provision_anonymous_handle(account_id, HandleId::gen()).await.unwrap());
+        assert_eq!(*mock.handles.lock().unwrap(), vec![(account_id, *CLAIMED_HANDLE_ID)]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,216 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+use super::provision::{ProvisionAnonymousHandle, ProvisionAnonymousHandleError};
+
+// 一度に読み込むアカウント数
+pub const REPAIR_BATCH_SIZE: i32 = 500;
+
+pub(crate) trait RepairAnonymousHandles: ProvisionAnonymousHandle {
+    // 匿名名義を持たない既存のアカウントに匿名名義を作成する
+    // 所有者の索引の導入前に作成された名義にも、索引を作成する
+    async fn repair_anonymous_handles(&self) -> Fallible<RepairSummary, RepairAnonymousHandlesError> {
+        let mut summary = RepairSummary::default();
+        let mut cursor = AccountCursor::START;
+
+        loop {
+            let (account_ids, next_cursor) = self.fetch_accounts(cursor, REPAIR_BATCH_SIZE).await?;
+
+            for account_id in account_ids {
+                let handles = self.fetch_handles(account_id).await?;
+
+                // 既存の索引は同じ値で上書きされるため、有無を確かめずに作成する
+                for (handle_id, _) in &handles {
+                    self.insert_handle_owner(*handle_id, account_id).await?;
+                    summary.indexed += 1;
+                }
+
+                let anonymous_handle_id = handles.into_iter()
+                    .find_map(|(handle_id, is_anonymous)| is_anonymous.then_some(handle_id));
+
+                // 共有数の導入前に作成された匿名名義には共有数がないため、なければ作成する
+                if let Some(handle_id) = anonymous_handle_id {
+                    self.insert_missing_share_count(account_id, handle_id).await?;
+                }
+
+                // 確保の記録がない匿名名義が既にあれば、新たに作らずにそのIDを確保する
+                let candidate = anonymous_handle_id.unwrap_or_else(HandleId::gen);
+
+                if self.provision_anonymous_handle(account_id, candidate).await? {
+                    summary.repaired += 1;
+                }
+
+                summary.scanned += 1;
+            }
+
+            match next_cursor {
+                Some(next_cursor) => cursor = next_cursor,
+                None => return Ok(summary),
+            }
+        }
+    }
+
+    // `cursor`より後のアカウントを`limit`件まで取得し、続きがあれば次のカーソルを返す
+    async fn fetch_accounts(&self, cursor: AccountCursor, limit: i32) -> Fallible<(Vec<AccountId>, Option<AccountCursor>), RepairAnonymousHandlesError>;
+
+    // アカウントの名義と、それが匿名名義かどうかを取得する
+    async fn fetch_handles(&self, account_id: AccountId) -> Fallible<Vec<(HandleId, bool)>, RepairAnonymousHandlesError>;
+
+    async fn insert_handle_owner(&self, handle_id: HandleId, account_id: AccountId) -> Fallible<(), RepairAnonymousHandlesError>;
+
+    // 既存の共有数は上書きしない
+    async fn insert_missing_share_count(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), RepairAnonymousHandlesError>;
+}
+
+#[derive(Debug, Error)]
+pub enum RepairAnonymousHandlesError {
+    #[error("アカウントの取得に失敗しました")]
+    FetchAccountsFailed(#[source] anyhow::Error),
+    #[error("名義の取得に失敗しました")]
+    FetchHandlesFailed(#[source] anyhow::Error),
+    #[error("名義の所有者の索引の作成に失敗しました")]
+    InsertHandleOwnerFailed(#[source] anyhow::Error),
+    #[error("名義の共有数の作成に失敗しました")]
+    InsertShareCountFailed(#[source] anyhow::Error),
+    #[error(transparent)]
+    ProvisionAnonymousHandle(#[from] ProvisionAnonymousHandleError),
+}
+
+// アカウントのパーティションキーのトークン
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AccountCursor(i64);
+
+impl AccountCursor {
+    pub const START: AccountCursor = AccountCursor(i64::MIN);
+
+    pub const fn of(token: i64) -> Self {
+        Self(token)
+    }
+
+    pub fn value(&self) -> i64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct RepairSummary {
+    scanned: u32,
+    repaired: u32,
+    indexed: u32,
+}
+
+impl RepairSummary {
+    pub fn scanned(&self) -> u32 {
+        self.scanned
+    }
+
+    pub fn repaired(&self) -> u32 {
+        self.repaired
+    }
+
+    pub fn indexed(&self) -> u32 {
+        self.indexed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::{LazyLock, Mutex}};
+
+    use crate::common::{fallible::Fallible, handle::{anonymous::provision::{ProvisionAnonymousHandle, ProvisionAnonymousHandleError}, id::HandleId}, profile::account_id::AccountId};
+
+    use super::{AccountCursor, RepairAnonymousHandles, RepairAnonymousHandlesError};
+
+    static ACCOUNT_IDS: LazyLock<Vec<AccountId>> = LazyLock::new(|| (0..5).map(|_| AccountId::gen()).collect());
+
+    struct MockRepairAnonymousHandles {
+        claims: Mutex<HashMap<AccountId, HandleId>>,
+        handles: Mutex<HashMap<AccountId, HandleId>>,
+        named_handles: Mutex<HashMap<AccountId, HandleId>>,
+        owners: Mutex<HashMap<HandleId, AccountId>>,
+        share_counts: Mutex<HashMap<HandleId, i32>>,
+    }
+
+    impl ProvisionAnonymousHandle for MockRepairAnonymousHandles {
+        async fn claim_anonymous_handle_id(&self, account_id: AccountId, candidate: HandleId) -> Fallible<Option<HandleId>, ProvisionAnonymousHandleError> {
+            let mut claims = self.claims.lock().unwrap();
+            let claimed = claims.get(&account_id).copied();
+            claims.entry(account_id).or_insert(candidate);
+            Ok(claimed)
+        }
+
+        async fn anonymous_handle_exists(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, ProvisionAnonymousHandleError> {
+            Ok(self.handles.lock().unwrap().get(&account_id) == Some(&handle_id))
+        }
+
+        async fn insert_anonymous_handle(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), ProvisionAnonymousHandleError> {
+            self.handles.lock().unwrap().insert(account_id, handle_id);
+            Ok(())
+        }
+    }
+
+    impl RepairAnonymousHandles for MockRepairAnonymousHandles {
+        async fn fetch_accounts(&self, cursor: AccountCursor, limit: i32) -> Fallible<(Vec<AccountId>, Option<AccountCursor>), RepairAnonymousHandlesError> {
+            // 2件ずつ返し、カーソルには配列の位置を用いる
+            let start = if cursor == AccountCursor::START { 0 } else { cursor.value() as usize };
+            let end = (start + 2).min(ACCOUNT_IDS.len());
+            assert!(limit > 0);
+
+            let next_cursor = (end < ACCOUNT_IDS.len()).then(|| AccountCursor::of(end as i64));
+            Ok((ACCOUNT_IDS[start..end].to_vec(), next_cursor))
+        }
+
+        async fn fetch_handles(&self, account_id: AccountId) -> Fallible<Vec<(HandleId, bool)>, RepairAnonymousHandlesError> {
+            let anonymous = self.handles.lock().unwrap().get(&account_id).map(|handle_id| (*handle_id, true));
+            let named = self.named_handles.lock().unwrap().get(&account_id).map(|handle_id| (*handle_id, false));
+            Ok(named.into_iter().chain(anonymous).collect())
+        }
+
+        async fn insert_handle_owner(&self, handle_id: HandleId, account_id: AccountId) -> Fallible<(), RepairAnonymousHandlesError> {
+            self.owners.lock().unwrap().insert(handle_id, account_id);
+            Ok(())
+        }
+
+        async fn insert_missing_share_count(&self, _: AccountId, handle_id: HandleId) -> Fallible<(), RepairAnonymousHandlesError> {
+            self.share_counts.lock().unwrap().entry(handle_id).or_insert(0);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn repair() {
+        // 1件目は確保済みで名義あり、2件目は確保の記録がない既存の匿名名義あり、3件目は索引のない名前付きの名義あり
+        let legacy_handle_id = HandleId::gen();
+        let named_handle_id = HandleId::gen();
+        let mock = MockRepairAnonymousHandles {
+            claims: Mutex::new(HashMap::from([(ACCOUNT_IDS[0], HandleId::gen())])),
+            handles: Mutex::new(HashMap::new()),
+            named_handles: Mutex::new(HashMap::from([(ACCOUNT_IDS[2], named_handle_id)])),
+            owners: Mutex::new(HashMap::new()),
+            share_counts: Mutex::new(HashMap::new()),
+        };
+        let claimed = mock.claims.lock().unwrap()[&ACCOUNT_IDS[0]];
+        mock.handles.lock().unwrap().extend([(ACCOUNT_IDS[0], claimed), (ACCOUNT_IDS[1], legacy_handle_id)]);
+        mock.share_counts.lock().unwrap().insert(claimed, 3);
+
+        let summary = mock.repair_anonymous_handles().await.unwrap();
+        assert_eq!(summary.scanned(), 5);
+        assert_eq!(summary.repaired(), 3);
+        assert_eq!(summary.indexed(), 3);
+
+        // 既存の匿名名義のIDが確保される
+        assert_eq!(mock.claims.lock().unwrap()[&ACCOUNT_IDS[1]], legacy_handle_id);
+        assert_eq!(mock.handles.lock().unwrap().len(), 5);
+
+        // 既存の匿名名義に共有数が作成され、既存の共有数は上書きされない
+        let share_counts = mock.share_counts.lock().unwrap();
+        assert_eq!(share_counts[&legacy_handle_id], 0);
+        assert_eq!(share_counts[&claimed], 3);
+
+        // 既存の名義に所有者の索引が作成される
+        let owners = mock.owners.lock().unwrap();
+        assert_eq!(owners[&legacy_handle_id], ACCOUNT_IDS[1]);
+        assert_eq!(owners[&named_handle_id], ACCOUNT_IDS[2]);
+    }
+}
\ No newline at end of file
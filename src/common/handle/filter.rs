@@ -0,0 +1,37 @@
+use std::fmt::{self, Display};
+
+// アカウントごとに他者の名義を非表示にするリスト
+// ミュート・ブロックともに自分のフィードから投稿を隠し、ブロックはさらにその名義をフォローできなくする
+// 相手側の名義からの関わりは制限しない。アカウント単位で拒否すると、同じ所有者の名義が結び付いてしまうため
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HandleFilterKind {
+    Block,
+    Mute,
+}
+
+impl HandleFilterKind {
+    pub const ALL: [HandleFilterKind; 2] = [HandleFilterKind::Block, HandleFilterKind::Mute];
+
+    pub fn table(&self) -> &'static str {
+        match self {
+            HandleFilterKind::Block => "blocks",
+            HandleFilterKind::Mute => "mutes",
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            HandleFilterKind::Block => "/blocks",
+            HandleFilterKind::Mute => "/mutes",
+        }
+    }
+}
+
+impl Display for HandleFilterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandleFilterKind::Block => write!(f, "ブロック"),
+            HandleFilterKind::Mute => write!(f, "ミュート"),
+        }
+    }
+}
\ No newline at end of file
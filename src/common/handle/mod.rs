@@ -1,3 +1,6 @@
+pub mod anonymous;
+pub mod filter;
 pub mod id;
 pub mod name;
+pub mod policy;
 pub mod share_count;
\ No newline at end of file
@@ -0,0 +1,104 @@
+use unicode_normalization::UnicodeNormalization;
+
+// 名義の比較に用いる骨格文字列を求める
+// 互換分解(NFKC)で全角・半角や合字を揃えたうえで小文字化し、
+// 見た目の紛らわしい文字を代表の文字へ寄せ、区切りや不可視の文字を取り除く
+pub fn skeleton(name: &str) -> String {
+    name.nfkc()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !is_ignorable(*c))
+        .map(fold_confusable)
+        .collect()
+}
+
+pub(super) fn is_ignorable(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(c,
+            '-' | '_' | '.' | '・' | 'ー' | '~' |
+            '\u{200B}'..='\u{200F}' | // ゼロ幅文字と方向制御文字
+            '\u{2060}'..='\u{2064}' |
+            '\u{FEFF}'
+        )
+}
+
+fn fold_confusable(c: char) -> char {
+    match c {
+        // キリル文字
+        'а' => 'a',
+        'в' => 'b',
+        'е' | 'ё' => 'e',
+        'к' => 'k',
+        'м' => 'm',
+        'н' => 'h',
+        'о' => 'o',
+        'р' => 'p',
+        'с' => 'c',
+        'т' => 't',
+        'у' => 'y',
+        'х' => 'x',
+        'і' | 'ї' => 'l',
+        'ј' => 'j',
+        'ѕ' => 's',
+        // ギリシャ文字
+        'α' => 'a',
+        'β' => 'b',
+        'ε' => 'e',
+        'η' => 'n',
+        'ι' => 'l',
+        'κ' => 'k',
+        'ν' => 'v',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'τ' => 't',
+        'υ' => 'u',
+        'χ' => 'x',
+        // 数字や記号による置き換え
+        '0' => 'o',
+        '1' | 'i' | '|' | '!' => 'l',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        '8' => 'b',
+        // ひらがなはカタカナとして比較する
+        'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::skeleton;
+
+    #[test]
+    fn width_and_case() {
+        assert_eq!(skeleton("ＮｅｔＭａｔｅ"), skeleton("netmate"));
+        assert_eq!(skeleton("ﾈｯﾄﾒｲﾄ"), skeleton("ネットメイト"));
+    }
+
+    #[test]
+    fn homoglyph() {
+        // キリル文字の"а"と"е"
+        assert_eq!(skeleton("nеtmаtе"), skeleton("netmate"));
+        assert_eq!(skeleton("adm1n"), skeleton("admin"));
+        assert_eq!(skeleton("N3TM4T3"), skeleton("netmate"));
+    }
+
+    #[test]
+    fn separator_and_invisible() {
+        assert_eq!(skeleton("net_mate"), skeleton("netmate"));
+        assert_eq!(skeleton("net\u{200B}mate"), skeleton("netmate"));
+        assert_eq!(skeleton("ネット・メイト"), skeleton("ネットメイト"));
+    }
+
+    #[test]
+    fn kana() {
+        assert_eq!(skeleton("ねっとめいと"), skeleton("ネットメイト"));
+    }
+
+    #[test]
+    fn distinct() {
+        assert_ne!(skeleton("netmate"), skeleton("netmates"));
+        assert_ne!(skeleton("管理者"), skeleton("管理"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+// 匿名名義を除いて、アカウントが持てる名義の数
+const BASE_HANDLE_LIMIT: u32 = 3;
+const MAX_HANDLE_LIMIT: u32 = 10;
+
+// 共有数の合計がこの値に達するごとに上限を1つ増やす
+const SHARES_PER_EXTRA_HANDLE: u32 = 100;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct HandleLimit(u32);
+
+impl HandleLimit {
+    // 投稿による貢献が多いアカウントほど多くの名義を持てる
+    pub fn for_contribution(total_share_count: u32) -> Self {
+        let extra = total_share_count / SHARES_PER_EXTRA_HANDLE;
+        Self(BASE_HANDLE_LIMIT.saturating_add(extra).min(MAX_HANDLE_LIMIT))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_reached(&self, onymous_handle_count: u32) -> bool {
+        onymous_handle_count >= self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HandleLimit, BASE_HANDLE_LIMIT, MAX_HANDLE_LIMIT, SHARES_PER_EXTRA_HANDLE};
+
+    #[test]
+    fn base() {
+        assert_eq!(HandleLimit::for_contribution(0).value(), BASE_HANDLE_LIMIT);
+        assert_eq!(HandleLimit::for_contribution(SHARES_PER_EXTRA_HANDLE - 1).value(), BASE_HANDLE_LIMIT);
+    }
+
+    #[test]
+    fn contribution() {
+        assert_eq!(HandleLimit::for_contribution(SHARES_PER_EXTRA_HANDLE * 2).value(), BASE_HANDLE_LIMIT + 2);
+        assert_eq!(HandleLimit::for_contribution(u32::MAX).value(), MAX_HANDLE_LIMIT);
+    }
+
+    #[test]
+    fn reached() {
+        let limit = HandleLimit::for_contribution(0);
+        assert!(!limit.is_reached(BASE_HANDLE_LIMIT - 1));
+        assert!(limit.is_reached(BASE_HANDLE_LIMIT));
+    }
+}
\ No newline at end of file
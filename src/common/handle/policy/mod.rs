@@ -0,0 +1,85 @@
+pub mod confusable;
+pub mod limit;
+pub mod profanity;
+pub mod reserved;
+
+use thiserror::Error;
+
+use super::name::HandleName;
+
+use self::{confusable::skeleton, profanity::{LoadProfanityFilterError, ProfanityFilter}, reserved::is_reserved};
+
+// 名義の名前の方針
+// 予約語と不適切な語は、見た目の紛らわしい文字を寄せた骨格文字列で比較する
+pub struct HandleNamePolicy {
+    profanity_filter: ProfanityFilter,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CheckHandleNamePolicyError {
+    #[error("予約されている名義です")]
+    Reserved,
+    #[error("不適切な語を含む名義です")]
+    Profane,
+}
+
+impl HandleNamePolicy {
+    pub fn new(profanity_filter: ProfanityFilter) -> Self {
+        Self { profanity_filter }
+    }
+
+    // HANDLE_PROFANE_WORDS_PATH: 不適切な語の一覧のパス(任意)
+    pub fn from_env() -> Result<Self, LoadProfanityFilterError> {
+        ProfanityFilter::from_env().map(Self::new)
+    }
+
+    pub fn check(&self, handle_name: &HandleName) -> Result<(), CheckHandleNamePolicyError> {
+        let name_skeleton = skeleton(handle_name.value());
+
+        if is_reserved(&name_skeleton) {
+            return Err(CheckHandleNamePolicyError::Reserved);
+        }
+
+        if self.profanity_filter.contains_profanity(handle_name.value()) {
+            return Err(CheckHandleNamePolicyError::Profane);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::common::handle::name::HandleName;
+
+    use super::{profanity::ProfanityFilter, CheckHandleNamePolicyError, HandleNamePolicy};
+
+    fn check(name: &str) -> Result<(), CheckHandleNamePolicyError> {
+        HandleNamePolicy::new(ProfanityFilter::parse("badword")).check(&HandleName::from_str(name).unwrap())
+    }
+
+    #[test]
+    fn acceptable() {
+        assert_eq!(check("たなか"), Ok(()));
+    }
+
+    #[test]
+    fn reserved() {
+        assert_eq!(check("ＮｅｔＭａｔｅ"), Err(CheckHandleNamePolicyError::Reserved));
+    }
+
+    #[test]
+    fn profane() {
+        assert_eq!(check("b4dw0rd"), Err(CheckHandleNamePolicyError::Profane));
+    }
+
+    #[test]
+    fn containing_profanity_across_words() {
+        let policy = HandleNamePolicy::new(ProfanityFilter::bundled());
+        for name in ["Kinoshita", "Yamashita", "Matsushita", "시발점"] {
+            assert_eq!(policy.check(&HandleName::from_str(name).unwrap()), Ok(()), "{}", name);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::{fs, io};
+
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+use super::confusable::{is_ignorable, skeleton};
+
+const BUNDLED_WORDS: &str = include_str!("profane_words.txt");
+
+// 名義に含めることを認めない語の一覧
+// 1行に1語を記し、`#`から始まる行と空行は読み飛ばす
+pub struct ProfanityFilter(Vec<String>);
+
+#[derive(Debug, Error)]
+pub enum LoadProfanityFilterError {
+    #[error("不適切な語の一覧を読み込めませんでした")]
+    ReadFailed(#[source] io::Error),
+}
+
+impl ProfanityFilter {
+    // HANDLE_PROFANE_WORDS_PATH: 不適切な語の一覧のパス(任意、既定は同梱の一覧)
+    pub fn from_env() -> Result<Self, LoadProfanityFilterError> {
+        match dotenvy::var("HANDLE_PROFANE_WORDS_PATH") {
+            Ok(path) => fs::read_to_string(path)
+                .map(|list| Self::parse(&list))
+                .map_err(LoadProfanityFilterError::ReadFailed),
+            Err(_) => Ok(Self::bundled()),
+        }
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_WORDS)
+    }
+
+    pub fn parse(list: &str) -> Self {
+        let words = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(skeleton)
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        Self(words)
+    }
+
+    // 名義を語に区切り、連続する語を繋げたものが不適切な語と一致するかを調べる
+    // 部分文字列で照合すると"Kinoshita"や"시발점"のような無関係な語まで拒否してしまうため、語の境界で照合する
+    pub fn contains_profanity(&self, name: &str) -> bool {
+        let words = words(name);
+
+        (0..words.len()).any(|start| {
+            let mut joined = String::new();
+            words[start..].iter().any(|word| {
+                joined.push_str(word);
+                self.0.contains(&joined)
+            })
+        })
+    }
+}
+
+// 区切り文字、大文字の始まり、ハングルとそれ以外の境界で区切る
+// 語の間に区切りを置かない漢字と仮名は、1文字ずつを語とする
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev: Option<char> = None;
+
+    for c in name.nfkc() {
+        let is_boundary = prev.is_some_and(|prev| {
+            is_ideographic(prev)
+                || is_ideographic(c)
+                || is_hangul(prev) != is_hangul(c)
+                || (prev.is_lowercase() && c.is_uppercase())
+        });
+
+        if is_ignorable(c) || is_boundary {
+            words.push(skeleton(&word));
+            word.clear();
+        }
+
+        if is_ignorable(c) {
+            prev = None;
+        } else {
+            word.push(c);
+            prev = Some(c);
+        }
+    }
+    words.push(skeleton(&word));
+
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' | // ひらがなとカタカナ
+        '\u{31F0}'..='\u{31FF}' |
+        '\u{3400}'..='\u{4DBF}' | // CJK統合漢字
+        '\u{4E00}'..='\u{9FFF}' |
+        '\u{F900}'..='\u{FAFF}'
+    )
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' |
+        '\u{3130}'..='\u{318F}' |
+        '\u{AC00}'..='\u{D7AF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProfanityFilter;
+
+    #[test]
+    fn parse() {
+        let filter = ProfanityFilter::parse("# コメント\n\n Badword \n");
+        assert!(filter.contains_profanity("badword"));
+        assert!(!filter.contains_profanity("コメント"));
+    }
+
+    #[test]
+    fn obfuscated() {
+        let filter = ProfanityFilter::parse("badword");
+        assert!(filter.contains_profanity("my_B4DW0RD_name"));
+        assert!(filter.contains_profanity("myBadwordName"));
+        assert!(filter.contains_profanity("b-a-d-w-o-r-d"));
+        assert!(!filter.contains_profanity("goodword"));
+    }
+
+    #[test]
+    fn word_boundary() {
+        let filter = ProfanityFilter::bundled();
+        for name in ["Kinoshita", "Yamashita", "Matsushita", "Shitake", "Scunthorpe", "시발점"] {
+            assert!(!filter.contains_profanity(name), "{}", name);
+        }
+        assert!(filter.contains_profanity("kino_sh1t"));
+        assert!(filter.contains_profanity("SHIT"));
+        assert!(filter.contains_profanity("시발"));
+        assert!(filter.contains_profanity("시발abc"));
+    }
+
+    #[test]
+    fn ideographic() {
+        let filter = ProfanityFilter::bundled();
+        assert!(filter.contains_profanity("お前死ね"));
+        assert!(filter.contains_profanity("ﾁﾝｺ"));
+        assert!(!filter.contains_profanity("田中"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::sync::LazyLock;
+
+use super::confusable::skeleton;
+
+// サービスや運営を装う名義を防ぐため、対応する4言語で予約する
+const RESERVED_NAMES: [&str; 25] = [
+    // 英語
+    "admin",
+    "administrator",
+    "moderator",
+    "official",
+    "staff",
+    "support",
+    "system",
+    // 日本語
+    "管理者",
+    "運営",
+    "公式",
+    "モデレーター",
+    "サポート",
+    // 韓国語
+    "관리자",
+    "운영자",
+    "운영진",
+    "공식",
+    "고객센터",
+    // 繁体字中国語
+    "管理員",
+    "官方",
+    "版主",
+    "客服",
+    "系統",
+    "營運",
+    "運營",
+    "站長",
+];
+
+// 名義の一部に含まれる場合も予約とみなす、サービス名
+const RESERVED_SUBSTRINGS: [&str; 3] = [
+    "netmate",
+    "ネットメイト",
+    "넷메이트",
+];
+
+static RESERVED_NAME_SKELETONS: LazyLock<Vec<String>> = LazyLock::new(|| RESERVED_NAMES.iter().map(|name| skeleton(name)).collect());
+
+static RESERVED_SUBSTRING_SKELETONS: LazyLock<Vec<String>> = LazyLock::new(|| RESERVED_SUBSTRINGS.iter().map(|name| skeleton(name)).collect());
+
+// `name_skeleton`は`skeleton`で求めたものであること
+pub fn is_reserved(name_skeleton: &str) -> bool {
+    RESERVED_NAME_SKELETONS.iter().any(|reserved| reserved == name_skeleton)
+        || RESERVED_SUBSTRING_SKELETONS.iter().any(|reserved| name_skeleton.contains(reserved.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::handle::policy::confusable::skeleton;
+
+    use super::is_reserved;
+
+    fn reserved(name: &str) -> bool {
+        is_reserved(&skeleton(name))
+    }
+
+    #[test]
+    fn exact() {
+        assert!(reserved("Admin"));
+        assert!(reserved("ａｄｍ１ｎ"));
+        assert!(reserved("運営"));
+        assert!(reserved("관리자"));
+        assert!(reserved("管理員"));
+
+        // 完全一致のみ
+        assert!(!reserved("badminton"));
+        assert!(!reserved("運営好き"));
+    }
+
+    #[test]
+    fn service_name() {
+        assert!(reserved("NetMate公式"));
+        assert!(reserved("the_net.mate"));
+        assert!(reserved("ねっとめいと"));
+        assert!(reserved("넷메이트팬"));
+    }
+}
\ No newline at end of file
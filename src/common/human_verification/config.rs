@@ -0,0 +1,157 @@
+use std::{str::FromStr, sync::Arc};
+
+use reqwest::Client;
+use thiserror::Error;
+
+use crate::common::fallible::Fallible;
+
+use super::{siteverify::SiteverifyVerifier, stub::StubHumanVerifier, token::HumanVerificationToken, verify::{ChallengeMaxAge, HumanVerificationError, HumanVerificationExpectation, HumanVerifier, SiteverifyOutcome}};
+
+// Turnstileのトークンの有効期限に合わせる
+pub const DEFAULT_MAX_CHALLENGE_AGE: ChallengeMaxAge = ChallengeMaxAge::minutes(5);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HumanVerifierProvider {
+    Turnstile,
+    HCaptcha,
+    ReCaptcha,
+    Stub,
+}
+
+impl HumanVerifierProvider {
+    // スタブは外部APIに問い合わせない
+    pub fn siteverify_url(&self) -> Option<&'static str> {
+        match self {
+            // https://developers.cloudflare.com/turnstile/get-started/server-side-validation/
+            HumanVerifierProvider::Turnstile => Some("https://challenges.cloudflare.com/turnstile/v0/siteverify"),
+            // https://docs.hcaptcha.com/#verify-the-user-response-server-side
+            HumanVerifierProvider::HCaptcha => Some("https://api.hcaptcha.com/siteverify"),
+            // https://developers.google.com/recaptcha/docs/verify
+            HumanVerifierProvider::ReCaptcha => Some("https://www.google.com/recaptcha/api/siteverify"),
+            HumanVerifierProvider::Stub => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("有効な人間確認プロバイダではありません")]
+pub struct ParseHumanVerifierProviderError;
+
+impl FromStr for HumanVerifierProvider {
+    type Err = ParseHumanVerifierProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "turnstile" => Ok(HumanVerifierProvider::Turnstile),
+            "hcaptcha" => Ok(HumanVerifierProvider::HCaptcha),
+            "recaptcha" => Ok(HumanVerifierProvider::ReCaptcha),
+            // 誰でも通過できるため、本番環境では選択できないようにする
+            #[cfg(debug_assertions)]
+            "stub" => Ok(HumanVerifierProvider::Stub),
+            _ => Err(ParseHumanVerifierProviderError)
+        }
+    }
+}
+
+pub enum ConfiguredHumanVerifier {
+    Siteverify(SiteverifyVerifier),
+    Stub(StubHumanVerifier),
+}
+
+impl ConfiguredHumanVerifier {
+    pub fn new(provider: HumanVerifierProvider, client: Arc<Client>, secret_key: String, expectation: HumanVerificationExpectation) -> Self {
+        match provider.siteverify_url() {
+            Some(url) => Self::Siteverify(SiteverifyVerifier::new(client, url, secret_key, expectation)),
+            None => Self::Stub(StubHumanVerifier::new(expectation)),
+        }
+    }
+
+    // HUMAN_VERIFIER_PROVIDER: turnstile | hcaptcha | recaptcha | stub
+    // HUMAN_VERIFIER_SECRET_KEY: プロバイダのシークレットキー(stubでは不要)
+    // HUMAN_VERIFIER_HOSTNAME: ウィジェットを設置したホスト名
+    // HUMAN_VERIFIER_ACTION: ウィジェットに設定したアクション名(任意)
+    pub fn from_env(client: Arc<Client>) -> Result<Self, LoadHumanVerifierConfigError> {
+        fn var(key: &'static str) -> Result<String, LoadHumanVerifierConfigError> {
+            dotenvy::var(key).map_err(|_| LoadHumanVerifierConfigError::MissingVariable(key))
+        }
+
+        let provider = HumanVerifierProvider::from_str(&var("HUMAN_VERIFIER_PROVIDER")?)
+            .map_err(LoadHumanVerifierConfigError::InvalidProvider)?;
+
+        let secret_key = match provider {
+            HumanVerifierProvider::Stub => String::new(),
+            _ => var("HUMAN_VERIFIER_SECRET_KEY")?,
+        };
+
+        let expectation = HumanVerificationExpectation::new(
+            var("HUMAN_VERIFIER_HOSTNAME")?,
+            dotenvy::var("HUMAN_VERIFIER_ACTION").ok(),
+            DEFAULT_MAX_CHALLENGE_AGE
+        );
+
+        Ok(Self::new(provider, client, secret_key, expectation))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadHumanVerifierConfigError {
+    #[error("環境変数{0}が設定されていません")]
+    MissingVariable(&'static str),
+    #[error("人間確認プロバイダの解析に失敗しました")]
+    InvalidProvider(#[source] ParseHumanVerifierProviderError),
+}
+
+impl HumanVerifier for ConfiguredHumanVerifier {
+    async fn fetch_siteverify_outcome(&self, token: &HumanVerificationToken) -> Fallible<SiteverifyOutcome, HumanVerificationError> {
+        match self {
+            Self::Siteverify(verifier) => verifier.fetch_siteverify_outcome(token).await,
+            Self::Stub(verifier) => verifier.fetch_siteverify_outcome(token).await,
+        }
+    }
+
+    fn expectation(&self) -> &HumanVerificationExpectation {
+        match self {
+            Self::Siteverify(verifier) => verifier.expectation(),
+            Self::Stub(verifier) => verifier.expectation(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{HumanVerifierProvider, ParseHumanVerifierProviderError};
+
+    #[test]
+    fn parse_provider() {
+        assert_eq!(HumanVerifierProvider::from_str("turnstile"), Ok(HumanVerifierProvider::Turnstile));
+        assert_eq!(HumanVerifierProvider::from_str("hcaptcha"), Ok(HumanVerifierProvider::HCaptcha));
+        assert_eq!(HumanVerifierProvider::from_str("recaptcha"), Ok(HumanVerifierProvider::ReCaptcha));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn parse_stub_provider() {
+        assert_eq!(HumanVerifierProvider::from_str("stub"), Ok(HumanVerifierProvider::Stub));
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn reject_stub_provider() {
+        assert_eq!(HumanVerifierProvider::from_str("stub"), Err(ParseHumanVerifierProviderError));
+    }
+
+    #[test]
+    fn siteverify_url() {
+        assert!(HumanVerifierProvider::Turnstile.siteverify_url().is_some());
+        assert!(HumanVerifierProvider::HCaptcha.siteverify_url().is_some());
+        assert!(HumanVerifierProvider::ReCaptcha.siteverify_url().is_some());
+        assert_eq!(HumanVerifierProvider::Stub.siteverify_url(), None);
+    }
+
+    #[test]
+    fn parse_unknown_provider() {
+        assert_eq!(HumanVerifierProvider::from_str("captcha"), Err(ParseHumanVerifierProviderError));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod config;
+pub mod siteverify;
+pub mod stub;
+pub mod token;
+pub mod verify;
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use reqwest::Client;
+
+use crate::common::fallible::Fallible;
+
+use super::{token::HumanVerificationToken, verify::{HumanVerificationError, HumanVerificationExpectation, HumanVerifier, SiteverifyOutcome}};
+
+// Turnstile, hCaptcha, reCAPTCHA のsiteverify APIは、いずれも同じ形式で応答するため、URLのみを切り替える
+pub struct SiteverifyVerifier {
+    client: Arc<Client>,
+    url: &'static str,
+    secret_key: String,
+    expectation: HumanVerificationExpectation,
+}
+
+impl SiteverifyVerifier {
+    pub fn new(client: Arc<Client>, url: &'static str, secret_key: String, expectation: HumanVerificationExpectation) -> Self {
+        Self { client, url, secret_key, expectation }
+    }
+}
+
+impl HumanVerifier for SiteverifyVerifier {
+    async fn fetch_siteverify_outcome(&self, token: &HumanVerificationToken) -> Fallible<SiteverifyOutcome, HumanVerificationError> {
+        self.client.post(self.url)
+            .form(&[("secret", self.secret_key.as_str()), ("response", token.value().as_str())])
+            .send()
+            .await
+            .map_err(|e| HumanVerificationError::SiteverifyFailed(e.into()))?
+            .json::<SiteverifyOutcome>()
+            .await
+            .map_err(|e| HumanVerificationError::SiteverifyFailed(e.into()))
+    }
+
+    fn expectation(&self) -> &HumanVerificationExpectation {
+        &self.expectation
+    }
+}
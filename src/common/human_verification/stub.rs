@@ -0,0 +1,62 @@
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::common::fallible::Fallible;
+
+use super::{token::HumanVerificationToken, verify::{HumanVerificationError, HumanVerificationExpectation, HumanVerifier, SiteverifyOutcome}};
+
+// Turnstileのテスト用サイトキーが発行するダミートークンと同じ値を用いる
+// https://developers.cloudflare.com/turnstile/troubleshooting/testing/
+pub const STUB_PASSING_TOKEN: &str = "XXXX.DUMMY.TOKEN.XXXX";
+
+// 外部APIに問い合わせず、`STUB_PASSING_TOKEN`のみを通過させる
+pub struct StubHumanVerifier {
+    expectation: HumanVerificationExpectation,
+}
+
+impl StubHumanVerifier {
+    pub fn new(expectation: HumanVerificationExpectation) -> Self {
+        Self { expectation }
+    }
+}
+
+impl HumanVerifier for StubHumanVerifier {
+    async fn fetch_siteverify_outcome(&self, token: &HumanVerificationToken) -> Fallible<SiteverifyOutcome, HumanVerificationError> {
+        let challenge_ts = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .map_err(|e| HumanVerificationError::SiteverifyFailed(e.into()))?;
+
+        Ok(SiteverifyOutcome::new(
+            token.value() == STUB_PASSING_TOKEN,
+            Some(challenge_ts),
+            Some(self.expectation.hostname().clone()),
+            self.expectation.action().cloned()
+        ))
+    }
+
+    fn expectation(&self) -> &HumanVerificationExpectation {
+        &self.expectation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::human_verification::{token::HumanVerificationToken, verify::{ChallengeMaxAge, HumanVerificationExpectation, HumanVerifier}};
+
+    use super::{StubHumanVerifier, STUB_PASSING_TOKEN};
+
+    fn stub() -> StubHumanVerifier {
+        StubHumanVerifier::new(HumanVerificationExpectation::new(String::from("localhost"), Some(String::from("test")), ChallengeMaxAge::minutes(5)))
+    }
+
+    #[tokio::test]
+    async fn passing_token() {
+        let token = HumanVerificationToken::new(String::from(STUB_PASSING_TOKEN));
+        assert!(stub().verify(&token).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn failing_token() {
+        let token = HumanVerificationToken::new(String::from("invalid"));
+        assert!(!stub().verify(&token).await.unwrap());
+    }
+}
@@ -1,9 +1,9 @@
 use std::fmt::{self, Display};
 
 #[derive(Debug, PartialEq)]
-pub struct TurnstileToken(String);
+pub struct HumanVerificationToken(String);
 
-impl TurnstileToken {
+impl HumanVerificationToken {
     pub fn new(token: String) -> Self {
         Self(token)
     }
@@ -13,7 +13,7 @@ impl TurnstileToken {
     }
 }
 
-impl Display for TurnstileToken {
+impl Display for HumanVerificationToken {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.value())
     }
@@ -0,0 +1,189 @@
+use serde::Deserialize;
+use thiserror::Error;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::common::{fallible::Fallible, unixtime::UnixtimeMillis};
+
+use super::token::HumanVerificationToken;
+
+pub(crate) trait HumanVerifier {
+    async fn verify(&self, token: &HumanVerificationToken) -> Fallible<bool, HumanVerificationError> {
+        let outcome = self.fetch_siteverify_outcome(token).await?;
+        Ok(outcome.satisfies(self.expectation(), UnixtimeMillis::now()))
+    }
+
+    async fn fetch_siteverify_outcome(&self, token: &HumanVerificationToken) -> Fallible<SiteverifyOutcome, HumanVerificationError>;
+
+    fn expectation(&self) -> &HumanVerificationExpectation;
+}
+
+#[derive(Debug, Error)]
+pub enum HumanVerificationError {
+    #[error("siteverify APIへの問い合わせに失敗しました")]
+    SiteverifyFailed(#[source] anyhow::Error),
+}
+
+// 検証サーバーとの時刻のずれを許容する範囲
+const CLOCK_SKEW_TOLERANCE_MILLIS: i128 = 30 * 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct SiteverifyOutcome {
+    #[serde(default)]
+    success: bool,
+    challenge_ts: Option<String>,
+    hostname: Option<String>,
+    action: Option<String>,
+}
+
+impl SiteverifyOutcome {
+    pub fn new(success: bool, challenge_ts: Option<String>, hostname: Option<String>, action: Option<String>) -> Self {
+        Self { success, challenge_ts, hostname, action }
+    }
+
+    pub fn satisfies(&self, expectation: &HumanVerificationExpectation, now: UnixtimeMillis) -> bool {
+        self.success
+            && self.hostname.as_deref() == Some(expectation.hostname().as_str())
+            && expectation.action().is_none_or(|action| self.action.as_deref() == Some(action.as_str()))
+            && self.is_fresh(expectation.max_challenge_age(), now)
+    }
+
+    fn is_fresh(&self, max_challenge_age: ChallengeMaxAge, now: UnixtimeMillis) -> bool {
+        self.challenge_ts
+            .as_deref()
+            .and_then(|challenge_ts| OffsetDateTime::parse(challenge_ts, &Rfc3339).ok())
+            .map(|challenged_at| {
+                let age = now.value() as i128 - challenged_at.unix_timestamp_nanos() / 1_000_000;
+                (-CLOCK_SKEW_TOLERANCE_MILLIS..=max_challenge_age.as_millis() as i128).contains(&age)
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HumanVerificationExpectation {
+    hostname: String,
+    action: Option<String>,
+    max_challenge_age: ChallengeMaxAge,
+}
+
+impl HumanVerificationExpectation {
+    pub fn new(hostname: String, action: Option<String>, max_challenge_age: ChallengeMaxAge) -> Self {
+        Self { hostname, action, max_challenge_age }
+    }
+
+    pub fn hostname(&self) -> &String {
+        &self.hostname
+    }
+
+    pub fn action(&self) -> Option<&String> {
+        self.action.as_ref()
+    }
+
+    pub fn max_challenge_age(&self) -> ChallengeMaxAge {
+        self.max_challenge_age
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ChallengeMaxAge(u64);
+
+impl ChallengeMaxAge {
+    pub const fn seconds(seconds: u64) -> Self {
+        Self(seconds)
+    }
+
+    pub const fn minutes(minutes: u64) -> Self {
+        Self::seconds(minutes * 60)
+    }
+
+    pub fn as_millis(&self) -> u64 {
+        self.0 * 1000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+    use crate::common::unixtime::UnixtimeMillis;
+
+    use super::{ChallengeMaxAge, HumanVerificationExpectation, SiteverifyOutcome};
+
+    const HOSTNAME: &str = "netmate.app";
+    const ACTION: &str = "issue_api_key";
+    const MAX_CHALLENGE_AGE: ChallengeMaxAge = ChallengeMaxAge::minutes(5);
+
+    fn expectation(action: Option<&str>) -> HumanVerificationExpectation {
+        HumanVerificationExpectation::new(String::from(HOSTNAME), action.map(String::from), MAX_CHALLENGE_AGE)
+    }
+
+    fn challenge_ts(now: UnixtimeMillis, age_millis: i64) -> Option<String> {
+        let challenged_at = (now.value() as i64 - age_millis) as i128 * 1_000_000;
+        OffsetDateTime::from_unix_timestamp_nanos(challenged_at)
+            .ok()
+            .and_then(|t| t.format(&Rfc3339).ok())
+    }
+
+    fn outcome(success: bool, age_millis: i64, hostname: &str, action: Option<&str>, now: UnixtimeMillis) -> SiteverifyOutcome {
+        SiteverifyOutcome::new(success, challenge_ts(now, age_millis), Some(String::from(hostname)), action.map(String::from))
+    }
+
+    #[test]
+    fn satisfied() {
+        let now = UnixtimeMillis::now();
+        assert!(outcome(true, 1000, HOSTNAME, Some(ACTION), now).satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn unsuccessful() {
+        let now = UnixtimeMillis::now();
+        assert!(!outcome(false, 1000, HOSTNAME, Some(ACTION), now).satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn different_hostname() {
+        let now = UnixtimeMillis::now();
+        assert!(!outcome(true, 1000, "example.com", Some(ACTION), now).satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn different_action() {
+        let now = UnixtimeMillis::now();
+        assert!(!outcome(true, 1000, HOSTNAME, Some("sign_in"), now).satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn action_not_expected() {
+        let now = UnixtimeMillis::now();
+        assert!(outcome(true, 1000, HOSTNAME, None, now).satisfies(&expectation(None), now));
+    }
+
+    #[test]
+    fn stale_challenge() {
+        let now = UnixtimeMillis::now();
+        let age_millis = MAX_CHALLENGE_AGE.as_millis() as i64 + 1;
+        assert!(!outcome(true, age_millis, HOSTNAME, Some(ACTION), now).satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn challenge_from_future() {
+        let now = UnixtimeMillis::now();
+        assert!(!outcome(true, -60 * 1000, HOSTNAME, Some(ACTION), now).satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn no_challenge_ts() {
+        let now = UnixtimeMillis::now();
+        let outcome = SiteverifyOutcome::new(true, None, Some(String::from(HOSTNAME)), Some(String::from(ACTION)));
+        assert!(!outcome.satisfies(&expectation(Some(ACTION)), now));
+    }
+
+    #[test]
+    fn deserialize_siteverify_response() {
+        let json = r#"{"success":true,"challenge_ts":"2024-08-01T00:00:00.000Z","hostname":"netmate.app","error-codes":[],"action":"issue_api_key","cdata":""}"#;
+        let outcome: SiteverifyOutcome = serde_json::from_str(json).unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.hostname.as_deref(), Some(HOSTNAME));
+        assert_eq!(outcome.action.as_deref(), Some(ACTION));
+    }
+}
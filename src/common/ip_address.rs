@@ -0,0 +1,71 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+// 1つの利用者に割り当てられる範囲にまとめる
+// IPv6では/64が割り当てられることが多い
+pub fn host_prefix(ip_address: IpAddr) -> IpAddr {
+    match unmap(ip_address) {
+        IpAddr::V6(v6) => IpAddr::V6(mask_v6(v6, 64)),
+        v4 => v4,
+    }
+}
+
+// 同じネットワークに属する範囲にまとめる
+pub fn network_prefix(ip_address: IpAddr) -> IpAddr {
+    match unmap(ip_address) {
+        IpAddr::V4(v4) => IpAddr::V4(Ipv4Addr::from(u32::from(v4) & !(u8::MAX as u32))),
+        IpAddr::V6(v6) => IpAddr::V6(mask_v6(v6, 48)),
+    }
+}
+
+// デュアルスタックのソケットではIPv4の接続元がIPv4射影アドレスとして現れる
+fn unmap(ip_address: IpAddr) -> IpAddr {
+    match ip_address {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip_address, IpAddr::V4),
+        v4 => v4,
+    }
+}
+
+fn mask_v6(v6: Ipv6Addr, prefix_len: u32) -> Ipv6Addr {
+    Ipv6Addr::from(u128::from(v6) & !(u128::MAX >> prefix_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::{host_prefix, network_prefix};
+
+    fn ip_address(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn host_prefix_v4() {
+        assert_eq!(host_prefix(ip_address("192.0.2.1")), ip_address("192.0.2.1"));
+    }
+
+    #[test]
+    fn host_prefix_v6() {
+        assert_eq!(host_prefix(ip_address("2001:db8:1:2:3:4:5:6")), ip_address("2001:db8:1:2::"));
+    }
+
+    #[test]
+    fn host_prefix_v4_mapped() {
+        assert_eq!(host_prefix(ip_address("::ffff:192.0.2.1")), ip_address("192.0.2.1"));
+    }
+
+    #[test]
+    fn network_prefix_v4() {
+        assert_eq!(network_prefix(ip_address("192.0.2.1")), ip_address("192.0.2.0"));
+    }
+
+    #[test]
+    fn network_prefix_v6() {
+        assert_eq!(network_prefix(ip_address("2001:db8:1:2:3:4:5:6")), ip_address("2001:db8:1::"));
+    }
+
+    #[test]
+    fn network_prefix_v4_mapped() {
+        assert_eq!(network_prefix(ip_address("::ffff:192.0.2.1")), ip_address("192.0.2.0"));
+    }
+}
@@ -2,16 +2,21 @@ pub mod auth;
 pub mod api_key;
 pub mod character_count;
 pub mod consensus;
+pub mod contribution;
 pub mod cycle;
 pub mod email;
 pub mod fallible;
+pub mod follow;
 pub mod handle;
+pub mod human_verification;
+pub mod ip_address;
+pub mod moderation;
 pub mod page;
+pub mod post;
 pub mod profile;
 pub mod rating;
 pub mod session;
 pub mod tag;
 pub mod token;
-pub mod turnstile;
 pub mod unixtime;
 pub mod uuid;
\ No newline at end of file
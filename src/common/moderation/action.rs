@@ -0,0 +1,89 @@
+use std::fmt::{self, Display};
+
+use scylla::{frame::response::result::ColumnType, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::common::uuid::uuid7::Uuid7;
+
+// 監査ログに記録する、モデレーターによる対応の種類
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    HidePost,
+    SuspendAccount,
+    RenameTag,
+    Dismiss,
+}
+
+impl ModerationAction {
+    pub fn value(&self) -> i8 {
+        match self {
+            ModerationAction::HidePost => 0,
+            ModerationAction::SuspendAccount => 1,
+            ModerationAction::RenameTag => 2,
+            ModerationAction::Dismiss => 3,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("モデレーションの対応の種類の解析に失敗しました")]
+pub struct ParseModerationActionError;
+
+impl TryFrom<i8> for ModerationAction {
+    type Error = ParseModerationActionError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ModerationAction::HidePost),
+            1 => Ok(ModerationAction::SuspendAccount),
+            2 => Ok(ModerationAction::RenameTag),
+            3 => Ok(ModerationAction::Dismiss),
+            _ => Err(ParseModerationActionError),
+        }
+    }
+}
+
+impl Display for ModerationAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModerationAction::HidePost => write!(f, "投稿の非表示"),
+            ModerationAction::SuspendAccount => write!(f, "アカウントの停止"),
+            ModerationAction::RenameTag => write!(f, "タグ名の変更"),
+            ModerationAction::Dismiss => write!(f, "却下"),
+        }
+    }
+}
+
+impl SerializeValue for ModerationAction {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+// 監査ログを記録順に並べるため、UUIDv7を用いる
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AuditLogId(Uuid7);
+
+impl AuditLogId {
+    pub fn gen() -> Self {
+        AuditLogId(Uuid7::now())
+    }
+
+    pub fn value(&self) -> Uuid7 {
+        self.0
+    }
+}
+
+impl Display for AuditLogId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl SerializeValue for AuditLogId {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.0, typ, writer)
+    }
+}
\ No newline at end of file
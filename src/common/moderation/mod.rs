@@ -0,0 +1,4 @@
+pub mod action;
+pub mod reason;
+pub mod report_id;
+pub mod target;
\ No newline at end of file
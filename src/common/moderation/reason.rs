@@ -0,0 +1,101 @@
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// 通報理由
+// 自由記述は個人情報を含み得るため受け付けず、理由コードのみを記録する
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    SexualContent,
+    Violence,
+    Impersonation,
+    PersonalInformation,
+    Other,
+}
+
+impl ReportReason {
+    pub fn value(&self) -> i8 {
+        match self {
+            ReportReason::Spam => 0,
+            ReportReason::Harassment => 1,
+            ReportReason::HateSpeech => 2,
+            ReportReason::SexualContent => 3,
+            ReportReason::Violence => 4,
+            ReportReason::Impersonation => 5,
+            ReportReason::PersonalInformation => 6,
+            ReportReason::Other => 7,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("通報理由の解析に失敗しました")]
+pub struct ParseReportReasonError;
+
+impl TryFrom<i8> for ReportReason {
+    type Error = ParseReportReasonError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ReportReason::Spam),
+            1 => Ok(ReportReason::Harassment),
+            2 => Ok(ReportReason::HateSpeech),
+            3 => Ok(ReportReason::SexualContent),
+            4 => Ok(ReportReason::Violence),
+            5 => Ok(ReportReason::Impersonation),
+            6 => Ok(ReportReason::PersonalInformation),
+            7 => Ok(ReportReason::Other),
+            _ => Err(ParseReportReasonError),
+        }
+    }
+}
+
+impl SerializeValue for ReportReason {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for ReportReason {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        i8::from_cql(cql_val).and_then(|v| ReportReason::try_from(v).map_err(|_| FromCqlValError::BadVal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseReportReasonError, ReportReason};
+
+    const ALL: [ReportReason; 8] = [
+        ReportReason::Spam,
+        ReportReason::Harassment,
+        ReportReason::HateSpeech,
+        ReportReason::SexualContent,
+        ReportReason::Violence,
+        ReportReason::Impersonation,
+        ReportReason::PersonalInformation,
+        ReportReason::Other,
+    ];
+
+    #[test]
+    fn round_trip() {
+        for reason in ALL {
+            assert_eq!(ReportReason::try_from(reason.value()), Ok(reason));
+        }
+    }
+
+    #[test]
+    fn unknown_value() {
+        assert_eq!(ReportReason::try_from(8), Err(ParseReportReasonError));
+    }
+
+    #[test]
+    fn deserialize_snake_case() {
+        let reason: ReportReason = serde_json::from_str(r#""personal_information""#).unwrap();
+        assert_eq!(reason, ReportReason::PersonalInformation);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::fmt::{self, Display};
+
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::common::uuid::uuid7::Uuid7;
+
+// 通報順に対応できるよう、UUIDv7を用いる
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ReportId(Uuid7);
+
+impl ReportId {
+    pub fn gen() -> Self {
+        ReportId(Uuid7::now())
+    }
+
+    pub const fn of(value: Uuid7) -> Self {
+        ReportId(value)
+    }
+
+    pub fn value(&self) -> Uuid7 {
+        self.0
+    }
+}
+
+impl Display for ReportId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for ReportId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&self.value(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReportId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Uuid7::deserialize(deserializer)
+            .map(ReportId::of)
+    }
+}
+
+impl SerializeValue for ReportId {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.0, typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for ReportId {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        Uuid7::from_cql(cql_val).map(ReportId)
+    }
+}
\ No newline at end of file
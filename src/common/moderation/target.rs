@@ -0,0 +1,157 @@
+use std::fmt::{self, Display};
+
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::common::{handle::id::HandleId, post::id::PostId, tag::{non_top_tag::NonTopTagId, tag_id::TagId}, uuid::{uuid4::Uuid4, uuid7::Uuid7}};
+
+// 通報の対象
+// トップタグは運営が管理しているため、通報できるのは非トップタグのみ
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum ReportTarget {
+    Post(PostId),
+    Handle(HandleId),
+    Tag(NonTopTagId),
+}
+
+impl ReportTarget {
+    pub fn kind(&self) -> ReportTargetKind {
+        match self {
+            ReportTarget::Post(_) => ReportTargetKind::Post,
+            ReportTarget::Handle(_) => ReportTargetKind::Handle,
+            ReportTarget::Tag(_) => ReportTargetKind::Tag,
+        }
+    }
+
+    // 投稿IDはUUIDv7、それ以外はUUIDv4のため、共通の型で返す
+    pub fn id(&self) -> Uuid {
+        match self {
+            ReportTarget::Post(post_id) => *post_id.value().value(),
+            ReportTarget::Handle(handle_id) => handle_id.value().value(),
+            ReportTarget::Tag(tag_id) => tag_id.value().value().value(),
+        }
+    }
+
+    pub fn of(kind: ReportTargetKind, id: Uuid) -> Result<Self, ParseReportTargetError> {
+        match kind {
+            ReportTargetKind::Post => Uuid7::try_from(id)
+                .map(|id| ReportTarget::Post(PostId::of(id)))
+                .map_err(|_| ParseReportTargetError::InvalidId),
+            ReportTargetKind::Handle => Uuid4::try_from(id)
+                .map(|id| ReportTarget::Handle(HandleId::of(id)))
+                .map_err(|_| ParseReportTargetError::InvalidId),
+            ReportTargetKind::Tag => Uuid4::try_from(id)
+                .map_err(|_| ParseReportTargetError::InvalidId)
+                .and_then(|id| NonTopTagId::try_from(TagId::of(id)).map_err(|_| ParseReportTargetError::TopTag))
+                .map(ReportTarget::Tag),
+        }
+    }
+}
+
+impl Display for ReportTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind(), self.id())
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseReportTargetError {
+    #[error("通報対象の種類が不正です")]
+    InvalidKind,
+    #[error("通報対象のIDが不正です")]
+    InvalidId,
+    #[error("トップタグは通報できません")]
+    TopTag,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ReportTargetKind {
+    Post,
+    Handle,
+    Tag,
+}
+
+impl ReportTargetKind {
+    pub fn value(&self) -> i8 {
+        match self {
+            ReportTargetKind::Post => 0,
+            ReportTargetKind::Handle => 1,
+            ReportTargetKind::Tag => 2,
+        }
+    }
+}
+
+impl TryFrom<i8> for ReportTargetKind {
+    type Error = ParseReportTargetError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ReportTargetKind::Post),
+            1 => Ok(ReportTargetKind::Handle),
+            2 => Ok(ReportTargetKind::Tag),
+            _ => Err(ParseReportTargetError::InvalidKind),
+        }
+    }
+}
+
+impl Display for ReportTargetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportTargetKind::Post => write!(f, "post"),
+            ReportTargetKind::Handle => write!(f, "handle"),
+            ReportTargetKind::Tag => write!(f, "tag"),
+        }
+    }
+}
+
+impl SerializeValue for ReportTargetKind {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for ReportTargetKind {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        i8::from_cql(cql_val).and_then(|v| ReportTargetKind::try_from(v).map_err(|_| FromCqlValError::BadVal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{common::{handle::id::HandleId, post::id::PostId, tag::top_tag}, helper::test::mock_non_top_tag_id};
+
+    use super::{ParseReportTargetError, ReportTarget, ReportTargetKind};
+
+    #[test]
+    fn round_trip() {
+        for target in [ReportTarget::Post(PostId::gen()), ReportTarget::Handle(HandleId::gen()), ReportTarget::Tag(mock_non_top_tag_id(1))] {
+            assert_eq!(ReportTarget::of(target.kind(), target.id()), Ok(target));
+            assert_eq!(ReportTargetKind::try_from(target.kind().value()), Ok(target.kind()));
+        }
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let handle_id = HandleId::gen();
+        assert_eq!(ReportTarget::of(ReportTargetKind::Post, handle_id.value().value()), Err(ParseReportTargetError::InvalidId));
+        assert_eq!(ReportTarget::of(ReportTargetKind::Handle, Uuid::now_v7()), Err(ParseReportTargetError::InvalidId));
+    }
+
+    #[test]
+    fn top_tag() {
+        let top_tag_id = top_tag::JAPANESE.value().value();
+        assert_eq!(ReportTarget::of(ReportTargetKind::Tag, top_tag_id.value()), Err(ParseReportTargetError::TopTag));
+    }
+
+    #[test]
+    fn serialize_as_tagged_json() {
+        let post_id = PostId::gen();
+        let json = serde_json::to_string(&ReportTarget::Post(post_id)).unwrap();
+        assert_eq!(json, format!(r#"{{"type":"post","id":"{}"}}"#, post_id));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use std::str::FromStr;
+
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::common::character_count::calculate_character_cost;
+
+const POST_CONTENT_MAX_CHARACTER_COST: usize = 1000;
+
+#[derive(Debug)]
+pub struct PostContent(String);
+
+impl PostContent {
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl FromStr for PostContent {
+    type Err = ParsePostContentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(ParsePostContentError::Empty);
+        }
+
+        // 文字コスト計算はO(n)であるため、非常に長い文字列は事前に除外する
+        // 根拠は`HandleName`と同じ
+        if s.len() > POST_CONTENT_MAX_CHARACTER_COST * 2 {
+            return Err(ParsePostContentError::CharacterCostOverflow);
+        }
+
+        if calculate_character_cost(s) > POST_CONTENT_MAX_CHARACTER_COST {
+            return Err(ParsePostContentError::CharacterCostOverflow);
+        }
+
+        Ok(PostContent(String::from(s)))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParsePostContentError {
+    #[error("空の投稿は許可されていません")]
+    Empty,
+    #[error("文字数が多すぎます")]
+    CharacterCostOverflow,
+}
+
+impl Serialize for PostContent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&self.value(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PostContent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)
+            .and_then(|v| PostContent::from_str(v.as_str()).map_err(de::Error::custom))
+    }
+}
+
+impl SerializeValue for PostContent {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.0, typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for PostContent {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        String::from_cql(cql_val)
+            .and_then(|v| PostContent::from_str(&v).map_err(|_| FromCqlValError::BadVal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{ParsePostContentError, PostContent, POST_CONTENT_MAX_CHARACTER_COST};
+
+    #[test]
+    fn valid() {
+        assert!(PostContent::from_str("こんにちは").is_ok());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(matches!(PostContent::from_str(" \n"), Err(ParsePostContentError::Empty)));
+    }
+
+    #[test]
+    fn too_long() {
+        assert!(matches!(PostContent::from_str(&"あ".repeat(POST_CONTENT_MAX_CHARACTER_COST / 2 + 1)), Err(ParsePostContentError::CharacterCostOverflow)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use super::{id::PostId, summary::PostSummary};
+
+// 新しい順に並んだ投稿と、続きを取得するためのカーソル
+#[derive(Debug, Serialize)]
+pub struct Feed {
+    posts: Vec<PostSummary>,
+    next_cursor: Option<PostId>,
+}
+
+impl Feed {
+    pub fn new(posts: Vec<PostSummary>, next_cursor: Option<PostId>) -> Self {
+        Self { posts, next_cursor }
+    }
+
+    pub fn posts(&self) -> &Vec<PostSummary> {
+        &self.posts
+    }
+
+    pub fn next_cursor(&self) -> Option<PostId> {
+        self.next_cursor
+    }
+}
+
+// 各索引は新しい順に並んでいる前提で、重複を除いて新しい順に`limit`件まで統合する
+// PostIdはUUIDv7であるため、IDの順序は投稿時刻の順序と一致する
+pub fn merge_post_indexes(indexes: Vec<Vec<PostId>>, limit: usize) -> Vec<PostId> {
+    let mut merged = indexes.into_iter()
+        .flatten()
+        .collect::<Vec<PostId>>();
+
+    merged.sort_unstable_by(|a, b| b.cmp(a));
+    merged.dedup();
+    merged.truncate(limit);
+
+    merged
+}
+
+// 件数が上限に達していれば、最も古い投稿を次のカーソルとする
+pub fn next_cursor(post_ids: &[PostId], limit: usize) -> Option<PostId> {
+    if post_ids.len() < limit {
+        None
+    } else {
+        post_ids.last().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::post::id::PostId;
+
+    use super::{merge_post_indexes, next_cursor};
+
+    fn desc(mut ids: Vec<PostId>) -> Vec<PostId> {
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids
+    }
+
+    #[test]
+    fn merge() {
+        let ids = desc((0..6).map(|_| PostId::gen()).collect());
+
+        let a = vec![ids[0], ids[2], ids[4]];
+        let b = vec![ids[1], ids[2], ids[5]];
+        let c = vec![ids[3]];
+
+        assert_eq!(merge_post_indexes(vec![a.clone(), b.clone(), c.clone()], 10), ids);
+        assert_eq!(merge_post_indexes(vec![a, b, c], 3), ids[..3]);
+        assert!(merge_post_indexes(vec![], 3).is_empty());
+    }
+
+    #[test]
+    fn cursor() {
+        let ids = desc((0..3).map(|_| PostId::gen()).collect());
+
+        assert_eq!(next_cursor(&ids, 3), Some(ids[2]));
+        assert_eq!(next_cursor(&ids, 4), None);
+    }
+}
\ No newline at end of file
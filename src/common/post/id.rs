@@ -0,0 +1,62 @@
+use std::fmt::{self, Display};
+
+use redis::{RedisWrite, ToRedisArgs};
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::common::uuid::uuid7::Uuid7;
+
+// 投稿順に並ぶよう、UUIDv7を用いる
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PostId(Uuid7);
+
+impl PostId {
+    pub fn gen() -> Self {
+        PostId(Uuid7::now())
+    }
+
+    pub const fn of(value: Uuid7) -> Self {
+        PostId(value)
+    }
+
+    pub fn value(&self) -> Uuid7 {
+        self.0
+    }
+}
+
+impl Display for PostId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for PostId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&self.value(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PostId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Uuid7::deserialize(deserializer)
+            .map(PostId::of)
+    }
+}
+
+impl SerializeValue for PostId {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.0, typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for PostId {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        Uuid7::from_cql(cql_val).map(PostId)
+    }
+}
+
+impl ToRedisArgs for PostId {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        self.value().write_redis_args(out)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use crate::common::{handle::id::HandleId, profile::account_id::AccountId, tag::language_group::LanguageGroup};
+
+use super::tags::PostTags;
+
+// 投稿の編集や削除の際に参照する、本文以外の情報
+#[derive(Debug, Clone)]
+pub struct PostMetadata {
+    account_id: AccountId,
+    handle_id: HandleId,
+    language_group: LanguageGroup,
+    tags: PostTags,
+}
+
+impl PostMetadata {
+    pub fn new(account_id: AccountId, handle_id: HandleId, language_group: LanguageGroup, tags: PostTags) -> Self {
+        Self { account_id, handle_id, language_group, tags }
+    }
+
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    pub fn handle_id(&self) -> HandleId {
+        self.handle_id
+    }
+
+    pub fn language_group(&self) -> LanguageGroup {
+        self.language_group
+    }
+
+    pub fn tags(&self) -> &PostTags {
+        &self.tags
+    }
+}
\ No newline at end of file
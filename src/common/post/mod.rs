@@ -0,0 +1,6 @@
+pub mod content;
+pub mod feed;
+pub mod id;
+pub mod metadata;
+pub mod summary;
+pub mod tags;
\ No newline at end of file
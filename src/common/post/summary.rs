@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+use crate::common::{handle::id::HandleId, unixtime::UnixtimeMillis};
+
+use super::{content::PostContent, id::PostId, tags::PostTags};
+
+// フィードなどで公開する投稿の内容
+// 投稿者の`AccountId`は含めない
+#[derive(Debug, Serialize)]
+pub struct PostSummary {
+    post_id: PostId,
+    handle_id: HandleId,
+    content: PostContent,
+    tags: PostTags,
+    posted_at: UnixtimeMillis,
+    edited_at: Option<UnixtimeMillis>,
+}
+
+impl PostSummary {
+    pub fn new(post_id: PostId, handle_id: HandleId, content: PostContent, tags: PostTags, posted_at: UnixtimeMillis, edited_at: Option<UnixtimeMillis>) -> Self {
+        Self { post_id, handle_id, content, tags, posted_at, edited_at }
+    }
+
+    pub fn post_id(&self) -> PostId {
+        self.post_id
+    }
+
+    pub fn handle_id(&self) -> HandleId {
+        self.handle_id
+    }
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::common::{tag::{non_top_tag::NonTopTagId, tag_id::TagId}, uuid::uuid4::Uuid4};
+
+const MAX_POST_TAGS: usize = 10;
+
+// 投稿に付けるタグ
+// 重複は取り除き、指定された順序を保つ
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PostTags(Vec<NonTopTagId>);
+
+impl PostTags {
+    pub fn value(&self) -> &Vec<NonTopTagId> {
+        &self.0
+    }
+
+    pub fn contains(&self, tag_id: &NonTopTagId) -> bool {
+        self.0.contains(tag_id)
+    }
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("タグが多すぎます")]
+pub struct ParsePostTagsError;
+
+impl TryFrom<Vec<NonTopTagId>> for PostTags {
+    type Error = ParsePostTagsError;
+
+    fn try_from(value: Vec<NonTopTagId>) -> Result<Self, Self::Error> {
+        let mut tags = Vec::with_capacity(value.len());
+
+        for tag_id in value {
+            if !tags.contains(&tag_id) {
+                tags.push(tag_id);
+            }
+        }
+
+        if tags.len() > MAX_POST_TAGS {
+            Err(ParsePostTagsError)
+        } else {
+            Ok(PostTags(tags))
+        }
+    }
+}
+
+impl Serialize for PostTags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(NonTopTagId::value))
+    }
+}
+
+impl<'de> Deserialize<'de> for PostTags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<NonTopTagId>::deserialize(deserializer)
+            .and_then(|v| PostTags::try_from(v).map_err(de::Error::custom))
+    }
+}
+
+impl SerializeValue for PostTags {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.0, typ, writer)
+    }
+}
+
+// 空のリストはnullとして返される
+impl FromCqlVal<Option<CqlValue>> for PostTags {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        let Some(cql_val) = cql_val else {
+            return Ok(PostTags::default());
+        };
+
+        Vec::<Uuid>::from_cql(cql_val)?
+            .into_iter()
+            .map(|uuid| {
+                Uuid4::try_from(uuid)
+                    .ok()
+                    .and_then(|uuid| NonTopTagId::try_from(TagId::of(uuid)).ok())
+                    .ok_or(FromCqlValError::BadVal)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(PostTags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tag::non_top_tag::NonTopTagId;
+
+    use super::{ParsePostTagsError, PostTags, MAX_POST_TAGS};
+
+    #[test]
+    fn deduplicated() {
+        let (a, b) = (NonTopTagId::gen(), NonTopTagId::gen());
+        assert_eq!(PostTags::try_from(vec![a, b, a]).unwrap().value(), &vec![a, b]);
+    }
+
+    #[test]
+    fn too_many() {
+        let tags: Vec<_> = (0..=MAX_POST_TAGS).map(|_| NonTopTagId::gen()).collect();
+        assert_eq!(PostTags::try_from(tags), Err(ParsePostTagsError));
+    }
+}
\ No newline at end of file
@@ -10,6 +10,41 @@ pub enum Language {
     AmericanEnglish = 3,
 }
 
+impl Language {
+    // 翻訳の網羅性の検証に用いるため、言語を追加した際は必ず追記する
+    pub const ALL: [Language; 4] = [Language::Japanese, Language::Korean, Language::TaiwaneseMandarin, Language::AmericanEnglish];
+
+    // Accept-Languageヘッダの値から、品質値が最も高い対応言語を選ぶ(同値の場合は先に記述された方を優先する)
+    pub fn from_accept_language(value: &str) -> Option<Language> {
+        let mut selected: Option<(Language, f32)> = None;
+
+        for item in value.split(',') {
+            let mut params = item.split(';');
+            let tag = params.next().unwrap_or_default().trim().to_ascii_lowercase();
+
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let language = match tag.split('-').collect::<Vec<_>>().as_slice() {
+                ["ja", ..] => Language::Japanese,
+                ["ko", ..] => Language::Korean,
+                // 繁体字を用いる地域のみ台湾華語とみなす
+                ["zh", "tw" | "hant" | "hk" | "mo", ..] => Language::TaiwaneseMandarin,
+                ["en", ..] => Language::AmericanEnglish,
+                _ => continue,
+            };
+
+            if quality > 0.0 && selected.is_none_or(|(_, q)| quality > q) {
+                selected = Some((language, quality));
+            }
+        }
+
+        selected.map(|(language, _)| language)
+    }
+}
+
 #[derive(Debug, PartialEq, Error)]
 #[error("有効な言語ではありません")]
 pub struct ParseLanguageError;
@@ -116,6 +151,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn accept_language() {
+        assert_eq!(Language::from_accept_language("ja"), Some(Language::Japanese));
+        assert_eq!(Language::from_accept_language("ko-KR,ko;q=0.9"), Some(Language::Korean));
+        assert_eq!(Language::from_accept_language("zh-TW"), Some(Language::TaiwaneseMandarin));
+        assert_eq!(Language::from_accept_language("zh-Hant-TW"), Some(Language::TaiwaneseMandarin));
+        assert_eq!(Language::from_accept_language("en-GB"), Some(Language::AmericanEnglish));
+    }
+
+    #[test]
+    fn accept_language_by_quality() {
+        assert_eq!(Language::from_accept_language("fr;q=1.0, en;q=0.5, ja;q=0.8"), Some(Language::Japanese));
+        assert_eq!(Language::from_accept_language("ja, en"), Some(Language::Japanese));
+        assert_eq!(Language::from_accept_language("ja;q=0, en;q=0.1"), Some(Language::AmericanEnglish));
+    }
+
+    #[test]
+    fn unsupported_accept_language() {
+        assert_eq!(Language::from_accept_language("zh-CN, fr, *"), None);
+        assert_eq!(Language::from_accept_language(""), None);
+    }
+
+    #[test]
+    fn all_languages_listed() {
+        for i in 0u8..=u8::MAX {
+            if let Ok(language) = Language::try_from(i) {
+                assert!(Language::ALL.contains(&language));
+            }
+        }
+    }
+
     #[test]
     fn deserialize_valid_json() {
         let json = r#"0"#;
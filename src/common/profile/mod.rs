@@ -1,4 +1,5 @@
 pub mod account_id;
 pub mod birth_year;
 pub mod language;
-pub mod region;
\ No newline at end of file
+pub mod region;
+pub mod role;
\ No newline at end of file
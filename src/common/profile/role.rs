@@ -0,0 +1,143 @@
+use std::fmt::{self, Display};
+
+use redis::{FromRedisValue, RedisResult, RedisWrite, ToRedisArgs};
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use thiserror::Error;
+
+// アカウントの権限
+// 上位の権限は下位の権限を含み、管理者はモデレーターとしても振る舞える
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Role {
+    User = 0,
+    Moderator = 1,
+    Admin = 2,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::User, Role::Moderator, Role::Admin];
+
+    pub fn value(&self) -> i8 {
+        *self as i8
+    }
+
+    fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("権限の解析に失敗しました")]
+pub struct ParseRoleError;
+
+impl TryFrom<i8> for Role {
+    type Error = ParseRoleError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Role::User),
+            1 => Ok(Role::Moderator),
+            2 => Ok(Role::Admin),
+            _ => Err(ParseRoleError),
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Role::User => write!(f, "一般"),
+            Role::Moderator => write!(f, "モデレーター"),
+            Role::Admin => write!(f, "管理者"),
+        }
+    }
+}
+
+impl SerializeValue for Role {
+    fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
+        SerializeValue::serialize(&self.value(), typ, writer)
+    }
+}
+
+impl FromCqlVal<Option<CqlValue>> for Role {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        i8::from_cql(cql_val).and_then(|v| Role::try_from(v).map_err(|_| FromCqlValError::BadVal))
+    }
+}
+
+// アカウントが持つ権限の集合
+// キャッシュしやすいよう、ビット集合で表す
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Roles(u8);
+
+impl Roles {
+    // サインインしている全てのアカウントは一般の権限を持つ
+    pub const fn user() -> Self {
+        Roles(1 << (Role::User as u8))
+    }
+
+    pub fn with(self, role: Role) -> Self {
+        Roles(self.0 | role.bit())
+    }
+
+    pub fn contains(&self, role: Role) -> bool {
+        self.0 & role.bit() != 0
+    }
+
+    pub fn satisfies(&self, required: Role) -> bool {
+        Role::ALL.iter().any(|role| *role >= required && self.contains(*role))
+    }
+}
+
+impl FromIterator<Role> for Roles {
+    fn from_iter<I: IntoIterator<Item = Role>>(iter: I) -> Self {
+        iter.into_iter().fold(Roles::user(), Roles::with)
+    }
+}
+
+impl FromRedisValue for Roles {
+    fn from_redis_value(v: &redis::Value) -> RedisResult<Self> {
+        u8::from_redis_value(v).map(Roles)
+    }
+}
+
+impl ToRedisArgs for Roles {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        self.0.write_redis_args(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseRoleError, Role, Roles};
+
+    #[test]
+    fn round_trip() {
+        for role in Role::ALL {
+            assert_eq!(Role::try_from(role.value()), Ok(role));
+        }
+        assert_eq!(Role::try_from(3), Err(ParseRoleError));
+    }
+
+    #[test]
+    fn every_account_is_user() {
+        let roles = Roles::from_iter([]);
+        assert!(roles.satisfies(Role::User));
+        assert!(!roles.satisfies(Role::Moderator));
+        assert!(!roles.satisfies(Role::Admin));
+    }
+
+    #[test]
+    fn admin_satisfies_moderator() {
+        let roles = Roles::from_iter([Role::Admin]);
+        assert!(roles.satisfies(Role::Moderator));
+        assert!(roles.satisfies(Role::Admin));
+        assert!(!roles.contains(Role::Moderator));
+    }
+
+    #[test]
+    fn moderator_does_not_satisfy_admin() {
+        let roles = Roles::from_iter([Role::Moderator]);
+        assert!(roles.satisfies(Role::Moderator));
+        assert!(!roles.satisfies(Role::Admin));
+    }
+}
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::fmt::{self, Display};
 
 use redis::{RedisWrite, ToRedisArgs};
-use scylla::{frame::response::result::ColumnType, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
-use serde::{de, Deserialize, Deserializer};
+use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::{ColumnType, CqlValue}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 use super::{tag_id::TagId, top_tag::is_top_tag_id};
@@ -47,6 +47,12 @@ impl Display for NonTopTagId {
     }
 }
 
+impl Serialize for NonTopTagId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&self.value(), serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for NonTopTagId {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         TagId::deserialize(deserializer)
@@ -64,4 +70,10 @@ impl SerializeValue for NonTopTagId {
     fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
         SerializeValue::serialize(&self.value(), typ, writer)
     }
+}
+
+impl FromCqlVal<Option<CqlValue>> for NonTopTagId {
+    fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+        TagId::from_cql(cql_val).and_then(|v| NonTopTagId::try_from(v).map_err(|_| FromCqlValError::BadVal))
+    }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use redis::{FromRedisValue, RedisResult, RedisWrite, ToRedisArgs};
 use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::{response::result::{ColumnType, CqlValue}, value::CqlTimestamp}, serialize::{value::SerializeValue, writers::WrittenCellProof, CellWriter, SerializationError}};
+use serde::{Serialize, Serializer};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct UnixtimeMillis(u64);
@@ -36,9 +37,15 @@ impl From<UnixtimeMillis> for i64 {
     }
 }
 
+impl Serialize for UnixtimeMillis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&self.value(), serializer)
+    }
+}
+
 impl SerializeValue for UnixtimeMillis {
     fn serialize<'b>(&self, typ: &ColumnType, writer: CellWriter<'b>) -> Result<WrittenCellProof<'b>, SerializationError> {
-        i64::from(*self).serialize(typ, writer)
+        SerializeValue::serialize(&i64::from(*self), typ, writer)
     }
 }
 
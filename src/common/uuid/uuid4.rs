@@ -37,7 +37,7 @@ impl TryFrom<Uuid> for Uuid4 {
     type Error = ParseUuid4Error;
 
     fn try_from(value: Uuid) -> Result<Self, Self::Error> {
-        if value.get_version_num() == 7 {
+        if value.get_version_num() == 4 {
             Ok(Uuid4(value))
         } else {
             Err(ParseUuid4Error)
@@ -0,0 +1,103 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::common::{email::outbox::message::{Attempts, EmailMessageId}, fallible::Fallible, unixtime::UnixtimeMillis};
+
+// 送信予定時刻からこの時間を過ぎても送信されていないメールを、滞留しているとみなす
+const STUCK_THRESHOLD_MILLIS: u64 = 15 * 60 * 1000;
+
+pub(crate) trait ListStuckEmails {
+    async fn list_stuck_emails(&self) -> Fallible<StuckEmails, ListStuckEmailsError> {
+        let overdue_since = UnixtimeMillis::of(UnixtimeMillis::now().value().saturating_sub(STUCK_THRESHOLD_MILLIS));
+
+        let overdue = self.fetch_overdue_emails(overdue_since).await?;
+        let dead_lettered = self.fetch_dead_lettered_emails().await?;
+
+        Ok(StuckEmails { overdue, dead_lettered })
+    }
+
+    async fn fetch_overdue_emails(&self, overdue_since: UnixtimeMillis) -> Fallible<Vec<StuckEmail>, ListStuckEmailsError>;
+
+    async fn fetch_dead_lettered_emails(&self) -> Fallible<Vec<StuckEmail>, ListStuckEmailsError>;
+}
+
+#[derive(Debug, Error)]
+pub enum ListStuckEmailsError {
+    #[error("送信が遅延しているメールの取得に失敗しました")]
+    FetchOverdueEmailsFailed(#[source] anyhow::Error),
+    #[error("デッドレターとなったメールの取得に失敗しました")]
+    FetchDeadLetteredEmailsFailed(#[source] anyhow::Error),
+}
+
+#[derive(Debug, Serialize)]
+pub struct StuckEmails {
+    overdue: Vec<StuckEmail>,
+    dead_lettered: Vec<StuckEmail>,
+}
+
+impl StuckEmails {
+    pub fn overdue(&self) -> &Vec<StuckEmail> {
+        &self.overdue
+    }
+
+    pub fn dead_lettered(&self) -> &Vec<StuckEmail> {
+        &self.dead_lettered
+    }
+}
+
+// 本文は個人情報を含み得るため、調査に必要な情報のみを返す
+#[derive(Debug, Serialize)]
+pub struct StuckEmail {
+    message_id: EmailMessageId,
+    idempotency_key: String,
+    to: String,
+    subject: String,
+    attempts: Attempts,
+    next_attempt_at: u64,
+    last_error: Option<String>,
+}
+
+impl StuckEmail {
+    pub fn new(message_id: EmailMessageId, idempotency_key: String, to: String, subject: String, attempts: Attempts, next_attempt_at: UnixtimeMillis, last_error: Option<String>) -> Self {
+        Self { message_id, idempotency_key, to, subject, attempts, next_attempt_at: next_attempt_at.value(), last_error }
+    }
+
+    pub fn message_id(&self) -> EmailMessageId {
+        self.message_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::common::{email::outbox::message::{Attempts, EmailMessageId}, fallible::Fallible, unixtime::UnixtimeMillis};
+
+    use super::{ListStuckEmails, ListStuckEmailsError, StuckEmail};
+
+    static OVERDUE: LazyLock<EmailMessageId> = LazyLock::new(EmailMessageId::gen);
+    static DEAD_LETTERED: LazyLock<EmailMessageId> = LazyLock::new(EmailMessageId::gen);
+
+    struct MockListStuckEmails;
+
+    fn stuck_email(message_id: EmailMessageId) -> StuckEmail {
+        StuckEmail::new(message_id, String::from("key"), String::from("user@example.com"), String::from("件名"), Attempts::of(1), UnixtimeMillis::of(0), None)
+    }
+
+    impl ListStuckEmails for MockListStuckEmails {
+        async fn fetch_overdue_emails(&self, _: UnixtimeMillis) -> Fallible<Vec<StuckEmail>, ListStuckEmailsError> {
+            Ok(vec![stuck_email(*OVERDUE)])
+        }
+
+        async fn fetch_dead_lettered_emails(&self) -> Fallible<Vec<StuckEmail>, ListStuckEmailsError> {
+            Ok(vec![stuck_email(*DEAD_LETTERED)])
+        }
+    }
+
+    #[tokio::test]
+    async fn list_stuck_emails() {
+        let stuck_emails = MockListStuckEmails.list_stuck_emails().await.unwrap();
+        assert_eq!(stuck_emails.overdue()[0].message_id(), *OVERDUE);
+        assert_eq!(stuck_emails.dead_lettered()[0].message_id(), *DEAD_LETTERED);
+    }
+}
\ No newline at end of file
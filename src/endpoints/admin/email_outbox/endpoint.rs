@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::get, Json, Router};
+use scylla::Session;
+use tower::ServiceBuilder;
+
+use crate::{common::profile::role::Role, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, role_requirer, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{ListStuckEmails, ListStuckEmailsError, StuckEmails}, interpreter::ListStuckEmailsImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ListStuckEmailsImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "lsstkem", 60, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "lsstkem", 60, 15, TimeUnit::MINS).await?)
+        .layer(role_requirer(db.clone(), cache.clone(), Role::Admin).await?);
+
+    let interpreter = ListStuckEmailsImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/admin/email_outbox/stuck", get(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<ListStuckEmailsImpl>>
+) -> Result<Json<StuckEmails>, ListStuckEmailsError> {
+    routine.list_stuck_emails()
+        .await
+        .map(Json)
+}
+
+impl IntoResponse for ListStuckEmailsError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{email::{address::Email, outbox::message::{Attempts, EmailMessageId, OutboxStatus}}, fallible::Fallible, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{ListStuckEmails, ListStuckEmailsError, StuckEmail};
+
+// 一度に表示する件数の上限
+const MAX_STUCK_EMAILS: i32 = 100;
+
+pub struct ListStuckEmailsImpl {
+    db: Arc<Session>,
+    select_overdue_emails: Arc<PreparedStatement>,
+    select_dead_lettered_emails: Arc<PreparedStatement>,
+}
+
+impl ListStuckEmailsImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_overdue_emails = prepare(&db, "SELECT message_id, idempotency_key, to_address, subject, attempts, next_attempt_at, last_error FROM email_outbox WHERE status = ? AND next_attempt_at <= ? LIMIT ?").await?;
+
+        // 新しくデッドレターとなったものから表示する
+        let select_dead_lettered_emails = prepare(&db, "SELECT message_id, idempotency_key, to_address, subject, attempts, next_attempt_at, last_error FROM email_outbox WHERE status = ? ORDER BY next_attempt_at DESC LIMIT ?").await?;
+
+        Ok(Self { db, select_overdue_emails, select_dead_lettered_emails })
+    }
+}
+
+type StuckEmailRow = (EmailMessageId, String, Email, String, i32, UnixtimeMillis, Option<String>);
+
+fn to_stuck_email((message_id, idempotency_key, to, subject, attempts, next_attempt_at, last_error): StuckEmailRow) -> StuckEmail {
+    StuckEmail::new(message_id, idempotency_key, to.value().clone(), subject, Attempts::from(attempts), next_attempt_at, last_error)
+}
+
+impl ListStuckEmails for ListStuckEmailsImpl {
+    async fn fetch_overdue_emails(&self, overdue_since: UnixtimeMillis) -> Fallible<Vec<StuckEmail>, ListStuckEmailsError> {
+        self.db
+            .execute_unpaged(&self.select_overdue_emails, (OutboxStatus::Pending, overdue_since, MAX_STUCK_EMAILS))
+            .await
+            .map_err(|e| ListStuckEmailsError::FetchOverdueEmailsFailed(e.into()))?
+            .rows_typed::<StuckEmailRow>()
+            .map(|rows| rows.flatten().map(to_stuck_email).collect())
+            .map_err(|e| ListStuckEmailsError::FetchOverdueEmailsFailed(e.into()))
+    }
+
+    async fn fetch_dead_lettered_emails(&self) -> Fallible<Vec<StuckEmail>, ListStuckEmailsError> {
+        self.db
+            .execute_unpaged(&self.select_dead_lettered_emails, (OutboxStatus::Dead, MAX_STUCK_EMAILS))
+            .await
+            .map_err(|e| ListStuckEmailsError::FetchDeadLetteredEmailsFailed(e.into()))?
+            .rows_typed::<StuckEmailRow>()
+            .map(|rows| rows.flatten().map(to_stuck_email).collect())
+            .map_err(|e| ListStuckEmailsError::FetchDeadLetteredEmailsFailed(e.into()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod email_outbox;
\ No newline at end of file
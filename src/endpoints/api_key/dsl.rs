@@ -1,9 +1,9 @@
 use thiserror::Error;
 
-use crate::common::{api_key::{expiration::ApiKeyExpirationSeconds, key::ApiKey, API_KEY_EXPIRATION}, fallible::Fallible, turnstile::TurnstileToken};
+use crate::common::{api_key::{expiration::ApiKeyExpirationSeconds, key::ApiKey, API_KEY_EXPIRATION}, fallible::Fallible, human_verification::token::HumanVerificationToken};
 
 pub(crate) trait IssueApiKey {
-    async fn issue_api_key(&self, token: &TurnstileToken) -> Fallible<ApiKey, IssueApiKeyError> {
+    async fn issue_api_key(&self, token: &HumanVerificationToken) -> Fallible<ApiKey, IssueApiKeyError> {
         if self.is_valid_token(token).await? {
             let new_api_key = self.assign_new_api_key_if_unused().await?;
             Ok(new_api_key)
@@ -16,7 +16,7 @@ pub(crate) trait IssueApiKey {
         API_KEY_EXPIRATION
     }
 
-    async fn is_valid_token(&self, token: &TurnstileToken) -> Fallible<bool, IssueApiKeyError>;
+    async fn is_valid_token(&self, token: &HumanVerificationToken) -> Fallible<bool, IssueApiKeyError>;
 
     async fn assign_new_api_key_if_unused(&self) -> Fallible<ApiKey, IssueApiKeyError> {
         let mut new_api_key = ApiKey::gen();
@@ -50,16 +50,16 @@ pub enum IssueApiKeyError {
 mod tests {
     use std::sync::LazyLock;
 
-    use crate::common::{api_key::{expiration::ApiKeyExpirationSeconds, key::ApiKey}, fallible::Fallible, turnstile::TurnstileToken};
+    use crate::common::{api_key::{expiration::ApiKeyExpirationSeconds, key::ApiKey}, fallible::Fallible, human_verification::token::HumanVerificationToken};
 
     use super::{IssueApiKey, IssueApiKeyError};
 
     struct MockIssueApiKey;
 
-    static INVALID_TOKEN: LazyLock<TurnstileToken> = LazyLock::new(|| TurnstileToken::new("0".to_string()));
+    static INVALID_TOKEN: LazyLock<HumanVerificationToken> = LazyLock::new(|| HumanVerificationToken::new("0".to_string()));
 
     impl IssueApiKey for MockIssueApiKey {
-        async fn is_valid_token(&self, token: &TurnstileToken) -> Fallible<bool, IssueApiKeyError> {
+        async fn is_valid_token(&self, token: &HumanVerificationToken) -> Fallible<bool, IssueApiKeyError> {
             if token == &*INVALID_TOKEN {
                 Ok(false)
             } else {
@@ -74,7 +74,7 @@ mod tests {
 
     #[tokio::test]
     async fn valid_token() {
-        assert!(MockIssueApiKey.issue_api_key(&TurnstileToken::new("1".to_string())).await.is_ok());
+        assert!(MockIssueApiKey.issue_api_key(&HumanVerificationToken::new("1".to_string())).await.is_ok());
     }
 
     #[tokio::test]
@@ -1,21 +1,23 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::post, Form, Json, Router};
-use http::StatusCode;
-use reqwest::Client;
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Form, Json, Router};
+use scylla::Session;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
-use crate::{common::{api_key::key::ApiKey, turnstile::TurnstileToken}, helper::{error::InitError, redis::connection::Pool}};
+use crate::{common::{api_key::key::ApiKey, human_verification::{config::ConfiguredHumanVerifier, token::HumanVerificationToken}}, helper::{api_error::ApiError, error::InitError, middleware::error_localizer, redis::connection::Pool}};
 
 use super::{dsl::{IssueApiKey, IssueApiKeyError}, interpreter::IssueApiKeyImpl};
 
 // ここにレート制限がかけられないので、WAFなどで設定する必要がある
-pub async fn endpoint(cache: Arc<Pool>, client: Arc<Client>, turnstile_secret_key: String) -> Result<Router, InitError<IssueApiKeyImpl>> {
-    let sign_in = IssueApiKeyImpl::try_new(cache, client, turnstile_secret_key).await?;
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, verifier: ConfiguredHumanVerifier) -> Result<Router, InitError<IssueApiKeyImpl>> {
+    let error_localizer = error_localizer(db).await?;
+
+    let sign_in = IssueApiKeyImpl::try_new(cache, verifier).await?;
 
     let router = Router::new()
         .route("/", post(handler))
+        .layer(error_localizer)
         .with_state(Arc::new(sign_in));
 
     Ok(router)
@@ -23,28 +25,38 @@ pub async fn endpoint(cache: Arc<Pool>, client: Arc<Client>, turnstile_secret_ke
 
 pub async fn handler(
     State(routine): State<Arc<IssueApiKeyImpl>>,
-    Form(form): Form<TurnstileForm>,
-) -> Result<Json<Data>, StatusCode> {
-    match routine.issue_api_key(&TurnstileToken::new(form.cf_turnstile_token)).await {
+    Form(form): Form<HumanVerificationForm>,
+) -> Result<Json<Data>, IssueApiKeyError> {
+    match routine.issue_api_key(&HumanVerificationToken::new(form.token)).await {
         Ok(api_key) => Ok(Json(Data { api_key })),
         Err(e) => match e {
-            IssueApiKeyError::InvalidToken => Err(StatusCode::BAD_REQUEST),
+            IssueApiKeyError::InvalidToken => Err(e),
             e => {
                 error!(
                     error = %e,
                     "APIキーの発行に失敗しました"
                 );
 
-                Err(StatusCode::INTERNAL_SERVER_ERROR)
+                Err(e)
             }
         }
     }
 }
 
+impl IntoResponse for IssueApiKeyError {
+    fn into_response(self) -> Response {
+        match self {
+            IssueApiKeyError::InvalidToken => ApiError::INVALID_HUMAN_VERIFICATION_TOKEN,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
 #[derive(Deserialize)]
-pub struct TurnstileForm {
-    #[serde(rename = "cf-turnstile-token")]
-    cf_turnstile_token: String,
+pub struct HumanVerificationForm {
+    // 各プロバイダのウィジェットが送信するフィールド名を受け付ける
+    #[serde(rename = "cf-turnstile-response", alias = "cf-turnstile-token", alias = "h-captcha-response", alias = "g-recaptcha-response")]
+    token: String,
 }
 
 #[derive(Serialize)]
@@ -1,45 +1,28 @@
 use std::sync::Arc;
 
 use redis::cmd;
-use reqwest::{multipart::Form, Client};
-use serde_json::Value;
-use crate::{common::{api_key::{expiration::ApiKeyExpirationSeconds, refreshed_at::LastApiKeyRefreshedAt, key::ApiKey}, fallible::Fallible, turnstile::TurnstileToken, unixtime::UnixtimeMillis}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::NAMESPACE_SEPARATOR, namespaces::API_KEY}}};
+
+use crate::{common::{api_key::{expiration::ApiKeyExpirationSeconds, refreshed_at::LastApiKeyRefreshedAt, key::ApiKey}, fallible::Fallible, human_verification::{config::ConfiguredHumanVerifier, token::HumanVerificationToken, verify::HumanVerifier}, unixtime::UnixtimeMillis}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::NAMESPACE_SEPARATOR, namespaces::API_KEY}}};
 
 use super::dsl::{IssueApiKey, IssueApiKeyError};
 
 pub struct IssueApiKeyImpl {
     cache: Arc<Pool>,
-    client: Arc<Client>,
-    turnstile_secret_key: String,
+    verifier: ConfiguredHumanVerifier,
 }
 
 impl IssueApiKeyImpl {
-    pub async fn try_new(cache: Arc<Pool>, client: Arc<Client>, turnstile_secret_key: String) -> Result<Self, InitError<Self>> {
-        Ok(Self{ cache, client, turnstile_secret_key })
+    pub async fn try_new(cache: Arc<Pool>, verifier: ConfiguredHumanVerifier) -> Result<Self, InitError<Self>> {
+        Ok(Self{ cache, verifier })
     }
 }
 
 impl IssueApiKey for IssueApiKeyImpl {
-    async fn is_valid_token(&self, token: &TurnstileToken) -> Fallible<bool, IssueApiKeyError> {
-        let form = Form::new()
-            .text("secret", self.turnstile_secret_key.clone())
-            .text("response", token.value().clone());
-
-        let url = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
-        let response = self.client
-            .post(url)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| IssueApiKeyError::IsValidTokenFailed(e.into()))?;
-
-        let outcome: Value = response.json()
+    async fn is_valid_token(&self, token: &HumanVerificationToken) -> Fallible<bool, IssueApiKeyError> {
+        self.verifier
+            .verify(token)
             .await
-            .map_err(|e| IssueApiKeyError::IsValidTokenFailed(e.into()))?;
-
-        let success = outcome["success"].as_bool().unwrap_or(false);
-
-        Ok(success)
+            .map_err(|e| IssueApiKeyError::IsValidTokenFailed(e.into()))
     }
 
     async fn try_assign_new_api_key_if_unused(&self, new_api_key: &ApiKey, expiration: ApiKeyExpirationSeconds) -> Fallible<(), IssueApiKeyError> {
@@ -1,3 +1,5 @@
+pub mod resend_verification;
 pub mod sign_up;
 pub mod verify_email;
-mod value;
\ No newline at end of file
+mod value;
+mod verification_email;
\ No newline at end of file
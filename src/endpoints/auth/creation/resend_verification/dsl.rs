@@ -0,0 +1,99 @@
+use thiserror::Error;
+
+use crate::{common::{auth::one_time_token::OneTimeToken, email::address::Email, fallible::Fallible, profile::language::Language}, endpoints::auth::creation::sign_up::dsl::{ApplicationExpirationSeconds, CREATE_ACCOUNT_APPLICATION_EXPIRATION}};
+
+pub(crate) trait ResendVerificationEmail {
+    // 申請が存在しない場合も同じ結果を返し、申請の有無を推測させない
+    async fn resend_verification_email(&self, email: &Email) -> Fallible<(), ResendVerificationEmailError> {
+        // 申請は正規化したメールアドレスで登録されている
+        let email = &email.normalized();
+        let token = OneTimeToken::gen();
+
+        match self.rotate_application_token(email, &token, CREATE_ACCOUNT_APPLICATION_EXPIRATION).await? {
+            Some(language) => self.send_verification_email(email, language, &token, CREATE_ACCOUNT_APPLICATION_EXPIRATION).await,
+            None => Ok(()),
+        }
+    }
+
+    // 古いトークンを無効化して新しいトークンに付け替え、申請の言語を返す
+    // 申請が無い場合や、直前に再送したばかりの場合は`None`を返す
+    async fn rotate_application_token(&self, email: &Email, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Fallible<Option<Language>, ResendVerificationEmailError>;
+
+    async fn send_verification_email(&self, email: &Email, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Fallible<(), ResendVerificationEmailError>;
+}
+
+#[derive(Debug, Error)]
+pub enum ResendVerificationEmailError {
+    #[error("一時トークンの付け替えに失敗しました")]
+    RotateApplicationTokenFailed(#[source] anyhow::Error),
+    #[error("認証メールの送信に失敗しました")]
+    SendVerificationEmailFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::{LazyLock, Mutex}};
+
+    use thiserror::Error;
+
+    use crate::{common::{auth::one_time_token::OneTimeToken, email::address::Email, fallible::Fallible, profile::language::Language}, endpoints::auth::creation::sign_up::dsl::ApplicationExpirationSeconds};
+
+    use super::{ResendVerificationEmail, ResendVerificationEmailError};
+
+    static APPLIED: LazyLock<Email> = LazyLock::new(|| Email::from_str("applied@example.com").unwrap());
+    static NOT_APPLIED: LazyLock<Email> = LazyLock::new(|| Email::from_str("not-applied@example.com").unwrap());
+    static UNROTATABLE: LazyLock<Email> = LazyLock::new(|| Email::from_str("unrotatable@example.com").unwrap());
+
+    #[derive(Default)]
+    struct MockResendVerificationEmail {
+        rotated: Mutex<Option<OneTimeToken>>,
+        sent: Mutex<Option<(Language, OneTimeToken)>>,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("疑似エラー")]
+    struct MockError;
+
+    impl ResendVerificationEmail for MockResendVerificationEmail {
+        async fn rotate_application_token(&self, email: &Email, token: &OneTimeToken, _: ApplicationExpirationSeconds) -> Fallible<Option<Language>, ResendVerificationEmailError> {
+            if *email == *UNROTATABLE {
+                return Err(ResendVerificationEmailError::RotateApplicationTokenFailed(MockError.into()));
+            }
+
+            if *email != *APPLIED {
+                return Ok(None);
+            }
+
+            *self.rotated.lock().unwrap() = Some(token.clone());
+            Ok(Some(Language::Korean))
+        }
+
+        async fn send_verification_email(&self, _: &Email, language: Language, token: &OneTimeToken, _: ApplicationExpirationSeconds) -> Fallible<(), ResendVerificationEmailError> {
+            *self.sent.lock().unwrap() = Some((language, token.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn resend_with_rotated_token() {
+        let resend = MockResendVerificationEmail::default();
+        assert!(resend.resend_verification_email(&APPLIED).await.is_ok());
+
+        let rotated = resend.rotated.lock().unwrap().clone().unwrap();
+        assert_eq!(*resend.sent.lock().unwrap(), Some((Language::Korean, rotated)));
+    }
+
+    #[tokio::test]
+    async fn not_applied() {
+        let resend = MockResendVerificationEmail::default();
+        assert!(resend.resend_verification_email(&NOT_APPLIED).await.is_ok());
+        assert!(resend.sent.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn rotate_failed() {
+        let resend = MockResendVerificationEmail::default();
+        assert!(matches!(resend.resend_verification_email(&UNROTATABLE).await, Err(ResendVerificationEmailError::RotateApplicationTokenFailed(_))));
+        assert!(resend.sent.lock().unwrap().is_none());
+    }
+}
\ No newline at end of file
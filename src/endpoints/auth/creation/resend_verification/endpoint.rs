@@ -0,0 +1,59 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::{ConnectInfo, State}, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use scylla::Session;
+use serde::Deserialize;
+use tokio::task;
+use tower::ServiceBuilder;
+use tracing::info;
+
+use crate::{common::email::address::Email, helper::{error::InitError, middleware::{error_localizer, rate_limiter_by}, redis::connection::Pool}, middlewares::{limit::TimeUnit, rate_limit::dsl::rate_limit::RateLimitKeyStrategy}};
+
+use super::dsl::ResendVerificationEmail;
+use super::interpreter::ResendVerificationEmailImpl;
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ResendVerificationEmailImpl>> {
+    // 送信者としての悪用を防ぐため、アカウント作成の申請よりも厳しく制限する
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter_by(db.clone(), cache.clone(), "rsvem", 3, 1, TimeUnit::HOURS, RateLimitKeyStrategy::API_KEY.and(RateLimitKeyStrategy::IP_ADDRESS)).await?);
+
+    let resend = ResendVerificationEmailImpl::try_new(db, cache).await?;
+
+    let router = Router::new()
+        .route("/sign_up/resend", post(handler))
+        .layer(services)
+        .with_state(Arc::new(resend));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(routine): State<Arc<ResendVerificationEmailImpl>>,
+    Json(payload): Json<Payload>,
+) -> impl IntoResponse {
+    // 申請の有無によらず同じ応答を返し、処理時間の差も計測させない
+    task::spawn(async move {
+        match routine.resend_verification_email(&payload.email).await {
+            Ok(_) => info!(
+                ip_address = %addr.ip(),
+                email = %payload.email.value(),
+                "認証メールの再送が正常に処理されました。"
+            ),
+            Err(e) => info!(
+                ip_address = %addr.ip(),
+                email = %payload.email.value(),
+                error = %e,
+                "認証メールの再送に失敗しました。"
+            ),
+        }
+    });
+
+    StatusCode::OK
+}
+
+#[derive(Deserialize)]
+pub struct Payload {
+    pub email: Email,
+}
\ No newline at end of file
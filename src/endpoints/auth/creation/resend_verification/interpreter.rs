@@ -0,0 +1,65 @@
+use std::{sync::Arc, time::Duration};
+
+use redis::Script;
+use scylla::Session;
+
+use crate::{common::{auth::one_time_token::OneTimeToken, email::{address::Email, outbox::{enqueue::EnqueueEmail, interpreter::EmailOutboxImpl}, template::registry::EmailTemplates}, fallible::Fallible, profile::language::Language}, endpoints::auth::creation::{sign_up::dsl::ApplicationExpirationSeconds, value::{format_email_key, format_key, format_resend_cooldown_key, parse_language, PRE_VERIFICATION_ACCOUNTS_NAMESPACE}, verification_email::compose_verification_email}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::NAMESPACE_SEPARATOR}}};
+
+use super::dsl::{ResendVerificationEmail, ResendVerificationEmailError};
+
+// 同じメールアドレスへの再送の最短間隔
+const RESEND_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+pub struct ResendVerificationEmailImpl {
+    cache: Arc<Pool>,
+    rotate_application_token: Arc<Script>,
+    outbox: EmailOutboxImpl,
+    templates: EmailTemplates,
+}
+
+impl ResendVerificationEmailImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>) -> Result<Self, InitError<Self>> {
+        let rotate_application_token = Arc::new(Script::new(include_str!("rotate_application_token.lua")));
+        let outbox = EmailOutboxImpl::try_new(db)
+            .await
+            .map_err(|e| InitError::new(e.into()))?;
+        let templates = EmailTemplates::load().map_err(|e| InitError::new(e.into()))?;
+
+        Ok(Self { cache, rotate_application_token, outbox, templates })
+    }
+}
+
+impl ResendVerificationEmail for ResendVerificationEmailImpl {
+    async fn rotate_application_token(&self, email: &Email, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Fallible<Option<Language>, ResendVerificationEmailError> {
+        let mut conn = conn(&self.cache, |e| ResendVerificationEmailError::RotateApplicationTokenFailed(e.into())).await?;
+
+        let application = self.rotate_application_token
+            .key(format_email_key(email))
+            .key(format_key(token))
+            .key(format_resend_cooldown_key(email))
+            .arg(format!("{}{}", PRE_VERIFICATION_ACCOUNTS_NAMESPACE, NAMESPACE_SEPARATOR))
+            .arg(token)
+            .arg(expiration)
+            .arg(RESEND_COOLDOWN.as_secs())
+            .invoke_async::<Option<String>>(&mut *conn)
+            .await
+            .map_err(|e| ResendVerificationEmailError::RotateApplicationTokenFailed(e.into()))?;
+
+        match application {
+            Some(application) => parse_language(&application)
+                .map(Some)
+                .ok_or_else(|| ResendVerificationEmailError::RotateApplicationTokenFailed(anyhow::anyhow!("申請の言語を解析できませんでした"))),
+            None => Ok(None),
+        }
+    }
+
+    async fn send_verification_email(&self, email: &Email, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Fallible<(), ResendVerificationEmailError> {
+        let message = compose_verification_email(&self.templates, email, language, token, expiration)
+            .map_err(|e| ResendVerificationEmailError::SendVerificationEmailFailed(e.into()))?;
+
+        self.outbox
+            .enqueue(message)
+            .await
+            .map_err(|e| ResendVerificationEmailError::SendVerificationEmailFailed(e.into()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+-- KEYS[1]: メールアドレスから最新のトークンへの索引
+-- KEYS[2]: 新しいトークンの申請のキー
+-- KEYS[3]: 再送の間隔を空けるためのキー
+-- ARGV[1]: 申請のキーの接頭辞
+-- ARGV[2]: 新しいトークン
+-- ARGV[3]: 申請の有効期限(秒)
+-- ARGV[4]: 再送の間隔(秒)
+local old_token = redis.call('GET', KEYS[1])
+if not old_token then
+    return false
+end
+
+local old_key = ARGV[1] .. old_token
+local application = redis.call('GET', old_key)
+if not application then
+    return false
+end
+
+if not redis.call('SET', KEYS[3], 1, 'NX', 'EX', ARGV[4]) then
+    return false
+end
+
+-- 古いトークンを無効化し、新しいトークンで有効期限を延長した申請を登録する
+redis.call('SET', KEYS[2], application, 'EX', ARGV[3])
+redis.call('DEL', old_key)
+redis.call('SET', KEYS[1], ARGV[2], 'EX', ARGV[3])
+
+return application
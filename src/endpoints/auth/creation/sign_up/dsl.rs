@@ -3,27 +3,58 @@ use thiserror::Error;
 
 use crate::common::{auth::{one_time_token::OneTimeToken, password::{Password, PasswordHash}}, email::address::Email, fallible::Fallible, profile::{birth_year::BirthYear, language::Language, region::Region}};
 
-const CREATE_ACCOUNT_APPLICATION_EXPIRATION: ApplicationExpirationSeconds = ApplicationExpirationSeconds::days(1);
+pub const CREATE_ACCOUNT_APPLICATION_EXPIRATION: ApplicationExpirationSeconds = ApplicationExpirationSeconds::days(1);
 
 pub(crate) trait SignUp {
     async fn sign_up(&self, email: &Email, password: &Password, birth_year: BirthYear, region: Region, language: Language) -> Fallible<(), SignUpError> {
-        if self.is_available_email(email).await? {
+        // 表記揺れによる重複登録を防ぐため、正規化したメールアドレスで申請する
+        let normalized = email.normalized();
+
+        if self.is_available_email(email, &normalized).await? {
+            let email = &normalized;
+
             // この位置でパスワードのハッシュ化が行われ高い負荷が発生するため、
             // `sign_up`は自動化されたリクエストから特に保護されなければならない
             let hash: PasswordHash = password.hashed();
             let token = OneTimeToken::gen();
             self.apply_to_create_account(email, &hash, birth_year, region, language, &token, CREATE_ACCOUNT_APPLICATION_EXPIRATION).await?;
-            self.send_verification_email(email, language, &token).await
+            self.send_verification_email(email, language, &token, CREATE_ACCOUNT_APPLICATION_EXPIRATION).await
         } else {
             Err(SignUpError::UnavailableEmail)
         }
     }
 
-    async fn is_available_email(&self, email: &Email) -> Fallible<bool, SignUpError>;
+    // 漏洩の有無はメールアドレスの登録状況と無関係なため、申請の受付前に確認して結果を返してよい
+    async fn screen_password(&self, password: &Password) -> Fallible<(), SignUpError> {
+        if self.is_breached_password(password).await? {
+            Err(SignUpError::BreachedPassword)
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn is_breached_password(&self, password: &Password) -> Fallible<bool, SignUpError>;
+
+    async fn is_available_email(&self, email: &Email, normalized: &Email) -> Fallible<bool, SignUpError> {
+        if !self.satisfies_email_policy(normalized).await? {
+            return Ok(false);
+        }
+
+        match self.is_registered_email(normalized).await? {
+            true => Ok(false),
+            // 正規化の導入前に登録されたアカウントは、入力された表記のまま保存されている
+            false if normalized != email => self.is_registered_email(email).await.map(|registered| !registered),
+            false => Ok(true),
+        }
+    }
+
+    async fn satisfies_email_policy(&self, email: &Email) -> Fallible<bool, SignUpError>;
+
+    async fn is_registered_email(&self, email: &Email) -> Fallible<bool, SignUpError>;
 
     async fn apply_to_create_account(&self, email: &Email, password_hash: &PasswordHash, birth_year: BirthYear, region: Region, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Fallible<(), SignUpError>;
 
-    async fn send_verification_email(&self, email: &Email, language: Language, token: &OneTimeToken) -> Result<(), SignUpError>;
+    async fn send_verification_email(&self, email: &Email, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Result<(), SignUpError>;
 }
 
 #[derive(Debug, Error)]
@@ -32,6 +63,10 @@ pub enum SignUpError {
     PotentiallyUnavailableEmail(#[source] anyhow::Error),
     #[error("指定のメールアドレスは利用不能です")]
     UnavailableEmail,
+    #[error("パスワードの漏洩の確認に失敗しました")]
+    BreachCheckFailed(#[source] anyhow::Error),
+    #[error("漏洩したパスワードは利用できません")]
+    BreachedPassword,
     #[error("アカウント作成の申請に失敗しました")]
     ApplicationFailed(#[source] anyhow::Error),
     #[error("認証メールの送信に失敗しました")]
@@ -85,13 +120,32 @@ mod tests {
     const AVAILABLE_BUT_APPLICATION_FAILED: &str = "case3@example.com";
     const APPLIED_BUT_SEND_FAILED: &str = "case4@example.com";
     const SIGN_UP: &str = "case5@example.com";
+    // 正規化の導入前に、入力された表記のまま登録されたメールアドレス
+    const LEGACY: &str = "Legacy.User@Example.COM";
+
+    const BREACHED_PASSWORD: &str = "correct horse battery staple";
+    const UNCHECKABLE_PASSWORD: &str = "uncheckable password";
 
     impl SignUp for MockSignUp {
-        async fn is_available_email(&self, case: &Email) -> Fallible<bool, SignUpError> {
+        async fn is_breached_password(&self, password: &Password) -> Fallible<bool, SignUpError> {
+            match password.value().as_str() {
+                BREACHED_PASSWORD => Ok(true),
+                UNCHECKABLE_PASSWORD => Err(SignUpError::BreachCheckFailed(MockError.into())),
+                _ => Ok(false)
+            }
+        }
+
+        async fn satisfies_email_policy(&self, case: &Email) -> Fallible<bool, SignUpError> {
             match case.value().as_str() {
-                AVAILABLE_BUT_APPLICATION_FAILED | APPLIED_BUT_SEND_FAILED | SIGN_UP  => Ok(true),
-                UNAVAILABLE => Ok(false),
-                _ => Err(SignUpError::PotentiallyUnavailableEmail(MockError.into()))
+                POTENTIALLY_UNAVAILABLE => Err(SignUpError::PotentiallyUnavailableEmail(MockError.into())),
+                _ => Ok(true)
+            }
+        }
+
+        async fn is_registered_email(&self, case: &Email) -> Fallible<bool, SignUpError> {
+            match case.value().as_str() {
+                UNAVAILABLE | LEGACY => Ok(true),
+                _ => Ok(false)
             }
         }
 
@@ -102,7 +156,7 @@ mod tests {
             }
         }
     
-        async fn send_verification_email(&self, case: &Email, _: Language, _: &OneTimeToken) -> Fallible<(), SignUpError> {
+        async fn send_verification_email(&self, case: &Email, _: Language, _: &OneTimeToken, _: ApplicationExpirationSeconds) -> Fallible<(), SignUpError> {
             match case.value().as_str() {
                 SIGN_UP => Ok(()),
                 _ => Err(SignUpError::AuthenticationEmailSendFailed(MockError.into()))
@@ -136,6 +190,12 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn legacy_unavailable() {
+        let email = Email::from_str(LEGACY).unwrap();
+        assert!(!MockSignUp.is_available_email(&email, &email.normalized()).await.unwrap());
+    }
+
     #[tokio::test]
     async fn available_but_application_failed() {
         match test_sign_up(AVAILABLE_BUT_APPLICATION_FAILED).await.err().unwrap() {
@@ -156,4 +216,25 @@ mod tests {
     async fn sign_up() {
         assert!(test_sign_up(SIGN_UP).await.is_ok());
     }
+
+    #[tokio::test]
+    async fn breached_password() {
+        match MockSignUp.screen_password(&Password::new_unchecked(BREACHED_PASSWORD)).await.err().unwrap() {
+            SignUpError::BreachedPassword => (),
+            _ => panic!("予期しないエラーが発生しました")
+        }
+    }
+
+    #[tokio::test]
+    async fn breach_check_failed() {
+        match MockSignUp.screen_password(&Password::new_unchecked(UNCHECKABLE_PASSWORD)).await.err().unwrap() {
+            SignUpError::BreachCheckFailed(_) => (),
+            _ => panic!("予期しないエラーが発生しました")
+        }
+    }
+
+    #[tokio::test]
+    async fn unbreached_password() {
+        assert!(MockSignUp.screen_password(&Password::new_unchecked("SCBGpks6FfnCb6R")).await.is_ok());
+    }
 }
\ No newline at end of file
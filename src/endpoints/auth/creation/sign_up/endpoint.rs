@@ -1,23 +1,24 @@
 use std::{net::SocketAddr, sync::Arc};
 
-use axum::{extract::{ConnectInfo, State}, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{extract::{ConnectInfo, State}, http::StatusCode, response::{IntoResponse, Response}, routing::post, Json, Router};
 use scylla::Session;
 use serde::Deserialize;
 use tokio::task;
 use tower::ServiceBuilder;
 use tracing::info;
 
-use crate::{common::{auth::password::Password, email::address::Email, profile::{birth_year::BirthYear, language::Language, region::Region}}, helper::middleware::rate_limiter, middlewares::limit::TimeUnit};
+use crate::{common::{auth::{breach::config::ConfiguredPasswordBreachChecker, password::Password}, email::address::Email, profile::{birth_year::BirthYear, language::Language, region::Region}}, helper::{api_error::ApiError, middleware::{error_localizer, rate_limiter}}, middlewares::limit::TimeUnit};
 use crate::helper::{error::InitError, redis::connection::Pool};
 
-use super::dsl::SignUp;
+use super::dsl::{SignUp, SignUpError};
 use super::interpreter::SignUpImpl;
 
-pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<SignUpImpl>> {
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, breach_checker: ConfiguredPasswordBreachChecker) -> Result<Router, InitError<SignUpImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "sigup", 5, 6, TimeUnit::HOURS).await?);
 
-    let sign_up = SignUpImpl::try_new(db, cache).await?;
+    let sign_up = SignUpImpl::try_new(db, cache, breach_checker).await?;
 
     let router = Router::new()
         .route("/sign_up", post(handler))
@@ -31,7 +32,9 @@ pub async fn handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     State(routine): State<Arc<SignUpImpl>>,
     Json(payload): Json<Payload>,
-) -> impl IntoResponse {
+) -> Result<StatusCode, SignUpError> {
+    routine.screen_password(&payload.password).await?;
+
     // 非 quick exit パターンを採用し、攻撃者に処理時間の差を計測させない
     task::spawn(async move {
         match routine.sign_up(&payload.email, &payload.password, payload.birth_year, payload.region, payload.language).await {
@@ -55,7 +58,16 @@ pub async fn handler(
     });
 
     // `sign_up`の終了を待たずに返す
-    StatusCode::OK
+    Ok(StatusCode::OK)
+}
+
+impl IntoResponse for SignUpError {
+    fn into_response(self) -> Response {
+        match self {
+            SignUpError::BreachedPassword => ApiError::BREACHED_PASSWORD.into_response(),
+            _ => ApiError::INTERNAL.into_response(),
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -1,9 +1,9 @@
-use std::{str::FromStr, sync::{Arc, LazyLock}};
+use std::sync::Arc;
 
-use redis::cmd;
+use redis::pipe;
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::{common::{auth::{one_time_token::OneTimeToken, password::PasswordHash}, email::{address::Email, resend::ResendEmailSender, send::{Body, EmailSender, HtmlContent, NetmateEmail, PlainText, SenderName, Subject}}, fallible::Fallible, profile::{account_id::AccountId, birth_year::BirthYear, language::Language, region::Region}}, endpoints::auth::creation::value::{format_key, format_value}, helper::{error::InitError, redis::connection::{conn, Pool}, scylla::prepare}, translation::{ja, us_en}};
+use crate::{common::{auth::{breach::{check::PasswordBreachChecker, config::ConfiguredPasswordBreachChecker}, one_time_token::OneTimeToken, password::{Password, PasswordHash}}, email::{address::Email, policy::{mx::DnsMxResolver, CheckEmailPolicyError, EmailDomainPolicy}, outbox::{enqueue::EnqueueEmail, interpreter::EmailOutboxImpl}, template::registry::EmailTemplates}, fallible::Fallible, profile::{account_id::AccountId, birth_year::BirthYear, language::Language, region::Region}}, endpoints::auth::creation::{value::{format_email_key, format_key, format_value}, verification_email::compose_verification_email}, helper::{error::InitError, redis::connection::{conn, Pool}, scylla::prepare}};
 
 use super::dsl::{ApplicationExpirationSeconds, SignUp, SignUpError};
 
@@ -11,21 +11,42 @@ pub struct SignUpImpl {
     db: Arc<Session>,
     cache: Arc<Pool>,
     select_account_id: Arc<PreparedStatement>,
+    outbox: EmailOutboxImpl,
+    templates: EmailTemplates,
+    policy: EmailDomainPolicy<DnsMxResolver>,
+    breach_checker: ConfiguredPasswordBreachChecker,
 }
 
 impl SignUpImpl {
-    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>) -> Result<Self, InitError<SignUpImpl>> {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, breach_checker: ConfiguredPasswordBreachChecker) -> Result<Self, InitError<SignUpImpl>> {
         let select_account_id = prepare(&db, "SELECT id FROM accounts WHERE email = ? LIMIT 1 BYPASS CACHE").await?;
-        Ok(Self { db, cache, select_account_id })
+        let outbox = EmailOutboxImpl::try_new(db.clone())
+            .await
+            .map_err(|e| InitError::new(e.into()))?;
+        let templates = EmailTemplates::load().map_err(|e| InitError::new(e.into()))?;
+        let policy = EmailDomainPolicy::from_env().map_err(|e| InitError::new(e.into()))?;
+
+        Ok(Self { db, cache, select_account_id, outbox, templates, policy, breach_checker })
     }
 }
 
-static AUTHENTICATION_EMAIL_ADDRESS: LazyLock<NetmateEmail> = LazyLock::new(|| NetmateEmail::try_from(Email::from_str("verify-email@account.netmate.app").unwrap()).unwrap());
-static JA_AUTHENTICATION_EMAIL_SUBJECT: LazyLock<Subject> = LazyLock::new(|| Subject::from_str(ja::sign_up::AUTHENTICATION_EMAIL_SUBJECT).unwrap());
-static US_EN_AUTHENTICATION_EMAIL_SUBJECT: LazyLock<Subject> = LazyLock::new(|| Subject::from_str(us_en::sign_up::AUTHENTICATION_EMAIL_SUBJECT).unwrap());
-
 impl SignUp for SignUpImpl {
-    async fn is_available_email(&self, email: &Email) -> Fallible<bool, SignUpError> {
+    async fn is_breached_password(&self, password: &Password) -> Fallible<bool, SignUpError> {
+        self.breach_checker
+            .is_breached(password)
+            .await
+            .map_err(|e| SignUpError::BreachCheckFailed(e.into()))
+    }
+
+    async fn satisfies_email_policy(&self, email: &Email) -> Fallible<bool, SignUpError> {
+        match self.policy.check(email).await {
+            Ok(()) => Ok(true),
+            Err(CheckEmailPolicyError::ResolveMxFailed(e)) => Err(SignUpError::PotentiallyUnavailableEmail(e)),
+            Err(_) => Ok(false),
+        }
+    }
+
+    async fn is_registered_email(&self, email: &Email) -> Fallible<bool, SignUpError> {
         self.db
             .execute_unpaged(&self.select_account_id, (email, ))
             .await
@@ -38,31 +59,30 @@ impl SignUp for SignUpImpl {
     async fn apply_to_create_account(&self, email: &Email, password_hash: &PasswordHash, birth_year: BirthYear, region: Region, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Result<(), SignUpError> {
         let mut conn = conn(&self.cache, |e| SignUpError::ApplicationFailed(e.into())).await?;
 
-        cmd("SET")
+        // 申請と索引の一方だけが残らないよう、まとめて登録する
+        pipe()
+            .atomic()
+            .cmd("SET")
             .arg(format_key(token))
             .arg(format_value(email, password_hash, birth_year, region, language))
             .arg("EX")
             .arg(expiration)
+            .cmd("SET")
+            .arg(format_email_key(email))
+            .arg(token)
+            .arg("EX")
+            .arg(expiration)
             .exec_async(&mut *conn)
             .await
             .map_err(|e| SignUpError::ApplicationFailed(e.into()))
     }
 
-    async fn send_verification_email(&self, email: &Email, language: Language, token: &OneTimeToken) -> Result<(), SignUpError> {
-        let sender_name = SenderName::by(language);
-
-        // ユーザーの設定言語に応じたテキストを取得する
-        let (subject, html_content, plain_text) = match language {
-            Language::Japanese => (&*JA_AUTHENTICATION_EMAIL_SUBJECT, ja::sign_up::ATUHENTICATION_EMAIL_BODY_HTML, ja::sign_up::AUTHENTICATION_EMAIL_BODY_PLAIN),
-            _ => (&*US_EN_AUTHENTICATION_EMAIL_SUBJECT, us_en::sign_up::ATUHENTICATION_EMAIL_BODY_HTML, us_en::sign_up::AUTHENTICATION_EMAIL_BODY_PLAIN),
-        };
-
-        let body = Body::new(
-            HtmlContent::new(&html_content.replace("{token}", token.value())),
-            PlainText::new(&plain_text.replace("{token}", token.value()))
-        );
+    async fn send_verification_email(&self, email: &Email, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Result<(), SignUpError> {
+        let message = compose_verification_email(&self.templates, email, language, token, expiration)
+            .map_err(|e| SignUpError::AuthenticationEmailSendFailed(e.into()))?;
 
-        ResendEmailSender::send(&AUTHENTICATION_EMAIL_ADDRESS, email, &sender_name, subject, &body)
+        self.outbox
+            .enqueue(message)
             .await
             .map_err(|e| SignUpError::AuthenticationEmailSendFailed(e.into()))
     }
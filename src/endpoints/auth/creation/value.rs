@@ -2,12 +2,19 @@ use crate::{common::{auth::{one_time_token::OneTimeToken, password::PasswordHash
 
 pub const PRE_VERIFICATION_ACCOUNTS_NAMESPACE: Namespace = Namespace::of("pav");
 
+// 認証メールの再送時にメールアドレスから申請を引けるよう、メールアドレスから最新のトークンへの索引を持つ
+pub const PRE_VERIFICATION_EMAILS_NAMESPACE: Namespace = Namespace::of("pavem");
+
 pub const PRE_VERFICATION_ACCOUNTS_VALUE_SEPARATOR: char = '$';
 
 pub fn format_key(token: &OneTimeToken) -> String {
     format!("{}{}{}", PRE_VERIFICATION_ACCOUNTS_NAMESPACE, NAMESPACE_SEPARATOR, token)
 }
 
+pub fn format_email_key(email: &Email) -> String {
+    format!("{}{}{}", PRE_VERIFICATION_EMAILS_NAMESPACE, NAMESPACE_SEPARATOR, email)
+}
+
 pub fn format_value(email: &Email, password_hash: &PasswordHash, birth_year: BirthYear, region: Region, language: Language) -> String {
     format!(
         "{}{}{}{}{}{}{}{}{}",
@@ -21,4 +28,20 @@ pub fn format_value(email: &Email, password_hash: &PasswordHash, birth_year: Bir
         PRE_VERFICATION_ACCOUNTS_VALUE_SEPARATOR,
         u8::from(language)
     )
+}
+
+// 認証メールの再送は、同じメールアドレスに対してこの間隔を空ける
+pub const RESEND_COOLDOWN_NAMESPACE: Namespace = Namespace::of("rsvcd");
+
+pub fn format_resend_cooldown_key(email: &Email) -> String {
+    format!("{}{}{}", RESEND_COOLDOWN_NAMESPACE, NAMESPACE_SEPARATOR, email)
+}
+
+// 申請の値から言語を取り出す
+pub fn parse_language(value: &str) -> Option<Language> {
+    value
+        .split(PRE_VERFICATION_ACCOUNTS_VALUE_SEPARATOR)
+        .nth(4)
+        .and_then(|language| language.parse::<u8>().ok())
+        .and_then(|language| Language::try_from(language).ok())
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use std::{str::FromStr, sync::LazyLock};
+
+use crate::common::{auth::one_time_token::OneTimeToken, email::{address::Email, outbox::message::{IdempotencyKey, OutboxMessage}, send::{NetmateEmail, SenderName}, template::{kind::EmailTemplateKind, registry::{EmailTemplates, RenderEmailError}, variable::TemplateVariables}}, profile::language::Language, unixtime::UnixtimeMillis};
+
+use super::sign_up::dsl::ApplicationExpirationSeconds;
+
+static AUTHENTICATION_EMAIL_ADDRESS: LazyLock<NetmateEmail> = LazyLock::new(|| NetmateEmail::try_from(Email::from_str("verify-email@account.netmate.app").unwrap()).unwrap());
+
+// アカウント作成の申請時と認証メールの再送時で、同じ内容の認証メールを組み立てる
+pub fn compose_verification_email(templates: &EmailTemplates, email: &Email, language: Language, token: &OneTimeToken, expiration: ApplicationExpirationSeconds) -> Result<OutboxMessage, RenderEmailError> {
+    let expires_at = UnixtimeMillis::of(UnixtimeMillis::now().value() + expiration.as_secs() as u64 * 1000);
+
+    let variables = TemplateVariables::new()
+        .text("verification_url", &format!("https://netmate.app/verify-email/{}", token.value()))
+        .datetime("expires_at", expires_at);
+
+    // ユーザーの設定言語に応じたテンプレートで描画する
+    let (subject, body) = templates.render(EmailTemplateKind::EmailVerification, language, &variables)?;
+
+    // 認証メールはトークンごとに1通だけ送信する
+    let idempotency_key = IdempotencyKey::new(format!("sign_up:{}", token.value()));
+
+    Ok(OutboxMessage::compose(idempotency_key, AUTHENTICATION_EMAIL_ADDRESS.clone(), email.clone(), SenderName::by(language), subject, body))
+}
\ No newline at end of file
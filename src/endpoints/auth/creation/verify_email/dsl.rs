@@ -11,6 +11,9 @@ pub(crate) trait VerifyEmail {
         let account_id = AccountId::gen();
         match self.create_account(account_id, &email, &password_hash, birth_year, region, language).await {
             Ok(_) => {
+                // 失敗しても修復ジョブが作成するため続行
+                let _ = self.create_anonymous_handle(account_id).await;
+
                 // 失敗しても期限切れで自動削除されるため続行
                 let _ = self.delete_account_creation_application_by(token).await;
                 Ok((account_id, TopTagId::from(LanguageGroup::from(language))))
@@ -27,6 +30,8 @@ pub(crate) trait VerifyEmail {
 
     async fn create_account(&self, account_id: AccountId, email: &Email, password_hash: &PasswordHash, birth_year: BirthYear, region: Region, language: Language) -> Fallible<(), VerifyEmailError>;
 
+    async fn create_anonymous_handle(&self, account_id: AccountId) -> Fallible<(), VerifyEmailError>;
+
     async fn delete_account_creation_application_by(&self, token: &OneTimeToken) -> Fallible<(), VerifyEmailError>;
 }
 
@@ -42,6 +47,8 @@ pub enum VerifyEmailError {
     CreateAccountFailed(#[source] anyhow::Error),
     #[error("アカウントが既に存在しています")]
     AccountAlreadyExists,
+    #[error("匿名名義の作成に失敗しました")]
+    CreateAnonymousHandleFailed(#[source] anyhow::Error),
     #[error("アカウント作成申請データの削除に失敗しました")]
     DeleteAccountCreationApplicationFailed(#[source] anyhow::Error),
 }
@@ -94,6 +101,10 @@ mod tests {
                 _ => panic!("予期しないエラーが発生しました")
             }
         }
+
+        async fn create_anonymous_handle(&self, _: AccountId) -> Fallible<(), VerifyEmailError> {
+            Ok(())
+        }
     
         async fn delete_account_creation_application_by(&self, _: &OneTimeToken) -> Fallible<(), VerifyEmailError> {
             Ok(())
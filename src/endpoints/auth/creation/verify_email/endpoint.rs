@@ -1,18 +1,19 @@
 use std::{net::SocketAddr, sync::Arc};
 
-use axum::{extract::{ConnectInfo, State}, http::StatusCode, response::{IntoResponse, Response}, routing::post, Json, Router};
+use axum::{extract::{ConnectInfo, State}, response::{IntoResponse, Response}, routing::post, Json, Router};
 use axum_macros::debug_handler;
 use scylla::Session;
 use serde::Serialize;
 use tower::ServiceBuilder;
 use tracing::info;
 
-use crate::{common::{auth::one_time_token::OneTimeToken, tag::top_tag::TopTagId}, helper::{error::InitError, middleware::{rate_limiter, session_starter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{auth::one_time_token::OneTimeToken, tag::top_tag::TopTagId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, rate_limiter, session_starter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::{dsl::{VerifyEmail, VerifyEmailError}, interpreter::VerifyEmailImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<VerifyEmailImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "vrfem", 3, 1, TimeUnit::HOURS).await?)
         .layer(session_starter(db.clone(), cache.clone()).await?);
 
@@ -31,7 +32,7 @@ pub async fn handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     State(routine): State<Arc<VerifyEmailImpl>>,
     Json(token): Json<OneTimeToken>
-) -> Result<Response, StatusCode> {
+) -> Result<Response, VerifyEmailError> {
     match routine.verify_email(&token).await {
         Ok((account_id, top_tag_id)) => {
             info!(
@@ -52,14 +53,21 @@ pub async fn handler(
                 "メールアドレスの認証に失敗しました。"
             );
 
-            match e {
-                VerifyEmailError::OneTimeTokenAuthenticationFailed | VerifyEmailError::AccountAlreadyExists => Err(StatusCode::BAD_REQUEST),
-                _ => Err(StatusCode::INTERNAL_SERVER_ERROR)
-            }
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for VerifyEmailError {
+    fn into_response(self) -> Response {
+        match self {
+            VerifyEmailError::OneTimeTokenAuthenticationFailed => ApiError::INVALID_VERIFICATION_TOKEN,
+            VerifyEmailError::AccountAlreadyExists => ApiError::ACCOUNT_ALREADY_EXISTS,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct Body {
     top_tag_id: TopTagId,
@@ -3,7 +3,7 @@ use std::{str::{FromStr, SplitN}, sync::Arc};
 use redis::cmd;
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::{common::{auth::{one_time_token::OneTimeToken, password::PasswordHash}, email::address::Email, fallible::Fallible, profile::{account_id::AccountId, birth_year::BirthYear, language::Language, region::Region}}, endpoints::auth::creation::value::{format_key, PRE_VERFICATION_ACCOUNTS_VALUE_SEPARATOR}, helper::{error::InitError, redis::connection::{conn, Pool}, scylla::prepare}};
+use crate::{common::{auth::{one_time_token::OneTimeToken, password::PasswordHash}, email::address::Email, fallible::Fallible, handle::{anonymous::{interpreter::AnonymousHandleProvisionerImpl, provision::ProvisionAnonymousHandle}, id::HandleId}, profile::{account_id::AccountId, birth_year::BirthYear, language::Language, region::Region}}, endpoints::auth::creation::value::{format_key, PRE_VERFICATION_ACCOUNTS_VALUE_SEPARATOR}, helper::{error::InitError, redis::connection::{conn, Pool}, scylla::{prepare, Transactional}}};
 
 use super::dsl::{VerifyEmail, VerifyEmailError};
 
@@ -11,13 +11,16 @@ pub struct VerifyEmailImpl {
     db: Arc<Session>,
     cache: Arc<Pool>,
     insert_account: Arc<PreparedStatement>,
+    anonymous_handle_provisioner: AnonymousHandleProvisionerImpl,
 }
 
 impl VerifyEmailImpl {
     pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>) -> Result<Self, InitError<VerifyEmailImpl>> {
         let insert_account = prepare(&db, "INSERT INTO accounts (id, email, password_hash, birth_year, region, language) VALUES (?, ?, ?, ?, ?, ?) IF NOT EXISTS").await?;
 
-        Ok(Self { db, cache, insert_account })
+        let anonymous_handle_provisioner = AnonymousHandleProvisionerImpl::try_new(db.clone()).await?;
+
+        Ok(Self { db, cache, insert_account, anonymous_handle_provisioner })
     }
 }
 
@@ -65,8 +68,15 @@ impl VerifyEmail for VerifyEmailImpl {
         self.db
             .execute_unpaged(&self.insert_account, (account_id, email, password_hash, birth_year, region, language))
             .await
+            .applied(VerifyEmailError::CreateAccountFailed, || VerifyEmailError::AccountAlreadyExists)
+    }
+
+    async fn create_anonymous_handle(&self, account_id: AccountId) -> Fallible<(), VerifyEmailError> {
+        self.anonymous_handle_provisioner
+            .provision_anonymous_handle(account_id, HandleId::gen())
+            .await
             .map(|_| ())
-            .map_err(|e| VerifyEmailError::CreateAccountFailed(e.into()))
+            .map_err(|e| VerifyEmailError::CreateAnonymousHandleFailed(e.into()))
     }
 
     async fn delete_account_creation_application_by(&self, token: &OneTimeToken) -> Fallible<(), VerifyEmailError> {
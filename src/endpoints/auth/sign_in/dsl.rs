@@ -1,26 +1,425 @@
+use std::net::IpAddr;
+
 use thiserror::Error;
+use tracing::warn;
+
+use crate::{common::{auth::password::{Password, PasswordHash, PasswordVerification, EMPTY_PASSWORD_HASH}, email::address::Email, fallible::Fallible, human_verification::token::HumanVerificationToken, ip_address::{host_prefix, network_prefix}, profile::account_id::{AccountId, EMPTY_ACCOUNT_ID}}, middlewares::limit::{Count, TimeWindow}};
+
+// 同じメールアドレスで、この回数までは待たずに再試行できる
+const EMAIL_FREE_FAILURES: u32 = 3;
+const EMAIL_FAILURE_WINDOW: TimeWindow = TimeWindow::days(1);
+const EMAIL_BASE_LOCKOUT: RetryAfter = RetryAfter::seconds(1);
+// 第三者が他人のメールアドレスを締め出せないよう、上限を短めにする
+const EMAIL_MAX_LOCKOUT: RetryAfter = RetryAfter::seconds(15 * 60);
+
+const IP_ADDRESS_MAX_FAILURES: Count = Count::new(20);
+const IP_ADDRESS_FAILURE_WINDOW: TimeWindow = TimeWindow::hours(1);
+
+const NETWORK_MAX_FAILURES: Count = Count::new(100);
+const NETWORK_FAILURE_WINDOW: TimeWindow = TimeWindow::hours(1);
 
-use crate::common::{auth::password::{Password, PasswordHash, EMPTY_PASSWORD_HASH}, email::address::Email, fallible::Fallible, profile::account_id::{AccountId, EMPTY_ACCOUNT_ID}};
+// 全体の失敗が急増した場合は、一定期間すべてのサインインに人間確認を求める
+const GLOBAL_MAX_FAILURES: Count = Count::new(500);
+const GLOBAL_FAILURE_WINDOW: TimeWindow = TimeWindow::minutes(5);
+const BREAKER_COOLDOWN: TimeWindow = TimeWindow::minutes(15);
 
 pub(crate) trait SignIn {
-    async fn sign_in(&self, email: &Email, password: &Password) -> Fallible<Option<AccountId>, SignInError> {
+    async fn sign_in(&self, email: &Email, password: &Password, ip_address: IpAddr, challenge_token: Option<&HumanVerificationToken>) -> Fallible<Option<AccountId>, SignInError> {
+        // アカウントは正規化したメールアドレスで登録されている
+        let normalized = email.normalized();
+
+        self.guard(&normalized, ip_address, challenge_token).await?;
+
         // 時間差攻撃を防ぐためメールアドレスが存在しない場合もパスワードの検証を行う
-        let (password_hash, account_id) = self.fetch_password_hash_and_account_id(email)
+        let (password_hash, account_id) = self.find_account(email, &normalized)
             .await?
             .unwrap_or_else(|| (EMPTY_PASSWORD_HASH.clone(), EMPTY_ACCOUNT_ID));
 
-        if password_hash.verify(password) {
+        let email = &normalized;
+
+        let verification = password_hash.verify(password);
+
+        if verification.is_matched() {
+            // 停止の事実は、パスワードを知っている本人にのみ伝える
+            if self.is_suspended(account_id).await? {
+                return Err(SignInError::AccountSuspended);
+            }
+
+            // 失敗しても続行
+            let _ = self.clear_email_failures(email).await;
+
+            // 平文のパスワードが手元にあるのはサインイン時のみのため、ここで現在のパラメータとペッパーに移行する
+            // 失敗しても次回のサインイン時に再試行される
+            if verification == PasswordVerification::MatchedButOutdated {
+                let _ = self.update_password_hash(account_id, &password_hash, &password.hashed()).await;
+            }
+
             Ok(Some(account_id))
         } else {
+            // Redisの障害でサインイン全体が失敗しないよう、記録に失敗しても通常の失敗として応答する
+            if let Err(e) = self.record_failure(email, ip_address).await {
+                warn!(error = %e, "サインインの失敗の記録に失敗しました。");
+            }
+
             Ok(None)
         }
     }
 
+    async fn find_account(&self, email: &Email, normalized: &Email) -> Fallible<Option<(PasswordHash, AccountId)>, SignInError> {
+        match self.fetch_password_hash_and_account_id(normalized).await? {
+            // 正規化の導入前に登録されたアカウントは、入力された表記のまま保存されている
+            None if normalized != email => self.fetch_password_hash_and_account_id(email).await,
+            account => Ok(account),
+        }
+    }
+
+    async fn guard(&self, email: &Email, ip_address: IpAddr, challenge_token: Option<&HumanVerificationToken>) -> Fallible<(), SignInError> {
+        if let Some(retry_after) = self.fetch_email_lockout(email).await? {
+            return Err(SignInError::Throttled(retry_after));
+        }
+
+        for counter in [FailureCounter::ip_address(ip_address), FailureCounter::network(ip_address)] {
+            if let Some(failures) = self.fetch_failures(counter).await? {
+                if counter.max_failures().is_some_and(|max_failures| failures.count() >= max_failures) {
+                    return Err(SignInError::Throttled(failures.retry_after()));
+                }
+            }
+        }
+
+        if self.is_breaker_open().await? {
+            match challenge_token {
+                Some(token) if self.verify_challenge(token).await? => (),
+                _ => return Err(SignInError::ChallengeRequired),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(&self, email: &Email, ip_address: IpAddr) -> Fallible<(), SignInError> {
+        let email_failures = self.increment_failures(FailureCounter::Email(email)).await?;
+
+        if let Some(lockout) = email_lockout(email_failures) {
+            self.lock_email(email, lockout).await?;
+        }
+
+        self.increment_failures(FailureCounter::ip_address(ip_address)).await?;
+        self.increment_failures(FailureCounter::network(ip_address)).await?;
+
+        // 攻撃が続く間は開いたままにする
+        if self.increment_failures(FailureCounter::Global).await? >= GLOBAL_MAX_FAILURES {
+            self.trip_breaker(BREAKER_COOLDOWN).await?;
+        }
+
+        Ok(())
+    }
+
     async fn fetch_password_hash_and_account_id(&self, email: &Email) -> Fallible<Option<(PasswordHash, AccountId)>, SignInError>;
+
+    // 並行してパスワードが変更された場合は、`old_password_hash`と一致しないため更新しない
+    async fn update_password_hash(&self, account_id: AccountId, old_password_hash: &PasswordHash, new_password_hash: &PasswordHash) -> Fallible<(), SignInError>;
+
+    async fn is_suspended(&self, account_id: AccountId) -> Fallible<bool, SignInError>;
+
+    async fn fetch_email_lockout(&self, email: &Email) -> Fallible<Option<RetryAfter>, SignInError>;
+
+    async fn lock_email(&self, email: &Email, lockout: RetryAfter) -> Fallible<(), SignInError>;
+
+    async fn clear_email_failures(&self, email: &Email) -> Fallible<(), SignInError>;
+
+    async fn fetch_failures(&self, counter: FailureCounter<'_>) -> Fallible<Option<Failures>, SignInError>;
+
+    // 失敗を1回数え、数えた後の失敗回数を返す
+    async fn increment_failures(&self, counter: FailureCounter<'_>) -> Fallible<Count, SignInError>;
+
+    async fn is_breaker_open(&self) -> Fallible<bool, SignInError>;
+
+    async fn trip_breaker(&self, cooldown: TimeWindow) -> Fallible<(), SignInError>;
+
+    async fn verify_challenge(&self, token: &HumanVerificationToken) -> Fallible<bool, SignInError>;
+}
+
+// 無料の回数を超えた失敗ごとに待ち時間を倍にする
+fn email_lockout(email_failures: Count) -> Option<RetryAfter> {
+    let exceeded = email_failures.value().checked_sub(EMAIL_FREE_FAILURES).filter(|exceeded| *exceeded > 0)?;
+
+    let lockout = 1u32.checked_shl(exceeded - 1)
+        .and_then(|multiplier| EMAIL_BASE_LOCKOUT.value().checked_mul(multiplier))
+        .map_or(EMAIL_MAX_LOCKOUT, |seconds| RetryAfter::seconds(seconds).min(EMAIL_MAX_LOCKOUT));
+
+    Some(lockout)
 }
 
 #[derive(Debug, Error)]
 pub enum SignInError {
     #[error("パスワードハッシュとアカウントIDの取得に失敗しました")]
     FetchPasswordHashAndAccountIdFailed(#[source] anyhow::Error),
+    #[error("パスワードハッシュの更新に失敗しました")]
+    UpdatePasswordHashFailed(#[source] anyhow::Error),
+    #[error("アカウントの停止の確認に失敗しました")]
+    CheckSuspensionFailed(#[source] anyhow::Error),
+    #[error("アカウントが停止されています")]
+    AccountSuspended,
+    #[error("サインインの試行が制限されています")]
+    Throttled(RetryAfter),
+    #[error("人間確認が必要です")]
+    ChallengeRequired,
+    #[error("人間確認の検証に失敗しました")]
+    VerifyChallengeFailed(#[source] anyhow::Error),
+    #[error("メールアドレスの締め出しの確認に失敗しました")]
+    FetchEmailLockoutFailed(#[source] anyhow::Error),
+    #[error("メールアドレスの締め出しに失敗しました")]
+    LockEmailFailed(#[source] anyhow::Error),
+    #[error("メールアドレスの失敗回数の削除に失敗しました")]
+    ClearEmailFailuresFailed(#[source] anyhow::Error),
+    #[error("失敗回数の取得に失敗しました")]
+    FetchFailuresFailed(#[source] anyhow::Error),
+    #[error("失敗回数のインクリメントに失敗しました")]
+    IncrementFailuresFailed(#[source] anyhow::Error),
+    #[error("サーキットブレーカーの状態の取得に失敗しました")]
+    IsBreakerOpenFailed(#[source] anyhow::Error),
+    #[error("サーキットブレーカーの作動に失敗しました")]
+    TripBreakerFailed(#[source] anyhow::Error),
+}
+
+// 失敗回数を数える単位
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailureCounter<'a> {
+    Email(&'a Email),
+    IpAddress(IpAddr),
+    Network(IpAddr),
+    Global,
+}
+
+impl FailureCounter<'_> {
+    pub fn ip_address(ip_address: IpAddr) -> Self {
+        Self::IpAddress(host_prefix(ip_address))
+    }
+
+    // 多数のアドレスを持つ攻撃者に備え、IPv4では/24、IPv6では/48単位でも数える
+    pub fn network(ip_address: IpAddr) -> Self {
+        Self::Network(network_prefix(ip_address))
+    }
+
+    pub fn time_window(&self) -> TimeWindow {
+        match self {
+            Self::Email(_) => EMAIL_FAILURE_WINDOW,
+            Self::IpAddress(_) => IP_ADDRESS_FAILURE_WINDOW,
+            Self::Network(_) => NETWORK_FAILURE_WINDOW,
+            Self::Global => GLOBAL_FAILURE_WINDOW,
+        }
+    }
+
+    // メールアドレスは待ち時間で、全体はサーキットブレーカーで制限する
+    fn max_failures(&self) -> Option<Count> {
+        match self {
+            Self::IpAddress(_) => Some(IP_ADDRESS_MAX_FAILURES),
+            Self::Network(_) => Some(NETWORK_MAX_FAILURES),
+            Self::Email(_) | Self::Global => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Failures {
+    count: Count,
+    retry_after: RetryAfter,
+}
+
+impl Failures {
+    pub fn new(count: Count, retry_after: RetryAfter) -> Self {
+        Self { count, retry_after }
+    }
+
+    pub fn count(&self) -> Count {
+        self.count
+    }
+
+    // 失敗回数がリセットされるまでの時間
+    pub fn retry_after(&self) -> RetryAfter {
+        self.retry_after
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RetryAfter(u32);
+
+impl RetryAfter {
+    pub const fn seconds(seconds: u32) -> Self {
+        Self(seconds)
+    }
+
+    pub const fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::IpAddr, str::FromStr, sync::LazyLock};
+
+    use crate::{common::{auth::password::PasswordHash, email::address::Email, fallible::Fallible, human_verification::token::HumanVerificationToken, profile::account_id::AccountId}, middlewares::limit::{Count, TimeWindow}};
+
+    use super::{email_lockout, FailureCounter, Failures, RetryAfter, SignIn, SignInError, EMAIL_MAX_LOCKOUT, IP_ADDRESS_MAX_FAILURES, NETWORK_MAX_FAILURES};
+
+    static EMAIL: LazyLock<Email> = LazyLock::new(|| Email::from_str("user@example.com").unwrap());
+    static LOCKED_EMAIL: LazyLock<Email> = LazyLock::new(|| Email::from_str("locked@example.com").unwrap());
+    // 正規化の導入前に、入力された表記のまま登録されたメールアドレス
+    static LEGACY_EMAIL: LazyLock<Email> = LazyLock::new(|| Email::from_str("Legacy.User@Example.COM").unwrap());
+    static LEGACY_ACCOUNT_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    const IP_ADDRESS: &str = "192.0.2.1";
+    const THROTTLED_IP_ADDRESS: &str = "198.51.100.1";
+    const THROTTLED_NETWORK_IP_ADDRESS: &str = "203.0.113.1";
+    // `THROTTLED_NETWORK_IP_ADDRESS`と同じ/24に属する
+    const NEIGHBOR_IP_ADDRESS: &str = "203.0.113.2";
+
+    const PASSING_TOKEN: &str = "passing";
+
+    struct MockSignIn {
+        is_breaker_open: bool,
+    }
+
+    fn ip_address(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    impl SignIn for MockSignIn {
+        async fn fetch_password_hash_and_account_id(&self, email: &Email) -> Fallible<Option<(PasswordHash, AccountId)>, SignInError> {
+            if email == &*LEGACY_EMAIL {
+                Ok(Some((PasswordHash::new_unchecked("$argon2id$v=19$m=19456,t=2,p=1$c29tZXNhbHQ$aGFzaA"), *LEGACY_ACCOUNT_ID)))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn update_password_hash(&self, _: AccountId, _: &PasswordHash, _: &PasswordHash) -> Fallible<(), SignInError> {
+            Ok(())
+        }
+
+        async fn is_suspended(&self, _: AccountId) -> Fallible<bool, SignInError> {
+            Ok(false)
+        }
+
+        async fn fetch_email_lockout(&self, email: &Email) -> Fallible<Option<RetryAfter>, SignInError> {
+            if email == &*LOCKED_EMAIL {
+                Ok(Some(RetryAfter::seconds(60)))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn lock_email(&self, _: &Email, _: RetryAfter) -> Fallible<(), SignInError> {
+            Ok(())
+        }
+
+        async fn clear_email_failures(&self, _: &Email) -> Fallible<(), SignInError> {
+            Ok(())
+        }
+
+        async fn fetch_failures(&self, counter: FailureCounter<'_>) -> Fallible<Option<Failures>, SignInError> {
+            if counter == FailureCounter::ip_address(ip_address(THROTTLED_IP_ADDRESS)) {
+                Ok(Some(Failures::new(IP_ADDRESS_MAX_FAILURES, RetryAfter::seconds(60))))
+            } else if counter == FailureCounter::network(ip_address(THROTTLED_NETWORK_IP_ADDRESS)) {
+                Ok(Some(Failures::new(NETWORK_MAX_FAILURES, RetryAfter::seconds(60))))
+            } else {
+                Ok(Some(Failures::new(Count::new(1), RetryAfter::seconds(60))))
+            }
+        }
+
+        async fn increment_failures(&self, _: FailureCounter<'_>) -> Fallible<Count, SignInError> {
+            Ok(Count::new(1))
+        }
+
+        async fn is_breaker_open(&self) -> Fallible<bool, SignInError> {
+            Ok(self.is_breaker_open)
+        }
+
+        async fn trip_breaker(&self, _: TimeWindow) -> Fallible<(), SignInError> {
+            Ok(())
+        }
+
+        async fn verify_challenge(&self, token: &HumanVerificationToken) -> Fallible<bool, SignInError> {
+            Ok(token.value() == PASSING_TOKEN)
+        }
+    }
+
+    async fn test_guard(email: &Email, ip_address_str: &str, is_breaker_open: bool, challenge_token: Option<&str>) -> Fallible<(), SignInError> {
+        let token = challenge_token.map(|token| HumanVerificationToken::new(String::from(token)));
+        MockSignIn { is_breaker_open }.guard(email, ip_address(ip_address_str), token.as_ref()).await
+    }
+
+    #[tokio::test]
+    async fn not_throttled() {
+        let result = test_guard(&EMAIL, IP_ADDRESS, false, None).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn locked_email() {
+        let result = test_guard(&LOCKED_EMAIL, IP_ADDRESS, false, None).await;
+        assert!(matches!(result, Err(SignInError::Throttled(_))));
+    }
+
+    #[tokio::test]
+    async fn throttled_ip_address() {
+        let result = test_guard(&EMAIL, THROTTLED_IP_ADDRESS, false, None).await;
+        assert!(matches!(result, Err(SignInError::Throttled(_))));
+    }
+
+    #[tokio::test]
+    async fn throttled_network() {
+        let result = test_guard(&EMAIL, NEIGHBOR_IP_ADDRESS, false, None).await;
+        assert!(matches!(result, Err(SignInError::Throttled(_))));
+    }
+
+    #[tokio::test]
+    async fn breaker_open_without_challenge() {
+        let result = test_guard(&EMAIL, IP_ADDRESS, true, None).await;
+        assert!(matches!(result, Err(SignInError::ChallengeRequired)));
+    }
+
+    #[tokio::test]
+    async fn breaker_open_with_failing_challenge() {
+        let result = test_guard(&EMAIL, IP_ADDRESS, true, Some("failing")).await;
+        assert!(matches!(result, Err(SignInError::ChallengeRequired)));
+    }
+
+    #[tokio::test]
+    async fn breaker_open_with_passing_challenge() {
+        let result = test_guard(&EMAIL, IP_ADDRESS, true, Some(PASSING_TOKEN)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn find_legacy_account() {
+        let mock = MockSignIn { is_breaker_open: false };
+        let normalized = LEGACY_EMAIL.normalized();
+
+        let account = mock.find_account(&LEGACY_EMAIL, &normalized).await.unwrap();
+        assert_eq!(account.map(|(_, account_id)| account_id), Some(*LEGACY_ACCOUNT_ID));
+    }
+
+    #[tokio::test]
+    async fn find_no_account() {
+        let mock = MockSignIn { is_breaker_open: false };
+        let email = Email::from_str("Unknown@Example.COM").unwrap();
+
+        let account = mock.find_account(&email, &email.normalized()).await.unwrap();
+        assert!(account.is_none());
+    }
+
+    #[test]
+    fn email_lockout_backoff() {
+        assert_eq!(email_lockout(Count::new(3)), None);
+        assert_eq!(email_lockout(Count::new(4)), Some(RetryAfter::seconds(1)));
+        assert_eq!(email_lockout(Count::new(5)), Some(RetryAfter::seconds(2)));
+        assert_eq!(email_lockout(Count::new(8)), Some(RetryAfter::seconds(16)));
+    }
+
+    #[test]
+    fn email_lockout_capped() {
+        assert_eq!(email_lockout(Count::new(20)), Some(EMAIL_MAX_LOCKOUT));
+        assert_eq!(email_lockout(Count::new(u32::MAX)), Some(EMAIL_MAX_LOCKOUT));
+    }
 }
\ No newline at end of file
@@ -1,22 +1,23 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use axum::{extract::{ConnectInfo, State}, response::{IntoResponse, Response}, routing::post, Json, Router};
-use http::StatusCode;
+use http::{header::RETRY_AFTER, StatusCode};
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::info;
 
-use crate::{common::{auth::password::Password, email::address::Email}, helper::{error::InitError, middleware::{rate_limiter, session_starter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{auth::password::Password, email::address::Email, human_verification::{config::ConfiguredHumanVerifier, token::HumanVerificationToken}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, rate_limiter, session_starter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::SignIn, interpreter::SignInImpl};
+use super::{dsl::{SignIn, SignInError}, interpreter::SignInImpl};
 
-pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<SignInImpl>> {
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, verifier: ConfiguredHumanVerifier) -> Result<Router, InitError<SignInImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "sigin", 10, 1, TimeUnit::HOURS).await?)
-        .layer(session_starter(db.clone(), cache).await?);
+        .layer(session_starter(db.clone(), cache.clone()).await?);
 
-    let sign_in = SignInImpl::try_new(db).await?;
+    let sign_in = SignInImpl::try_new(db, cache, verifier).await?;
 
     let router = Router::new()
         .route("/sign_in", post(handler))
@@ -30,8 +31,10 @@ pub async fn handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     State(routine): State<Arc<SignInImpl>>,
     Json(payload): Json<Payload>,
-) -> Result<Response, StatusCode> {
-    match routine.sign_in(&payload.email, &payload.password).await {
+) -> Result<Response, Response> {
+    let challenge_token = payload.challenge_token.map(HumanVerificationToken::new);
+
+    match routine.sign_in(&payload.email, &payload.password, addr.ip(), challenge_token.as_ref()).await {
         Ok(Some(account_id)) => {
             info!(
                 ip_address = %addr.ip(),
@@ -52,9 +55,30 @@ pub async fn handler(
                 "ログインに失敗しました。"
             );
 
-            Err(StatusCode::BAD_REQUEST)
+            Err(ApiError::SIGN_IN_FAILED.into_response())
+        },
+        Err(e) => {
+            info!(
+                ip_address = %addr.ip(),
+                email = %payload.email,
+                error = %e,
+                "ログインを拒否しました。"
+            );
+
+            Err(e.into_response())
         },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+impl IntoResponse for SignInError {
+    fn into_response(self) -> Response {
+        match self {
+            SignInError::Throttled(retry_after) => ([(RETRY_AFTER, retry_after.value())], ApiError::SIGN_IN_THROTTLED).into_response(),
+            // クライアントに人間確認を表示させ、トークンを付けて再送させる
+            SignInError::ChallengeRequired => ApiError::SIGN_IN_CHALLENGE_REQUIRED.into_response(),
+            SignInError::AccountSuspended => ApiError::ACCOUNT_SUSPENDED.into_response(),
+            _ => ApiError::INTERNAL.into_response(),
+        }
     }
 }
 
@@ -62,4 +86,7 @@ pub async fn handler(
 pub struct Payload {
     pub email: Email,
     pub password: Password,
+    // サーキットブレーカーが開いている間のみ必要
+    #[serde(default)]
+    pub challenge_token: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,6 @@
+local current
+current = redis.call("incr", KEYS[1])
+if current == 1 then
+    redis.call("expire", KEYS[1], ARGV[1])
+end
+return current
\ No newline at end of file
@@ -1,21 +1,45 @@
 use std::sync::Arc;
 
+use redis::{cmd, pipe, Script};
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::{common::{auth::password::PasswordHash, email::address::Email, fallible::Fallible, profile::account_id::AccountId}, helper::{error::InitError, scylla::prepare}};
+use crate::{common::{auth::password::PasswordHash, email::address::Email, fallible::Fallible, human_verification::{config::ConfiguredHumanVerifier, token::HumanVerificationToken, verify::HumanVerifier}, profile::account_id::AccountId}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}, namespaces::{SIGN_IN_BREAKER, SIGN_IN_FAILURE, SIGN_IN_FAILURE_BY_EMAIL, SIGN_IN_FAILURE_BY_IP, SIGN_IN_FAILURE_BY_NETWORK, SIGN_IN_LOCK_BY_EMAIL}}, scylla::{prepare, Transactional}}, middlewares::limit::{Count, TimeWindow}};
 
-use super::dsl::{SignIn, SignInError};
+use super::dsl::{FailureCounter, Failures, RetryAfter, SignIn, SignInError};
 
 pub struct SignInImpl {
     db: Arc<Session>,
+    cache: Arc<Pool>,
+    verifier: ConfiguredHumanVerifier,
     select_password_hash_and_account_id: Arc<PreparedStatement>,
+    update_password_hash: Arc<PreparedStatement>,
+    select_suspension: Arc<PreparedStatement>,
+    incr_and_expire_if_first: Arc<Script>,
 }
 
 impl SignInImpl {
-    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, verifier: ConfiguredHumanVerifier) -> Result<Self, InitError<Self>> {
         let select_password_hash_and_account_id = prepare(&db, "SELECT password_hash, id FROM accounts WHERE email = ? LIMIT 1").await?;
+        let update_password_hash = prepare(&db, "UPDATE accounts SET password_hash = ? WHERE id = ? IF password_hash = ?").await?;
+        let select_suspension = prepare(&db, "SELECT account_id FROM suspended_accounts WHERE account_id = ?").await?;
 
-        Ok(Self { db, select_password_hash_and_account_id })
+        let incr_and_expire_if_first = Arc::new(Script::new(include_str!("incr_and_expire_if_first.lua")));
+
+        Ok(Self { db, cache, verifier, select_password_hash_and_account_id, update_password_hash, select_suspension, incr_and_expire_if_first })
+    }
+}
+
+// 大文字と小文字を変えて失敗回数の制限を回避されないようにする
+fn email_key(namespace: Namespace, email: &Email) -> String {
+    format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, email.value().to_lowercase())
+}
+
+fn failure_key(counter: FailureCounter<'_>) -> String {
+    match counter {
+        FailureCounter::Email(email) => email_key(SIGN_IN_FAILURE_BY_EMAIL, email),
+        FailureCounter::IpAddress(ip_address) => format!("{}{}{}", SIGN_IN_FAILURE_BY_IP, NAMESPACE_SEPARATOR, ip_address),
+        FailureCounter::Network(ip_address) => format!("{}{}{}", SIGN_IN_FAILURE_BY_NETWORK, NAMESPACE_SEPARATOR, ip_address),
+        FailureCounter::Global => SIGN_IN_FAILURE.to_string(),
     }
 }
 
@@ -28,4 +52,122 @@ impl SignIn for SignInImpl {
             .maybe_first_row_typed()
             .map_err(|e| SignInError::FetchPasswordHashAndAccountIdFailed(e.into()))
     }
+
+    async fn update_password_hash(&self, account_id: AccountId, old_password_hash: &PasswordHash, new_password_hash: &PasswordHash) -> Fallible<(), SignInError> {
+        let res = self.db
+            .execute_unpaged(&self.update_password_hash, (new_password_hash, account_id, old_password_hash))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) => Ok(()),
+            Err(Some(e)) => Err(SignInError::UpdatePasswordHashFailed(e)),
+            // 並行して変更された新しいパスワードを上書きしないよう、移行は諦める
+            Err(None) => Ok(()),
+        }
+    }
+
+    async fn is_suspended(&self, account_id: AccountId) -> Fallible<bool, SignInError> {
+        self.db
+            .execute_unpaged(&self.select_suspension, (account_id, ))
+            .await
+            .map_err(|e| SignInError::CheckSuspensionFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| SignInError::CheckSuspensionFailed(e.into()))
+    }
+
+    async fn fetch_email_lockout(&self, email: &Email) -> Fallible<Option<RetryAfter>, SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::FetchEmailLockoutFailed(e.into())).await?;
+
+        // キーが存在しない場合は負の値が返る
+        cmd("TTL")
+            .arg(email_key(SIGN_IN_LOCK_BY_EMAIL, email))
+            .query_async::<i64>(&mut *conn)
+            .await
+            .map_err(|e| SignInError::FetchEmailLockoutFailed(e.into()))
+            .map(|ttl| u32::try_from(ttl).ok().filter(|ttl| *ttl > 0).map(RetryAfter::seconds))
+    }
+
+    async fn lock_email(&self, email: &Email, lockout: RetryAfter) -> Fallible<(), SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::LockEmailFailed(e.into())).await?;
+
+        cmd("SET")
+            .arg(email_key(SIGN_IN_LOCK_BY_EMAIL, email))
+            .arg(1)
+            .arg("EX")
+            .arg(lockout.value())
+            .exec_async(&mut *conn)
+            .await
+            .map_err(|e| SignInError::LockEmailFailed(e.into()))
+    }
+
+    async fn clear_email_failures(&self, email: &Email) -> Fallible<(), SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::ClearEmailFailuresFailed(e.into())).await?;
+
+        cmd("DEL")
+            .arg(email_key(SIGN_IN_FAILURE_BY_EMAIL, email))
+            .arg(email_key(SIGN_IN_LOCK_BY_EMAIL, email))
+            .exec_async(&mut *conn)
+            .await
+            .map_err(|e| SignInError::ClearEmailFailuresFailed(e.into()))
+    }
+
+    async fn fetch_failures(&self, counter: FailureCounter<'_>) -> Fallible<Option<Failures>, SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::FetchFailuresFailed(e.into())).await?;
+
+        let key = failure_key(counter);
+
+        let (count, ttl) = pipe()
+            .cmd("GET").arg(&key)
+            .cmd("TTL").arg(&key)
+            .query_async::<(Option<Count>, i64)>(&mut *conn)
+            .await
+            .map_err(|e| SignInError::FetchFailuresFailed(e.into()))?;
+
+        let retry_after = RetryAfter::seconds(u32::try_from(ttl).unwrap_or(0));
+
+        Ok(count.map(|count| Failures::new(count, retry_after)))
+    }
+
+    async fn increment_failures(&self, counter: FailureCounter<'_>) -> Fallible<Count, SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::IncrementFailuresFailed(e.into())).await?;
+
+        self.incr_and_expire_if_first
+            .key(failure_key(counter))
+            .arg(counter.time_window())
+            .invoke_async::<Count>(&mut *conn)
+            .await
+            .map_err(|e| SignInError::IncrementFailuresFailed(e.into()))
+    }
+
+    async fn is_breaker_open(&self) -> Fallible<bool, SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::IsBreakerOpenFailed(e.into())).await?;
+
+        cmd("EXISTS")
+            .arg(SIGN_IN_BREAKER.to_string())
+            .query_async::<bool>(&mut *conn)
+            .await
+            .map_err(|e| SignInError::IsBreakerOpenFailed(e.into()))
+    }
+
+    async fn trip_breaker(&self, cooldown: TimeWindow) -> Fallible<(), SignInError> {
+        let mut conn = conn(&self.cache, |e| SignInError::TripBreakerFailed(e.into())).await?;
+
+        cmd("SET")
+            .arg(SIGN_IN_BREAKER.to_string())
+            .arg(1)
+            .arg("EX")
+            .arg(cooldown)
+            .exec_async(&mut *conn)
+            .await
+            .map_err(|e| SignInError::TripBreakerFailed(e.into()))
+    }
+
+    async fn verify_challenge(&self, token: &HumanVerificationToken) -> Fallible<bool, SignInError> {
+        self.verifier
+            .verify(token)
+            .await
+            .map_err(|e| SignInError::VerifyChallengeFailed(e.into()))
+    }
 }
\ No newline at end of file
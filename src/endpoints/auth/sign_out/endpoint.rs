@@ -3,19 +3,20 @@ use std::{net::SocketAddr, sync::Arc};
 use axum::{extract::{ConnectInfo, State}, routing::post, Extension, Router};
 use axum_extra::extract::cookie::{Cookie, CookieJar};
 use axum_macros::debug_handler;
-use http::StatusCode;
 use scylla::Session;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{profile::account_id::AccountId, session::{cookie::{REFRESH_PAIR_COOKIE_KEY, REFRESH_PAIR_SEPARATOR, SESSION_COOKIE_KEY}, session_series::SessionSeries}}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{profile::account_id::AccountId, session::{cookie::{REFRESH_PAIR_COOKIE_KEY, REFRESH_PAIR_SEPARATOR, SESSION_COOKIE_KEY}, session_series::SessionSeries}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::{dsl::SignOut, interpreter::SignOutImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<SignOutImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "sigot", 10, 1, TimeUnit::HOURS).await?)
-        .layer(session_manager(db.clone(), cache.clone()).await?);
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "sigot", 10, 1, TimeUnit::HOURS).await?);
 
     let sign_out = SignOutImpl::try_new(db, cache).await?;
 
@@ -33,9 +34,9 @@ async fn handler(
     State(routine): State<Arc<SignOutImpl>>,
     Extension(account_id): Extension<AccountId>,
     mut jar: CookieJar,
-) -> Result<CookieJar, StatusCode> {
+) -> Result<CookieJar, ApiError> {
     let session_series: SessionSeries = extract_session_series(&jar)
-        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        .ok_or(ApiError::INTERNAL)?;
 
     // ログアウトの成否にかかわらず、クッキーを削除する
     jar = jar.remove(Cookie::build(SESSION_COOKIE_KEY));
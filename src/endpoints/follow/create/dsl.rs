@@ -0,0 +1,152 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, follow::followee::Followee, handle::id::HandleId, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId};
+
+// ホームタイムラインの構築時に読み込む索引の数を抑えるための上限
+pub const MAX_FOLLOWS: u32 = 200;
+
+pub(crate) trait Follow {
+    async fn follow(&self, account_id: AccountId, followee: Followee) -> Fallible<(), FollowError> {
+        match followee {
+            Followee::Handle(handle_id) => {
+                let owner = self.fetch_handle_owner(handle_id)
+                    .await?
+                    .ok_or(FollowError::FolloweeNotFound)?;
+
+                // 自分の名義をフォローしてフォロワー数を水増しできないようにする
+                if owner == account_id {
+                    return Err(FollowError::CannotFollowOwnHandle);
+                }
+
+                // ブロックした名義はフィードから隠しているため、存在しない名義と同じ扱いにする
+                // 所有者のアカウント単位で確認すると、同じ所有者の名義がまとめて拒否されて結び付いてしまうため、フォロー対象の名義のみを確認する
+                if self.is_blocking(account_id, handle_id).await? {
+                    return Err(FollowError::FolloweeNotFound);
+                }
+            },
+            Followee::Tag(tag_id) => {
+                if !self.tag_exists(tag_id).await? {
+                    return Err(FollowError::FolloweeNotFound);
+                }
+            },
+        }
+
+        // 同時にフォローされた場合は上限をわずかに超え得る
+        if self.count_follows(account_id).await? >= MAX_FOLLOWS {
+            return Err(FollowError::FollowLimitReached);
+        }
+
+        self.insert_follow(account_id, followee).await
+    }
+
+    async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, FollowError>;
+
+    // `account_id`が`handle_id`の名義をブロックしているかどうか
+    async fn is_blocking(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, FollowError>;
+
+    async fn tag_exists(&self, tag_id: NonTopTagId) -> Fallible<bool, FollowError>;
+
+    async fn count_follows(&self, account_id: AccountId) -> Fallible<u32, FollowError>;
+
+    // 既にフォローしている場合は何もしない
+    async fn insert_follow(&self, account_id: AccountId, followee: Followee) -> Fallible<(), FollowError>;
+}
+
+#[derive(Debug, Error)]
+pub enum FollowError {
+    #[error("フォロー対象の確認に失敗しました")]
+    FetchFolloweeFailed(#[source] anyhow::Error),
+    #[error("フォロー対象が存在しません")]
+    FolloweeNotFound,
+    #[error("自分の名義はフォローできません")]
+    CannotFollowOwnHandle,
+    #[error("ブロックの確認に失敗しました")]
+    CheckBlockFailed(#[source] anyhow::Error),
+    #[error("フォロー数の確認に失敗しました")]
+    CountFollowsFailed(#[source] anyhow::Error),
+    #[error("フォロー数が上限に達しています")]
+    FollowLimitReached,
+    #[error("フォローに失敗しました")]
+    InsertFollowFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::{common::{fallible::Fallible, follow::followee::Followee, handle::id::HandleId, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId}, helper::test::mock_non_top_tag_id};
+
+    use super::{Follow, FollowError, MAX_FOLLOWS};
+
+    static ACCOUNT_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static OTHER_ACCOUNT_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static OWN_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static OTHER_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static BLOCKED_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+
+    struct MockFollow {
+        follow_count: u32,
+    }
+
+    impl Follow for MockFollow {
+        async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, FollowError> {
+            if handle_id == *OWN_HANDLE_ID {
+                Ok(Some(*ACCOUNT_ID))
+            } else if handle_id == *OTHER_HANDLE_ID || handle_id == *BLOCKED_HANDLE_ID {
+                Ok(Some(*OTHER_ACCOUNT_ID))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn is_blocking(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, FollowError> {
+            Ok(account_id == *ACCOUNT_ID && handle_id == *BLOCKED_HANDLE_ID)
+        }
+
+        async fn tag_exists(&self, tag_id: NonTopTagId) -> Fallible<bool, FollowError> {
+            Ok(tag_id == *TAG_ID)
+        }
+
+        async fn count_follows(&self, _: AccountId) -> Fallible<u32, FollowError> {
+            Ok(self.follow_count)
+        }
+
+        async fn insert_follow(&self, _: AccountId, _: Followee) -> Fallible<(), FollowError> {
+            Ok(())
+        }
+    }
+
+    async fn test_follow(followee: Followee, follow_count: u32) -> Fallible<(), FollowError> {
+        MockFollow { follow_count }.follow(*ACCOUNT_ID, followee).await
+    }
+
+    #[tokio::test]
+    async fn follow() {
+        assert!(test_follow(Followee::Handle(*OTHER_HANDLE_ID), 0).await.is_ok());
+        assert!(test_follow(Followee::Tag(*TAG_ID), MAX_FOLLOWS - 1).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn followee_not_found() {
+        assert!(matches!(test_follow(Followee::Handle(HandleId::gen()), 0).await, Err(FollowError::FolloweeNotFound)));
+        assert!(matches!(test_follow(Followee::Tag(mock_non_top_tag_id(2)), 0).await, Err(FollowError::FolloweeNotFound)));
+    }
+
+    #[tokio::test]
+    async fn blocked() {
+        assert!(matches!(test_follow(Followee::Handle(*BLOCKED_HANDLE_ID), 0).await, Err(FollowError::FolloweeNotFound)));
+        // 同じ所有者の別の名義には影響しない
+        assert!(test_follow(Followee::Handle(*OTHER_HANDLE_ID), 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn own_handle() {
+        assert!(matches!(test_follow(Followee::Handle(*OWN_HANDLE_ID), 0).await, Err(FollowError::CannotFollowOwnHandle)));
+    }
+
+    #[tokio::test]
+    async fn follow_limit_reached() {
+        assert!(matches!(test_follow(Followee::Tag(*TAG_ID), MAX_FOLLOWS).await, Err(FollowError::FollowLimitReached)));
+    }
+}
\ No newline at end of file
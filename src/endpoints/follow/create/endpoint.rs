@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
+use http::StatusCode;
+use scylla::Session;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{follow::followee::Followee, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{Follow, FollowError}, interpreter::FollowImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<FollowImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "crflw", 60, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "crflw", 60, 15, TimeUnit::MINS).await?);
+
+    let follow = FollowImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/follows", post(handler))
+        .layer(services)
+        .with_state(Arc::new(follow));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<FollowImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Json(followee): Json<Followee>
+) -> Result<StatusCode, FollowError> {
+    match routine.follow(account_id, followee).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e @ (FollowError::FolloweeNotFound | FollowError::CannotFollowOwnHandle | FollowError::FollowLimitReached)) => Err(e),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                followee = %followee,
+                "フォローに失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for FollowError {
+    fn into_response(self) -> Response {
+        match self {
+            FollowError::FolloweeNotFound => ApiError::FOLLOWEE_NOT_FOUND,
+            FollowError::CannotFollowOwnHandle => ApiError::CANNOT_FOLLOW_OWN_HANDLE,
+            FollowError::FollowLimitReached => ApiError::FOLLOW_LIMIT_REACHED,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
\ No newline at end of file
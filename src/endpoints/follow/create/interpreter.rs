@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, follow::followee::Followee, handle::id::HandleId, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+use super::dsl::{Follow, FollowError};
+
+pub struct FollowImpl {
+    db: Arc<Session>,
+    select_handle_owner: Arc<PreparedStatement>,
+    select_block: Arc<PreparedStatement>,
+    select_tag: Arc<PreparedStatement>,
+    count_follows: Arc<PreparedStatement>,
+    insert_follow: Arc<PreparedStatement>,
+    select_followed_at: Arc<PreparedStatement>,
+    insert_follower: Arc<PreparedStatement>,
+    increment_follower_count: Arc<PreparedStatement>,
+}
+
+impl FollowImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_handle_owner = prepare(&db, "SELECT account_id FROM handle_owners WHERE handle_id = ?").await?;
+
+        let select_block = prepare(&db, "SELECT handle_id FROM blocks WHERE account_id = ? AND handle_id = ?").await?;
+
+        let select_tag = prepare(&db, "SELECT id FROM tags WHERE id = ?").await?;
+
+        let count_follows = prepare(&db, "SELECT COUNT(*) FROM follows WHERE account_id = ?").await?;
+
+        let insert_follow = prepare(&db, "INSERT INTO follows (account_id, followee_type, followee_id, followed_at) VALUES (?, ?, ?, ?) IF NOT EXISTS").await?;
+
+        let select_followed_at = prepare(&db, "SELECT followed_at FROM follows WHERE account_id = ? AND followee_type = ? AND followee_id = ?").await?;
+
+        let insert_follower = prepare(&db, "INSERT INTO followers (followee_type, followee_id, account_id, followed_at) VALUES (?, ?, ?, ?)").await?;
+
+        let increment_follower_count = prepare(&db, "UPDATE follower_counts SET follower_count = follower_count + 1 WHERE followee_type = ? AND followee_id = ?").await?;
+
+        Ok(Self { db, select_handle_owner, select_block, select_tag, count_follows, insert_follow, select_followed_at, insert_follower, increment_follower_count })
+    }
+}
+
+impl Follow for FollowImpl {
+    async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, FollowError> {
+        self.db
+            .execute_unpaged(&self.select_handle_owner, (handle_id, ))
+            .await
+            .map_err(|e| FollowError::FetchFolloweeFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, )>()
+            .map(|row| row.map(|(account_id, )| account_id))
+            .map_err(|e| FollowError::FetchFolloweeFailed(e.into()))
+    }
+
+    async fn is_blocking(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, FollowError> {
+        self.db
+            .execute_unpaged(&self.select_block, (account_id, handle_id))
+            .await
+            .map_err(|e| FollowError::CheckBlockFailed(e.into()))?
+            .maybe_first_row_typed::<(HandleId, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| FollowError::CheckBlockFailed(e.into()))
+    }
+
+    async fn tag_exists(&self, tag_id: NonTopTagId) -> Fallible<bool, FollowError> {
+        self.db
+            .execute_unpaged(&self.select_tag, (tag_id, ))
+            .await
+            .map_err(|e| FollowError::FetchFolloweeFailed(e.into()))?
+            .maybe_first_row_typed::<(NonTopTagId, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| FollowError::FetchFolloweeFailed(e.into()))
+    }
+
+    async fn count_follows(&self, account_id: AccountId) -> Fallible<u32, FollowError> {
+        self.db
+            .execute_unpaged(&self.count_follows, (account_id, ))
+            .await
+            .map_err(|e| FollowError::CountFollowsFailed(e.into()))?
+            .first_row_typed::<(i64, )>()
+            .map(|(count, )| count as u32)
+            .map_err(|e| FollowError::CountFollowsFailed(e.into()))
+    }
+
+    async fn insert_follow(&self, account_id: AccountId, followee: Followee) -> Fallible<(), FollowError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> FollowError {
+            FollowError::InsertFollowFailed(e.into())
+        }
+
+        let followed_at = UnixtimeMillis::now();
+
+        let res = self.db
+            .execute_unpaged(&self.insert_follow, (account_id, followee.kind(), followee.id(), followed_at))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) => (),
+            Err(Some(e)) => return Err(handle_error(e)),
+            // 既にフォローしている場合は、フォロワー数を増やさない
+            // 前回の逆方向の辺の書き込みが失敗していても再試行で直るよう、フォローした時刻で書き直す
+            Err(None) => {
+                let (followed_at, ) = self.db
+                    .execute_unpaged(&self.select_followed_at, (account_id, followee.kind(), followee.id()))
+                    .await
+                    .map_err(handle_error)?
+                    .first_row_typed::<(UnixtimeMillis, )>()
+                    .map_err(handle_error)?;
+
+                return self.db
+                    .execute_unpaged(&self.insert_follower, (followee.kind(), followee.id(), account_id, followed_at))
+                    .await
+                    .map(|_| ())
+                    .map_err(handle_error);
+            },
+        }
+
+        // 逆方向の辺とフォロワー数は、フォローが確定した後に更新する
+        self.db
+            .execute_unpaged(&self.insert_follower, (followee.kind(), followee.id(), account_id, followed_at))
+            .await
+            .map_err(handle_error)?;
+
+        self.db
+            .execute_unpaged(&self.increment_follower_count, (followee.kind(), followee.id()))
+            .await
+            .map(|_| ())
+            .map_err(handle_error)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, follow::followee::Followee, profile::account_id::AccountId};
+
+pub(crate) trait Unfollow {
+    // フォローしていない場合は`FollowNotFound`を返す
+    async fn unfollow(&self, account_id: AccountId, followee: Followee) -> Fallible<(), UnfollowError>;
+}
+
+#[derive(Debug, Error)]
+pub enum UnfollowError {
+    #[error("フォローしていません")]
+    FollowNotFound,
+    #[error("フォローの解除に失敗しました")]
+    DeleteFollowFailed(#[source] anyhow::Error),
+}
\ No newline at end of file
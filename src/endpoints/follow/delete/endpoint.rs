@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::delete, Extension, Json, Router};
+use http::StatusCode;
+use scylla::Session;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{follow::followee::Followee, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{Unfollow, UnfollowError}, interpreter::UnfollowImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<UnfollowImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "dlflw", 60, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "dlflw", 60, 15, TimeUnit::MINS).await?);
+
+    let unfollow = UnfollowImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/follows", delete(handler))
+        .layer(services)
+        .with_state(Arc::new(unfollow));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<UnfollowImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Json(followee): Json<Followee>
+) -> Result<StatusCode, UnfollowError> {
+    match routine.unfollow(account_id, followee).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(UnfollowError::FollowNotFound) => Err(UnfollowError::FollowNotFound),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                followee = %followee,
+                "フォローの解除に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for UnfollowError {
+    fn into_response(self) -> Response {
+        match self {
+            UnfollowError::FollowNotFound => ApiError::FOLLOW_NOT_FOUND,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
\ No newline at end of file
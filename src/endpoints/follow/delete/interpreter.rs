@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, follow::followee::Followee, profile::account_id::AccountId}, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+use super::dsl::{Unfollow, UnfollowError};
+
+pub struct UnfollowImpl {
+    db: Arc<Session>,
+    delete_follow: Arc<PreparedStatement>,
+    delete_follower: Arc<PreparedStatement>,
+    decrement_follower_count: Arc<PreparedStatement>,
+}
+
+impl UnfollowImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let delete_follow = prepare(&db, "DELETE FROM follows WHERE account_id = ? AND followee_type = ? AND followee_id = ? IF EXISTS").await?;
+
+        let delete_follower = prepare(&db, "DELETE FROM followers WHERE followee_type = ? AND followee_id = ? AND account_id = ?").await?;
+
+        let decrement_follower_count = prepare(&db, "UPDATE follower_counts SET follower_count = follower_count - 1 WHERE followee_type = ? AND followee_id = ?").await?;
+
+        Ok(Self { db, delete_follow, delete_follower, decrement_follower_count })
+    }
+}
+
+impl Unfollow for UnfollowImpl {
+    async fn unfollow(&self, account_id: AccountId, followee: Followee) -> Fallible<(), UnfollowError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> UnfollowError {
+            UnfollowError::DeleteFollowFailed(e.into())
+        }
+
+        // 削除が適用された場合のみフォロワー数を減らし、同時の解除で二重に減らないようにする
+        let is_deleted = match self.db
+            .execute_unpaged(&self.delete_follow, (account_id, followee.kind(), followee.id()))
+            .await
+            .applied(Some, || None)
+        {
+            Ok(()) => true,
+            Err(None) => false,
+            Err(Some(e)) => return Err(handle_error(e)),
+        };
+
+        // 前回の逆方向の辺の削除が失敗していても再試行で直るよう、フォローしていない場合も逆方向の辺を削除する
+        self.db
+            .execute_unpaged(&self.delete_follower, (followee.kind(), followee.id(), account_id))
+            .await
+            .map_err(handle_error)?;
+
+        if !is_deleted {
+            return Err(UnfollowError::FollowNotFound);
+        }
+
+        self.db
+            .execute_unpaged(&self.decrement_follower_count, (followee.kind(), followee.id()))
+            .await
+            .map(|_| ())
+            .map_err(handle_error)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, follow::followee::{FollowCursor, Followee}, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+// 1ページあたりのフォロー数
+pub const FOLLOWS_PAGE_SIZE: usize = 50;
+
+pub(crate) trait ListFollows {
+    async fn list_follows(&self, account_id: AccountId, after: Option<FollowCursor>) -> Fallible<FollowPage, ListFollowsError> {
+        let follows = self.fetch_follows(account_id, after, FOLLOWS_PAGE_SIZE).await?;
+
+        // 件数が上限に達していれば、最後のフォロー対象を次のカーソルとする
+        let next_cursor = match follows.last() {
+            Some(follow) if follows.len() >= FOLLOWS_PAGE_SIZE => Some(FollowCursor::from(follow.followee)),
+            _ => None,
+        };
+
+        Ok(FollowPage { follows, next_cursor })
+    }
+
+    // `after`より後のフォローを`limit`件まで取得する
+    async fn fetch_follows(&self, account_id: AccountId, after: Option<FollowCursor>, limit: usize) -> Fallible<Vec<FollowEntry>, ListFollowsError>;
+}
+
+#[derive(Debug, Serialize)]
+pub struct FollowPage {
+    follows: Vec<FollowEntry>,
+    next_cursor: Option<FollowCursor>,
+}
+
+impl FollowPage {
+    pub fn follows(&self) -> &Vec<FollowEntry> {
+        &self.follows
+    }
+
+    pub fn next_cursor(&self) -> Option<FollowCursor> {
+        self.next_cursor
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FollowEntry {
+    #[serde(flatten)]
+    followee: Followee,
+    followed_at: UnixtimeMillis,
+}
+
+impl FollowEntry {
+    pub fn new(followee: Followee, followed_at: UnixtimeMillis) -> Self {
+        Self { followee, followed_at }
+    }
+
+    pub fn followee(&self) -> Followee {
+        self.followee
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ListFollowsError {
+    #[error("フォローの一覧の取得に失敗しました")]
+    FetchFollowsFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{fallible::Fallible, follow::followee::{FollowCursor, Followee}, handle::id::HandleId, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+    use super::{FollowEntry, ListFollows, ListFollowsError, FOLLOWS_PAGE_SIZE};
+
+    struct MockListFollows {
+        followees: Vec<Followee>,
+    }
+
+    impl ListFollows for MockListFollows {
+        async fn fetch_follows(&self, _: AccountId, after: Option<FollowCursor>, limit: usize) -> Fallible<Vec<FollowEntry>, ListFollowsError> {
+            Ok(self.followees
+                .iter()
+                .filter(|followee| after.is_none_or(|after| **followee > after.value()))
+                .take(limit)
+                .map(|followee| FollowEntry::new(*followee, UnixtimeMillis::now()))
+                .collect())
+        }
+    }
+
+    fn mock(count: usize) -> MockListFollows {
+        let mut followees = (0..count).map(|_| Followee::Handle(HandleId::gen())).collect::<Vec<_>>();
+        followees.sort_unstable();
+
+        MockListFollows { followees }
+    }
+
+    #[tokio::test]
+    async fn single_page() {
+        let page = mock(3).list_follows(AccountId::gen(), None).await.unwrap();
+
+        assert_eq!(page.follows().len(), 3);
+        assert_eq!(page.next_cursor(), None);
+    }
+
+    #[tokio::test]
+    async fn paginate() {
+        let mock = mock(FOLLOWS_PAGE_SIZE + 1);
+
+        let first = mock.list_follows(AccountId::gen(), None).await.unwrap();
+        assert_eq!(first.next_cursor(), Some(FollowCursor::from(mock.followees[FOLLOWS_PAGE_SIZE - 1])));
+
+        let second = mock.list_follows(AccountId::gen(), first.next_cursor()).await.unwrap();
+        let followees = second.follows().iter().map(FollowEntry::followee).collect::<Vec<_>>();
+        assert_eq!(followees, mock.followees[FOLLOWS_PAGE_SIZE..]);
+        assert_eq!(second.next_cursor(), None);
+    }
+
+    #[test]
+    fn serialize_flattened() {
+        let handle_id = HandleId::gen();
+        let json = serde_json::to_value(FollowEntry::new(Followee::Handle(handle_id), UnixtimeMillis::from(1000))).unwrap();
+
+        assert_eq!(json["type"], "handle");
+        assert_eq!(json["id"], handle_id.to_string());
+        assert_eq!(json["followed_at"], 1000);
+    }
+}
\ No newline at end of file
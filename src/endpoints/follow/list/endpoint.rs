@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{extract::{Query, State}, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{follow::followee::FollowCursor, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{FollowPage, ListFollows, ListFollowsError}, interpreter::ListFollowsImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ListFollowsImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "lsflw", 90, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "lsflw", 90, 15, TimeUnit::MINS).await?);
+
+    let list_follows = ListFollowsImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/follows", get(handler))
+        .layer(services)
+        .with_state(Arc::new(list_follows));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<ListFollowsImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Query(query): Query<ListQuery>
+) -> Result<Json<FollowPage>, ListFollowsError> {
+    match routine.list_follows(account_id, query.after).await {
+        Ok(page) => Ok(Json(page)),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                "フォローの一覧の取得に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for ListFollowsError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListQuery {
+    after: Option<FollowCursor>,
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, follow::followee::{FollowCursor, Followee, FolloweeKind}, profile::account_id::AccountId, unixtime::UnixtimeMillis, uuid::uuid4::Uuid4}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{FollowEntry, ListFollows, ListFollowsError};
+
+pub struct ListFollowsImpl {
+    db: Arc<Session>,
+    select_follows: Arc<PreparedStatement>,
+    select_follows_after: Arc<PreparedStatement>,
+}
+
+impl ListFollowsImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_follows = prepare(&db, "SELECT followee_type, followee_id, followed_at FROM follows WHERE account_id = ? LIMIT ?").await?;
+
+        let select_follows_after = prepare(&db, "SELECT followee_type, followee_id, followed_at FROM follows WHERE account_id = ? AND (followee_type, followee_id) > (?, ?) LIMIT ?").await?;
+
+        Ok(Self { db, select_follows, select_follows_after })
+    }
+}
+
+impl ListFollows for ListFollowsImpl {
+    async fn fetch_follows(&self, account_id: AccountId, after: Option<FollowCursor>, limit: usize) -> Fallible<Vec<FollowEntry>, ListFollowsError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ListFollowsError {
+            ListFollowsError::FetchFollowsFailed(e.into())
+        }
+
+        let limit = limit as i32;
+
+        let result = match after {
+            Some(after) => {
+                let after = after.value();
+                self.db.execute_unpaged(&self.select_follows_after, (account_id, after.kind(), after.id(), limit)).await
+            },
+            None => self.db.execute_unpaged(&self.select_follows, (account_id, limit)).await,
+        };
+
+        result.map_err(handle_error)?
+            .rows_typed::<(FolloweeKind, Uuid4, UnixtimeMillis)>()
+            .map_err(handle_error)?
+            .map(|row| {
+                let (kind, id, followed_at) = row.map_err(handle_error)?;
+                Followee::of(kind, id)
+                    .map(|followee| FollowEntry::new(followee, followed_at))
+                    .map_err(handle_error)
+            })
+            .collect()
+    }
+}
\ No newline at end of file
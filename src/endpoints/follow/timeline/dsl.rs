@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::{common::{fallible::Fallible, follow::followee::Followee, handle::id::HandleId, post::{feed::{merge_post_indexes, next_cursor, Feed}, id::PostId, summary::PostSummary}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId}, endpoints::{handle_filter::hidden::exclude_hidden_handles, tag::feed::dsl::{FEED_PAGE_SIZE, MAX_EXPANDED_TAGS}}};
+
+pub(crate) trait GetHomeTimeline {
+    async fn get_home_timeline(&self, account_id: AccountId, before: Option<PostId>) -> Fallible<Feed, GetHomeTimelineError> {
+        let followees = self.fetch_followees(account_id).await?;
+        let hidden_handle_ids = self.fetch_hidden_handles(account_id).await?;
+
+        let mut handle_ids = vec![];
+        let mut tag_ids = vec![];
+
+        // フォロー後にブロック・ミュートした名義の索引は読み込まない
+        for followee in followees {
+            match followee {
+                Followee::Handle(handle_id) if hidden_handle_ids.contains(&handle_id) => (),
+                Followee::Handle(handle_id) => handle_ids.push(handle_id),
+                Followee::Tag(tag_id) => tag_ids.push(tag_id),
+            }
+        }
+
+        // フォローしているタグを優先し、残りの枠で安定した下位タグと同値タグに展開する
+        tag_ids.truncate(MAX_EXPANDED_TAGS);
+
+        for tag_id in tag_ids.clone() {
+            if tag_ids.len() >= MAX_EXPANDED_TAGS {
+                break;
+            }
+
+            for related_tag_id in self.fetch_stable_subtags_and_equivalents(tag_id).await? {
+                if tag_ids.len() >= MAX_EXPANDED_TAGS {
+                    break;
+                }
+
+                if !tag_ids.contains(&related_tag_id) {
+                    tag_ids.push(related_tag_id);
+                }
+            }
+        }
+
+        let indexes = self.fetch_post_indexes(&handle_ids, &tag_ids, before, FEED_PAGE_SIZE).await?;
+        let post_ids = merge_post_indexes(indexes, FEED_PAGE_SIZE);
+        let next_cursor = next_cursor(&post_ids, FEED_PAGE_SIZE);
+
+        // カーソルは除外前の索引から求めるため、除外によってページが短くなっても続きを取得できる
+        let mut posts = self.fetch_posts(&post_ids).await?;
+        exclude_hidden_handles(&mut posts, &hidden_handle_ids);
+
+        Ok(Feed::new(posts, next_cursor))
+    }
+
+    async fn fetch_followees(&self, account_id: AccountId) -> Fallible<Vec<Followee>, GetHomeTimelineError>;
+
+    // ブロック・ミュートした名義
+    async fn fetch_hidden_handles(&self, account_id: AccountId) -> Fallible<HashSet<HandleId>, GetHomeTimelineError>;
+
+    async fn fetch_stable_subtags_and_equivalents(&self, tag_id: NonTopTagId) -> Fallible<Vec<NonTopTagId>, GetHomeTimelineError>;
+
+    // 名義とタグの索引ごとに、`before`より古い投稿を新しい順に`limit`件まで取得する
+    // 索引の順序は問わない
+    async fn fetch_post_indexes(&self, handle_ids: &[HandleId], tag_ids: &[NonTopTagId], before: Option<PostId>, limit: usize) -> Fallible<Vec<Vec<PostId>>, GetHomeTimelineError>;
+
+    async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetHomeTimelineError>;
+}
+
+#[derive(Debug, Error)]
+pub enum GetHomeTimelineError {
+    #[error("フォローの取得に失敗しました")]
+    FetchFolloweesFailed(#[source] anyhow::Error),
+    #[error("ブロック・ミュートした名義の取得に失敗しました")]
+    FetchHiddenHandlesFailed(#[source] anyhow::Error),
+    #[error("関連タグの取得に失敗しました")]
+    FetchRelatedTagsFailed(#[source] anyhow::Error),
+    #[error("投稿の索引の取得に失敗しました")]
+    FetchPostIdsFailed(#[source] anyhow::Error),
+    #[error("投稿の取得に失敗しました")]
+    FetchPostsFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::{HashMap, HashSet}, sync::{LazyLock, Mutex}};
+
+    use crate::{common::{fallible::Fallible, follow::followee::Followee, handle::id::HandleId, post::{id::PostId, summary::PostSummary, tags::PostTags}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId, unixtime::UnixtimeMillis}, endpoints::tag::feed::dsl::{FEED_PAGE_SIZE, MAX_EXPANDED_TAGS}, helper::test::mock_non_top_tag_id};
+
+    use super::{GetHomeTimeline, GetHomeTimelineError};
+
+    static HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+    static SUBTAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(2));
+    static HIDDEN_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    struct MockGetHomeTimeline {
+        followees: Vec<Followee>,
+        handle_index: Vec<PostId>,
+        tag_indexes: HashMap<NonTopTagId, Vec<PostId>>,
+        requested_tags: Mutex<Vec<NonTopTagId>>,
+        requested_handles: Mutex<Vec<HandleId>>,
+        hidden_post_ids: Vec<PostId>,
+    }
+
+    impl MockGetHomeTimeline {
+        fn new(followees: Vec<Followee>, handle_index: Vec<PostId>, tag_indexes: HashMap<NonTopTagId, Vec<PostId>>) -> Self {
+            Self {
+                followees,
+                handle_index,
+                tag_indexes,
+                requested_tags: Mutex::new(vec![]),
+                requested_handles: Mutex::new(vec![]),
+                hidden_post_ids: vec![],
+            }
+        }
+    }
+
+    fn page(ids: &[PostId], before: Option<PostId>, limit: usize) -> Vec<PostId> {
+        ids.iter().filter(|id| before.is_none_or(|before| **id < before)).take(limit).copied().collect()
+    }
+
+    impl GetHomeTimeline for MockGetHomeTimeline {
+        async fn fetch_followees(&self, _: AccountId) -> Fallible<Vec<Followee>, GetHomeTimelineError> {
+            Ok(self.followees.clone())
+        }
+
+        async fn fetch_hidden_handles(&self, _: AccountId) -> Fallible<HashSet<HandleId>, GetHomeTimelineError> {
+            Ok(HashSet::from([*HIDDEN_HANDLE_ID]))
+        }
+
+        async fn fetch_stable_subtags_and_equivalents(&self, tag_id: NonTopTagId) -> Fallible<Vec<NonTopTagId>, GetHomeTimelineError> {
+            if tag_id == *TAG_ID {
+                Ok(vec![*SUBTAG_ID])
+            } else {
+                Ok(vec![])
+            }
+        }
+
+        async fn fetch_post_indexes(&self, handle_ids: &[HandleId], tag_ids: &[NonTopTagId], before: Option<PostId>, limit: usize) -> Fallible<Vec<Vec<PostId>>, GetHomeTimelineError> {
+            self.requested_handles.lock().unwrap().extend_from_slice(handle_ids);
+            self.requested_tags.lock().unwrap().extend_from_slice(tag_ids);
+
+            let handle_indexes = handle_ids.iter().map(|_| page(&self.handle_index, before, limit));
+            let tag_indexes = tag_ids.iter().map(|tag_id| self.tag_indexes.get(tag_id).map(|ids| page(ids, before, limit)).unwrap_or_default());
+
+            Ok(handle_indexes.chain(tag_indexes).collect())
+        }
+
+        async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetHomeTimelineError> {
+            Ok(post_ids.iter()
+                .map(|id| {
+                    let handle_id = if self.hidden_post_ids.contains(id) { *HIDDEN_HANDLE_ID } else { *HANDLE_ID };
+                    PostSummary::new(*id, handle_id, "本文".parse().unwrap(), PostTags::default(), UnixtimeMillis::now(), None)
+                })
+                .collect())
+        }
+    }
+
+    fn desc_ids(count: usize) -> Vec<PostId> {
+        let mut ids = (0..count).map(|_| PostId::gen()).collect::<Vec<_>>();
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids
+    }
+
+    #[tokio::test]
+    async fn merge_handles_and_tags() {
+        let ids = desc_ids(6);
+
+        let mock = MockGetHomeTimeline::new(
+            vec![Followee::Handle(*HANDLE_ID), Followee::Tag(*TAG_ID)],
+            vec![ids[0], ids[3]],
+            HashMap::from([(*TAG_ID, vec![ids[1], ids[4]]), (*SUBTAG_ID, vec![ids[2], ids[5]])]),
+        );
+
+        let feed = mock.get_home_timeline(AccountId::gen(), None).await.unwrap();
+
+        let post_ids = feed.posts().iter().map(PostSummary::post_id).collect::<Vec<_>>();
+        assert_eq!(post_ids, ids);
+        assert_eq!(*mock.requested_tags.lock().unwrap(), vec![*TAG_ID, *SUBTAG_ID]);
+    }
+
+    #[tokio::test]
+    async fn paginate() {
+        let ids = desc_ids(FEED_PAGE_SIZE + 3);
+
+        let mock = MockGetHomeTimeline::new(vec![Followee::Handle(*HANDLE_ID)], ids.clone(), HashMap::new());
+
+        let first = mock.get_home_timeline(AccountId::gen(), None).await.unwrap();
+        assert_eq!(first.next_cursor(), Some(ids[FEED_PAGE_SIZE - 1]));
+
+        let second = mock.get_home_timeline(AccountId::gen(), first.next_cursor()).await.unwrap();
+        let post_ids = second.posts().iter().map(PostSummary::post_id).collect::<Vec<_>>();
+        assert_eq!(post_ids, ids[FEED_PAGE_SIZE..]);
+        assert_eq!(second.next_cursor(), None);
+    }
+
+    #[tokio::test]
+    async fn limit_expanded_tags() {
+        let followees = (0..MAX_EXPANDED_TAGS + 10)
+            .map(|_| Followee::Tag(NonTopTagId::gen()))
+            .collect();
+
+        let mock = MockGetHomeTimeline::new(followees, vec![], HashMap::new());
+
+        mock.get_home_timeline(AccountId::gen(), None).await.unwrap();
+        assert_eq!(mock.requested_tags.lock().unwrap().len(), MAX_EXPANDED_TAGS);
+    }
+
+    #[tokio::test]
+    async fn skip_hidden_followees() {
+        let mock = MockGetHomeTimeline::new(vec![Followee::Handle(*HANDLE_ID), Followee::Handle(*HIDDEN_HANDLE_ID)], vec![], HashMap::new());
+
+        mock.get_home_timeline(AccountId::gen(), None).await.unwrap();
+        assert_eq!(*mock.requested_handles.lock().unwrap(), vec![*HANDLE_ID]);
+    }
+
+    #[tokio::test]
+    async fn exclude_hidden_handles() {
+        let ids = desc_ids(4);
+
+        // フォローしているタグに、ブロック・ミュートした名義の投稿が含まれる
+        let mut mock = MockGetHomeTimeline::new(vec![Followee::Tag(*TAG_ID)], vec![], HashMap::from([(*TAG_ID, ids.clone())]));
+        mock.hidden_post_ids = vec![ids[1]];
+
+        let feed = mock.get_home_timeline(AccountId::gen(), None).await.unwrap();
+
+        let post_ids = feed.posts().iter().map(PostSummary::post_id).collect::<Vec<_>>();
+        assert_eq!(post_ids, vec![ids[0], ids[2], ids[3]]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{extract::{Query, State}, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{post::{feed::Feed, id::PostId}, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{GetHomeTimeline, GetHomeTimelineError}, interpreter::GetHomeTimelineImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<GetHomeTimelineImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "hmtln", 90, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "hmtln", 90, 15, TimeUnit::MINS).await?);
+
+    let interpreter = GetHomeTimelineImpl::try_new(db, cache).await?;
+
+    let router = Router::new()
+        .route("/timeline", get(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<GetHomeTimelineImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Query(query): Query<TimelineQuery>
+) -> Result<Json<Feed>, GetHomeTimelineError> {
+    match routine.get_home_timeline(account_id, query.before).await {
+        Ok(feed) => Ok(Json(feed)),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                "ホームタイムラインの取得に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for GetHomeTimelineError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TimelineQuery {
+    before: Option<PostId>,
+}
\ No newline at end of file
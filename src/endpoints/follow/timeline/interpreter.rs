@@ -0,0 +1,140 @@
+use std::{collections::HashSet, sync::Arc};
+
+use scylla::{prepared_statement::PreparedStatement, serialize::value::SerializeValue, Session};
+use tokio::task::JoinSet;
+
+use crate::{common::{fallible::Fallible, follow::followee::{Followee, FolloweeKind}, handle::id::HandleId, post::{id::PostId, summary::PostSummary}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId, uuid::uuid4::Uuid4}, endpoints::{handle_filter::hidden::HiddenHandleFetcher, post::summary::PostSummaryFetcher, tag::related::StableRelatedTagFetcher}, helper::{error::InitError, redis::connection::Pool, scylla::prepare}};
+
+use super::dsl::{GetHomeTimeline, GetHomeTimelineError};
+
+pub struct GetHomeTimelineImpl {
+    db: Arc<Session>,
+    related_tag_fetcher: StableRelatedTagFetcher,
+    post_summary_fetcher: PostSummaryFetcher,
+    hidden_handle_fetcher: HiddenHandleFetcher,
+    select_followees: Arc<PreparedStatement>,
+    select_latest_post_ids_by_handle: Arc<PreparedStatement>,
+    select_post_ids_by_handle_before: Arc<PreparedStatement>,
+    select_latest_post_ids_by_tag: Arc<PreparedStatement>,
+    select_post_ids_by_tag_before: Arc<PreparedStatement>,
+}
+
+impl GetHomeTimelineImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>) -> Result<Self, InitError<Self>> {
+        let related_tag_fetcher = StableRelatedTagFetcher::new(cache);
+
+        let post_summary_fetcher = PostSummaryFetcher::try_new(db.clone()).await?;
+
+        let hidden_handle_fetcher = HiddenHandleFetcher::try_new(db.clone()).await?;
+
+        let select_followees = prepare(&db, "SELECT followee_type, followee_id FROM follows WHERE account_id = ?").await?;
+
+        let select_latest_post_ids_by_handle = prepare(&db, "SELECT post_id FROM posts_by_handle WHERE handle_id = ? LIMIT ?").await?;
+
+        let select_post_ids_by_handle_before = prepare(&db, "SELECT post_id FROM posts_by_handle WHERE handle_id = ? AND post_id < ? LIMIT ?").await?;
+
+        let select_latest_post_ids_by_tag = prepare(&db, "SELECT post_id FROM posts_by_tag WHERE tag_id = ? LIMIT ?").await?;
+
+        let select_post_ids_by_tag_before = prepare(&db, "SELECT post_id FROM posts_by_tag WHERE tag_id = ? AND post_id < ? LIMIT ?").await?;
+
+        Ok(Self {
+            db,
+            related_tag_fetcher,
+            post_summary_fetcher,
+            hidden_handle_fetcher,
+            select_followees,
+            select_latest_post_ids_by_handle,
+            select_post_ids_by_handle_before,
+            select_latest_post_ids_by_tag,
+            select_post_ids_by_tag_before,
+        })
+    }
+}
+
+impl GetHomeTimeline for GetHomeTimelineImpl {
+    async fn fetch_followees(&self, account_id: AccountId) -> Fallible<Vec<Followee>, GetHomeTimelineError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> GetHomeTimelineError {
+            GetHomeTimelineError::FetchFolloweesFailed(e.into())
+        }
+
+        self.db
+            .execute_unpaged(&self.select_followees, (account_id, ))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(FolloweeKind, Uuid4)>()
+            .map_err(handle_error)?
+            .map(|row| {
+                let (kind, id) = row.map_err(handle_error)?;
+                Followee::of(kind, id).map_err(handle_error)
+            })
+            .collect()
+    }
+
+    async fn fetch_hidden_handles(&self, account_id: AccountId) -> Fallible<HashSet<HandleId>, GetHomeTimelineError> {
+        self.hidden_handle_fetcher
+            .fetch(account_id)
+            .await
+            .map_err(GetHomeTimelineError::FetchHiddenHandlesFailed)
+    }
+
+    async fn fetch_stable_subtags_and_equivalents(&self, tag_id: NonTopTagId) -> Fallible<Vec<NonTopTagId>, GetHomeTimelineError> {
+        self.related_tag_fetcher
+            .fetch(tag_id)
+            .await
+            .map_err(GetHomeTimelineError::FetchRelatedTagsFailed)
+    }
+
+    async fn fetch_post_indexes(&self, handle_ids: &[HandleId], tag_ids: &[NonTopTagId], before: Option<PostId>, limit: usize) -> Fallible<Vec<Vec<PostId>>, GetHomeTimelineError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> GetHomeTimelineError {
+            GetHomeTimelineError::FetchPostIdsFailed(e.into())
+        }
+
+        let limit = limit as i32;
+        let mut tasks = JoinSet::new();
+
+        // 索引ごとに別のパーティションを読むため、並行して取得する
+        for handle_id in handle_ids.iter().copied() {
+            let db = self.db.clone();
+            let select_latest = self.select_latest_post_ids_by_handle.clone();
+            let select_before = self.select_post_ids_by_handle_before.clone();
+
+            tasks.spawn(async move { fetch_post_ids(&db, &select_latest, &select_before, handle_id, before, limit).await });
+        }
+
+        for tag_id in tag_ids.iter().copied() {
+            let db = self.db.clone();
+            let select_latest = self.select_latest_post_ids_by_tag.clone();
+            let select_before = self.select_post_ids_by_tag_before.clone();
+
+            tasks.spawn(async move { fetch_post_ids(&db, &select_latest, &select_before, tag_id, before, limit).await });
+        }
+
+        let mut indexes = Vec::with_capacity(tasks.len());
+
+        // 失敗した場合は`tasks`の破棄により残りの取得も中止される
+        while let Some(result) = tasks.join_next().await {
+            indexes.push(result.map_err(handle_error)?.map_err(handle_error)?);
+        }
+
+        Ok(indexes)
+    }
+
+    async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetHomeTimelineError> {
+        self.post_summary_fetcher
+            .fetch(post_ids)
+            .await
+            .map_err(GetHomeTimelineError::FetchPostsFailed)
+    }
+}
+
+// `before`より古い投稿を新しい順に`limit`件まで取得する
+async fn fetch_post_ids<K: SerializeValue>(db: &Session, select_latest: &PreparedStatement, select_before: &PreparedStatement, key: K, before: Option<PostId>, limit: i32) -> anyhow::Result<Vec<PostId>> {
+    let result = match before {
+        Some(before) => db.execute_unpaged(select_before, (key, before, limit)).await?,
+        None => db.execute_unpaged(select_latest, (key, limit)).await?,
+    };
+
+    result.rows_typed::<(PostId, )>()?
+        .map(|row| row.map(|(post_id, )| post_id).map_err(Into::into))
+        .collect()
+}
\ No newline at end of file
@@ -8,14 +8,16 @@ use serde::Serialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{handle::{id::HandleId, share_count::HandleShareCount}, profile::account_id::AccountId}, helper::{cache::{check_if_none_match, create_etag}, error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{handle::{id::HandleId, share_count::HandleShareCount}, profile::account_id::AccountId}, helper::{api_error::ApiError, cache::{check_if_none_match, create_etag}, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::CountHandlesShare, interpreter::CountHandlesShareImpl};
+use super::{dsl::{CountHandlesShare, CountHandlesShareError}, interpreter::CountHandlesShareImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<CountHandlesShareImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "cnths", 120, 1, TimeUnit::HOURS).await?)
-        .layer(session_manager(db.clone(), cache).await?);
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "cnths", 120, 1, TimeUnit::HOURS).await?);
 
     let count_handles_share = CountHandlesShareImpl::try_new(db).await?;
 
@@ -32,7 +34,7 @@ pub async fn handler(
     State(routine): State<Arc<CountHandlesShareImpl>>,
     Extension(account_id): Extension<AccountId>,
     headers: HeaderMap,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, CountHandlesShareError> {
     match routine.count_handles_share(account_id).await {
         Ok(handles) => {
             let handles = handles.into_iter()
@@ -61,11 +63,17 @@ pub async fn handler(
                 account_id = %account_id,
                 "アカウントの名義の取得に失敗しました"
             );
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for CountHandlesShareError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct Body {
     handles: Vec<HandleInfo>,
@@ -1,17 +1,99 @@
 use thiserror::Error;
 
-use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId};
+use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName, policy::{limit::HandleLimit, CheckHandleNamePolicyError, HandleNamePolicy}}, profile::account_id::AccountId};
 
 pub(crate) trait CreateHandle {
     async fn create_handle(&self, account_id: AccountId, handle_name: HandleName) -> Fallible<(), CreateHandleError> {
+        self.handle_name_policy()
+            .check(&handle_name)
+            .map_err(|e| match e {
+                CheckHandleNamePolicyError::Reserved => CreateHandleError::ReservedHandleName,
+                CheckHandleNamePolicyError::Profane => CreateHandleError::ProfaneHandleName,
+            })?;
+
+        // 同時に作成された場合は上限をわずかに超え得るが、厳密さより可用性を優先する
+        let (onymous_handle_count, total_share_count) = self.count_handles(account_id).await?;
+
+        if HandleLimit::for_contribution(total_share_count).is_reached(onymous_handle_count) {
+            return Err(CreateHandleError::HandleLimitReached);
+        }
+
         self.add_handle(account_id, HandleId::gen(), handle_name).await
     }
 
+    fn handle_name_policy(&self) -> &HandleNamePolicy;
+
+    // 匿名名義を除いた名義の数と、全ての名義の共有数の合計を返す
+    async fn count_handles(&self, account_id: AccountId) -> Fallible<(u32, u32), CreateHandleError>;
+
     async fn add_handle(&self, account_id: AccountId, handle_id: HandleId, handle_name: HandleName) -> Fallible<(), CreateHandleError>;
 }
 
 #[derive(Debug, Error)]
 pub enum CreateHandleError {
+    #[error("予約されている名義です")]
+    ReservedHandleName,
+    #[error("不適切な語を含む名義です")]
+    ProfaneHandleName,
+    #[error("名義の数の確認に失敗しました")]
+    CountHandlesFailed(#[source] anyhow::Error),
+    #[error("名義の数が上限に達しています")]
+    HandleLimitReached,
     #[error("名義の作成に失敗しました")]
     CreateHandleFailed(#[source] anyhow::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::LazyLock};
+
+    use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName, policy::{profanity::ProfanityFilter, HandleNamePolicy}}, profile::account_id::AccountId};
+
+    use super::{CreateHandle, CreateHandleError};
+
+    static POLICY: LazyLock<HandleNamePolicy> = LazyLock::new(|| HandleNamePolicy::new(ProfanityFilter::parse("badword")));
+
+    struct MockCreateHandle {
+        onymous_handle_count: u32,
+        total_share_count: u32,
+    }
+
+    impl CreateHandle for MockCreateHandle {
+        fn handle_name_policy(&self) -> &HandleNamePolicy {
+            &POLICY
+        }
+
+        async fn count_handles(&self, _: AccountId) -> Fallible<(u32, u32), CreateHandleError> {
+            Ok((self.onymous_handle_count, self.total_share_count))
+        }
+
+        async fn add_handle(&self, _: AccountId, _: HandleId, _: HandleName) -> Fallible<(), CreateHandleError> {
+            Ok(())
+        }
+    }
+
+    async fn test_create_handle(name: &str, onymous_handle_count: u32, total_share_count: u32) -> Fallible<(), CreateHandleError> {
+        MockCreateHandle { onymous_handle_count, total_share_count }
+            .create_handle(AccountId::gen(), HandleName::from_str(name).unwrap())
+            .await
+    }
+
+    #[tokio::test]
+    async fn create_handle() {
+        assert!(test_create_handle("たなか", 0, 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn naming_policy() {
+        assert!(matches!(test_create_handle("Admin", 0, 0).await, Err(CreateHandleError::ReservedHandleName)));
+        assert!(matches!(test_create_handle("badw0rd", 0, 0).await, Err(CreateHandleError::ProfaneHandleName)));
+    }
+
+    #[tokio::test]
+    async fn handle_limit() {
+        assert!(matches!(test_create_handle("たなか", 3, 0).await, Err(CreateHandleError::HandleLimitReached)));
+
+        // 貢献に応じて上限が増える
+        assert!(test_create_handle("たなか", 3, 100).await.is_ok());
+    }
 }
\ No newline at end of file
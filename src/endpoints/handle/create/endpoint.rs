@@ -1,20 +1,22 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::post, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
 use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{handle::name::HandleName, profile::account_id::AccountId}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{handle::name::HandleName, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::CreateHandle, interpreter::CreateHandleImpl};
+use super::{dsl::{CreateHandle, CreateHandleError}, interpreter::CreateHandleImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<CreateHandleImpl>> {
     let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
     .layer(rate_limiter(db.clone(), cache.clone(), "crehd", 10, 1, TimeUnit::HOURS).await?)
-    .layer(session_manager(db.clone(), cache).await?);
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "crehd", 10, 1, TimeUnit::HOURS).await?);
 
     let create_handle = CreateHandleImpl::try_new(db).await?;
 
@@ -30,19 +32,31 @@ pub async fn handler(
     State(routine): State<Arc<CreateHandleImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>
-) -> StatusCode {
+) -> Result<StatusCode, CreateHandleError> {
     match routine.create_handle(account_id, payload.handle_name).await {
-        Ok(_) => StatusCode::CREATED,
+        Ok(_) => Ok(StatusCode::CREATED),
+        Err(e @ (CreateHandleError::ReservedHandleName | CreateHandleError::ProfaneHandleName | CreateHandleError::HandleLimitReached)) => Err(e),
         Err(e) => {
             error!(
                 error = %e,
                 "名義の作成に失敗しました"
             );
-            StatusCode::INTERNAL_SERVER_ERROR
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for CreateHandleError {
+    fn into_response(self) -> Response {
+        match self {
+            CreateHandleError::ReservedHandleName => ApiError::RESERVED_HANDLE_NAME,
+            CreateHandleError::ProfaneHandleName => ApiError::PROFANE_HANDLE_NAME,
+            CreateHandleError::HandleLimitReached => ApiError::HANDLE_LIMIT_REACHED,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Payload {
     handle_name: HandleName
@@ -2,36 +2,83 @@ use std::sync::Arc;
 
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::{common::{fallible::Fallible, handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId}, helper::{error::InitError, scylla::prepare}};
+use crate::{common::{fallible::Fallible, handle::{id::HandleId, name::HandleName, policy::HandleNamePolicy}, profile::account_id::AccountId}, helper::{error::InitError, scylla::prepare}};
 
 use super::dsl::{CreateHandle, CreateHandleError};
 
 pub struct CreateHandleImpl {
     db: Arc<Session>,
+    policy: HandleNamePolicy,
+    select_handle_names: Arc<PreparedStatement>,
+    select_handle_share_counts: Arc<PreparedStatement>,
     insert_handle: Arc<PreparedStatement>,
-    insert_handle_share_count: Arc<PreparedStatement>,
 }
 
 impl CreateHandleImpl {
     pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
-        let insert_handle = prepare(&db, "INSERT INTO handles (account_id, handle_id, handle_name) VALUES (?, ?, ?)").await?;
-        
-        let insert_handle_share_count = prepare(&db, "INSERT INTO handle_share_counts (account_id, handle_id, share_count) VALUES (?, ?, ?)").await?;
+        let policy = HandleNamePolicy::from_env().map_err(|e| InitError::new(e.into()))?;
 
-        Ok(Self { db, insert_handle, insert_handle_share_count })
+        let select_handle_names = prepare(&db, "SELECT handle_name FROM handles WHERE account_id = ?").await?;
+
+        let select_handle_share_counts = prepare(&db, "SELECT share_count FROM handle_share_counts WHERE account_id = ?").await?;
+
+        // ログ付きバッチで名義、共有数、所有者の索引をまとめて作成する
+        // 所有者の索引は公開プロフィールから所有者を引くために用いる
+        let insert_handle = prepare(&db, "
+            BEGIN BATCH
+                INSERT INTO handles (account_id, handle_id, handle_name) VALUES (?, ?, ?);
+                INSERT INTO handle_share_counts (account_id, handle_id, share_count) VALUES (?, ?, 0);
+                INSERT INTO handle_owners (handle_id, account_id) VALUES (?, ?);
+            APPLY BATCH
+        ").await?;
+
+        Ok(Self { db, policy, select_handle_names, select_handle_share_counts, insert_handle })
     }
 }
 
 impl CreateHandle for CreateHandleImpl {
-    async fn add_handle(&self, account_id: AccountId, handle_id: HandleId, handle_name: HandleName) -> Fallible<(), CreateHandleError> {
-        self.db
-            .execute_unpaged(&self.insert_handle, (account_id, handle_id, handle_name))
+    fn handle_name_policy(&self) -> &HandleNamePolicy {
+        &self.policy
+    }
+
+    async fn count_handles(&self, account_id: AccountId) -> Fallible<(u32, u32), CreateHandleError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> CreateHandleError {
+            CreateHandleError::CountHandlesFailed(e.into())
+        }
+
+        let mut onymous_handle_count = 0;
+
+        for row in self.db
+            .execute_unpaged(&self.select_handle_names, (account_id, ))
             .await
-            .map(|_| ())
-            .map_err(|e| CreateHandleError::CreateHandleFailed(e.into()))?;
+            .map_err(handle_error)?
+            .rows_typed::<(String, )>()
+            .map_err(handle_error)?
+        {
+            // 空の名前は匿名名義を表す
+            let (handle_name, ) = row.map_err(handle_error)?;
+
+            if !handle_name.is_empty() {
+                onymous_handle_count += 1;
+            }
+        }
+
+        let total_share_count = self.db
+            .execute_unpaged(&self.select_handle_share_counts, (account_id, ))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(i32, )>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(share_count, )| share_count.max(0) as u32))
+            .sum::<Result<u32, _>>()
+            .map_err(handle_error)?;
+
+        Ok((onymous_handle_count, total_share_count))
+    }
 
+    async fn add_handle(&self, account_id: AccountId, handle_id: HandleId, handle_name: HandleName) -> Fallible<(), CreateHandleError> {
         self.db
-            .execute_unpaged(&self.insert_handle_share_count, (account_id, handle_id, 0))
+            .execute_unpaged(&self.insert_handle, (account_id, handle_id, handle_name, account_id, handle_id, handle_id, account_id))
             .await
             .map(|_| ())
             .map_err(|e| CreateHandleError::CreateHandleFailed(e.into()))
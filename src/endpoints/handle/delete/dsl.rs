@@ -3,13 +3,82 @@ use thiserror::Error;
 use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
 
 pub(crate) trait DeleteHandle {
+    async fn delete_handle(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), DeleteHandleError> {
+        // 空の名前は匿名名義を表す
+        let handle_name = self.fetch_handle_name(account_id, handle_id)
+            .await?
+            .ok_or(DeleteHandleError::HandleNotFound)?;
+
+        if handle_name.is_empty() {
+            return Err(DeleteHandleError::AnonymousHandle);
+        }
+
+        self.delete_handle_if_onymous(account_id, handle_id).await
+    }
+
+    async fn fetch_handle_name(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<Option<String>, DeleteHandleError>;
+
+    // 匿名名義が記名になることはないため、条件付き削除が適用されない場合は名義が削除済みとみなす
     async fn delete_handle_if_onymous(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), DeleteHandleError>;
 }
 
 #[derive(Debug, Error)]
 pub enum DeleteHandleError {
+    #[error("名義の取得に失敗しました")]
+    FetchHandleNameFailed(#[source] anyhow::Error),
+    #[error("名義が存在しません")]
+    HandleNotFound,
     #[error("匿名名義は削除できません")]
     AnonymousHandle,
     #[error("名義の削除に失敗しました")]
     DeleteHandleFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+    use super::{DeleteHandle, DeleteHandleError};
+
+    static ONYMOUS_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static ANONYMOUS_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    struct MockDeleteHandle;
+
+    impl DeleteHandle for MockDeleteHandle {
+        async fn fetch_handle_name(&self, _: AccountId, handle_id: HandleId) -> Fallible<Option<String>, DeleteHandleError> {
+            if handle_id == *ONYMOUS_HANDLE_ID {
+                Ok(Some(String::from("すずき")))
+            } else if handle_id == *ANONYMOUS_HANDLE_ID {
+                Ok(Some(String::new()))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn delete_handle_if_onymous(&self, _: AccountId, _: HandleId) -> Fallible<(), DeleteHandleError> {
+            Ok(())
+        }
+    }
+
+    async fn test_delete_handle(handle_id: HandleId) -> Fallible<(), DeleteHandleError> {
+        MockDeleteHandle.delete_handle(AccountId::gen(), handle_id).await
+    }
+
+    #[tokio::test]
+    async fn delete_handle() {
+        assert!(test_delete_handle(*ONYMOUS_HANDLE_ID).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn anonymous_handle() {
+        assert!(matches!(test_delete_handle(*ANONYMOUS_HANDLE_ID).await, Err(DeleteHandleError::AnonymousHandle)));
+    }
+
+    #[tokio::test]
+    async fn handle_not_found() {
+        assert!(matches!(test_delete_handle(HandleId::gen()).await, Err(DeleteHandleError::HandleNotFound)));
+    }
 }
\ No newline at end of file
@@ -1,19 +1,21 @@
 use std::sync::Arc;
 
-use axum::{extract::{Path, State}, routing::post, Extension, Router};
+use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::post, Extension, Router};
 use http::StatusCode;
 use scylla::Session;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{handle::id::HandleId, profile::account_id::AccountId}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{handle::id::HandleId, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::{dsl::{DeleteHandle, DeleteHandleError}, interpreter::DeleteHandleImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<DeleteHandleImpl>> {
     let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
     .layer(rate_limiter(db.clone(), cache.clone(), "delhd", 10, 1, TimeUnit::HOURS).await?)
-    .layer(session_manager(db.clone(), cache).await?);
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "delhd", 10, 1, TimeUnit::HOURS).await?);
 
     let delete_handle = DeleteHandleImpl::try_new(db).await?;
 
@@ -29,16 +31,26 @@ pub async fn handler(
     State(routine): State<Arc<DeleteHandleImpl>>,
     Extension(account_id): Extension<AccountId>,
     Path(handle_id): Path<HandleId>
-) -> StatusCode {
-    match routine.delete_handle_if_onymous(account_id, handle_id).await {
-        Ok(_) => StatusCode::NO_CONTENT,
-        Err(DeleteHandleError::AnonymousHandle) => StatusCode::FORBIDDEN,
+) -> Result<StatusCode, DeleteHandleError> {
+    match routine.delete_handle(account_id, handle_id).await {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Err(e @ (DeleteHandleError::AnonymousHandle | DeleteHandleError::HandleNotFound)) => Err(e),
         Err(e) => {
             error!(
                 error = %e,
                 "名義の削除に失敗しました"
             );
-            StatusCode::INTERNAL_SERVER_ERROR
+            Err(e)
         }
     }
 }
+
+impl IntoResponse for DeleteHandleError {
+    fn into_response(self) -> Response {
+        match self {
+            DeleteHandleError::AnonymousHandle => ApiError::ANONYMOUS_HANDLE,
+            DeleteHandleError::HandleNotFound => ApiError::HANDLE_NOT_FOUND,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
\ No newline at end of file
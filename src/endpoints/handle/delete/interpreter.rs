@@ -8,21 +8,37 @@ use super::dsl::{DeleteHandle, DeleteHandleError};
 
 pub struct DeleteHandleImpl {
     db: Arc<Session>,
+    select_handle_name: Arc<PreparedStatement>,
     delete_handle_if_not_anonymous: Arc<PreparedStatement>,
     delete_handle_share_count: Arc<PreparedStatement>,
+    delete_handle_owner: Arc<PreparedStatement>,
 }
 
 impl DeleteHandleImpl {
     pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_handle_name = prepare(&db, "SELECT handle_name FROM handles WHERE account_id = ? AND handle_id = ?").await?;
+
         let delete_handle_if_not_anonymous = prepare(&db, "DELETE FROM handles WHERE account_id = ? AND handle_id = ? IF handle_name != ''").await?;
 
         let delete_handle_share_count = prepare(&db, "DELETE FROM handle_share_counts WHERE account_id = ? AND handle_id = ?").await?;
 
-        Ok(Self { db, delete_handle_if_not_anonymous, delete_handle_share_count })
+        let delete_handle_owner = prepare(&db, "DELETE FROM handle_owners WHERE handle_id = ?").await?;
+
+        Ok(Self { db, select_handle_name, delete_handle_if_not_anonymous, delete_handle_share_count, delete_handle_owner })
     }
 }
 
 impl DeleteHandle for DeleteHandleImpl {
+    async fn fetch_handle_name(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<Option<String>, DeleteHandleError> {
+        self.db
+            .execute_unpaged(&self.select_handle_name, (account_id, handle_id))
+            .await
+            .map_err(|e| DeleteHandleError::FetchHandleNameFailed(e.into()))?
+            .maybe_first_row_typed::<(String, )>()
+            .map(|row| row.map(|(handle_name, )| handle_name))
+            .map_err(|e| DeleteHandleError::FetchHandleNameFailed(e.into()))
+    }
+
     async fn delete_handle_if_onymous(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), DeleteHandleError> {
         fn handle_error<E: Into<anyhow::Error>>(e: E) -> DeleteHandleError {
             DeleteHandleError::DeleteHandleFailed(e.into())
@@ -32,12 +48,18 @@ impl DeleteHandle for DeleteHandleImpl {
         self.db
             .execute_unpaged(&self.delete_handle_if_not_anonymous, (account_id, handle_id))
             .await
-            .applied(DeleteHandleError::DeleteHandleFailed, || DeleteHandleError::AnonymousHandle)?;
+            .applied(DeleteHandleError::DeleteHandleFailed, || DeleteHandleError::HandleNotFound)?;
 
         // 共有数の削除
         self.db
             .execute_unpaged(&self.delete_handle_share_count, (account_id, handle_id))
             .await
+            .map_err(handle_error)?;
+
+        // 所有者の索引の削除
+        self.db
+            .execute_unpaged(&self.delete_handle_owner, (handle_id, ))
+            .await
             .map(|_| ())
             .map_err(handle_error)
     }
@@ -8,14 +8,16 @@ use serde::Serialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId}, helper::{cache::{check_if_none_match, create_etag}, error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId}, helper::{api_error::ApiError, cache::{check_if_none_match, create_etag}, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::ListHandles, interpreter::ListHandlesImpl};
+use super::{dsl::{ListHandles, ListHandlesError}, interpreter::ListHandlesImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ListHandlesImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "lishd", 30, 1, TimeUnit::HOURS).await?)
-        .layer(session_manager(db.clone(), cache).await?);
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "lishd", 30, 1, TimeUnit::HOURS).await?);
 
     let get_handles = ListHandlesImpl::try_new(db).await?;
 
@@ -32,7 +34,7 @@ pub async fn handler(
     State(routine): State<Arc<ListHandlesImpl>>,
     Extension(account_id): Extension<AccountId>,
     headers: HeaderMap,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, ListHandlesError> {
     match routine.list_handles(account_id).await {
         Ok(handles) => {
             let handles = handles.into_iter()
@@ -60,11 +62,17 @@ pub async fn handler(
                 error = %e,
                 "アカウントの名義の取得に失敗しました"
             );
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for ListHandlesError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct Body {
     handles: Vec<Handle>,
@@ -2,4 +2,5 @@ pub mod create;
 pub mod delete;
 pub mod list;
 pub mod rename;
-pub mod count;
\ No newline at end of file
+pub mod count;
+pub mod profile;
\ No newline at end of file
@@ -0,0 +1,195 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName}, post::{id::PostId, summary::PostSummary}, profile::account_id::AccountId};
+
+// プロフィールに表示する最近の投稿数
+pub const RECENT_POSTS_LIMIT: usize = 20;
+
+pub(crate) trait GetHandleProfile {
+    // 所有者の`AccountId`は名義の取得にのみ用い、結果には含めない
+    async fn get_handle_profile(&self, handle_id: HandleId) -> Fallible<HandleProfile, GetHandleProfileError> {
+        let account_id = self.fetch_handle_owner(handle_id)
+            .await?
+            .ok_or(GetHandleProfileError::HandleNotFound)?;
+
+        // 索引の更新後に名義が削除された場合も存在しないものとして扱う
+        let handle_name = self.fetch_handle_name(account_id, handle_id)
+            .await?
+            .ok_or(GetHandleProfileError::HandleNotFound)?;
+
+        let share_count = self.fetch_share_count(account_id, handle_id).await?;
+        let follower_count = self.fetch_follower_count(handle_id).await?;
+
+        let post_ids = self.fetch_recent_post_ids(handle_id, RECENT_POSTS_LIMIT).await?;
+        let recent_posts = self.fetch_posts(&post_ids).await?;
+
+        Ok(HandleProfile {
+            id: handle_id,
+            anonymous: handle_name.is_none(),
+            name: handle_name,
+            share_count,
+            follower_count,
+            recent_posts,
+        })
+    }
+
+    async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, GetHandleProfileError>;
+
+    // 名義が存在しない場合は`None`、匿名名義の場合は`Some(None)`を返す
+    async fn fetch_handle_name(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<Option<Option<HandleName>>, GetHandleProfileError>;
+
+    async fn fetch_share_count(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<u32, GetHandleProfileError>;
+
+    async fn fetch_follower_count(&self, handle_id: HandleId) -> Fallible<u64, GetHandleProfileError>;
+
+    // 新しい順に`limit`件まで取得する
+    async fn fetch_recent_post_ids(&self, handle_id: HandleId, limit: usize) -> Fallible<Vec<PostId>, GetHandleProfileError>;
+
+    async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetHandleProfileError>;
+}
+
+// 同じアカウントの他の名義と結び付けられる情報は含めない
+#[derive(Debug, Serialize)]
+pub struct HandleProfile {
+    id: HandleId,
+    name: Option<HandleName>,
+    anonymous: bool,
+    share_count: u32,
+    follower_count: u64,
+    recent_posts: Vec<PostSummary>,
+}
+
+impl HandleProfile {
+    pub fn name(&self) -> Option<&HandleName> {
+        self.name.as_ref()
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    pub fn share_count(&self) -> u32 {
+        self.share_count
+    }
+
+    pub fn follower_count(&self) -> u64 {
+        self.follower_count
+    }
+
+    pub fn recent_posts(&self) -> &[PostSummary] {
+        &self.recent_posts
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GetHandleProfileError {
+    #[error("名義の所有者の取得に失敗しました")]
+    FetchHandleOwnerFailed(#[source] anyhow::Error),
+    #[error("名義が存在しません")]
+    HandleNotFound,
+    #[error("名義の取得に失敗しました")]
+    FetchHandleNameFailed(#[source] anyhow::Error),
+    #[error("名義の共有数の取得に失敗しました")]
+    FetchShareCountFailed(#[source] anyhow::Error),
+    #[error("フォロワー数の取得に失敗しました")]
+    FetchFollowerCountFailed(#[source] anyhow::Error),
+    #[error("名義の投稿の取得に失敗しました")]
+    FetchPostIdsFailed(#[source] anyhow::Error),
+    #[error("投稿の取得に失敗しました")]
+    FetchPostsFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::LazyLock};
+
+    use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName}, post::{id::PostId, summary::PostSummary, tags::PostTags}, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+    use super::{GetHandleProfile, GetHandleProfileError, RECENT_POSTS_LIMIT};
+
+    static ACCOUNT_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static ONYMOUS_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static ANONYMOUS_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    // 所有者の索引のみ残り、名義が削除された状態
+    static DELETED_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    struct MockGetHandleProfile;
+
+    impl GetHandleProfile for MockGetHandleProfile {
+        async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, GetHandleProfileError> {
+            if [*ONYMOUS_HANDLE_ID, *ANONYMOUS_HANDLE_ID, *DELETED_HANDLE_ID].contains(&handle_id) {
+                Ok(Some(*ACCOUNT_ID))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn fetch_handle_name(&self, _: AccountId, handle_id: HandleId) -> Fallible<Option<Option<HandleName>>, GetHandleProfileError> {
+            if handle_id == *ONYMOUS_HANDLE_ID {
+                Ok(Some(Some(HandleName::from_str("すずき").unwrap())))
+            } else if handle_id == *ANONYMOUS_HANDLE_ID {
+                Ok(Some(None))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn fetch_share_count(&self, _: AccountId, _: HandleId) -> Fallible<u32, GetHandleProfileError> {
+            Ok(7)
+        }
+
+        async fn fetch_follower_count(&self, _: HandleId) -> Fallible<u64, GetHandleProfileError> {
+            Ok(3)
+        }
+
+        async fn fetch_recent_post_ids(&self, _: HandleId, limit: usize) -> Fallible<Vec<PostId>, GetHandleProfileError> {
+            Ok((0..limit).map(|_| PostId::gen()).collect())
+        }
+
+        async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetHandleProfileError> {
+            Ok(post_ids.iter()
+                .map(|id| PostSummary::new(*id, *ONYMOUS_HANDLE_ID, "本文".parse().unwrap(), PostTags::default(), UnixtimeMillis::now(), None))
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn onymous_handle() {
+        let profile = MockGetHandleProfile.get_handle_profile(*ONYMOUS_HANDLE_ID).await.unwrap();
+
+        assert_eq!(profile.name().map(HandleName::value).map(String::as_str), Some("すずき"));
+        assert!(!profile.is_anonymous());
+        assert_eq!(profile.share_count(), 7);
+        assert_eq!(profile.follower_count(), 3);
+        assert_eq!(profile.recent_posts().len(), RECENT_POSTS_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn anonymous_handle() {
+        let profile = MockGetHandleProfile.get_handle_profile(*ANONYMOUS_HANDLE_ID).await.unwrap();
+
+        assert!(profile.name().is_none());
+        assert!(profile.is_anonymous());
+    }
+
+    #[tokio::test]
+    async fn handle_not_found() {
+        let res = MockGetHandleProfile.get_handle_profile(HandleId::gen()).await;
+        assert!(matches!(res, Err(GetHandleProfileError::HandleNotFound)));
+    }
+
+    #[tokio::test]
+    async fn deleted_handle() {
+        let res = MockGetHandleProfile.get_handle_profile(*DELETED_HANDLE_ID).await;
+        assert!(matches!(res, Err(GetHandleProfileError::HandleNotFound)));
+    }
+
+    #[tokio::test]
+    async fn account_id_is_not_serialized() {
+        let profile = MockGetHandleProfile.get_handle_profile(*ONYMOUS_HANDLE_ID).await.unwrap();
+        let json = serde_json::to_string(&profile).unwrap();
+
+        assert!(!json.contains(&ACCOUNT_ID.to_string()));
+    }
+}
\ No newline at end of file
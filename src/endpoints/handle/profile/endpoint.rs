@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::get, Json, Router};
+use http::{header::{CACHE_CONTROL, ETAG}, HeaderMap, HeaderValue, StatusCode};
+use scylla::Session;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::handle::id::HandleId, helper::{api_error::ApiError, cache::{check_if_none_match, create_etag}, error::InitError, middleware::{error_localizer, rate_limiter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{GetHandleProfile, GetHandleProfileError, HandleProfile}, interpreter::GetHandleProfileImpl};
+
+// セッションを必要としない公開エンドポイント
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<GetHandleProfileImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache, "gthpr", 300, 15, TimeUnit::MINS).await?);
+
+    let interpreter = GetHandleProfileImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/handles/:id", get(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<GetHandleProfileImpl>>,
+    Path(handle_id): Path<HandleId>,
+    headers: HeaderMap,
+) -> Result<Response, GetHandleProfileError> {
+    match routine.get_handle_profile(handle_id).await {
+        Ok(profile) => {
+            let bytes = to_bytes(&profile);
+
+            if let Some(if_none_match) = headers.get("if-none-match") {
+                if check_if_none_match(&bytes, if_none_match) {
+                    return Ok(StatusCode::NOT_MODIFIED.into_response());
+                }
+            }
+
+            const CACHE_CONTROL_VALUE: HeaderValue = HeaderValue::from_static("public, max-age=60, must-revalidate");
+
+            Ok((
+                [(CACHE_CONTROL, CACHE_CONTROL_VALUE), (ETAG, create_etag(&bytes))],
+                Json(profile)
+            ).into_response())
+        },
+        Err(GetHandleProfileError::HandleNotFound) => Err(GetHandleProfileError::HandleNotFound),
+        Err(e) => {
+            error!(
+                error = %e,
+                handle_id = %handle_id,
+                "名義のプロフィールの取得に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for GetHandleProfileError {
+    fn into_response(self) -> Response {
+        match self {
+            GetHandleProfileError::HandleNotFound => ApiError::HANDLE_NOT_FOUND,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
+// 投稿の編集や共有数の変化も検出するため、応答の全体から算出する
+pub fn to_bytes(profile: &HandleProfile) -> Vec<u8> {
+    serde_json::to_vec(profile).unwrap_or_default()
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use std::{str::FromStr, sync::Arc};
+
+use scylla::{frame::value::Counter, prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, follow::followee::FolloweeKind, handle::{id::HandleId, name::HandleName}, post::{id::PostId, summary::PostSummary}, profile::account_id::AccountId}, endpoints::post::summary::PostSummaryFetcher, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{GetHandleProfile, GetHandleProfileError};
+
+pub struct GetHandleProfileImpl {
+    db: Arc<Session>,
+    post_summary_fetcher: PostSummaryFetcher,
+    select_handle_owner: Arc<PreparedStatement>,
+    select_handle_name: Arc<PreparedStatement>,
+    select_share_count: Arc<PreparedStatement>,
+    select_follower_count: Arc<PreparedStatement>,
+    select_recent_post_ids: Arc<PreparedStatement>,
+}
+
+impl GetHandleProfileImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let post_summary_fetcher = PostSummaryFetcher::try_new(db.clone()).await?;
+
+        let select_handle_owner = prepare(&db, "SELECT account_id FROM handle_owners WHERE handle_id = ?").await?;
+
+        let select_handle_name = prepare(&db, "SELECT handle_name FROM handles WHERE account_id = ? AND handle_id = ?").await?;
+
+        let select_share_count = prepare(&db, "SELECT share_count FROM handle_share_counts WHERE account_id = ? AND handle_id = ?").await?;
+
+        let select_follower_count = prepare(&db, "SELECT follower_count FROM follower_counts WHERE followee_type = ? AND followee_id = ?").await?;
+
+        let select_recent_post_ids = prepare(&db, "SELECT post_id FROM posts_by_handle WHERE handle_id = ? LIMIT ?").await?;
+
+        Ok(Self { db, post_summary_fetcher, select_handle_owner, select_handle_name, select_share_count, select_follower_count, select_recent_post_ids })
+    }
+}
+
+impl GetHandleProfile for GetHandleProfileImpl {
+    async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, GetHandleProfileError> {
+        self.db
+            .execute_unpaged(&self.select_handle_owner, (handle_id, ))
+            .await
+            .map_err(|e| GetHandleProfileError::FetchHandleOwnerFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, )>()
+            .map(|row| row.map(|(account_id, )| account_id))
+            .map_err(|e| GetHandleProfileError::FetchHandleOwnerFailed(e.into()))
+    }
+
+    async fn fetch_handle_name(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<Option<Option<HandleName>>, GetHandleProfileError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> GetHandleProfileError {
+            GetHandleProfileError::FetchHandleNameFailed(e.into())
+        }
+
+        let handle_name = self.db
+            .execute_unpaged(&self.select_handle_name, (account_id, handle_id))
+            .await
+            .map_err(handle_error)?
+            .maybe_first_row_typed::<(String, )>()
+            .map_err(handle_error)?;
+
+        // 空の名前は匿名名義を表す
+        match handle_name {
+            Some((handle_name, )) if handle_name.is_empty() => Ok(Some(None)),
+            Some((handle_name, )) => HandleName::from_str(&handle_name)
+                .map(|handle_name| Some(Some(handle_name)))
+                .map_err(handle_error),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_share_count(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<u32, GetHandleProfileError> {
+        self.db
+            .execute_unpaged(&self.select_share_count, (account_id, handle_id))
+            .await
+            .map_err(|e| GetHandleProfileError::FetchShareCountFailed(e.into()))?
+            .maybe_first_row_typed::<(i32, )>()
+            .map(|row| row.map_or(0, |(share_count, )| share_count.max(0) as u32))
+            .map_err(|e| GetHandleProfileError::FetchShareCountFailed(e.into()))
+    }
+
+    async fn fetch_follower_count(&self, handle_id: HandleId) -> Fallible<u64, GetHandleProfileError> {
+        // 一度もフォローされていない名義には行が存在しない
+        self.db
+            .execute_unpaged(&self.select_follower_count, (FolloweeKind::Handle, handle_id))
+            .await
+            .map_err(|e| GetHandleProfileError::FetchFollowerCountFailed(e.into()))?
+            .maybe_first_row_typed::<(Counter, )>()
+            .map(|row| row.map_or(0, |(Counter(follower_count), )| follower_count.max(0) as u64))
+            .map_err(|e| GetHandleProfileError::FetchFollowerCountFailed(e.into()))
+    }
+
+    async fn fetch_recent_post_ids(&self, handle_id: HandleId, limit: usize) -> Fallible<Vec<PostId>, GetHandleProfileError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> GetHandleProfileError {
+            GetHandleProfileError::FetchPostIdsFailed(e.into())
+        }
+
+        self.db
+            .execute_unpaged(&self.select_recent_post_ids, (handle_id, limit as i32))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(PostId, )>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(post_id, )| post_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)
+    }
+
+    async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetHandleProfileError> {
+        self.post_summary_fetcher
+            .fetch(post_ids)
+            .await
+            .map_err(GetHandleProfileError::FetchPostsFailed)
+    }
+}
\ No newline at end of file
@@ -1,13 +1,151 @@
 use thiserror::Error;
+use tracing::warn;
 
-use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId};
+use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName, policy::{CheckHandleNamePolicyError, HandleNamePolicy}}, profile::account_id::AccountId};
 
 pub(crate) trait RenameHandle {
-    async fn rename_handle_if_onymous(&self, account_id: AccountId, handle_id: HandleId, new_handle_name: HandleName) -> Fallible<(), RenameHandleError>;
+    async fn rename_handle(&self, account_id: AccountId, handle_id: HandleId, new_handle_name: HandleName) -> Fallible<(), RenameHandleError> {
+        self.handle_name_policy()
+            .check(&new_handle_name)
+            .map_err(|e| match e {
+                CheckHandleNamePolicyError::Reserved => RenameHandleError::ReservedHandleName,
+                CheckHandleNamePolicyError::Profane => RenameHandleError::ProfaneHandleName,
+            })?;
+
+        // 空の名前は匿名名義を表す
+        let previous_handle_name = self.fetch_handle_name(account_id, handle_id)
+            .await?
+            .ok_or(RenameHandleError::HandleNotFound)?;
+
+        if previous_handle_name.is_empty() {
+            return Err(RenameHandleError::AnonymousHandle);
+        }
+
+        self.rename_handle_if_unchanged(account_id, handle_id, &previous_handle_name, &new_handle_name).await?;
+
+        // 履歴は名義の変更とは別のパーティションにあり、まとめて条件付きで書き込めないため、記録に失敗しても変更は取り消さない
+        if let Err(e) = self.record_rename(handle_id, &previous_handle_name, &new_handle_name).await {
+            warn!(handle_id = %handle_id, error = %e, "名義の変更履歴の記録に失敗しました。");
+        }
+
+        Ok(())
+    }
+
+    fn handle_name_policy(&self) -> &HandleNamePolicy;
+
+    async fn fetch_handle_name(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<Option<String>, RenameHandleError>;
+
+    // 読み取った後に名前が変更されたか、名義が削除された場合は適用されない
+    async fn rename_handle_if_unchanged(&self, account_id: AccountId, handle_id: HandleId, previous_handle_name: &str, new_handle_name: &HandleName) -> Fallible<(), RenameHandleError>;
+
+    async fn record_rename(&self, handle_id: HandleId, previous_handle_name: &str, new_handle_name: &HandleName) -> Fallible<(), RenameHandleError>;
 }
 
 #[derive(Debug, Error)]
 pub enum RenameHandleError {
+    #[error("予約されている名義です")]
+    ReservedHandleName,
+    #[error("不適切な語を含む名義です")]
+    ProfaneHandleName,
+    #[error("名義の取得に失敗しました")]
+    FetchHandleNameFailed(#[source] anyhow::Error),
+    #[error("名義が存在しません")]
+    HandleNotFound,
+    #[error("匿名名義は編集できません")]
+    AnonymousHandle,
     #[error("名義の編集に失敗しました")]
-    RenameHandleFailed(#[source] anyhow::Error)
+    RenameHandleFailed(#[source] anyhow::Error),
+    #[error("名義が並行して編集されました")]
+    RenamedConcurrently,
+    #[error("名義の変更履歴の記録に失敗しました")]
+    RecordRenameFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::LazyLock};
+
+    use thiserror::Error;
+
+    use crate::common::{fallible::Fallible, handle::{id::HandleId, name::HandleName, policy::{profanity::ProfanityFilter, HandleNamePolicy}}, profile::account_id::AccountId};
+
+    use super::{RenameHandle, RenameHandleError};
+
+    static POLICY: LazyLock<HandleNamePolicy> = LazyLock::new(|| HandleNamePolicy::new(ProfanityFilter::parse("badword")));
+    static ONYMOUS_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static ANONYMOUS_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    // 変更履歴の記録に失敗する名前
+    const UNRECORDABLE_HANDLE_NAME: &str = "さとう";
+
+    struct MockRenameHandle;
+
+    #[derive(Debug, Error)]
+    #[error("疑似エラー")]
+    struct MockError;
+
+    impl RenameHandle for MockRenameHandle {
+        fn handle_name_policy(&self) -> &HandleNamePolicy {
+            &POLICY
+        }
+
+        async fn fetch_handle_name(&self, _: AccountId, handle_id: HandleId) -> Fallible<Option<String>, RenameHandleError> {
+            if handle_id == *ONYMOUS_HANDLE_ID {
+                Ok(Some(String::from("すずき")))
+            } else if handle_id == *ANONYMOUS_HANDLE_ID {
+                Ok(Some(String::new()))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn rename_handle_if_unchanged(&self, _: AccountId, _: HandleId, previous_handle_name: &str, _: &HandleName) -> Fallible<(), RenameHandleError> {
+            assert_eq!(previous_handle_name, "すずき");
+            Ok(())
+        }
+
+        async fn record_rename(&self, _: HandleId, previous_handle_name: &str, new_handle_name: &HandleName) -> Fallible<(), RenameHandleError> {
+            assert_eq!(previous_handle_name, "すずき");
+
+            if new_handle_name.value() == UNRECORDABLE_HANDLE_NAME {
+                Err(RenameHandleError::RecordRenameFailed(MockError.into()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    async fn test_rename_handle_of(handle_id: HandleId, name: &str) -> Fallible<(), RenameHandleError> {
+        MockRenameHandle.rename_handle(AccountId::gen(), handle_id, HandleName::from_str(name).unwrap()).await
+    }
+
+    async fn test_rename_handle(name: &str) -> Fallible<(), RenameHandleError> {
+        test_rename_handle_of(*ONYMOUS_HANDLE_ID, name).await
+    }
+
+    #[tokio::test]
+    async fn rename_handle() {
+        assert!(test_rename_handle("たなか").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn record_rename_failed() {
+        assert!(test_rename_handle(UNRECORDABLE_HANDLE_NAME).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn naming_policy() {
+        assert!(matches!(test_rename_handle("運営").await, Err(RenameHandleError::ReservedHandleName)));
+        assert!(matches!(test_rename_handle("BADWORD").await, Err(RenameHandleError::ProfaneHandleName)));
+    }
+
+    #[tokio::test]
+    async fn anonymous_handle() {
+        assert!(matches!(test_rename_handle_of(*ANONYMOUS_HANDLE_ID, "たなか").await, Err(RenameHandleError::AnonymousHandle)));
+    }
+
+    #[tokio::test]
+    async fn handle_not_found() {
+        assert!(matches!(test_rename_handle_of(HandleId::gen(), "たなか").await, Err(RenameHandleError::HandleNotFound)));
+    }
 }
\ No newline at end of file
@@ -1,20 +1,22 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::patch, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::patch, Extension, Json, Router};
 use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::RenameHandle, interpreter::RenameHandleImpl};
+use super::{dsl::{RenameHandle, RenameHandleError}, interpreter::RenameHandleImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<RenameHandleImpl>> {
     let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
     .layer(rate_limiter(db.clone(), cache.clone(), "renhd", 30, 1, TimeUnit::HOURS).await?)
-    .layer(session_manager(db.clone(), cache).await?);
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "renhd", 30, 1, TimeUnit::HOURS).await?);
 
     let rename_handle = RenameHandleImpl::try_new(db).await?;
 
@@ -30,19 +32,33 @@ pub async fn handler(
     State(routine): State<Arc<RenameHandleImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>
-) -> StatusCode {
-    match routine.rename_handle_if_onymous(account_id, payload.handle_id, payload.new_handle_name).await {
-        Ok(_) => StatusCode::OK,
+) -> Result<StatusCode, RenameHandleError> {
+    match routine.rename_handle(account_id, payload.handle_id, payload.new_handle_name).await {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(e @ (RenameHandleError::ReservedHandleName | RenameHandleError::ProfaneHandleName | RenameHandleError::HandleNotFound | RenameHandleError::AnonymousHandle)) => Err(e),
         Err(e) => {
             error!(
                 error = %e,
                 "名義の編集に失敗しました"
             );
-            StatusCode::INTERNAL_SERVER_ERROR
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for RenameHandleError {
+    fn into_response(self) -> Response {
+        match self {
+            RenameHandleError::ReservedHandleName => ApiError::RESERVED_HANDLE_NAME,
+            RenameHandleError::ProfaneHandleName => ApiError::PROFANE_HANDLE_NAME,
+            RenameHandleError::HandleNotFound => ApiError::HANDLE_NOT_FOUND,
+            RenameHandleError::AnonymousHandle => ApiError::ANONYMOUS_HANDLE_RENAME,
+            RenameHandleError::RenamedConcurrently => ApiError::HANDLE_RENAME_CONFLICT,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Payload {
     handle_id: HandleId,
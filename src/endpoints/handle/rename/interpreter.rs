@@ -2,29 +2,59 @@ use std::sync::Arc;
 
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::{common::{fallible::Fallible, handle::{id::HandleId, name::HandleName}, profile::account_id::AccountId}, helper::{error::InitError, scylla::prepare}};
+use crate::{common::{fallible::Fallible, handle::{id::HandleId, name::HandleName, policy::HandleNamePolicy}, profile::account_id::AccountId, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::{prepare, Transactional}}};
 
 use super::dsl::{RenameHandle, RenameHandleError};
 
 pub struct RenameHandleImpl {
     db: Arc<Session>,
+    policy: HandleNamePolicy,
+    select_handle_name: Arc<PreparedStatement>,
     update_handle_name: Arc<PreparedStatement>,
+    insert_handle_name_history: Arc<PreparedStatement>,
 }
 
 impl RenameHandleImpl {
     pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
-        let update_handle_name = prepare(&db, "UPDATE handles SET handle_name = ? WHERE account_id = ? AND handle_id = ? IF handle_name != ''").await?;
+        let policy = HandleNamePolicy::from_env().map_err(|e| InitError::new(e.into()))?;
 
-        Ok(Self { db, update_handle_name })
+        let select_handle_name = prepare(&db, "SELECT handle_name FROM handles WHERE account_id = ? AND handle_id = ?").await?;
+
+        let update_handle_name = prepare(&db, "UPDATE handles SET handle_name = ? WHERE account_id = ? AND handle_id = ? IF handle_name = ?").await?;
+
+        let insert_handle_name_history = prepare(&db, "INSERT INTO handle_name_history (handle_id, renamed_at, previous_handle_name, new_handle_name) VALUES (?, ?, ?, ?)").await?;
+
+        Ok(Self { db, policy, select_handle_name, update_handle_name, insert_handle_name_history })
     }
 }
 
 impl RenameHandle for RenameHandleImpl {
-    async fn rename_handle_if_onymous(&self, account_id: AccountId, handle_id: HandleId, new_handle_name: HandleName) -> Fallible<(), RenameHandleError> {
+    fn handle_name_policy(&self) -> &HandleNamePolicy {
+        &self.policy
+    }
+
+    async fn fetch_handle_name(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<Option<String>, RenameHandleError> {
+        self.db
+            .execute_unpaged(&self.select_handle_name, (account_id, handle_id))
+            .await
+            .map_err(|e| RenameHandleError::FetchHandleNameFailed(e.into()))?
+            .maybe_first_row_typed::<(String, )>()
+            .map(|row| row.map(|(handle_name, )| handle_name))
+            .map_err(|e| RenameHandleError::FetchHandleNameFailed(e.into()))
+    }
+
+    async fn rename_handle_if_unchanged(&self, account_id: AccountId, handle_id: HandleId, previous_handle_name: &str, new_handle_name: &HandleName) -> Fallible<(), RenameHandleError> {
+        self.db
+            .execute_unpaged(&self.update_handle_name, (new_handle_name, account_id, handle_id, previous_handle_name))
+            .await
+            .applied(RenameHandleError::RenameHandleFailed, || RenameHandleError::RenamedConcurrently)
+    }
+
+    async fn record_rename(&self, handle_id: HandleId, previous_handle_name: &str, new_handle_name: &HandleName) -> Fallible<(), RenameHandleError> {
         self.db
-            .execute_unpaged(&self.update_handle_name, (new_handle_name, account_id, handle_id))
+            .execute_unpaged(&self.insert_handle_name_history, (handle_id, UnixtimeMillis::now(), previous_handle_name, new_handle_name))
             .await
             .map(|_| ())
-            .map_err(|e| RenameHandleError::RenameHandleFailed(e.into()))
+            .map_err(|e| RenameHandleError::RecordRenameFailed(e.into()))
     }
 }
\ No newline at end of file
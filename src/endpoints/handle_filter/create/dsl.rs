@@ -0,0 +1,109 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+// フィードの組み立て時に一度に読み込むため、リストごとの上限を設ける
+pub const MAX_HANDLE_FILTERS: u32 = 1000;
+
+pub(crate) trait AddHandleFilter {
+    async fn add_handle_filter(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), AddHandleFilterError> {
+        let owner = self.fetch_handle_owner(handle_id)
+            .await?
+            .ok_or(AddHandleFilterError::HandleNotFound)?;
+
+        if owner == account_id {
+            return Err(AddHandleFilterError::CannotFilterOwnHandle);
+        }
+
+        // 同時に追加された場合は上限をわずかに超え得る
+        if self.count_handle_filters(account_id).await? >= MAX_HANDLE_FILTERS {
+            return Err(AddHandleFilterError::HandleFilterLimitReached);
+        }
+
+        self.insert_handle_filter(account_id, handle_id).await
+    }
+
+    async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, AddHandleFilterError>;
+
+    async fn count_handle_filters(&self, account_id: AccountId) -> Fallible<u32, AddHandleFilterError>;
+
+    // 既に追加されている場合は追加日時を更新する
+    async fn insert_handle_filter(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), AddHandleFilterError>;
+}
+
+#[derive(Debug, Error)]
+pub enum AddHandleFilterError {
+    #[error("名義の所有者の取得に失敗しました")]
+    FetchHandleOwnerFailed(#[source] anyhow::Error),
+    #[error("名義が存在しません")]
+    HandleNotFound,
+    #[error("自分の名義はブロック・ミュートできません")]
+    CannotFilterOwnHandle,
+    #[error("ブロック・ミュートの数の確認に失敗しました")]
+    CountHandleFiltersFailed(#[source] anyhow::Error),
+    #[error("ブロック・ミュートの数が上限に達しています")]
+    HandleFilterLimitReached,
+    #[error("ブロック・ミュートの追加に失敗しました")]
+    InsertHandleFilterFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+    use super::{AddHandleFilter, AddHandleFilterError, MAX_HANDLE_FILTERS};
+
+    static ACCOUNT_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static OWN_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static OTHER_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    struct MockAddHandleFilter {
+        filter_count: u32,
+    }
+
+    impl AddHandleFilter for MockAddHandleFilter {
+        async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, AddHandleFilterError> {
+            if handle_id == *OWN_HANDLE_ID {
+                Ok(Some(*ACCOUNT_ID))
+            } else if handle_id == *OTHER_HANDLE_ID {
+                Ok(Some(AccountId::gen()))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn count_handle_filters(&self, _: AccountId) -> Fallible<u32, AddHandleFilterError> {
+            Ok(self.filter_count)
+        }
+
+        async fn insert_handle_filter(&self, _: AccountId, _: HandleId) -> Fallible<(), AddHandleFilterError> {
+            Ok(())
+        }
+    }
+
+    async fn test_add_handle_filter(handle_id: HandleId, filter_count: u32) -> Fallible<(), AddHandleFilterError> {
+        MockAddHandleFilter { filter_count }.add_handle_filter(*ACCOUNT_ID, handle_id).await
+    }
+
+    #[tokio::test]
+    async fn add_handle_filter() {
+        assert!(test_add_handle_filter(*OTHER_HANDLE_ID, MAX_HANDLE_FILTERS - 1).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn handle_not_found() {
+        assert!(matches!(test_add_handle_filter(HandleId::gen(), 0).await, Err(AddHandleFilterError::HandleNotFound)));
+    }
+
+    #[tokio::test]
+    async fn own_handle() {
+        assert!(matches!(test_add_handle_filter(*OWN_HANDLE_ID, 0).await, Err(AddHandleFilterError::CannotFilterOwnHandle)));
+    }
+
+    #[tokio::test]
+    async fn limit_reached() {
+        assert!(matches!(test_add_handle_filter(*OTHER_HANDLE_ID, MAX_HANDLE_FILTERS).await, Err(AddHandleFilterError::HandleFilterLimitReached)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
+use http::StatusCode;
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{handle::{filter::HandleFilterKind, id::HandleId}, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{AddHandleFilter, AddHandleFilterError}, interpreter::AddHandleFilterImpl};
+
+// `/blocks`と`/mutes`を同じ処理で提供する
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<AddHandleFilterImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "crhfl", 60, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "crhfl", 60, 15, TimeUnit::MINS).await?);
+
+    let mut router = Router::new();
+
+    for kind in HandleFilterKind::ALL {
+        let add_handle_filter = AddHandleFilterImpl::try_new(db.clone(), kind).await?;
+
+        router = router.merge(
+            Router::new()
+                .route(kind.path(), post(handler))
+                .with_state(Arc::new(add_handle_filter))
+        );
+    }
+
+    Ok(router.layer(services))
+}
+
+pub async fn handler(
+    State(routine): State<Arc<AddHandleFilterImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Json(payload): Json<Payload>
+) -> Result<StatusCode, AddHandleFilterError> {
+    match routine.add_handle_filter(account_id, payload.handle_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e @ (AddHandleFilterError::HandleNotFound | AddHandleFilterError::CannotFilterOwnHandle | AddHandleFilterError::HandleFilterLimitReached)) => Err(e),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                handle_id = %payload.handle_id,
+                kind = %routine.kind(),
+                "ブロック・ミュートの追加に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for AddHandleFilterError {
+    fn into_response(self) -> Response {
+        match self {
+            AddHandleFilterError::HandleNotFound => ApiError::HANDLE_NOT_FOUND,
+            AddHandleFilterError::CannotFilterOwnHandle => ApiError::CANNOT_FILTER_OWN_HANDLE,
+            AddHandleFilterError::HandleFilterLimitReached => ApiError::HANDLE_FILTER_LIMIT_REACHED,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Payload {
+    handle_id: HandleId,
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, handle::{filter::HandleFilterKind, id::HandleId}, profile::account_id::AccountId, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{AddHandleFilter, AddHandleFilterError};
+
+pub struct AddHandleFilterImpl {
+    db: Arc<Session>,
+    kind: HandleFilterKind,
+    select_handle_owner: Arc<PreparedStatement>,
+    count_handle_filters: Arc<PreparedStatement>,
+    insert_handle_filter: Arc<PreparedStatement>,
+}
+
+impl AddHandleFilterImpl {
+    pub async fn try_new(db: Arc<Session>, kind: HandleFilterKind) -> Result<Self, InitError<Self>> {
+        let select_handle_owner = prepare(&db, "SELECT account_id FROM handle_owners WHERE handle_id = ?").await?;
+
+        let count_handle_filters = prepare(&db, &format!("SELECT COUNT(*) FROM {} WHERE account_id = ?", kind.table())).await?;
+
+        let insert_handle_filter = prepare(&db, &format!("INSERT INTO {} (account_id, handle_id, added_at) VALUES (?, ?, ?)", kind.table())).await?;
+
+        Ok(Self { db, kind, select_handle_owner, count_handle_filters, insert_handle_filter })
+    }
+
+    pub fn kind(&self) -> HandleFilterKind {
+        self.kind
+    }
+}
+
+impl AddHandleFilter for AddHandleFilterImpl {
+    async fn fetch_handle_owner(&self, handle_id: HandleId) -> Fallible<Option<AccountId>, AddHandleFilterError> {
+        self.db
+            .execute_unpaged(&self.select_handle_owner, (handle_id, ))
+            .await
+            .map_err(|e| AddHandleFilterError::FetchHandleOwnerFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, )>()
+            .map(|row| row.map(|(account_id, )| account_id))
+            .map_err(|e| AddHandleFilterError::FetchHandleOwnerFailed(e.into()))
+    }
+
+    async fn count_handle_filters(&self, account_id: AccountId) -> Fallible<u32, AddHandleFilterError> {
+        self.db
+            .execute_unpaged(&self.count_handle_filters, (account_id, ))
+            .await
+            .map_err(|e| AddHandleFilterError::CountHandleFiltersFailed(e.into()))?
+            .first_row_typed::<(i64, )>()
+            .map(|(count, )| count as u32)
+            .map_err(|e| AddHandleFilterError::CountHandleFiltersFailed(e.into()))
+    }
+
+    async fn insert_handle_filter(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), AddHandleFilterError> {
+        self.db
+            .execute_unpaged(&self.insert_handle_filter, (account_id, handle_id, UnixtimeMillis::now()))
+            .await
+            .map(|_| ())
+            .map_err(|e| AddHandleFilterError::InsertHandleFilterFailed(e.into()))
+    }
+}
\ No newline at end of file
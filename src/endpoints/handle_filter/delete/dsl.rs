@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId};
+
+pub(crate) trait RemoveHandleFilter {
+    // 追加されていない場合は`HandleFilterNotFound`を返す
+    async fn remove_handle_filter(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), RemoveHandleFilterError>;
+}
+
+#[derive(Debug, Error)]
+pub enum RemoveHandleFilterError {
+    #[error("ブロック・ミュートされていません")]
+    HandleFilterNotFound,
+    #[error("ブロック・ミュートの解除に失敗しました")]
+    DeleteHandleFilterFailed(#[source] anyhow::Error),
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, handle::{filter::HandleFilterKind, id::HandleId}, profile::account_id::AccountId}, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+use super::dsl::{RemoveHandleFilter, RemoveHandleFilterError};
+
+pub struct RemoveHandleFilterImpl {
+    db: Arc<Session>,
+    kind: HandleFilterKind,
+    delete_handle_filter: Arc<PreparedStatement>,
+}
+
+impl RemoveHandleFilterImpl {
+    pub async fn try_new(db: Arc<Session>, kind: HandleFilterKind) -> Result<Self, InitError<Self>> {
+        let delete_handle_filter = prepare(&db, &format!("DELETE FROM {} WHERE account_id = ? AND handle_id = ? IF EXISTS", kind.table())).await?;
+
+        Ok(Self { db, kind, delete_handle_filter })
+    }
+
+    pub fn kind(&self) -> HandleFilterKind {
+        self.kind
+    }
+}
+
+impl RemoveHandleFilter for RemoveHandleFilterImpl {
+    async fn remove_handle_filter(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), RemoveHandleFilterError> {
+        self.db
+            .execute_unpaged(&self.delete_handle_filter, (account_id, handle_id))
+            .await
+            .applied(RemoveHandleFilterError::DeleteHandleFilterFailed, || RemoveHandleFilterError::HandleFilterNotFound)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use std::{collections::HashSet, sync::Arc};
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{handle::{filter::HandleFilterKind, id::HandleId}, post::summary::PostSummary, profile::account_id::AccountId}, helper::{error::InitError, scylla::prepare}};
+
+// フィードなどで共有する、ブロック・ミュートした名義の取得
+pub struct HiddenHandleFetcher {
+    db: Arc<Session>,
+    select_handle_filters: Vec<Arc<PreparedStatement>>,
+}
+
+impl HiddenHandleFetcher {
+    pub async fn try_new<T>(db: Arc<Session>) -> Result<Self, InitError<T>> {
+        let mut select_handle_filters = Vec::with_capacity(HandleFilterKind::ALL.len());
+
+        for kind in HandleFilterKind::ALL {
+            select_handle_filters.push(prepare(&db, &format!("SELECT handle_id FROM {} WHERE account_id = ?", kind.table())).await?);
+        }
+
+        Ok(Self { db, select_handle_filters })
+    }
+
+    // ブロックとミュートのどちらかに含まれる名義を返す
+    pub async fn fetch(&self, account_id: AccountId) -> anyhow::Result<HashSet<HandleId>> {
+        let mut handle_ids = HashSet::new();
+
+        for select_handle_filters in &self.select_handle_filters {
+            for row in self.db
+                .execute_unpaged(select_handle_filters, (account_id, ))
+                .await?
+                .rows_typed::<(HandleId, )>()?
+            {
+                let (handle_id, ) = row?;
+                handle_ids.insert(handle_id);
+            }
+        }
+
+        Ok(handle_ids)
+    }
+}
+
+// 非表示の名義による投稿を取り除く
+pub fn exclude_hidden_handles(posts: &mut Vec<PostSummary>, hidden_handle_ids: &HashSet<HandleId>) {
+    posts.retain(|post| !hidden_handle_ids.contains(&post.handle_id()));
+}
\ No newline at end of file
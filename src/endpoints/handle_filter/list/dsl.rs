@@ -0,0 +1,112 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+// 1ページあたりの名義数
+pub const HANDLE_FILTERS_PAGE_SIZE: usize = 50;
+
+pub(crate) trait ListHandleFilters {
+    async fn list_handle_filters(&self, account_id: AccountId, after: Option<HandleId>) -> Fallible<HandleFilterPage, ListHandleFiltersError> {
+        let handles = self.fetch_handle_filters(account_id, after, HANDLE_FILTERS_PAGE_SIZE).await?;
+
+        // 件数が上限に達していれば、最後の名義を次のカーソルとする
+        let next_cursor = match handles.last() {
+            Some(handle) if handles.len() >= HANDLE_FILTERS_PAGE_SIZE => Some(handle.id),
+            _ => None,
+        };
+
+        Ok(HandleFilterPage { handles, next_cursor })
+    }
+
+    // `after`より後の名義を`limit`件まで取得する
+    async fn fetch_handle_filters(&self, account_id: AccountId, after: Option<HandleId>, limit: usize) -> Fallible<Vec<FilteredHandle>, ListHandleFiltersError>;
+}
+
+#[derive(Debug, Serialize)]
+pub struct HandleFilterPage {
+    handles: Vec<FilteredHandle>,
+    next_cursor: Option<HandleId>,
+}
+
+impl HandleFilterPage {
+    pub fn handles(&self) -> &Vec<FilteredHandle> {
+        &self.handles
+    }
+
+    pub fn next_cursor(&self) -> Option<HandleId> {
+        self.next_cursor
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FilteredHandle {
+    id: HandleId,
+    added_at: UnixtimeMillis,
+}
+
+impl FilteredHandle {
+    pub fn new(id: HandleId, added_at: UnixtimeMillis) -> Self {
+        Self { id, added_at }
+    }
+
+    pub fn id(&self) -> HandleId {
+        self.id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ListHandleFiltersError {
+    #[error("ブロック・ミュートの一覧の取得に失敗しました")]
+    FetchHandleFiltersFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{fallible::Fallible, handle::id::HandleId, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+    use super::{FilteredHandle, ListHandleFilters, ListHandleFiltersError, HANDLE_FILTERS_PAGE_SIZE};
+
+    struct MockListHandleFilters {
+        handle_ids: Vec<HandleId>,
+    }
+
+    impl ListHandleFilters for MockListHandleFilters {
+        async fn fetch_handle_filters(&self, _: AccountId, after: Option<HandleId>, limit: usize) -> Fallible<Vec<FilteredHandle>, ListHandleFiltersError> {
+            Ok(self.handle_ids
+                .iter()
+                .filter(|id| after.is_none_or(|after| **id > after))
+                .take(limit)
+                .map(|id| FilteredHandle::new(*id, UnixtimeMillis::now()))
+                .collect())
+        }
+    }
+
+    fn mock(count: usize) -> MockListHandleFilters {
+        let mut handle_ids = (0..count).map(|_| HandleId::gen()).collect::<Vec<_>>();
+        handle_ids.sort_unstable();
+
+        MockListHandleFilters { handle_ids }
+    }
+
+    #[tokio::test]
+    async fn single_page() {
+        let page = mock(3).list_handle_filters(AccountId::gen(), None).await.unwrap();
+
+        assert_eq!(page.handles().len(), 3);
+        assert_eq!(page.next_cursor(), None);
+    }
+
+    #[tokio::test]
+    async fn paginate() {
+        let mock = mock(HANDLE_FILTERS_PAGE_SIZE + 1);
+
+        let first = mock.list_handle_filters(AccountId::gen(), None).await.unwrap();
+        assert_eq!(first.next_cursor(), Some(mock.handle_ids[HANDLE_FILTERS_PAGE_SIZE - 1]));
+
+        let second = mock.list_handle_filters(AccountId::gen(), first.next_cursor()).await.unwrap();
+        let handle_ids = second.handles().iter().map(FilteredHandle::id).collect::<Vec<_>>();
+        assert_eq!(handle_ids, mock.handle_ids[HANDLE_FILTERS_PAGE_SIZE..]);
+        assert_eq!(second.next_cursor(), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use axum::{extract::{Query, State}, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{handle::{filter::HandleFilterKind, id::HandleId}, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{HandleFilterPage, ListHandleFilters, ListHandleFiltersError}, interpreter::ListHandleFiltersImpl};
+
+// `/blocks`と`/mutes`を同じ処理で提供する
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ListHandleFiltersImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "lshfl", 90, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "lshfl", 90, 15, TimeUnit::MINS).await?);
+
+    let mut router = Router::new();
+
+    for kind in HandleFilterKind::ALL {
+        let list_handle_filters = ListHandleFiltersImpl::try_new(db.clone(), kind).await?;
+
+        router = router.merge(
+            Router::new()
+                .route(kind.path(), get(handler))
+                .with_state(Arc::new(list_handle_filters))
+        );
+    }
+
+    Ok(router.layer(services))
+}
+
+pub async fn handler(
+    State(routine): State<Arc<ListHandleFiltersImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Query(query): Query<ListQuery>
+) -> Result<Json<HandleFilterPage>, ListHandleFiltersError> {
+    match routine.list_handle_filters(account_id, query.after).await {
+        Ok(page) => Ok(Json(page)),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                kind = %routine.kind(),
+                "ブロック・ミュートの一覧の取得に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for ListHandleFiltersError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListQuery {
+    after: Option<HandleId>,
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, handle::{filter::HandleFilterKind, id::HandleId}, profile::account_id::AccountId, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{FilteredHandle, ListHandleFilters, ListHandleFiltersError};
+
+pub struct ListHandleFiltersImpl {
+    db: Arc<Session>,
+    kind: HandleFilterKind,
+    select_handle_filters: Arc<PreparedStatement>,
+    select_handle_filters_after: Arc<PreparedStatement>,
+}
+
+impl ListHandleFiltersImpl {
+    pub async fn try_new(db: Arc<Session>, kind: HandleFilterKind) -> Result<Self, InitError<Self>> {
+        let select_handle_filters = prepare(&db, &format!("SELECT handle_id, added_at FROM {} WHERE account_id = ? LIMIT ?", kind.table())).await?;
+
+        let select_handle_filters_after = prepare(&db, &format!("SELECT handle_id, added_at FROM {} WHERE account_id = ? AND handle_id > ? LIMIT ?", kind.table())).await?;
+
+        Ok(Self { db, kind, select_handle_filters, select_handle_filters_after })
+    }
+
+    pub fn kind(&self) -> HandleFilterKind {
+        self.kind
+    }
+}
+
+impl ListHandleFilters for ListHandleFiltersImpl {
+    async fn fetch_handle_filters(&self, account_id: AccountId, after: Option<HandleId>, limit: usize) -> Fallible<Vec<FilteredHandle>, ListHandleFiltersError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ListHandleFiltersError {
+            ListHandleFiltersError::FetchHandleFiltersFailed(e.into())
+        }
+
+        let limit = limit as i32;
+
+        let result = match after {
+            Some(after) => self.db.execute_unpaged(&self.select_handle_filters_after, (account_id, after, limit)).await,
+            None => self.db.execute_unpaged(&self.select_handle_filters, (account_id, limit)).await,
+        };
+
+        result.map_err(handle_error)?
+            .rows_typed::<(HandleId, UnixtimeMillis)>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(handle_id, added_at)| FilteredHandle::new(handle_id, added_at)).map_err(handle_error))
+            .collect()
+    }
+}
\ No newline at end of file
@@ -1,5 +1,11 @@
+pub mod admin;
 pub mod api_key;
 pub mod auth;
+pub mod follow;
 pub mod handle;
+pub mod handle_filter;
+pub mod moderation;
+pub mod post;
 pub mod profile;
-pub mod tag;
\ No newline at end of file
+pub mod tag;
+pub mod webhook;
\ No newline at end of file
@@ -0,0 +1,280 @@
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::common::{fallible::Fallible, moderation::{action::ModerationAction, report_id::ReportId, target::ReportTarget}, post::id::PostId, profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, tag_name::TagName}};
+
+pub(crate) trait Moderate {
+    async fn moderate(&self, account_id: AccountId, report_id: ReportId, request: ModerationRequest) -> Fallible<(), ModerateError> {
+        let (target, resolution) = self.fetch_report(report_id)
+            .await?
+            .ok_or(ModerateError::ReportNotFound)?;
+
+        if resolution.is_some() {
+            return Err(ModerateError::ReportAlreadyResolved);
+        }
+
+        let action = request.action();
+
+        // 複数のモデレーターが同時に同じ通報へ対応しないよう、対応の前に通報を確保する
+        if !self.claim_report(account_id, report_id, action).await? {
+            return Err(ModerateError::ReportAlreadyResolved);
+        }
+
+        // 対応できなかった場合は確保を解除し、改めて対応できるようにする
+        let previous_name = match self.apply(report_id, request, target).await {
+            Ok(previous_name) => previous_name,
+            Err(e) => {
+                if let Err(e) = self.release_report(account_id, report_id).await {
+                    warn!(report_id = %report_id, error = %e, "通報の確保の解除に失敗しました。");
+                }
+                return Err(e);
+            },
+        };
+
+        // キューから取り除くより先に記録し、取り除けなくても誰が何をしたかを追えるようにする
+        self.record_audit_log(account_id, report_id, action, target, previous_name).await?;
+        self.remove_from_queue(report_id).await
+    }
+
+    // 変更前のタグ名がある場合はそれを返す
+    async fn apply(&self, report_id: ReportId, request: ModerationRequest, target: ReportTarget) -> Fallible<Option<TagName>, ModerateError> {
+        // 対応の種類ごとに、適用できる通報対象が決まっている
+        match (request, target) {
+            (ModerationRequest::HidePost, ReportTarget::Post(post_id)) => {
+                if !self.hide_post(post_id).await? {
+                    return Err(ModerateError::TargetNotFound);
+                }
+                Ok(None)
+            },
+            // 投稿を通報された場合も、投稿者のアカウントを停止できる
+            (ModerationRequest::SuspendAccount, ReportTarget::Post(_) | ReportTarget::Handle(_)) => {
+                let owner = self.fetch_target_owner(target)
+                    .await?
+                    .ok_or(ModerateError::TargetNotFound)?;
+
+                self.suspend_account(owner, report_id).await?;
+                Ok(None)
+            },
+            (ModerationRequest::RenameTag { name }, ReportTarget::Tag(tag_id)) => {
+                let previous_name = self.rename_tag(tag_id, &name)
+                    .await?
+                    .ok_or(ModerateError::TargetNotFound)?;
+                Ok(Some(previous_name))
+            },
+            (ModerationRequest::Dismiss, _) => Ok(None),
+            _ => Err(ModerateError::ActionNotApplicable),
+        }
+    }
+
+    // 通報対象と、対応済みであればその種類を返す
+    async fn fetch_report(&self, report_id: ReportId) -> Fallible<Option<(ReportTarget, Option<ModerationAction>)>, ModerateError>;
+
+    // 投稿が存在しない場合は`false`を返す
+    async fn hide_post(&self, post_id: PostId) -> Fallible<bool, ModerateError>;
+
+    async fn fetch_target_owner(&self, target: ReportTarget) -> Fallible<Option<AccountId>, ModerateError>;
+
+    async fn suspend_account(&self, account_id: AccountId, report_id: ReportId) -> Fallible<(), ModerateError>;
+
+    // 変更前のタグ名を返す
+    async fn rename_tag(&self, tag_id: NonTopTagId, name: &TagName) -> Fallible<Option<TagName>, ModerateError>;
+
+    async fn record_audit_log(&self, account_id: AccountId, report_id: ReportId, action: ModerationAction, target: ReportTarget, previous_name: Option<TagName>) -> Fallible<(), ModerateError>;
+
+    // 既に対応済みの場合は`false`を返す
+    async fn claim_report(&self, account_id: AccountId, report_id: ReportId, action: ModerationAction) -> Fallible<bool, ModerateError>;
+
+    async fn release_report(&self, account_id: AccountId, report_id: ReportId) -> Fallible<(), ModerateError>;
+
+    async fn remove_from_queue(&self, report_id: ReportId) -> Fallible<(), ModerateError>;
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ModerationRequest {
+    HidePost,
+    SuspendAccount,
+    RenameTag { name: TagName },
+    Dismiss,
+}
+
+impl ModerationRequest {
+    pub fn action(&self) -> ModerationAction {
+        match self {
+            ModerationRequest::HidePost => ModerationAction::HidePost,
+            ModerationRequest::SuspendAccount => ModerationAction::SuspendAccount,
+            ModerationRequest::RenameTag { .. } => ModerationAction::RenameTag,
+            ModerationRequest::Dismiss => ModerationAction::Dismiss,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ModerateError {
+    #[error("通報の取得に失敗しました")]
+    FetchReportFailed(#[source] anyhow::Error),
+    #[error("通報が存在しません")]
+    ReportNotFound,
+    #[error("既に対応済みの通報です")]
+    ReportAlreadyResolved,
+    #[error("通報対象に適用できない対応です")]
+    ActionNotApplicable,
+    #[error("通報対象が存在しません")]
+    TargetNotFound,
+    #[error("投稿の非表示に失敗しました")]
+    HidePostFailed(#[source] anyhow::Error),
+    #[error("通報対象の所有者の取得に失敗しました")]
+    FetchTargetOwnerFailed(#[source] anyhow::Error),
+    #[error("アカウントの停止に失敗しました")]
+    SuspendAccountFailed(#[source] anyhow::Error),
+    #[error("タグ名の変更に失敗しました")]
+    RenameTagFailed(#[source] anyhow::Error),
+    #[error("監査ログの記録に失敗しました")]
+    RecordAuditLogFailed(#[source] anyhow::Error),
+    #[error("通報の確保に失敗しました")]
+    ClaimReportFailed(#[source] anyhow::Error),
+    #[error("通報の確保の解除に失敗しました")]
+    ReleaseReportFailed(#[source] anyhow::Error),
+    #[error("キューからの通報の削除に失敗しました")]
+    RemoveFromQueueFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr, sync::{LazyLock, Mutex}};
+
+    use crate::{common::{fallible::Fallible, handle::id::HandleId, moderation::{action::ModerationAction, report_id::ReportId, target::ReportTarget}, post::id::PostId, profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, tag_name::TagName}}, helper::test::mock_non_top_tag_id};
+
+    use super::{Moderate, ModerateError, ModerationRequest};
+
+    static MODERATOR: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static OWNER: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static POST: LazyLock<PostId> = LazyLock::new(PostId::gen);
+    static HANDLE: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static TAG: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+
+    struct MockModerate {
+        reports: Mutex<HashMap<ReportId, (ReportTarget, Option<ModerationAction>)>>,
+        hidden: Mutex<Vec<PostId>>,
+        suspended: Mutex<Vec<AccountId>>,
+        audit_log: Mutex<Vec<(ModerationAction, Option<String>)>>,
+    }
+
+    impl MockModerate {
+        fn new(target: ReportTarget) -> (Self, ReportId) {
+            let report_id = ReportId::gen();
+            let mock = Self {
+                reports: Mutex::new(HashMap::from([(report_id, (target, None))])),
+                hidden: Mutex::new(Vec::new()),
+                suspended: Mutex::new(Vec::new()),
+                audit_log: Mutex::new(Vec::new()),
+            };
+            (mock, report_id)
+        }
+    }
+
+    impl Moderate for MockModerate {
+        async fn fetch_report(&self, report_id: ReportId) -> Fallible<Option<(ReportTarget, Option<ModerationAction>)>, ModerateError> {
+            Ok(self.reports.lock().unwrap().get(&report_id).copied())
+        }
+
+        async fn hide_post(&self, post_id: PostId) -> Fallible<bool, ModerateError> {
+            self.hidden.lock().unwrap().push(post_id);
+            Ok(post_id == *POST)
+        }
+
+        async fn fetch_target_owner(&self, target: ReportTarget) -> Fallible<Option<AccountId>, ModerateError> {
+            Ok((target == ReportTarget::Post(*POST) || target == ReportTarget::Handle(*HANDLE)).then_some(*OWNER))
+        }
+
+        async fn suspend_account(&self, account_id: AccountId, _: ReportId) -> Fallible<(), ModerateError> {
+            self.suspended.lock().unwrap().push(account_id);
+            Ok(())
+        }
+
+        async fn rename_tag(&self, tag_id: NonTopTagId, _: &TagName) -> Fallible<Option<TagName>, ModerateError> {
+            Ok((tag_id == *TAG).then(|| TagName::from_str("旧タグ名").unwrap()))
+        }
+
+        async fn record_audit_log(&self, _: AccountId, _: ReportId, action: ModerationAction, _: ReportTarget, previous_name: Option<TagName>) -> Fallible<(), ModerateError> {
+            self.audit_log.lock().unwrap().push((action, previous_name.map(|name| name.value().clone())));
+            Ok(())
+        }
+
+        async fn claim_report(&self, _: AccountId, report_id: ReportId, action: ModerationAction) -> Fallible<bool, ModerateError> {
+            match self.reports.lock().unwrap().get_mut(&report_id) {
+                Some((_, resolution @ None)) => {
+                    *resolution = Some(action);
+                    Ok(true)
+                },
+                _ => Ok(false),
+            }
+        }
+
+        async fn release_report(&self, _: AccountId, report_id: ReportId) -> Fallible<(), ModerateError> {
+            if let Some((_, resolution)) = self.reports.lock().unwrap().get_mut(&report_id) {
+                *resolution = None;
+            }
+            Ok(())
+        }
+
+        async fn remove_from_queue(&self, _: ReportId) -> Fallible<(), ModerateError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn hide_post() {
+        let (mock, report_id) = MockModerate::new(ReportTarget::Post(*POST));
+        mock.moderate(*MODERATOR, report_id, ModerationRequest::HidePost).await.unwrap();
+        assert_eq!(*mock.hidden.lock().unwrap(), vec![*POST]);
+        assert_eq!(*mock.audit_log.lock().unwrap(), vec![(ModerationAction::HidePost, None)]);
+
+        // 対応済みの通報には再度対応できない
+        let result = mock.moderate(*MODERATOR, report_id, ModerationRequest::Dismiss).await;
+        assert!(matches!(result.err(), Some(ModerateError::ReportAlreadyResolved)));
+    }
+
+    #[tokio::test]
+    async fn suspend_post_author() {
+        let (mock, report_id) = MockModerate::new(ReportTarget::Post(*POST));
+        mock.moderate(*MODERATOR, report_id, ModerationRequest::SuspendAccount).await.unwrap();
+        assert_eq!(*mock.suspended.lock().unwrap(), vec![*OWNER]);
+    }
+
+    #[tokio::test]
+    async fn rename_tag() {
+        let (mock, report_id) = MockModerate::new(ReportTarget::Tag(*TAG));
+        let request = ModerationRequest::RenameTag { name: TagName::from_str("新タグ名").unwrap() };
+        mock.moderate(*MODERATOR, report_id, request).await.unwrap();
+        assert_eq!(*mock.audit_log.lock().unwrap(), vec![(ModerationAction::RenameTag, Some(String::from("旧タグ名")))]);
+    }
+
+    #[tokio::test]
+    async fn action_not_applicable() {
+        let (mock, report_id) = MockModerate::new(ReportTarget::Tag(*TAG));
+        let result = mock.moderate(*MODERATOR, report_id, ModerationRequest::SuspendAccount).await;
+        assert!(matches!(result.err(), Some(ModerateError::ActionNotApplicable)));
+        assert!(mock.suspended.lock().unwrap().is_empty());
+        assert!(mock.audit_log.lock().unwrap().is_empty());
+
+        // 確保は解除され、改めて対応できる
+        assert!(mock.moderate(*MODERATOR, report_id, ModerationRequest::Dismiss).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn target_not_found() {
+        let (mock, report_id) = MockModerate::new(ReportTarget::Handle(HandleId::gen()));
+        let result = mock.moderate(*MODERATOR, report_id, ModerationRequest::SuspendAccount).await;
+        assert!(matches!(result.err(), Some(ModerateError::TargetNotFound)));
+        assert_eq!(mock.reports.lock().unwrap()[&report_id].1, None);
+    }
+
+    #[tokio::test]
+    async fn report_not_found() {
+        let (mock, _) = MockModerate::new(ReportTarget::Post(*POST));
+        let result = mock.moderate(*MODERATOR, ReportId::gen(), ModerationRequest::Dismiss).await;
+        assert!(matches!(result.err(), Some(ModerateError::ReportNotFound)));
+    }
+}
\ No newline at end of file
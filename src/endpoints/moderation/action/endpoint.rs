@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
+use elasticsearch::Elasticsearch;
+use http::StatusCode;
+use scylla::Session;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{moderation::report_id::ReportId, profile::{account_id::AccountId, role::Role}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, role_requirer, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{Moderate, ModerateError, ModerationRequest}, interpreter::ModerateImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, client: Arc<Elasticsearch>) -> Result<Router, InitError<ModerateImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "mdrpt", 300, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "mdrpt", 300, 15, TimeUnit::MINS).await?)
+        .layer(role_requirer(db.clone(), cache.clone(), Role::Moderator).await?);
+
+    let interpreter = ModerateImpl::try_new(db, cache, client).await?;
+
+    let router = Router::new()
+        .route("/moderation/reports/:report_id/actions", post(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<ModerateImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Path(report_id): Path<ReportId>,
+    Json(request): Json<ModerationRequest>
+) -> Result<StatusCode, ModerateError> {
+    let action = request.action();
+
+    match routine.moderate(account_id, report_id, request).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e @ (ModerateError::ReportNotFound | ModerateError::ReportAlreadyResolved | ModerateError::ActionNotApplicable | ModerateError::TargetNotFound)) => Err(e),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                report_id = %report_id,
+                action = %action,
+                "通報への対応に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for ModerateError {
+    fn into_response(self) -> Response {
+        match self {
+            ModerateError::ReportNotFound => ApiError::REPORT_NOT_FOUND,
+            ModerateError::ReportAlreadyResolved => ApiError::REPORT_ALREADY_RESOLVED,
+            ModerateError::ActionNotApplicable => ApiError::MODERATION_ACTION_NOT_APPLICABLE,
+            ModerateError::TargetNotFound => ApiError::REPORT_TARGET_NOT_FOUND,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
\ No newline at end of file
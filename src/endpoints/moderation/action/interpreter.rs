@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use elasticsearch::{Elasticsearch, UpdateByQueryParts};
+use redis::{cmd, Script};
+use scylla::{prepared_statement::PreparedStatement, Session};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{common::{fallible::Fallible, moderation::{action::{AuditLogId, ModerationAction}, report_id::ReportId, target::{ReportTarget, ReportTargetKind}}, post::id::PostId, profile::account_id::AccountId, session::session_series::SessionSeries, tag::{hierarchy::TagHierarchy, non_top_tag::NonTopTagId, tag_id::TagId, tag_name::TagName}, unixtime::UnixtimeMillis}, endpoints::moderation::queue::interpreter::MODERATION_QUEUE_PARTITION, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}, namespaces::{EQUIVALENT, SUB, SUPER, TAG_LIST}}, scylla::{prepare, Transactional}}, middlewares::session::RefreshPairKey};
+
+use super::dsl::{Moderate, ModerateError};
+
+// 関連タグの階層と、関連タグから見たこのタグの階層およびタグリストの名前空間
+const INVERSE_HIERARCHIES: [(TagHierarchy, TagHierarchy, Namespace); 3] = [
+    (TagHierarchy::Super, TagHierarchy::Sub, SUB),
+    (TagHierarchy::Equivalent, TagHierarchy::Equivalent, EQUIVALENT),
+    (TagHierarchy::Sub, TagHierarchy::Super, SUPER),
+];
+
+pub struct ModerateImpl {
+    db: Arc<Session>,
+    cache: Arc<Pool>,
+    client: Arc<Elasticsearch>,
+    select_report: Arc<PreparedStatement>,
+    hide_post: Arc<PreparedStatement>,
+    select_post_owner: Arc<PreparedStatement>,
+    select_handle_owner: Arc<PreparedStatement>,
+    insert_suspension: Arc<PreparedStatement>,
+    select_all_session_series: Arc<PreparedStatement>,
+    delete_all_session_series: Arc<PreparedStatement>,
+    select_tag_name: Arc<PreparedStatement>,
+    update_tag_name: Arc<PreparedStatement>,
+    select_related_tags: Arc<PreparedStatement>,
+    update_related_tag_name: Arc<PreparedStatement>,
+    rename_tag_list_member: Arc<Script>,
+    insert_audit_log: Arc<PreparedStatement>,
+    claim_report: Arc<PreparedStatement>,
+    release_report: Arc<PreparedStatement>,
+    delete_from_queue: Arc<PreparedStatement>,
+}
+
+impl ModerateImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, client: Arc<Elasticsearch>) -> Result<Self, InitError<Self>> {
+        let select_report = prepare(&db, "SELECT target_type, target_id, resolution FROM reports WHERE id = ?").await?;
+
+        let hide_post = prepare(&db, "UPDATE posts SET hidden = true WHERE id = ? IF EXISTS").await?;
+
+        let select_post_owner = prepare(&db, "SELECT account_id FROM posts WHERE id = ?").await?;
+
+        let select_handle_owner = prepare(&db, "SELECT account_id FROM handle_owners WHERE handle_id = ?").await?;
+
+        let insert_suspension = prepare(&db, "INSERT INTO suspended_accounts (account_id, report_id, suspended_at) VALUES (?, ?, ?)").await?;
+
+        let select_all_session_series = prepare(&db, "SELECT series FROM session_series WHERE account_id = ?").await?;
+
+        let delete_all_session_series = prepare(&db, "DELETE FROM session_series WHERE account_id = ?").await?;
+
+        let select_tag_name = prepare(&db, "SELECT name FROM tags WHERE id = ?").await?;
+
+        let update_tag_name = prepare(&db, "UPDATE tags SET name = ? WHERE id = ?").await?;
+
+        let select_related_tags = prepare(&db, "SELECT related_tag_id FROM hierarchical_tag_lists WHERE tag_id = ? AND hierarchy = ?").await?;
+
+        let update_related_tag_name = prepare(&db, "UPDATE hierarchical_tag_lists SET related_tag_name = ? WHERE tag_id = ? AND hierarchy = ? AND related_tag_id = ?").await?;
+
+        let rename_tag_list_member = Arc::new(Script::new(include_str!("rename_tag_list_member.lua")));
+
+        // 対象ごとに対応の履歴を辿れるよう、対象をパーティションとする
+        let insert_audit_log = prepare(&db, "INSERT INTO moderation_audit_log (target_type, target_id, id, moderator_id, report_id, action, previous_name) VALUES (?, ?, ?, ?, ?, ?, ?)").await?;
+
+        let claim_report = prepare(&db, "UPDATE reports SET resolution = ?, resolved_by = ?, resolved_at = ? WHERE id = ? IF resolution = null").await?;
+
+        // 他のモデレーターが確保し直した通報は解除しない
+        let release_report = prepare(&db, "UPDATE reports SET resolution = null, resolved_by = null, resolved_at = null WHERE id = ? IF resolved_by = ?").await?;
+
+        let delete_from_queue = prepare(&db, "DELETE FROM moderation_queue WHERE partition = ? AND report_id = ?").await?;
+
+        Ok(Self {
+            db,
+            cache,
+            client,
+            select_report,
+            hide_post,
+            select_post_owner,
+            select_handle_owner,
+            insert_suspension,
+            select_all_session_series,
+            delete_all_session_series,
+            select_tag_name,
+            update_tag_name,
+            select_related_tags,
+            update_related_tag_name,
+            rename_tag_list_member,
+            insert_audit_log,
+            claim_report,
+            release_report,
+            delete_from_queue,
+        })
+    }
+}
+
+impl Moderate for ModerateImpl {
+    async fn fetch_report(&self, report_id: ReportId) -> Fallible<Option<(ReportTarget, Option<ModerationAction>)>, ModerateError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ModerateError {
+            ModerateError::FetchReportFailed(e.into())
+        }
+
+        let row = self.db
+            .execute_unpaged(&self.select_report, (report_id, ))
+            .await
+            .map_err(handle_error)?
+            .maybe_first_row_typed::<(ReportTargetKind, Uuid, Option<i8>)>()
+            .map_err(handle_error)?;
+
+        let (kind, id, resolution) = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        // 未対応の通報はresolutionがnullになる
+        let resolution = resolution
+            .map(ModerationAction::try_from)
+            .transpose()
+            .map_err(handle_error)?;
+
+        ReportTarget::of(kind, id)
+            .map(|target| Some((target, resolution)))
+            .map_err(handle_error)
+    }
+
+    async fn hide_post(&self, post_id: PostId) -> Fallible<bool, ModerateError> {
+        let res = self.db
+            .execute_unpaged(&self.hide_post, (post_id, ))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) => Ok(true),
+            Err(Some(e)) => Err(ModerateError::HidePostFailed(e)),
+            Err(None) => Ok(false),
+        }
+    }
+
+    async fn fetch_target_owner(&self, target: ReportTarget) -> Fallible<Option<AccountId>, ModerateError> {
+        let statement = match target {
+            ReportTarget::Post(_) => &self.select_post_owner,
+            ReportTarget::Handle(_) => &self.select_handle_owner,
+            ReportTarget::Tag(_) => return Ok(None),
+        };
+
+        self.db
+            .execute_unpaged(statement, (target.id(), ))
+            .await
+            .map_err(|e| ModerateError::FetchTargetOwnerFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, )>()
+            .map(|row| row.map(|(account_id, )| account_id))
+            .map_err(|e| ModerateError::FetchTargetOwnerFailed(e.into()))
+    }
+
+    async fn suspend_account(&self, account_id: AccountId, report_id: ReportId) -> Fallible<(), ModerateError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ModerateError {
+            ModerateError::SuspendAccountFailed(e.into())
+        }
+
+        self.db
+            .execute_unpaged(&self.insert_suspension, (account_id, report_id, UnixtimeMillis::now()))
+            .await
+            .map_err(handle_error)?;
+
+        // リフレッシュトークンを全て無効にし、現在のセッションの期限が切れた後は再認証できないようにする
+        let refresh_pair_keys = self.db
+            .execute_unpaged(&self.select_all_session_series, (account_id, ))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(SessionSeries, )>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(session_series, )| RefreshPairKey::new(&session_series)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)?;
+
+        if !refresh_pair_keys.is_empty() {
+            let mut conn = conn(&self.cache, handle_error).await?;
+
+            cmd("DEL")
+                .arg(refresh_pair_keys.as_slice())
+                .query_async::<()>(&mut *conn)
+                .await
+                .map_err(handle_error)?;
+        }
+
+        self.db
+            .execute_unpaged(&self.delete_all_session_series, (account_id, ))
+            .await
+            .map(|_| ())
+            .map_err(handle_error)
+    }
+
+    async fn rename_tag(&self, tag_id: NonTopTagId, name: &TagName) -> Fallible<Option<TagName>, ModerateError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ModerateError {
+            ModerateError::RenameTagFailed(e.into())
+        }
+
+        let previous_name = match self.db
+            .execute_unpaged(&self.select_tag_name, (tag_id, ))
+            .await
+            .map_err(handle_error)?
+            .maybe_first_row_typed::<(TagName, )>()
+            .map_err(handle_error)?
+        {
+            Some((previous_name, )) => previous_name,
+            None => return Ok(None),
+        };
+
+        // タグ名は関連タグの一覧にも複製されているため、全て書き換える
+        // タグ本体は最後に更新し、途中で失敗しても変更前の名前から再試行できるようにする
+        let previous_member = format!("{}${}", tag_id, previous_name);
+        let member = format!("{}${}", tag_id, name);
+
+        let mut conn = conn(&self.cache, handle_error).await?;
+
+        for (hierarchy, inverse_hierarchy, namespace) in INVERSE_HIERARCHIES {
+            let related_tag_ids = self.db
+                .execute_unpaged(&self.select_related_tags, (tag_id, hierarchy))
+                .await
+                .map_err(handle_error)?
+                .rows_typed::<(TagId, )>()
+                .map_err(handle_error)?
+                .map(|row| row.map(|(related_tag_id, )| related_tag_id))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(handle_error)?;
+
+            for related_tag_id in related_tag_ids {
+                self.db
+                    .execute_unpaged(&self.update_related_tag_name, (name, related_tag_id, inverse_hierarchy, tag_id))
+                    .await
+                    .map_err(handle_error)?;
+
+                self.rename_tag_list_member
+                    .key(format!("{}{}{}{}{}", TAG_LIST, NAMESPACE_SEPARATOR, related_tag_id, NAMESPACE_SEPARATOR, namespace))
+                    .arg(&previous_member)
+                    .arg(&member)
+                    .invoke_async::<()>(&mut *conn)
+                    .await
+                    .map_err(handle_error)?;
+            }
+        }
+
+        self.client
+            .update_by_query(UpdateByQueryParts::Index(&["tags"]))
+            .body(json!({
+                "query": {
+                    "term": {
+                        "id": tag_id.to_string()
+                    }
+                },
+                "script": {
+                    "source": "ctx._source.name = params.name",
+                    "params": {
+                        "name": name
+                    }
+                }
+            }))
+            .send()
+            .await
+            .map_err(handle_error)?
+            .error_for_status_code()
+            .map_err(handle_error)?;
+
+        self.db
+            .execute_unpaged(&self.update_tag_name, (name, tag_id))
+            .await
+            .map_err(handle_error)?;
+
+        Ok(Some(previous_name))
+    }
+
+    async fn record_audit_log(&self, account_id: AccountId, report_id: ReportId, action: ModerationAction, target: ReportTarget, previous_name: Option<TagName>) -> Fallible<(), ModerateError> {
+        self.db
+            .execute_unpaged(&self.insert_audit_log, (target.kind(), target.id(), AuditLogId::gen(), account_id, report_id, action, previous_name))
+            .await
+            .map(|_| ())
+            .map_err(|e| ModerateError::RecordAuditLogFailed(e.into()))
+    }
+
+    async fn claim_report(&self, account_id: AccountId, report_id: ReportId, action: ModerationAction) -> Fallible<bool, ModerateError> {
+        let res = self.db
+            .execute_unpaged(&self.claim_report, (action, account_id, UnixtimeMillis::now(), report_id))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) => Ok(true),
+            Err(Some(e)) => Err(ModerateError::ClaimReportFailed(e)),
+            Err(None) => Ok(false),
+        }
+    }
+
+    async fn release_report(&self, account_id: AccountId, report_id: ReportId) -> Fallible<(), ModerateError> {
+        let res = self.db
+            .execute_unpaged(&self.release_report, (report_id, account_id))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) | Err(None) => Ok(()),
+            Err(Some(e)) => Err(ModerateError::ReleaseReportFailed(e)),
+        }
+    }
+
+    async fn remove_from_queue(&self, report_id: ReportId) -> Fallible<(), ModerateError> {
+        self.db
+            .execute_unpaged(&self.delete_from_queue, (MODERATION_QUEUE_PARTITION, report_id))
+            .await
+            .map(|_| ())
+            .map_err(|e| ModerateError::RemoveFromQueueFailed(e.into()))
+    }
+}
\ No newline at end of file
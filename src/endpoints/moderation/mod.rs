@@ -0,0 +1,3 @@
+pub mod action;
+pub mod queue;
+pub mod report;
\ No newline at end of file
@@ -0,0 +1,99 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, moderation::{reason::ReportReason, report_id::ReportId, target::ReportTarget}};
+
+// 1ページあたりの通報数
+pub const REPORTS_PAGE_SIZE: usize = 50;
+
+pub(crate) trait ListQueuedReports {
+    async fn list_queued_reports(&self, after: Option<ReportId>) -> Fallible<QueuedReportPage, ListQueuedReportsError> {
+        // 古い通報から順に対応できるよう、通報順に返す
+        let reports = self.fetch_queued_reports(after, REPORTS_PAGE_SIZE).await?;
+
+        let next_cursor = match reports.last() {
+            Some(report) if reports.len() >= REPORTS_PAGE_SIZE => Some(report.report_id),
+            _ => None,
+        };
+
+        Ok(QueuedReportPage { reports, next_cursor })
+    }
+
+    // `after`より後の未対応の通報を`limit`件まで取得する
+    async fn fetch_queued_reports(&self, after: Option<ReportId>, limit: usize) -> Fallible<Vec<QueuedReport>, ListQueuedReportsError>;
+}
+
+#[derive(Debug, Error)]
+pub enum ListQueuedReportsError {
+    #[error("未対応の通報の取得に失敗しました")]
+    FetchQueuedReportsFailed(#[source] anyhow::Error),
+}
+
+#[derive(Debug, Serialize)]
+pub struct QueuedReportPage {
+    reports: Vec<QueuedReport>,
+    next_cursor: Option<ReportId>,
+}
+
+impl QueuedReportPage {
+    pub fn reports(&self) -> &Vec<QueuedReport> {
+        &self.reports
+    }
+
+    pub fn next_cursor(&self) -> Option<ReportId> {
+        self.next_cursor
+    }
+}
+
+// 通報者はモデレーターにも明かさない
+#[derive(Debug, Serialize)]
+pub struct QueuedReport {
+    report_id: ReportId,
+    target: ReportTarget,
+    reason: ReportReason,
+}
+
+impl QueuedReport {
+    pub fn new(report_id: ReportId, target: ReportTarget, reason: ReportReason) -> Self {
+        Self { report_id, target, reason }
+    }
+
+    pub fn report_id(&self) -> ReportId {
+        self.report_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::common::{fallible::Fallible, handle::id::HandleId, moderation::{reason::ReportReason, report_id::ReportId, target::ReportTarget}};
+
+    use super::{ListQueuedReports, ListQueuedReportsError, QueuedReport, REPORTS_PAGE_SIZE};
+
+    static REPORT_IDS: LazyLock<Vec<ReportId>> = LazyLock::new(|| (0..REPORTS_PAGE_SIZE + 10).map(|_| ReportId::gen()).collect());
+
+    struct MockListQueuedReports;
+
+    impl ListQueuedReports for MockListQueuedReports {
+        async fn fetch_queued_reports(&self, after: Option<ReportId>, limit: usize) -> Fallible<Vec<QueuedReport>, ListQueuedReportsError> {
+            Ok(REPORT_IDS.iter()
+                .filter(|report_id| after.is_none_or(|after| **report_id > after))
+                .take(limit)
+                .map(|report_id| QueuedReport::new(*report_id, ReportTarget::Handle(HandleId::gen()), ReportReason::Spam))
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn paginate() {
+        let first = MockListQueuedReports.list_queued_reports(None).await.unwrap();
+        assert_eq!(first.reports().len(), REPORTS_PAGE_SIZE);
+        assert_eq!(first.next_cursor(), Some(REPORT_IDS[REPORTS_PAGE_SIZE - 1]));
+
+        let second = MockListQueuedReports.list_queued_reports(first.next_cursor()).await.unwrap();
+        assert_eq!(second.reports().len(), 10);
+        assert_eq!(second.reports()[0].report_id(), REPORT_IDS[REPORTS_PAGE_SIZE]);
+        assert_eq!(second.next_cursor(), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use axum::{extract::{Query, State}, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{moderation::report_id::ReportId, profile::{account_id::AccountId, role::Role}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, role_requirer, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{ListQueuedReports, ListQueuedReportsError, QueuedReportPage}, interpreter::ListQueuedReportsImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ListQueuedReportsImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "lsrpt", 300, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "lsrpt", 300, 15, TimeUnit::MINS).await?)
+        .layer(role_requirer(db.clone(), cache, Role::Moderator).await?);
+
+    let interpreter = ListQueuedReportsImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/moderation/reports", get(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<ListQueuedReportsImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Query(query): Query<ListQuery>
+) -> Result<Json<QueuedReportPage>, ListQueuedReportsError> {
+    match routine.list_queued_reports(query.after).await {
+        Ok(page) => Ok(Json(page)),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                "未対応の通報の一覧の取得に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for ListQueuedReportsError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListQuery {
+    after: Option<ReportId>,
+}
\ No newline at end of file
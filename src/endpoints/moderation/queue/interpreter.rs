@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+use uuid::Uuid;
+
+use crate::{common::{fallible::Fallible, moderation::{reason::ReportReason, report_id::ReportId, target::{ReportTarget, ReportTargetKind}}}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{ListQueuedReports, ListQueuedReportsError, QueuedReport};
+
+// 未対応の通報は件数が限られるため、単一のパーティションにUUIDv7の昇順で並べる
+pub const MODERATION_QUEUE_PARTITION: i8 = 0;
+
+pub struct ListQueuedReportsImpl {
+    db: Arc<Session>,
+    select_queued_reports: Arc<PreparedStatement>,
+    select_queued_reports_after: Arc<PreparedStatement>,
+}
+
+impl ListQueuedReportsImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_queued_reports = prepare(&db, "SELECT report_id, target_type, target_id, reason FROM moderation_queue WHERE partition = ? LIMIT ?").await?;
+
+        let select_queued_reports_after = prepare(&db, "SELECT report_id, target_type, target_id, reason FROM moderation_queue WHERE partition = ? AND report_id > ? LIMIT ?").await?;
+
+        Ok(Self { db, select_queued_reports, select_queued_reports_after })
+    }
+}
+
+impl ListQueuedReports for ListQueuedReportsImpl {
+    async fn fetch_queued_reports(&self, after: Option<ReportId>, limit: usize) -> Fallible<Vec<QueuedReport>, ListQueuedReportsError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ListQueuedReportsError {
+            ListQueuedReportsError::FetchQueuedReportsFailed(e.into())
+        }
+
+        let limit = limit as i32;
+
+        let result = match after {
+            Some(after) => self.db.execute_unpaged(&self.select_queued_reports_after, (MODERATION_QUEUE_PARTITION, after, limit)).await,
+            None => self.db.execute_unpaged(&self.select_queued_reports, (MODERATION_QUEUE_PARTITION, limit)).await,
+        };
+
+        result.map_err(handle_error)?
+            .rows_typed::<(ReportId, ReportTargetKind, Uuid, ReportReason)>()
+            .map_err(handle_error)?
+            .map(|row| {
+                let (report_id, kind, id, reason) = row.map_err(handle_error)?;
+                ReportTarget::of(kind, id)
+                    .map(|target| QueuedReport::new(report_id, target, reason))
+                    .map_err(handle_error)
+            })
+            .collect()
+    }
+}
\ No newline at end of file
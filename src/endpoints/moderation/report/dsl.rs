@@ -0,0 +1,145 @@
+use thiserror::Error;
+use tracing::warn;
+
+use crate::common::{fallible::Fallible, moderation::{reason::ReportReason, report_id::ReportId, target::ReportTarget}, profile::account_id::AccountId};
+
+pub(crate) trait Report {
+    async fn report(&self, account_id: AccountId, target: ReportTarget, reason: ReportReason) -> Fallible<ReportId, ReportError> {
+        if !self.target_exists(target).await? {
+            return Err(ReportError::TargetNotFound);
+        }
+
+        // 同じ対象を何度も通報してキューを埋められないよう、通報者と対象の組で重複を防ぐ
+        let report_id = ReportId::gen();
+
+        if !self.claim_report(account_id, target, report_id).await? {
+            return Err(ReportError::AlreadyReported);
+        }
+
+        // 登録に失敗した場合は重複の記録を取り消し、再度通報できるようにする
+        if let Err(e) = self.insert_report(report_id, account_id, target, reason).await {
+            if let Err(e) = self.release_report(account_id, target, report_id).await {
+                warn!(report_id = %report_id, error = %e, "通報の重複の記録の取り消しに失敗しました。");
+            }
+            return Err(e);
+        }
+
+        Ok(report_id)
+    }
+
+    async fn target_exists(&self, target: ReportTarget) -> Fallible<bool, ReportError>;
+
+    // 既に通報済みの場合は`false`を返す
+    async fn claim_report(&self, account_id: AccountId, target: ReportTarget, report_id: ReportId) -> Fallible<bool, ReportError>;
+
+    async fn release_report(&self, account_id: AccountId, target: ReportTarget, report_id: ReportId) -> Fallible<(), ReportError>;
+
+    async fn insert_report(&self, report_id: ReportId, account_id: AccountId, target: ReportTarget, reason: ReportReason) -> Fallible<(), ReportError>;
+}
+
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("通報対象の取得に失敗しました")]
+    FetchTargetFailed(#[source] anyhow::Error),
+    #[error("通報対象が存在しません")]
+    TargetNotFound,
+    #[error("通報の重複の確認に失敗しました")]
+    ClaimReportFailed(#[source] anyhow::Error),
+    #[error("既に通報済みです")]
+    AlreadyReported,
+    #[error("通報の重複の記録の取り消しに失敗しました")]
+    ReleaseReportFailed(#[source] anyhow::Error),
+    #[error("通報の登録に失敗しました")]
+    InsertReportFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::{LazyLock, Mutex}};
+
+    use crate::common::{fallible::Fallible, handle::id::HandleId, moderation::{reason::ReportReason, report_id::ReportId, target::ReportTarget}, post::id::PostId, profile::account_id::AccountId};
+
+    use super::{Report, ReportError};
+
+    static EXISTING_POST: LazyLock<PostId> = LazyLock::new(PostId::gen);
+
+    struct MockReport {
+        claimed: Mutex<HashSet<(AccountId, ReportTarget)>>,
+        inserted: Mutex<Vec<ReportId>>,
+        insert_fails: Mutex<bool>,
+    }
+
+    impl MockReport {
+        fn new() -> Self {
+            Self { claimed: Mutex::new(HashSet::new()), inserted: Mutex::new(Vec::new()), insert_fails: Mutex::new(false) }
+        }
+    }
+
+    impl Report for MockReport {
+        async fn target_exists(&self, target: ReportTarget) -> Fallible<bool, ReportError> {
+            Ok(target == ReportTarget::Post(*EXISTING_POST))
+        }
+
+        async fn claim_report(&self, account_id: AccountId, target: ReportTarget, _: ReportId) -> Fallible<bool, ReportError> {
+            Ok(self.claimed.lock().unwrap().insert((account_id, target)))
+        }
+
+        async fn release_report(&self, account_id: AccountId, target: ReportTarget, _: ReportId) -> Fallible<(), ReportError> {
+            self.claimed.lock().unwrap().remove(&(account_id, target));
+            Ok(())
+        }
+
+        async fn insert_report(&self, report_id: ReportId, _: AccountId, _: ReportTarget, _: ReportReason) -> Fallible<(), ReportError> {
+            if *self.insert_fails.lock().unwrap() {
+                return Err(ReportError::InsertReportFailed(anyhow::anyhow!("mock")));
+            }
+
+            self.inserted.lock().unwrap().push(report_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn report() {
+        let mock = MockReport::new();
+        let report_id = mock.report(AccountId::gen(), ReportTarget::Post(*EXISTING_POST), ReportReason::Spam).await.unwrap();
+        assert_eq!(*mock.inserted.lock().unwrap(), vec![report_id]);
+    }
+
+    #[tokio::test]
+    async fn target_not_found() {
+        let mock = MockReport::new();
+        let result = mock.report(AccountId::gen(), ReportTarget::Handle(HandleId::gen()), ReportReason::Spam).await;
+        assert!(matches!(result.err(), Some(ReportError::TargetNotFound)));
+        assert!(mock.inserted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn already_reported() {
+        let mock = MockReport::new();
+        let account_id = AccountId::gen();
+        mock.report(account_id, ReportTarget::Post(*EXISTING_POST), ReportReason::Spam).await.unwrap();
+
+        // 理由を変えても同じ対象は再度通報できない
+        let result = mock.report(account_id, ReportTarget::Post(*EXISTING_POST), ReportReason::Harassment).await;
+        assert!(matches!(result.err(), Some(ReportError::AlreadyReported)));
+        assert_eq!(mock.inserted.lock().unwrap().len(), 1);
+
+        // 別の通報者は通報できる
+        assert!(mock.report(AccountId::gen(), ReportTarget::Post(*EXISTING_POST), ReportReason::Spam).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn retry_after_insert_failed() {
+        let mock = MockReport::new();
+        let account_id = AccountId::gen();
+
+        *mock.insert_fails.lock().unwrap() = true;
+        let result = mock.report(account_id, ReportTarget::Post(*EXISTING_POST), ReportReason::Spam).await;
+        assert!(matches!(result.err(), Some(ReportError::InsertReportFailed(_))));
+
+        // 登録に失敗した通報は、重複とみなさず再度通報できる
+        *mock.insert_fails.lock().unwrap() = false;
+        assert!(mock.report(account_id, ReportTarget::Post(*EXISTING_POST), ReportReason::Spam).await.is_ok());
+    }
+}
\ No newline at end of file
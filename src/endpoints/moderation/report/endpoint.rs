@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
+use http::StatusCode;
+use scylla::Session;
+use serde::{Deserialize, Serialize};
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{moderation::{reason::ReportReason, report_id::ReportId, target::ReportTarget}, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{Report, ReportError}, interpreter::ReportImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ReportImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "crrpt", 30, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "crrpt", 30, 15, TimeUnit::MINS).await?);
+
+    let report = ReportImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/reports", post(handler))
+        .layer(services)
+        .with_state(Arc::new(report));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<ReportImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Json(payload): Json<Payload>
+) -> Result<(StatusCode, Json<Data>), ReportError> {
+    match routine.report(account_id, payload.target, payload.reason).await {
+        Ok(report_id) => Ok((StatusCode::CREATED, Json(Data { report_id }))),
+        Err(e @ (ReportError::TargetNotFound | ReportError::AlreadyReported)) => Err(e),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                target = %payload.target,
+                "通報に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for ReportError {
+    fn into_response(self) -> Response {
+        match self {
+            ReportError::TargetNotFound => ApiError::REPORT_TARGET_NOT_FOUND,
+            ReportError::AlreadyReported => ApiError::ALREADY_REPORTED,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Payload {
+    target: ReportTarget,
+    reason: ReportReason,
+}
+
+#[derive(Serialize)]
+pub struct Data {
+    report_id: ReportId,
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+use uuid::Uuid;
+
+use crate::{common::{fallible::Fallible, moderation::{reason::ReportReason, report_id::ReportId, target::ReportTarget}, profile::account_id::AccountId, unixtime::UnixtimeMillis}, endpoints::moderation::queue::interpreter::MODERATION_QUEUE_PARTITION, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+use super::dsl::{Report, ReportError};
+
+pub struct ReportImpl {
+    db: Arc<Session>,
+    select_post: Arc<PreparedStatement>,
+    select_handle: Arc<PreparedStatement>,
+    select_tag: Arc<PreparedStatement>,
+    insert_report_by_reporter: Arc<PreparedStatement>,
+    delete_report_by_reporter: Arc<PreparedStatement>,
+    insert_report: Arc<PreparedStatement>,
+    insert_into_queue: Arc<PreparedStatement>,
+}
+
+impl ReportImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_post = prepare(&db, "SELECT id FROM posts WHERE id = ?").await?;
+
+        let select_handle = prepare(&db, "SELECT handle_id FROM handle_owners WHERE handle_id = ?").await?;
+
+        let select_tag = prepare(&db, "SELECT id FROM tags WHERE id = ?").await?;
+
+        let insert_report_by_reporter = prepare(&db, "INSERT INTO reports_by_reporter (account_id, target_type, target_id, report_id) VALUES (?, ?, ?, ?) IF NOT EXISTS").await?;
+
+        // 同じ通報者が改めて通報した場合の記録は取り消さない
+        let delete_report_by_reporter = prepare(&db, "DELETE FROM reports_by_reporter WHERE account_id = ? AND target_type = ? AND target_id = ? IF report_id = ?").await?;
+
+        let insert_report = prepare(&db, "INSERT INTO reports (id, reporter_id, target_type, target_id, reason, reported_at) VALUES (?, ?, ?, ?, ?, ?)").await?;
+
+        let insert_into_queue = prepare(&db, "INSERT INTO moderation_queue (partition, report_id, target_type, target_id, reason) VALUES (?, ?, ?, ?, ?)").await?;
+
+        Ok(Self { db, select_post, select_handle, select_tag, insert_report_by_reporter, delete_report_by_reporter, insert_report, insert_into_queue })
+    }
+}
+
+impl Report for ReportImpl {
+    async fn target_exists(&self, target: ReportTarget) -> Fallible<bool, ReportError> {
+        let statement = match target {
+            ReportTarget::Post(_) => &self.select_post,
+            ReportTarget::Handle(_) => &self.select_handle,
+            ReportTarget::Tag(_) => &self.select_tag,
+        };
+
+        self.db
+            .execute_unpaged(statement, (target.id(), ))
+            .await
+            .map_err(|e| ReportError::FetchTargetFailed(e.into()))?
+            .maybe_first_row_typed::<(Uuid, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| ReportError::FetchTargetFailed(e.into()))
+    }
+
+    async fn claim_report(&self, account_id: AccountId, target: ReportTarget, report_id: ReportId) -> Fallible<bool, ReportError> {
+        let res = self.db
+            .execute_unpaged(&self.insert_report_by_reporter, (account_id, target.kind(), target.id(), report_id))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) => Ok(true),
+            Err(Some(e)) => Err(ReportError::ClaimReportFailed(e)),
+            Err(None) => Ok(false),
+        }
+    }
+
+    async fn release_report(&self, account_id: AccountId, target: ReportTarget, report_id: ReportId) -> Fallible<(), ReportError> {
+        let res = self.db
+            .execute_unpaged(&self.delete_report_by_reporter, (account_id, target.kind(), target.id(), report_id))
+            .await
+            .applied(Some, || None);
+
+        match res {
+            Ok(()) | Err(None) => Ok(()),
+            Err(Some(e)) => Err(ReportError::ReleaseReportFailed(e)),
+        }
+    }
+
+    async fn insert_report(&self, report_id: ReportId, account_id: AccountId, target: ReportTarget, reason: ReportReason) -> Fallible<(), ReportError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> ReportError {
+            ReportError::InsertReportFailed(e.into())
+        }
+
+        self.db
+            .execute_unpaged(&self.insert_report, (report_id, account_id, target.kind(), target.id(), reason, UnixtimeMillis::now()))
+            .await
+            .map_err(handle_error)?;
+
+        // モデレーターに通報者を明かさないよう、キューには通報者を含めない
+        self.db
+            .execute_unpaged(&self.insert_into_queue, (MODERATION_QUEUE_PARTITION, report_id, target.kind(), target.id(), reason))
+            .await
+            .map(|_| ())
+            .map_err(handle_error)
+    }
+}
\ No newline at end of file
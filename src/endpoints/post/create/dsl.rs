@@ -0,0 +1,112 @@
+use thiserror::Error;
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{content::PostContent, id::PostId, tags::PostTags}, profile::account_id::AccountId, tag::language_group::LanguageGroup}, endpoints::post::tags::{ValidatePostTags, ValidatePostTagsError}};
+
+pub(crate) trait CreatePost: ValidatePostTags {
+    async fn create_post(&self, account_id: AccountId, handle_id: HandleId, content: PostContent, tags: PostTags) -> Fallible<PostId, CreatePostError> {
+        if !self.is_handle_owner(account_id, handle_id).await? {
+            return Err(CreatePostError::NotHandleOwner);
+        }
+
+        // タグは投稿者の言語グループに属するものに限る
+        let language_group = self.fetch_language_group(account_id).await?;
+        self.validate_post_tags(language_group, &tags).await?;
+
+        let post_id = PostId::gen();
+        self.insert_post(post_id, account_id, handle_id, language_group, &content, &tags).await?;
+        self.increment_share_count(account_id, handle_id).await?;
+
+        Ok(post_id)
+    }
+
+    async fn is_handle_owner(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, CreatePostError>;
+
+    async fn fetch_language_group(&self, account_id: AccountId) -> Fallible<LanguageGroup, CreatePostError>;
+
+    async fn insert_post(&self, post_id: PostId, account_id: AccountId, handle_id: HandleId, language_group: LanguageGroup, content: &PostContent, tags: &PostTags) -> Fallible<(), CreatePostError>;
+
+    async fn increment_share_count(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), CreatePostError>;
+}
+
+#[derive(Debug, Error)]
+pub enum CreatePostError {
+    #[error("名義の所有者の確認に失敗しました")]
+    CheckHandleOwnerFailed(#[source] anyhow::Error),
+    #[error("自分の名義ではありません")]
+    NotHandleOwner,
+    #[error("言語グループの取得に失敗しました")]
+    FetchLanguageGroupFailed(#[source] anyhow::Error),
+    #[error(transparent)]
+    ValidatePostTags(#[from] ValidatePostTagsError),
+    #[error("投稿の作成に失敗しました")]
+    CreatePostFailed(#[source] anyhow::Error),
+    #[error("共有数の更新に失敗しました")]
+    IncrementShareCountFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{content::PostContent, id::PostId, tags::PostTags}, profile::account_id::AccountId, tag::{language_group::LanguageGroup, non_top_tag::NonTopTagId}}, endpoints::post::tags::{ValidatePostTags, ValidatePostTagsError}, helper::test::mock_non_top_tag_id};
+
+    use super::{CreatePost, CreatePostError};
+
+    static OWN_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+    static JAPANESE_TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+    static KOREAN_TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(2));
+
+    struct MockCreatePost;
+
+    impl ValidatePostTags for MockCreatePost {
+        async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError> {
+            if tag_id == *JAPANESE_TAG_ID {
+                Ok(Some(LanguageGroup::Japanese))
+            } else if tag_id == *KOREAN_TAG_ID {
+                Ok(Some(LanguageGroup::Korean))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    impl CreatePost for MockCreatePost {
+        async fn is_handle_owner(&self, _: AccountId, handle_id: HandleId) -> Fallible<bool, CreatePostError> {
+            Ok(handle_id == *OWN_HANDLE_ID)
+        }
+
+        async fn fetch_language_group(&self, _: AccountId) -> Fallible<LanguageGroup, CreatePostError> {
+            Ok(LanguageGroup::Japanese)
+        }
+
+        async fn insert_post(&self, _: PostId, _: AccountId, _: HandleId, _: LanguageGroup, _: &PostContent, _: &PostTags) -> Fallible<(), CreatePostError> {
+            Ok(())
+        }
+
+        async fn increment_share_count(&self, _: AccountId, _: HandleId) -> Fallible<(), CreatePostError> {
+            Ok(())
+        }
+    }
+
+    async fn test_create_post(handle_id: HandleId, tags: Vec<NonTopTagId>) -> Fallible<PostId, CreatePostError> {
+        let content = "本文".parse().unwrap();
+        MockCreatePost.create_post(AccountId::gen(), handle_id, content, PostTags::try_from(tags).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn create_post() {
+        assert!(test_create_post(*OWN_HANDLE_ID, vec![*JAPANESE_TAG_ID]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn not_handle_owner() {
+        let res = test_create_post(HandleId::gen(), vec![]).await;
+        assert!(matches!(res.err().unwrap(), CreatePostError::NotHandleOwner));
+    }
+
+    #[tokio::test]
+    async fn different_language_group() {
+        let res = test_create_post(*OWN_HANDLE_ID, vec![*KOREAN_TAG_ID]).await;
+        assert!(matches!(res.err().unwrap(), CreatePostError::ValidatePostTags(ValidatePostTagsError::DifferentLanguageGroup(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
+use http::StatusCode;
+use scylla::Session;
+use serde::{Deserialize, Serialize};
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{handle::id::HandleId, post::{content::PostContent, id::PostId, tags::PostTags}, profile::account_id::AccountId}, endpoints::post::tags::ValidatePostTagsError, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{CreatePost, CreatePostError}, interpreter::CreatePostImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<CreatePostImpl>> {
+    let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
+    .layer(rate_limiter(db.clone(), cache.clone(), "crpst", 30, 15, TimeUnit::MINS).await?)
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "crpst", 30, 15, TimeUnit::MINS).await?);
+
+    let create_post = CreatePostImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/posts", post(handler))
+        .layer(services)
+        .with_state(Arc::new(create_post));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<CreatePostImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Json(payload): Json<Payload>
+) -> Result<(StatusCode, Json<Data>), CreatePostError> {
+    match routine.create_post(account_id, payload.handle_id, payload.content, payload.tags).await {
+        Ok(post_id) => Ok((StatusCode::CREATED, Json(Data { post_id }))),
+        Err(e @ (CreatePostError::NotHandleOwner | CreatePostError::ValidatePostTags(ValidatePostTagsError::NonExistentTag(_) | ValidatePostTagsError::DifferentLanguageGroup(_)))) => Err(e),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                handle_id = %payload.handle_id,
+                "投稿の作成に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for CreatePostError {
+    fn into_response(self) -> Response {
+        match self {
+            CreatePostError::NotHandleOwner => ApiError::NOT_HANDLE_OWNER,
+            CreatePostError::ValidatePostTags(e) => return e.into_response(),
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Payload {
+    handle_id: HandleId,
+    content: PostContent,
+    #[serde(default)]
+    tags: PostTags,
+}
+
+#[derive(Serialize)]
+pub struct Data {
+    post_id: PostId,
+}
\ No newline at end of file
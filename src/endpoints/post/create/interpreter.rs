@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use scylla::{batch::{Batch, BatchType}, prepared_statement::PreparedStatement, serialize::row::SerializeRow, Session};
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{content::PostContent, id::PostId, tags::PostTags}, profile::{account_id::AccountId, language::Language}, tag::{language_group::LanguageGroup, non_top_tag::NonTopTagId}, unixtime::UnixtimeMillis}, endpoints::post::{share_count::HandleShareCounter, tags::{TagLanguageGroupFetcher, ValidatePostTags, ValidatePostTagsError}}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{CreatePost, CreatePostError};
+
+pub struct CreatePostImpl {
+    db: Arc<Session>,
+    tag_language_group_fetcher: TagLanguageGroupFetcher,
+    share_counter: HandleShareCounter,
+    select_handle: Arc<PreparedStatement>,
+    select_language: Arc<PreparedStatement>,
+    insert_post: Arc<PreparedStatement>,
+    insert_post_by_handle: Arc<PreparedStatement>,
+    insert_post_by_tag: Arc<PreparedStatement>,
+}
+
+impl CreatePostImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let tag_language_group_fetcher = TagLanguageGroupFetcher::try_new(db.clone()).await?;
+
+        let share_counter = HandleShareCounter::try_new(db.clone()).await?;
+
+        let select_handle = prepare(&db, "SELECT handle_id FROM handles WHERE account_id = ? AND handle_id = ?").await?;
+
+        let select_language = prepare(&db, "SELECT language FROM accounts WHERE id = ?").await?;
+
+        let insert_post = prepare(&db, "INSERT INTO posts (id, account_id, handle_id, language_group, content, tag_ids, posted_at) VALUES (?, ?, ?, ?, ?, ?, ?)").await?;
+
+        let insert_post_by_handle = prepare(&db, "INSERT INTO posts_by_handle (handle_id, post_id) VALUES (?, ?)").await?;
+
+        let insert_post_by_tag = prepare(&db, "INSERT INTO posts_by_tag (tag_id, post_id) VALUES (?, ?)").await?;
+
+        Ok(Self { db, tag_language_group_fetcher, share_counter, select_handle, select_language, insert_post, insert_post_by_handle, insert_post_by_tag })
+    }
+}
+
+impl ValidatePostTags for CreatePostImpl {
+    async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError> {
+        self.tag_language_group_fetcher.fetch_tag_language_group(tag_id).await
+    }
+}
+
+impl CreatePost for CreatePostImpl {
+    async fn is_handle_owner(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<bool, CreatePostError> {
+        self.db
+            .execute_unpaged(&self.select_handle, (account_id, handle_id))
+            .await
+            .map_err(|e| CreatePostError::CheckHandleOwnerFailed(e.into()))?
+            .maybe_first_row_typed::<(HandleId, )>()
+            .map(|row| row.is_some())
+            .map_err(|e| CreatePostError::CheckHandleOwnerFailed(e.into()))
+    }
+
+    async fn fetch_language_group(&self, account_id: AccountId) -> Fallible<LanguageGroup, CreatePostError> {
+        self.db
+            .execute_unpaged(&self.select_language, (account_id, ))
+            .await
+            .map_err(|e| CreatePostError::FetchLanguageGroupFailed(e.into()))?
+            .first_row_typed::<(Language, )>()
+            .map(|(language, )| LanguageGroup::from(language))
+            .map_err(|e| CreatePostError::FetchLanguageGroupFailed(e.into()))
+    }
+
+    async fn insert_post(&self, post_id: PostId, account_id: AccountId, handle_id: HandleId, language_group: LanguageGroup, content: &PostContent, tags: &PostTags) -> Fallible<(), CreatePostError> {
+        let post = (post_id, account_id, handle_id, language_group, content, tags, UnixtimeMillis::now());
+        let post_by_handle = (handle_id, post_id);
+        let posts_by_tag: Vec<_> = tags.value()
+            .iter()
+            .map(|tag_id| (tag_id, post_id))
+            .collect();
+
+        // 投稿と索引の一部だけが残らないよう、ログ付きバッチでまとめて書き込む
+        let mut batch = Batch::new(BatchType::Logged);
+        let mut values: Vec<&(dyn SerializeRow + Sync)> = Vec::with_capacity(posts_by_tag.len() + 2);
+
+        batch.append_statement((*self.insert_post).clone());
+        values.push(&post);
+
+        batch.append_statement((*self.insert_post_by_handle).clone());
+        values.push(&post_by_handle);
+
+        for post_by_tag in &posts_by_tag {
+            batch.append_statement((*self.insert_post_by_tag).clone());
+            values.push(post_by_tag);
+        }
+
+        self.db
+            .batch(&batch, values)
+            .await
+            .map(|_| ())
+            .map_err(|e| CreatePostError::CreatePostFailed(e.into()))
+    }
+
+    async fn increment_share_count(&self, account_id: AccountId, handle_id: HandleId) -> Fallible<(), CreatePostError> {
+        self.share_counter
+            .adjust(account_id, handle_id, 1)
+            .await
+            .map_err(CreatePostError::IncrementShareCountFailed)
+    }
+}
\ No newline at end of file
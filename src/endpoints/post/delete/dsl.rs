@@ -0,0 +1,81 @@
+use thiserror::Error;
+
+use crate::common::{fallible::Fallible, post::{id::PostId, metadata::PostMetadata}, profile::account_id::AccountId};
+
+pub(crate) trait DeletePost {
+    async fn delete_post(&self, account_id: AccountId, post_id: PostId) -> Fallible<(), DeletePostError> {
+        // 他人の投稿は存在しないものとして扱う
+        let metadata = self.fetch_post_metadata(post_id)
+            .await?
+            .filter(|metadata| metadata.account_id() == account_id)
+            .ok_or(DeletePostError::PostNotFound)?;
+
+        self.remove_post(post_id, &metadata).await?;
+        self.decrement_share_count(&metadata).await
+    }
+
+    async fn fetch_post_metadata(&self, post_id: PostId) -> Fallible<Option<PostMetadata>, DeletePostError>;
+
+    async fn remove_post(&self, post_id: PostId, metadata: &PostMetadata) -> Fallible<(), DeletePostError>;
+
+    async fn decrement_share_count(&self, metadata: &PostMetadata) -> Fallible<(), DeletePostError>;
+}
+
+#[derive(Debug, Error)]
+pub enum DeletePostError {
+    #[error("投稿の取得に失敗しました")]
+    FetchPostMetadataFailed(#[source] anyhow::Error),
+    #[error("投稿が見つかりません")]
+    PostNotFound,
+    #[error("投稿の削除に失敗しました")]
+    DeletePostFailed(#[source] anyhow::Error),
+    #[error("共有数の更新に失敗しました")]
+    DecrementShareCountFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::common::{fallible::Fallible, handle::id::HandleId, post::{id::PostId, metadata::PostMetadata, tags::PostTags}, profile::account_id::AccountId, tag::language_group::LanguageGroup};
+
+    use super::{DeletePost, DeletePostError};
+
+    static POSTER_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static EXISTENT_POST_ID: LazyLock<PostId> = LazyLock::new(PostId::gen);
+
+    struct MockDeletePost;
+
+    impl DeletePost for MockDeletePost {
+        async fn fetch_post_metadata(&self, post_id: PostId) -> Fallible<Option<PostMetadata>, DeletePostError> {
+            if post_id == *EXISTENT_POST_ID {
+                Ok(Some(PostMetadata::new(*POSTER_ID, HandleId::gen(), LanguageGroup::Japanese, PostTags::default())))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn remove_post(&self, _: PostId, _: &PostMetadata) -> Fallible<(), DeletePostError> {
+            Ok(())
+        }
+
+        async fn decrement_share_count(&self, _: &PostMetadata) -> Fallible<(), DeletePostError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_post() {
+        assert!(MockDeletePost.delete_post(*POSTER_ID, *EXISTENT_POST_ID).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn post_not_found() {
+        let res = MockDeletePost.delete_post(*POSTER_ID, PostId::gen()).await;
+        assert!(matches!(res.err().unwrap(), DeletePostError::PostNotFound));
+
+        // 他人の投稿
+        let res = MockDeletePost.delete_post(AccountId::gen(), *EXISTENT_POST_ID).await;
+        assert!(matches!(res.err().unwrap(), DeletePostError::PostNotFound));
+    }
+}
\ No newline at end of file
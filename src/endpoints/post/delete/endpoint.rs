@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::delete, Extension, Router};
+use http::StatusCode;
+use scylla::Session;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{post::id::PostId, profile::account_id::AccountId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{DeletePost, DeletePostError}, interpreter::DeletePostImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<DeletePostImpl>> {
+    let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
+    .layer(rate_limiter(db.clone(), cache.clone(), "dlpst", 30, 15, TimeUnit::MINS).await?)
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "dlpst", 30, 15, TimeUnit::MINS).await?);
+
+    let delete_post = DeletePostImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/posts/:id", delete(handler))
+        .layer(services)
+        .with_state(Arc::new(delete_post));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<DeletePostImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Path(post_id): Path<PostId>
+) -> Result<StatusCode, DeletePostError> {
+    match routine.delete_post(account_id, post_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(DeletePostError::PostNotFound) => Err(DeletePostError::PostNotFound),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                post_id = %post_id,
+                "投稿の削除に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for DeletePostError {
+    fn into_response(self) -> Response {
+        match self {
+            DeletePostError::PostNotFound => ApiError::POST_NOT_FOUND,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
\ No newline at end of file
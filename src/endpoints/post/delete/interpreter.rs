@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{id::PostId, metadata::PostMetadata, tags::PostTags}, profile::account_id::AccountId, tag::language_group::LanguageGroup}, endpoints::post::share_count::HandleShareCounter, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{DeletePost, DeletePostError};
+
+pub struct DeletePostImpl {
+    db: Arc<Session>,
+    share_counter: HandleShareCounter,
+    select_post_metadata: Arc<PreparedStatement>,
+    delete_post: Arc<PreparedStatement>,
+    delete_post_by_handle: Arc<PreparedStatement>,
+    delete_post_by_tag: Arc<PreparedStatement>,
+}
+
+impl DeletePostImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let share_counter = HandleShareCounter::try_new(db.clone()).await?;
+
+        let select_post_metadata = prepare(&db, "SELECT account_id, handle_id, language_group, tag_ids FROM posts WHERE id = ?").await?;
+
+        let delete_post = prepare(&db, "DELETE FROM posts WHERE id = ?").await?;
+
+        let delete_post_by_handle = prepare(&db, "DELETE FROM posts_by_handle WHERE handle_id = ? AND post_id = ?").await?;
+
+        let delete_post_by_tag = prepare(&db, "DELETE FROM posts_by_tag WHERE tag_id = ? AND post_id = ?").await?;
+
+        Ok(Self { db, share_counter, select_post_metadata, delete_post, delete_post_by_handle, delete_post_by_tag })
+    }
+}
+
+impl DeletePost for DeletePostImpl {
+    async fn fetch_post_metadata(&self, post_id: PostId) -> Fallible<Option<PostMetadata>, DeletePostError> {
+        self.db
+            .execute_unpaged(&self.select_post_metadata, (post_id, ))
+            .await
+            .map_err(|e| DeletePostError::FetchPostMetadataFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, HandleId, LanguageGroup, PostTags)>()
+            .map(|row| row.map(|(account_id, handle_id, language_group, tags)| PostMetadata::new(account_id, handle_id, language_group, tags)))
+            .map_err(|e| DeletePostError::FetchPostMetadataFailed(e.into()))
+    }
+
+    async fn remove_post(&self, post_id: PostId, metadata: &PostMetadata) -> Fallible<(), DeletePostError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> DeletePostError {
+            DeletePostError::DeletePostFailed(e.into())
+        }
+
+        // 索引を先に削除し、途中で失敗しても本体から再試行できるようにする
+        for tag_id in metadata.tags().value() {
+            self.db
+                .execute_unpaged(&self.delete_post_by_tag, (tag_id, post_id))
+                .await
+                .map_err(handle_error)?;
+        }
+
+        self.db
+            .execute_unpaged(&self.delete_post_by_handle, (metadata.handle_id(), post_id))
+            .await
+            .map_err(handle_error)?;
+
+        self.db
+            .execute_unpaged(&self.delete_post, (post_id, ))
+            .await
+            .map(|_| ())
+            .map_err(handle_error)
+    }
+
+    async fn decrement_share_count(&self, metadata: &PostMetadata) -> Fallible<(), DeletePostError> {
+        self.share_counter
+            .adjust(metadata.account_id(), metadata.handle_id(), -1)
+            .await
+            .map_err(DeletePostError::DecrementShareCountFailed)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+use thiserror::Error;
+
+use crate::{common::{fallible::Fallible, post::{content::PostContent, id::PostId, metadata::PostMetadata, tags::PostTags}, profile::account_id::AccountId}, endpoints::post::tags::{ValidatePostTags, ValidatePostTagsError}};
+
+pub(crate) trait EditPost: ValidatePostTags {
+    async fn edit_post(&self, account_id: AccountId, post_id: PostId, content: PostContent, tags: PostTags) -> Fallible<(), EditPostError> {
+        // 他人の投稿は存在しないものとして扱う
+        let metadata = self.fetch_post_metadata(post_id)
+            .await?
+            .filter(|metadata| metadata.account_id() == account_id)
+            .ok_or(EditPostError::PostNotFound)?;
+
+        // 言語グループは投稿時のものを用いる
+        self.validate_post_tags(metadata.language_group(), &tags).await?;
+
+        self.update_post(post_id, &metadata, &content, &tags).await
+    }
+
+    async fn fetch_post_metadata(&self, post_id: PostId) -> Fallible<Option<PostMetadata>, EditPostError>;
+
+    async fn update_post(&self, post_id: PostId, metadata: &PostMetadata, content: &PostContent, tags: &PostTags) -> Fallible<(), EditPostError>;
+}
+
+#[derive(Debug, Error)]
+pub enum EditPostError {
+    #[error("投稿の取得に失敗しました")]
+    FetchPostMetadataFailed(#[source] anyhow::Error),
+    #[error("投稿が見つかりません")]
+    PostNotFound,
+    #[error(transparent)]
+    ValidatePostTags(#[from] ValidatePostTagsError),
+    #[error("投稿の編集に失敗しました")]
+    EditPostFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{content::PostContent, id::PostId, metadata::PostMetadata, tags::PostTags}, profile::account_id::AccountId, tag::{language_group::LanguageGroup, non_top_tag::NonTopTagId}}, endpoints::post::tags::{ValidatePostTags, ValidatePostTagsError}, helper::test::mock_non_top_tag_id};
+
+    use super::{EditPost, EditPostError};
+
+    static POSTER_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static EXISTENT_POST_ID: LazyLock<PostId> = LazyLock::new(PostId::gen);
+    static KOREAN_TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+
+    struct MockEditPost;
+
+    impl ValidatePostTags for MockEditPost {
+        async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError> {
+            if tag_id == *KOREAN_TAG_ID {
+                Ok(Some(LanguageGroup::Korean))
+            } else {
+                Ok(Some(LanguageGroup::Japanese))
+            }
+        }
+    }
+
+    impl EditPost for MockEditPost {
+        async fn fetch_post_metadata(&self, post_id: PostId) -> Fallible<Option<PostMetadata>, EditPostError> {
+            if post_id == *EXISTENT_POST_ID {
+                Ok(Some(PostMetadata::new(*POSTER_ID, HandleId::gen(), LanguageGroup::Japanese, PostTags::default())))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn update_post(&self, _: PostId, _: &PostMetadata, _: &PostContent, _: &PostTags) -> Fallible<(), EditPostError> {
+            Ok(())
+        }
+    }
+
+    async fn test_edit_post(account_id: AccountId, post_id: PostId, tags: Vec<NonTopTagId>) -> Fallible<(), EditPostError> {
+        let content = "編集後".parse().unwrap();
+        MockEditPost.edit_post(account_id, post_id, content, PostTags::try_from(tags).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn edit_post() {
+        assert!(test_edit_post(*POSTER_ID, *EXISTENT_POST_ID, vec![mock_non_top_tag_id(2)]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn post_not_found() {
+        let res = test_edit_post(*POSTER_ID, PostId::gen(), vec![]).await;
+        assert!(matches!(res.err().unwrap(), EditPostError::PostNotFound));
+
+        // 他人の投稿
+        let res = test_edit_post(AccountId::gen(), *EXISTENT_POST_ID, vec![]).await;
+        assert!(matches!(res.err().unwrap(), EditPostError::PostNotFound));
+    }
+
+    #[tokio::test]
+    async fn different_language_group() {
+        let res = test_edit_post(*POSTER_ID, *EXISTENT_POST_ID, vec![*KOREAN_TAG_ID]).await;
+        assert!(matches!(res.err().unwrap(), EditPostError::ValidatePostTags(ValidatePostTagsError::DifferentLanguageGroup(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::patch, Extension, Json, Router};
+use http::StatusCode;
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{post::{content::PostContent, id::PostId, tags::PostTags}, profile::account_id::AccountId}, endpoints::post::tags::ValidatePostTagsError, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{EditPost, EditPostError}, interpreter::EditPostImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<EditPostImpl>> {
+    let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
+    .layer(rate_limiter(db.clone(), cache.clone(), "edpst", 30, 15, TimeUnit::MINS).await?)
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "edpst", 30, 15, TimeUnit::MINS).await?);
+
+    let edit_post = EditPostImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/posts/:id", patch(handler))
+        .layer(services)
+        .with_state(Arc::new(edit_post));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<EditPostImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Path(post_id): Path<PostId>,
+    Json(payload): Json<Payload>
+) -> Result<StatusCode, EditPostError> {
+    match routine.edit_post(account_id, post_id, payload.content, payload.tags).await {
+        Ok(()) => Ok(StatusCode::OK),
+        Err(e @ (EditPostError::PostNotFound | EditPostError::ValidatePostTags(ValidatePostTagsError::NonExistentTag(_) | ValidatePostTagsError::DifferentLanguageGroup(_)))) => Err(e),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                post_id = %post_id,
+                "投稿の編集に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for EditPostError {
+    fn into_response(self) -> Response {
+        match self {
+            EditPostError::PostNotFound => ApiError::POST_NOT_FOUND,
+            EditPostError::ValidatePostTags(e) => return e.into_response(),
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Payload {
+    content: PostContent,
+    #[serde(default)]
+    tags: PostTags,
+}
\ No newline at end of file
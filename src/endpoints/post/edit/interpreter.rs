@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use scylla::{batch::{Batch, BatchType}, prepared_statement::PreparedStatement, serialize::row::SerializeRow, Session};
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{content::PostContent, id::PostId, metadata::PostMetadata, tags::PostTags}, profile::account_id::AccountId, tag::{language_group::LanguageGroup, non_top_tag::NonTopTagId}, unixtime::UnixtimeMillis}, endpoints::post::tags::{TagLanguageGroupFetcher, ValidatePostTags, ValidatePostTagsError}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{EditPost, EditPostError};
+
+pub struct EditPostImpl {
+    db: Arc<Session>,
+    tag_language_group_fetcher: TagLanguageGroupFetcher,
+    select_post_metadata: Arc<PreparedStatement>,
+    update_post: Arc<PreparedStatement>,
+    insert_post_by_tag: Arc<PreparedStatement>,
+    delete_post_by_tag: Arc<PreparedStatement>,
+}
+
+impl EditPostImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let tag_language_group_fetcher = TagLanguageGroupFetcher::try_new(db.clone()).await?;
+
+        let select_post_metadata = prepare(&db, "SELECT account_id, handle_id, language_group, tag_ids FROM posts WHERE id = ?").await?;
+
+        let update_post = prepare(&db, "UPDATE posts SET content = ?, tag_ids = ?, edited_at = ? WHERE id = ?").await?;
+
+        let insert_post_by_tag = prepare(&db, "INSERT INTO posts_by_tag (tag_id, post_id) VALUES (?, ?)").await?;
+
+        let delete_post_by_tag = prepare(&db, "DELETE FROM posts_by_tag WHERE tag_id = ? AND post_id = ?").await?;
+
+        Ok(Self { db, tag_language_group_fetcher, select_post_metadata, update_post, insert_post_by_tag, delete_post_by_tag })
+    }
+}
+
+impl ValidatePostTags for EditPostImpl {
+    async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError> {
+        self.tag_language_group_fetcher.fetch_tag_language_group(tag_id).await
+    }
+}
+
+impl EditPost for EditPostImpl {
+    async fn fetch_post_metadata(&self, post_id: PostId) -> Fallible<Option<PostMetadata>, EditPostError> {
+        self.db
+            .execute_unpaged(&self.select_post_metadata, (post_id, ))
+            .await
+            .map_err(|e| EditPostError::FetchPostMetadataFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, HandleId, LanguageGroup, PostTags)>()
+            .map(|row| row.map(|(account_id, handle_id, language_group, tags)| PostMetadata::new(account_id, handle_id, language_group, tags)))
+            .map_err(|e| EditPostError::FetchPostMetadataFailed(e.into()))
+    }
+
+    async fn update_post(&self, post_id: PostId, metadata: &PostMetadata, content: &PostContent, tags: &PostTags) -> Fallible<(), EditPostError> {
+        let post = (content, tags, UnixtimeMillis::now(), post_id);
+
+        // タグ別の索引は差分のみ更新する
+        let added_posts_by_tag: Vec<_> = tags.value()
+            .iter()
+            .filter(|tag_id| !metadata.tags().contains(tag_id))
+            .map(|tag_id| (tag_id, post_id))
+            .collect();
+
+        let removed_posts_by_tag: Vec<_> = metadata.tags()
+            .value()
+            .iter()
+            .filter(|tag_id| !tags.contains(tag_id))
+            .map(|tag_id| (tag_id, post_id))
+            .collect();
+
+        // 投稿のタグと索引が食い違わないよう、ログ付きバッチでまとめて書き込む
+        let mut batch = Batch::new(BatchType::Logged);
+        let mut values: Vec<&(dyn SerializeRow + Sync)> = Vec::with_capacity(added_posts_by_tag.len() + removed_posts_by_tag.len() + 1);
+
+        batch.append_statement((*self.update_post).clone());
+        values.push(&post);
+
+        for post_by_tag in &added_posts_by_tag {
+            batch.append_statement((*self.insert_post_by_tag).clone());
+            values.push(post_by_tag);
+        }
+
+        for post_by_tag in &removed_posts_by_tag {
+            batch.append_statement((*self.delete_post_by_tag).clone());
+            values.push(post_by_tag);
+        }
+
+        self.db
+            .batch(&batch, values)
+            .await
+            .map(|_| ())
+            .map_err(|e| EditPostError::EditPostFailed(e.into()))
+    }
+}
\ No newline at end of file
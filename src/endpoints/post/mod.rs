@@ -0,0 +1,6 @@
+pub mod create;
+pub mod delete;
+pub mod edit;
+pub mod share_count;
+pub mod summary;
+pub mod tags;
\ No newline at end of file
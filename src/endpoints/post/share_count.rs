@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{handle::id::HandleId, profile::account_id::AccountId}, helper::{error::InitError, scylla::{prepare, Transactional}}};
+
+const MAX_ADJUST_ATTEMPTS: usize = 5;
+
+// 名義の共有数の増減
+// share_countはcounter型ではないため、軽量トランザクションで比較して更新する
+pub struct HandleShareCounter {
+    db: Arc<Session>,
+    select_share_count: Arc<PreparedStatement>,
+    update_share_count: Arc<PreparedStatement>,
+}
+
+impl HandleShareCounter {
+    pub async fn try_new<T>(db: Arc<Session>) -> Result<Self, InitError<T>> {
+        let select_share_count = prepare(&db, "SELECT share_count FROM handle_share_counts WHERE account_id = ? AND handle_id = ?").await?;
+
+        let update_share_count = prepare(&db, "UPDATE handle_share_counts SET share_count = ? WHERE account_id = ? AND handle_id = ? IF share_count = ?").await?;
+
+        Ok(Self { db, select_share_count, update_share_count })
+    }
+
+    pub async fn adjust(&self, account_id: AccountId, handle_id: HandleId, delta: i32) -> anyhow::Result<()> {
+        for _ in 0..MAX_ADJUST_ATTEMPTS {
+            let share_count = self.db
+                .execute_unpaged(&self.select_share_count, (account_id, handle_id))
+                .await?
+                .maybe_first_row_typed::<(i32, )>()?;
+
+            // 名義が削除されている場合は何もしない
+            let Some((share_count, )) = share_count else {
+                return Ok(());
+            };
+
+            let res = self.db
+                .execute_unpaged(&self.update_share_count, ((share_count + delta).max(0), account_id, handle_id, share_count))
+                .await
+                .applied(Some, || None);
+
+            match res {
+                Ok(()) => return Ok(()),
+                Err(Some(e)) => return Err(e),
+                // 他の更新と競合した場合は再試行
+                Err(None) => continue,
+            }
+        }
+
+        Err(anyhow!("共有数の更新が競合し続けました"))
+    }
+}
\ No newline at end of file
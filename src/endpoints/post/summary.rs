@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use scylla::{frame::value::CqlTimestamp, prepared_statement::PreparedStatement, Session};
+use tokio::task::JoinSet;
+
+use crate::{common::{handle::id::HandleId, post::{content::PostContent, id::PostId, summary::PostSummary, tags::PostTags}, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::prepare}};
+
+// フィードなどで共有する、投稿の取得
+pub struct PostSummaryFetcher {
+    db: Arc<Session>,
+    select_post: Arc<PreparedStatement>,
+}
+
+impl PostSummaryFetcher {
+    pub async fn try_new<T>(db: Arc<Session>) -> Result<Self, InitError<T>> {
+        let select_post = prepare(&db, "SELECT handle_id, content, tag_ids, posted_at, edited_at, hidden FROM posts WHERE id = ?").await?;
+
+        Ok(Self { db, select_post })
+    }
+
+    // 索引の更新後に削除された投稿と、モデレーターが非表示にした投稿は含めない
+    // 投稿ごとに別のパーティションを読むため、並行して取得したうえで`post_ids`の順に並べる
+    pub async fn fetch(&self, post_ids: &[PostId]) -> anyhow::Result<Vec<PostSummary>> {
+        let mut tasks = JoinSet::new();
+
+        for (index, post_id) in post_ids.iter().copied().enumerate() {
+            let db = self.db.clone();
+            let select_post = self.select_post.clone();
+
+            tasks.spawn(async move {
+                let row = db
+                    .execute_unpaged(&select_post, (post_id, ))
+                    .await?
+                    .maybe_first_row_typed::<(HandleId, PostContent, PostTags, UnixtimeMillis, Option<CqlTimestamp>, Option<bool>)>()?;
+
+                // 非表示にされていない投稿はhiddenがnullになる
+                let post = match row {
+                    Some((handle_id, content, tags, posted_at, edited_at, None | Some(false))) => {
+                        // 編集されていない投稿はedited_atがnullになる
+                        let edited_at = edited_at.map(|edited_at| UnixtimeMillis::from(edited_at.0));
+                        Some(PostSummary::new(post_id, handle_id, content, tags, posted_at, edited_at))
+                    },
+                    _ => None,
+                };
+
+                anyhow::Ok((index, post))
+            });
+        }
+
+        let mut posts: Vec<Option<PostSummary>> = post_ids.iter().map(|_| None).collect();
+
+        // 失敗した場合は`tasks`の破棄により残りの取得も中止される
+        while let Some(result) = tasks.join_next().await {
+            let (index, post) = result??;
+            posts[index] = post;
+        }
+
+        Ok(posts.into_iter().flatten().collect())
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use axum::response::{IntoResponse, Response};
+use scylla::{prepared_statement::PreparedStatement, Session};
+use thiserror::Error;
+
+use crate::{common::{fallible::Fallible, post::tags::PostTags, tag::{language_group::LanguageGroup, non_top_tag::NonTopTagId}}, helper::{api_error::ApiError, error::InitError, scylla::prepare}};
+
+// 投稿の作成と編集で共有する、タグの検証
+pub(crate) trait ValidatePostTags {
+    async fn validate_post_tags(&self, language_group: LanguageGroup, tags: &PostTags) -> Fallible<(), ValidatePostTagsError> {
+        for tag_id in tags.value() {
+            match self.fetch_tag_language_group(*tag_id).await? {
+                Some(tag_language_group) if tag_language_group == language_group => (),
+                Some(_) => return Err(ValidatePostTagsError::DifferentLanguageGroup(*tag_id)),
+                None => return Err(ValidatePostTagsError::NonExistentTag(*tag_id)),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError>;
+}
+
+#[derive(Debug, Error)]
+pub enum ValidatePostTagsError {
+    #[error("存在しないタグです: {0}")]
+    NonExistentTag(NonTopTagId),
+    #[error("投稿者と異なる言語グループのタグです: {0}")]
+    DifferentLanguageGroup(NonTopTagId),
+    #[error("タグの言語グループの取得に失敗しました")]
+    FetchTagLanguageGroupFailed(#[source] anyhow::Error),
+}
+
+pub struct TagLanguageGroupFetcher {
+    db: Arc<Session>,
+    select_tag_language_group: Arc<PreparedStatement>,
+}
+
+impl TagLanguageGroupFetcher {
+    pub async fn try_new<T>(db: Arc<Session>) -> Result<Self, InitError<T>> {
+        let select_tag_language_group = prepare(&db, "SELECT language_group FROM tags WHERE id = ?").await?;
+
+        Ok(Self { db, select_tag_language_group })
+    }
+}
+
+impl ValidatePostTags for TagLanguageGroupFetcher {
+    async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError> {
+        self.db
+            .execute_unpaged(&self.select_tag_language_group, (tag_id, ))
+            .await
+            .map_err(|e| ValidatePostTagsError::FetchTagLanguageGroupFailed(e.into()))?
+            .maybe_first_row_typed::<(LanguageGroup, )>()
+            .map(|row| row.map(|(language_group, )| language_group))
+            .map_err(|e| ValidatePostTagsError::FetchTagLanguageGroupFailed(e.into()))
+    }
+}
+
+impl IntoResponse for ValidatePostTagsError {
+    fn into_response(self) -> Response {
+        match self {
+            ValidatePostTagsError::NonExistentTag(_) => ApiError::POST_NON_EXISTENT_TAG,
+            ValidatePostTagsError::DifferentLanguageGroup(_) => ApiError::POST_DIFFERENT_LANGUAGE_GROUP,
+            ValidatePostTagsError::FetchTagLanguageGroupFailed(_) => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::{common::{fallible::Fallible, post::tags::PostTags, tag::{language_group::LanguageGroup, non_top_tag::NonTopTagId}}, helper::test::mock_non_top_tag_id};
+
+    use super::{ValidatePostTags, ValidatePostTagsError};
+
+    static JAPANESE_TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+    static KOREAN_TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(2));
+    static NON_EXISTENT_TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(3));
+
+    struct MockValidatePostTags;
+
+    impl ValidatePostTags for MockValidatePostTags {
+        async fn fetch_tag_language_group(&self, tag_id: NonTopTagId) -> Fallible<Option<LanguageGroup>, ValidatePostTagsError> {
+            if tag_id == *JAPANESE_TAG_ID {
+                Ok(Some(LanguageGroup::Japanese))
+            } else if tag_id == *KOREAN_TAG_ID {
+                Ok(Some(LanguageGroup::Korean))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    async fn test_validate_post_tags(tags: Vec<NonTopTagId>) -> Fallible<(), ValidatePostTagsError> {
+        MockValidatePostTags.validate_post_tags(LanguageGroup::Japanese, &PostTags::try_from(tags).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn validate_post_tags() {
+        assert!(test_validate_post_tags(vec![]).await.is_ok());
+        assert!(test_validate_post_tags(vec![*JAPANESE_TAG_ID]).await.is_ok());
+
+        let res = test_validate_post_tags(vec![*JAPANESE_TAG_ID, *KOREAN_TAG_ID]).await;
+        assert!(matches!(res.err().unwrap(), ValidatePostTagsError::DifferentLanguageGroup(tag_id) if tag_id == *KOREAN_TAG_ID));
+
+        let res = test_validate_post_tags(vec![*NON_EXISTENT_TAG_ID]).await;
+        assert!(matches!(res.err().unwrap(), ValidatePostTagsError::NonExistentTag(tag_id) if tag_id == *NON_EXISTENT_TAG_ID));
+    }
+}
\ No newline at end of file
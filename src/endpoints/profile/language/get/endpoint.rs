@@ -1,20 +1,21 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::get, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
 use axum_macros::debug_handler;
-use http::StatusCode;
 use scylla::Session;
 use tower::ServiceBuilder;
 use tracing::info;
 
-use crate::{common::profile::{account_id::AccountId, language::Language}, endpoints::profile::language::get::dsl::GetLanguage, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::profile::{account_id::AccountId, language::Language}, endpoints::profile::language::get::dsl::{GetLanguage, GetLanguageError}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::interpreter::GetLanguageImpl;
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<GetLanguageImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "getln", 5, 15, TimeUnit::MINS).await?)
-        .layer(session_manager(db.clone(), cache).await?);
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "getln", 5, 15, TimeUnit::MINS).await?);
 
     let get_language = GetLanguageImpl::try_new(db).await?;
 
@@ -31,7 +32,7 @@ pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, Init
 pub async fn handler(
     State(routine): State<Arc<GetLanguageImpl>>,
     Extension(account_id): Extension<AccountId>,
-) -> Result<Json<Language>, StatusCode> {
+) -> Result<Json<Language>, GetLanguageError> {
     match routine.get_language(account_id).await {
         Ok(language) => Ok(Json(language)),
         Err(e) => {
@@ -39,7 +40,13 @@ pub async fn handler(
                 error = %e,
                 "アカウントの言語設定を取得できませんでした。"
             );
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
+
+impl IntoResponse for GetLanguageError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
\ No newline at end of file
@@ -1,21 +1,22 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::post, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
 use axum_macros::debug_handler;
-use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::info;
 
-use crate::{common::profile::{account_id::AccountId, region::Region}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::profile::{account_id::AccountId, region::Region}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::SetRegion, interpreter::SetRegionImpl};
+use super::{dsl::{SetRegion, SetRegionError}, interpreter::SetRegionImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<SetRegionImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "setrg", 5, 1, TimeUnit::HOURS).await?)
-        .layer(session_manager(db.clone(), cache).await?);
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache, "setrg", 5, 1, TimeUnit::HOURS).await?);
 
     let set_region = SetRegionImpl::try_new(db).await?;
 
@@ -32,7 +33,7 @@ pub async fn handler(
     State(routine): State<Arc<SetRegionImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>,
-) -> Result<(), StatusCode> {
+) -> Result<(), SetRegionError> {
     match routine.set_region(account_id, payload.region).await {
         Ok(()) => Ok(()),
         Err(e) => {
@@ -40,11 +41,17 @@ pub async fn handler(
                 error = %e,
                 "アカウントの言語設定を変更できませんでした。"
             );
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for SetRegionError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Payload {
     region: Region
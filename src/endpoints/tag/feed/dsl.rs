@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{feed::{merge_post_indexes, next_cursor, Feed}, id::PostId, summary::PostSummary}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId}, endpoints::handle_filter::hidden::exclude_hidden_handles};
+
+// 1ページあたりの投稿数
+pub const FEED_PAGE_SIZE: usize = 20;
+
+// 展開するタグ数の上限
+// 上位のタグほど下位タグが多くなるため、索引の読み込み回数を制限する
+pub const MAX_EXPANDED_TAGS: usize = 50;
+
+pub(crate) trait GetTagFeed {
+    async fn get_tag_feed(&self, account_id: AccountId, tag_id: NonTopTagId, before: Option<PostId>) -> Fallible<Feed, GetTagFeedError> {
+        // 安定した下位タグと同値タグのみに展開し、未安定の提案による混入を防ぐ
+        let mut tag_ids = vec![tag_id];
+
+        for related_tag_id in self.fetch_stable_subtags_and_equivalents(tag_id).await? {
+            if tag_ids.len() >= MAX_EXPANDED_TAGS {
+                break;
+            }
+
+            if !tag_ids.contains(&related_tag_id) {
+                tag_ids.push(related_tag_id);
+            }
+        }
+
+        let mut indexes = Vec::with_capacity(tag_ids.len());
+
+        for tag_id in tag_ids {
+            indexes.push(self.fetch_post_ids_by_tag(tag_id, before, FEED_PAGE_SIZE).await?);
+        }
+
+        let post_ids = merge_post_indexes(indexes, FEED_PAGE_SIZE);
+        let next_cursor = next_cursor(&post_ids, FEED_PAGE_SIZE);
+
+        // カーソルは除外前の索引から求めるため、除外によってページが短くなっても続きを取得できる
+        let mut posts = self.fetch_posts(&post_ids).await?;
+        exclude_hidden_handles(&mut posts, &self.fetch_hidden_handles(account_id).await?);
+
+        Ok(Feed::new(posts, next_cursor))
+    }
+
+    // ブロック・ミュートした名義
+    async fn fetch_hidden_handles(&self, account_id: AccountId) -> Fallible<HashSet<HandleId>, GetTagFeedError>;
+
+    async fn fetch_stable_subtags_and_equivalents(&self, tag_id: NonTopTagId) -> Fallible<Vec<NonTopTagId>, GetTagFeedError>;
+
+    // `before`より古い投稿を新しい順に`limit`件まで取得する
+    async fn fetch_post_ids_by_tag(&self, tag_id: NonTopTagId, before: Option<PostId>, limit: usize) -> Fallible<Vec<PostId>, GetTagFeedError>;
+
+    async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetTagFeedError>;
+}
+
+#[derive(Debug, Error)]
+pub enum GetTagFeedError {
+    #[error("関連タグの取得に失敗しました")]
+    FetchRelatedTagsFailed(#[source] anyhow::Error),
+    #[error("タグ別の投稿の取得に失敗しました")]
+    FetchPostIdsFailed(#[source] anyhow::Error),
+    #[error("投稿の取得に失敗しました")]
+    FetchPostsFailed(#[source] anyhow::Error),
+    #[error("ブロック・ミュートした名義の取得に失敗しました")]
+    FetchHiddenHandlesFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::{HashMap, HashSet}, sync::{LazyLock, Mutex}};
+
+    use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{id::PostId, summary::PostSummary, tags::PostTags}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId, unixtime::UnixtimeMillis}, helper::test::mock_non_top_tag_id};
+
+    use super::{GetTagFeed, GetTagFeedError, FEED_PAGE_SIZE};
+
+    static TAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(1));
+    static SUBTAG_ID: LazyLock<NonTopTagId> = LazyLock::new(|| mock_non_top_tag_id(2));
+    static HIDDEN_HANDLE_ID: LazyLock<HandleId> = LazyLock::new(HandleId::gen);
+
+    struct MockGetTagFeed {
+        indexes: HashMap<NonTopTagId, Vec<PostId>>,
+        requested_tags: Mutex<Vec<NonTopTagId>>,
+        hidden_post_ids: Vec<PostId>,
+    }
+
+    impl GetTagFeed for MockGetTagFeed {
+        async fn fetch_stable_subtags_and_equivalents(&self, tag_id: NonTopTagId) -> Fallible<Vec<NonTopTagId>, GetTagFeedError> {
+            if tag_id == *TAG_ID {
+                Ok(vec![*SUBTAG_ID, *SUBTAG_ID])
+            } else {
+                Ok(vec![])
+            }
+        }
+
+        async fn fetch_post_ids_by_tag(&self, tag_id: NonTopTagId, before: Option<PostId>, limit: usize) -> Fallible<Vec<PostId>, GetTagFeedError> {
+            self.requested_tags.lock().unwrap().push(tag_id);
+
+            Ok(self.indexes
+                .get(&tag_id)
+                .map(|ids| ids.iter().filter(|id| before.is_none_or(|before| **id < before)).take(limit).copied().collect())
+                .unwrap_or_default())
+        }
+
+        async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetTagFeedError> {
+            Ok(post_ids.iter()
+                .map(|id| {
+                    let handle_id = if self.hidden_post_ids.contains(id) { *HIDDEN_HANDLE_ID } else { HandleId::gen() };
+                    PostSummary::new(*id, handle_id, "本文".parse().unwrap(), PostTags::default(), UnixtimeMillis::now(), None)
+                })
+                .collect())
+        }
+
+        async fn fetch_hidden_handles(&self, _: AccountId) -> Fallible<HashSet<HandleId>, GetTagFeedError> {
+            Ok(HashSet::from([*HIDDEN_HANDLE_ID]))
+        }
+    }
+
+    fn mock(tag_posts: usize, subtag_posts: usize) -> (MockGetTagFeed, Vec<PostId>) {
+        let mut ids = (0..tag_posts + subtag_posts).map(|_| PostId::gen()).collect::<Vec<_>>();
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+
+        // 親タグと下位タグに交互に振り分ける
+        let (mut tag, mut subtag) = (vec![], vec![]);
+        for (i, id) in ids.iter().enumerate() {
+            if i % 2 == 0 && tag.len() < tag_posts || subtag.len() >= subtag_posts {
+                tag.push(*id);
+            } else {
+                subtag.push(*id);
+            }
+        }
+
+        let indexes = HashMap::from([(*TAG_ID, tag), (*SUBTAG_ID, subtag)]);
+        (MockGetTagFeed { indexes, requested_tags: Mutex::new(vec![]), hidden_post_ids: vec![] }, ids)
+    }
+
+    #[tokio::test]
+    async fn expand_to_subtags() {
+        let (mock, ids) = mock(3, 3);
+        let feed = mock.get_tag_feed(AccountId::gen(), *TAG_ID, None).await.unwrap();
+
+        let post_ids = feed.posts().iter().map(PostSummary::post_id).collect::<Vec<_>>();
+        assert_eq!(post_ids, ids);
+        assert_eq!(feed.next_cursor(), None);
+
+        // 重複した関連タグは一度だけ読み込む
+        assert_eq!(*mock.requested_tags.lock().unwrap(), vec![*TAG_ID, *SUBTAG_ID]);
+    }
+
+    #[tokio::test]
+    async fn paginate() {
+        let (mock, ids) = mock(FEED_PAGE_SIZE, FEED_PAGE_SIZE);
+
+        let first = mock.get_tag_feed(AccountId::gen(), *TAG_ID, None).await.unwrap();
+        assert_eq!(first.next_cursor(), Some(ids[FEED_PAGE_SIZE - 1]));
+
+        let second = mock.get_tag_feed(AccountId::gen(), *TAG_ID, first.next_cursor()).await.unwrap();
+        let post_ids = second.posts().iter().map(PostSummary::post_id).collect::<Vec<_>>();
+        assert_eq!(post_ids, ids[FEED_PAGE_SIZE..]);
+    }
+
+    #[tokio::test]
+    async fn exclude_hidden_handles() {
+        let (mut mock, ids) = mock(FEED_PAGE_SIZE, FEED_PAGE_SIZE);
+        mock.hidden_post_ids = vec![ids[0]];
+
+        let feed = mock.get_tag_feed(AccountId::gen(), *TAG_ID, None).await.unwrap();
+
+        let post_ids = feed.posts().iter().map(PostSummary::post_id).collect::<Vec<_>>();
+        assert_eq!(post_ids, ids[1..FEED_PAGE_SIZE]);
+        assert_eq!(feed.next_cursor(), Some(ids[FEED_PAGE_SIZE - 1]));
+    }
+}
\ No newline at end of file
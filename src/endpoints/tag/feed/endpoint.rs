@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, Query, State}, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
+use scylla::Session;
+use serde::Deserialize;
+use tower::ServiceBuilder;
+use tracing::error;
+
+use crate::{common::{post::{feed::Feed, id::PostId}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{GetTagFeed, GetTagFeedError}, interpreter::GetTagFeedImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<GetTagFeedImpl>> {
+    let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
+    .layer(rate_limiter(db.clone(), cache.clone(), "tgfed", 90, 15, TimeUnit::MINS).await?)
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache.clone(), "tgfed", 90, 15, TimeUnit::MINS).await?);
+
+    let interpreter = GetTagFeedImpl::try_new(db, cache).await?;
+
+    let router = Router::new()
+        .route("/tags/:id/feed", get(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<GetTagFeedImpl>>,
+    Extension(account_id): Extension<AccountId>,
+    Path(tag_id): Path<NonTopTagId>,
+    Query(query): Query<FeedQuery>
+) -> Result<Json<Feed>, GetTagFeedError> {
+    match routine.get_tag_feed(account_id, tag_id, query.before).await {
+        Ok(feed) => Ok(Json(feed)),
+        Err(e) => {
+            error!(
+                error = %e,
+                account_id = %account_id,
+                tag_id = %tag_id,
+                "タグのフィードの取得に失敗しました"
+            );
+            Err(e)
+        }
+    }
+}
+
+impl IntoResponse for GetTagFeedError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct FeedQuery {
+    before: Option<PostId>,
+}
\ No newline at end of file
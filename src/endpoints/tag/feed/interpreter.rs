@@ -0,0 +1,74 @@
+use std::{collections::HashSet, sync::Arc};
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, handle::id::HandleId, post::{id::PostId, summary::PostSummary}, profile::account_id::AccountId, tag::non_top_tag::NonTopTagId}, endpoints::{handle_filter::hidden::HiddenHandleFetcher, post::summary::PostSummaryFetcher, tag::related::StableRelatedTagFetcher}, helper::{error::InitError, redis::connection::Pool, scylla::prepare}};
+
+use super::dsl::{GetTagFeed, GetTagFeedError};
+
+pub struct GetTagFeedImpl {
+    db: Arc<Session>,
+    related_tag_fetcher: StableRelatedTagFetcher,
+    post_summary_fetcher: PostSummaryFetcher,
+    hidden_handle_fetcher: HiddenHandleFetcher,
+    select_latest_post_ids: Arc<PreparedStatement>,
+    select_post_ids_before: Arc<PreparedStatement>,
+}
+
+impl GetTagFeedImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>) -> Result<Self, InitError<Self>> {
+        let related_tag_fetcher = StableRelatedTagFetcher::new(cache);
+
+        let post_summary_fetcher = PostSummaryFetcher::try_new(db.clone()).await?;
+
+        let hidden_handle_fetcher = HiddenHandleFetcher::try_new(db.clone()).await?;
+
+        let select_latest_post_ids = prepare(&db, "SELECT post_id FROM posts_by_tag WHERE tag_id = ? LIMIT ?").await?;
+
+        let select_post_ids_before = prepare(&db, "SELECT post_id FROM posts_by_tag WHERE tag_id = ? AND post_id < ? LIMIT ?").await?;
+
+        Ok(Self { db, related_tag_fetcher, post_summary_fetcher, hidden_handle_fetcher, select_latest_post_ids, select_post_ids_before })
+    }
+}
+
+impl GetTagFeed for GetTagFeedImpl {
+    async fn fetch_stable_subtags_and_equivalents(&self, tag_id: NonTopTagId) -> Fallible<Vec<NonTopTagId>, GetTagFeedError> {
+        self.related_tag_fetcher
+            .fetch(tag_id)
+            .await
+            .map_err(GetTagFeedError::FetchRelatedTagsFailed)
+    }
+
+    async fn fetch_post_ids_by_tag(&self, tag_id: NonTopTagId, before: Option<PostId>, limit: usize) -> Fallible<Vec<PostId>, GetTagFeedError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> GetTagFeedError {
+            GetTagFeedError::FetchPostIdsFailed(e.into())
+        }
+
+        let limit = limit as i32;
+
+        let result = match before {
+            Some(before) => self.db.execute_unpaged(&self.select_post_ids_before, (tag_id, before, limit)).await,
+            None => self.db.execute_unpaged(&self.select_latest_post_ids, (tag_id, limit)).await,
+        };
+
+        result.map_err(handle_error)?
+            .rows_typed::<(PostId, )>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(post_id, )| post_id).map_err(handle_error))
+            .collect()
+    }
+
+    async fn fetch_posts(&self, post_ids: &[PostId]) -> Fallible<Vec<PostSummary>, GetTagFeedError> {
+        self.post_summary_fetcher
+            .fetch(post_ids)
+            .await
+            .map_err(GetTagFeedError::FetchPostsFailed)
+    }
+
+    async fn fetch_hidden_handles(&self, account_id: AccountId) -> Fallible<HashSet<HandleId>, GetTagFeedError> {
+        self.hidden_handle_fetcher
+            .fetch(account_id)
+            .await
+            .map_err(GetTagFeedError::FetchHiddenHandlesFailed)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod dsl;
+pub mod endpoint;
+pub mod interpreter;
\ No newline at end of file
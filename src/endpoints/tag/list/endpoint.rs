@@ -8,12 +8,13 @@ use serde::Serialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{page::ZeroBasedPage, tag::{hierarchy::TagHierarchy, tag_id::TagId, tag_info::TagInfo}}, helper::{cache::{check_if_none_match, create_etag}, error::InitError, middleware::rate_limiter, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{page::ZeroBasedPage, tag::{hierarchy::TagHierarchy, tag_id::TagId, tag_info::TagInfo}}, helper::{api_error::ApiError, cache::{check_if_none_match, create_etag}, error::InitError, middleware::{error_localizer, rate_limiter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::ListRelatedTags, interpreter::ListRelatedTagsImpl};
+use super::{dsl::{ListRelatedTags, ListRelatedTagsError}, interpreter::ListRelatedTagsImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ListRelatedTagsImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db, cache.clone(), "lstrl", 90, 15, TimeUnit::MINS).await?);
 
     let interpreter = ListRelatedTagsImpl::try_new(cache).await?;
@@ -31,7 +32,7 @@ pub async fn handler(
     State(routine): State<Arc<ListRelatedTagsImpl>>,
     Path((tag_id, hierarchy, page, is_signed_in)): Path<(TagId, TagHierarchy, ZeroBasedPage, bool)>,
     headers: HeaderMap
-) -> Result<Response, StatusCode> {
+) -> Result<Response, ListRelatedTagsError> {
     match routine.list_related_tags(tag_id, hierarchy, page).await {
         Ok(tags) => {
             if is_signed_in {
@@ -65,11 +66,17 @@ pub async fn handler(
                 is_signed_in = %is_signed_in,
                 "タグリストの取得に失敗しました"
             );
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for ListRelatedTagsError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct Data {
     tags: Vec<TagInfo>,
@@ -1,6 +1,8 @@
+pub mod feed;
 pub mod list;
 pub mod proposal;
 pub mod rating;
+pub mod related;
 pub mod search;
 
 const PROPOSER_FLAG: i8 = 127;
\ No newline at end of file
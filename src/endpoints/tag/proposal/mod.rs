@@ -1,2 +1,15 @@
+use crate::middlewares::{limit::Count, quota_limit::{dsl::ChargedStatuses, policy::ContributionQuotaPolicy}};
+
 pub mod propose;
-pub mod withdraw;
\ No newline at end of file
+pub mod quota;
+pub mod withdraw;
+
+// 提案のクォータは、提案エンドポイントとクォータ確認エンドポイントで共有する
+const PROPOSAL_QUOTA_ENDPOINT_NAME: &str = "prtrl";
+const PROPOSAL_QUOTA_TIME_WINDOW_DAYS: u32 = 1;
+
+// 1日1件から始まり、採用された提案5件ごとに1件、評価の一致率に応じて最大4件が加算され、1日10件で頭打ちになる
+const PROPOSAL_QUOTA_POLICY: ContributionQuotaPolicy = ContributionQuotaPolicy::new(Count::new(1), 5, 20, 4, Count::new(10));
+
+// 不正な提案(400)などではクォータを消費しない
+const PROPOSAL_QUOTA_CHARGED_STATUSES: ChargedStatuses = ChargedStatuses::ONLY_OK;
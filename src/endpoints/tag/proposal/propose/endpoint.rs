@@ -1,21 +1,23 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::post, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Extension, Json, Router};
 use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{error::InitError, middleware::{quota_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, quota_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::propose::{ProposeTagRelation, ProposeTagRelationError}, interpreter::ProposeTagRelationImpl};
+use super::{super::{PROPOSAL_QUOTA_CHARGED_STATUSES, PROPOSAL_QUOTA_ENDPOINT_NAME, PROPOSAL_QUOTA_POLICY, PROPOSAL_QUOTA_TIME_WINDOW_DAYS}, dsl::{propose::{ProposeTagRelation, ProposeTagRelationError}, validate_topology::ValidateTopologyError}, interpreter::ProposeTagRelationImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<ProposeTagRelationImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "prtrl", 100, 15, TimeUnit::MINS).await?)
         .layer(session_manager(db.clone(), cache.clone()).await?)
-        .layer(quota_limiter(db.clone(), cache.clone(), "prtrl", 1, TimeUnit::DAYS).await?);
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "prtrl", 100, 15, TimeUnit::MINS).await?)
+        .layer(quota_limiter(db.clone(), cache.clone(), PROPOSAL_QUOTA_ENDPOINT_NAME, PROPOSAL_QUOTA_TIME_WINDOW_DAYS, TimeUnit::DAYS, PROPOSAL_QUOTA_POLICY, PROPOSAL_QUOTA_CHARGED_STATUSES).await?);
 
     let interpreter = ProposeTagRelationImpl::try_new(db, cache).await?;
 
@@ -31,12 +33,12 @@ pub async fn handler(
     State(routine): State<Arc<ProposeTagRelationImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>
-) -> StatusCode {
+) -> Result<StatusCode, ProposeTagRelationError> {
     match routine.propose_tag_relation(account_id, payload.subtag_id, payload.supertag_id, payload.relation).await {
-        Ok(()) => StatusCode::OK,
+        Ok(()) => Ok(StatusCode::OK),
         Err(e) => match e {
-            ProposeTagRelationError::InvalidTopology(_) | ProposeTagRelationError::HasAlreadyBeenProposed
-            | ProposeTagRelationError::NonExistentTag | ProposeTagRelationError::DifferentLanguageGroups => StatusCode::BAD_REQUEST,
+            ProposeTagRelationError::InvalidTopology(ValidateTopologyError::IsNotAcyclic | ValidateTopologyError::IsNotEquivalent) | ProposeTagRelationError::HasAlreadyBeenProposed
+            | ProposeTagRelationError::NonExistentTag | ProposeTagRelationError::DifferentLanguageGroups => Err(e),
             _ => {
                 error!(
                     error = %e,
@@ -47,12 +49,25 @@ pub async fn handler(
                     "タグ関係の提案に失敗しました"
                 );
     
-                StatusCode::INTERNAL_SERVER_ERROR
+                Err(e)
             }
         }
     }
 }
 
+impl IntoResponse for ProposeTagRelationError {
+    fn into_response(self) -> Response {
+        match self {
+            ProposeTagRelationError::InvalidTopology(ValidateTopologyError::IsNotAcyclic) => ApiError::CYCLIC_TAG_RELATION,
+            ProposeTagRelationError::InvalidTopology(ValidateTopologyError::IsNotEquivalent) => ApiError::NOT_EQUIVALENT_TAGS,
+            ProposeTagRelationError::HasAlreadyBeenProposed => ApiError::ALREADY_PROPOSED,
+            ProposeTagRelationError::NonExistentTag => ApiError::NON_EXISTENT_TAG,
+            ProposeTagRelationError::DifferentLanguageGroups => ApiError::DIFFERENT_LANGUAGE_GROUPS,
+            _ => ApiError::INTERNAL,
+        }.into_response()
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Payload {
     subtag_id: NonTopTagId,
@@ -0,0 +1,96 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{common::{fallible::Fallible, profile::account_id::AccountId}, middlewares::{limit::{Count, InculsiveLimit}, quota_limit::dsl::ConsumedQuota}};
+
+pub(crate) trait GetProposalQuota {
+    async fn get_proposal_quota(&self, account_id: AccountId) -> Fallible<ProposalQuota, GetProposalQuotaError> {
+        let limit = self.fetch_personal_limit(account_id).await?;
+
+        let consumed = self.fetch_consumed_quota(account_id)
+            .await?
+            .unwrap_or_else(|| ConsumedQuota::new(0));
+
+        Ok(ProposalQuota::new(limit, consumed))
+    }
+
+    async fn fetch_personal_limit(&self, account_id: AccountId) -> Fallible<InculsiveLimit, GetProposalQuotaError>;
+
+    async fn fetch_consumed_quota(&self, account_id: AccountId) -> Fallible<Option<ConsumedQuota>, GetProposalQuotaError>;
+}
+
+#[derive(Debug, Error)]
+pub enum GetProposalQuotaError {
+    #[error("個人のクォータ上限の取得に失敗しました")]
+    FetchPersonalLimitFailed(#[source] anyhow::Error),
+    #[error("消費クォータの取得に失敗しました")]
+    FetchConsumedQuotaFailed(#[source] anyhow::Error),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct ProposalQuota {
+    limit: InculsiveLimit,
+    consumed: ConsumedQuota,
+    remaining: Count,
+}
+
+impl ProposalQuota {
+    pub fn new(limit: InculsiveLimit, consumed: ConsumedQuota) -> Self {
+        // 上限が引き下げられた場合、消費クォータが上限を超えていることがある
+        let remaining = Count::new(limit.value().value().saturating_sub(consumed.value()));
+        Self { limit, consumed, remaining }
+    }
+
+    pub fn remaining(&self) -> Count {
+        self.remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use crate::{common::{fallible::Fallible, profile::account_id::AccountId}, middlewares::{limit::{Count, InculsiveLimit}, quota_limit::dsl::ConsumedQuota}};
+
+    use super::{GetProposalQuota, GetProposalQuotaError};
+
+    struct MockGetProposalQuota;
+
+    static UNCONSUMED: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static CONSUMED: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static OVERCONSUMED: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    impl GetProposalQuota for MockGetProposalQuota {
+        async fn fetch_personal_limit(&self, _: AccountId) -> Fallible<InculsiveLimit, GetProposalQuotaError> {
+            Ok(InculsiveLimit::new(Count::new(3)))
+        }
+
+        async fn fetch_consumed_quota(&self, account_id: AccountId) -> Fallible<Option<ConsumedQuota>, GetProposalQuotaError> {
+            if account_id == *UNCONSUMED {
+                Ok(None)
+            } else if account_id == *CONSUMED {
+                Ok(Some(ConsumedQuota::new(2)))
+            } else {
+                Ok(Some(ConsumedQuota::new(5)))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn unconsumed() {
+        let quota = MockGetProposalQuota.get_proposal_quota(*UNCONSUMED).await.unwrap();
+        assert_eq!(quota.remaining(), Count::new(3));
+    }
+
+    #[tokio::test]
+    async fn consumed() {
+        let quota = MockGetProposalQuota.get_proposal_quota(*CONSUMED).await.unwrap();
+        assert_eq!(quota.remaining(), Count::new(1));
+    }
+
+    #[tokio::test]
+    async fn overconsumed() {
+        let quota = MockGetProposalQuota.get_proposal_quota(*OVERCONSUMED).await.unwrap();
+        assert_eq!(quota.remaining(), Count::new(0));
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
+use scylla::Session;
+use tower::ServiceBuilder;
+
+use crate::{common::profile::account_id::AccountId, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+
+use super::{dsl::{GetProposalQuota, GetProposalQuotaError, ProposalQuota}, interpreter::GetProposalQuotaImpl};
+
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<GetProposalQuotaImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
+        .layer(rate_limiter(db.clone(), cache.clone(), "gtprq", 60, 15, TimeUnit::MINS).await?)
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "gtprq", 60, 15, TimeUnit::MINS).await?);
+
+    let interpreter = GetProposalQuotaImpl::try_new(db, cache).await?;
+
+    let router = Router::new()
+        .route("/proposals/quota", get(handler))
+        .layer(services)
+        .with_state(Arc::new(interpreter));
+
+    Ok(router)
+}
+
+pub async fn handler(
+    State(routine): State<Arc<GetProposalQuotaImpl>>,
+    Extension(account_id): Extension<AccountId>
+) -> Result<Json<ProposalQuota>, GetProposalQuotaError> {
+    routine.get_proposal_quota(account_id)
+        .await
+        .map(Json)
+}
+
+impl IntoResponse for GetProposalQuotaError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use scylla::Session;
+
+use crate::{common::{fallible::Fallible, profile::account_id::AccountId}, endpoints::tag::proposal::{PROPOSAL_QUOTA_CHARGED_STATUSES, PROPOSAL_QUOTA_ENDPOINT_NAME, PROPOSAL_QUOTA_POLICY, PROPOSAL_QUOTA_TIME_WINDOW_DAYS}, helper::{error::InitError, redis::{connection::Pool, namespace::Namespace}}, middlewares::{limit::{EndpointName, InculsiveLimit, TimeUnit}, quota_limit::{dsl::{ConsumedQuota, QuotaLimit}, interpreter::QuotaLimitImpl}}};
+
+use super::dsl::{GetProposalQuota, GetProposalQuotaError};
+
+// 提案エンドポイントのクォータ制限と同じ設定で参照する
+pub struct GetProposalQuotaImpl {
+    quota_limit: QuotaLimitImpl,
+}
+
+impl GetProposalQuotaImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>) -> Result<Self, InitError<Self>> {
+        let endpoint_name = EndpointName::new(Namespace::of(PROPOSAL_QUOTA_ENDPOINT_NAME));
+        let time_window = TimeUnit::DAYS.apply(PROPOSAL_QUOTA_TIME_WINDOW_DAYS);
+
+        let quota_limit = QuotaLimitImpl::try_new(db, cache, endpoint_name, time_window, PROPOSAL_QUOTA_POLICY, PROPOSAL_QUOTA_CHARGED_STATUSES)
+            .await
+            .map_err(|e| InitError::new(e.into()))?;
+
+        Ok(Self { quota_limit })
+    }
+}
+
+impl GetProposalQuota for GetProposalQuotaImpl {
+    async fn fetch_personal_limit(&self, account_id: AccountId) -> Fallible<InculsiveLimit, GetProposalQuotaError> {
+        self.quota_limit
+            .fetch_personal_limit(account_id)
+            .await
+            .map_err(|e| GetProposalQuotaError::FetchPersonalLimitFailed(e.into()))
+    }
+
+    async fn fetch_consumed_quota(&self, account_id: AccountId) -> Fallible<Option<ConsumedQuota>, GetProposalQuotaError> {
+        self.quota_limit
+            .fetch_consumed_quota(account_id)
+            .await
+            .map_err(|e| GetProposalQuotaError::FetchConsumedQuotaFailed(e.into()))
+    }
+}
@@ -1,19 +1,21 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::delete, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::delete, Extension, Json, Router};
 use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 
-use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::{dsl::{WithdrawTagRelationProposal, WithdrawTagRelationProposalError}, interpreter::WithdrawTagRelationProposalImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<WithdrawTagRelationProposalImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache.clone(), "wttrl", 100, 1, TimeUnit::HOURS).await?)
-        .layer(session_manager(db.clone(), cache.clone()).await?);
+        .layer(session_manager(db.clone(), cache.clone()).await?)
+        .layer(account_rate_limiter(db.clone(), cache.clone(), "wttrl", 100, 1, TimeUnit::HOURS).await?);
 
     let interpreter = WithdrawTagRelationProposalImpl::try_new(db, cache).await?;
 
@@ -29,13 +31,19 @@ pub async fn handler(
     State(routine): State<Arc<WithdrawTagRelationProposalImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>
-) -> StatusCode {
-    match routine.withdraw_tag_relation_proposal(account_id, payload.subtag_id, payload.supertag_id, payload.relation).await {
-        Ok(()) => StatusCode::OK,
-        Err(e) => match e {
-            WithdrawTagRelationProposalError::NotProposer | WithdrawTagRelationProposalError::CannotWithdraw => StatusCode::FORBIDDEN,
-            _ => StatusCode::INTERNAL_SERVER_ERROR
-        }
+) -> Result<StatusCode, WithdrawTagRelationProposalError> {
+    routine.withdraw_tag_relation_proposal(account_id, payload.subtag_id, payload.supertag_id, payload.relation)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+impl IntoResponse for WithdrawTagRelationProposalError {
+    fn into_response(self) -> Response {
+        match self {
+            WithdrawTagRelationProposalError::NotProposer => ApiError::NOT_PROPOSER,
+            WithdrawTagRelationProposalError::CannotWithdraw => ApiError::CANNOT_WITHDRAW,
+            _ => ApiError::INTERNAL,
+        }.into_response()
     }
 }
 
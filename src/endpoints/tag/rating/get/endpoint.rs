@@ -1,21 +1,22 @@
 use std::sync::Arc;
 
-use axum::{extract::{Path, State}, routing::get, Extension, Json, Router};
+use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::get, Extension, Json, Router};
 use axum_macros::debug_handler;
-use http::StatusCode;
 use scylla::Session;
 use serde::Serialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::GetTagRelationProposalOperation, interpreter::GetTagRelationRatingImpl};
+use super::{dsl::{GetTagRelationProposalOperation, GetTagRelationProposalOperationError}, interpreter::GetTagRelationRatingImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<GetTagRelationRatingImpl>> {
     let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
     .layer(rate_limiter(db.clone(), cache.clone(), "gttrr", 300, 15, TimeUnit::HOURS).await?)
-    .layer(session_manager(db.clone(), cache).await?);
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "gttrr", 300, 15, TimeUnit::HOURS).await?);
 
     let interpreter = GetTagRelationRatingImpl::try_new(db).await?;
 
@@ -32,7 +33,7 @@ pub async fn handler(
     State(routine): State<Arc<GetTagRelationRatingImpl>>,
     Extension(account_id): Extension<AccountId>,
     Path((subtag_id, supertag_id, relation)): Path<(NonTopTagId, NonTopTagId, TagRelation)>
-) -> Result<Json<Data>, StatusCode> {
+) -> Result<Json<Data>, GetTagRelationProposalOperationError> {
     match routine.get_tag_relation_proposal_operation(account_id, subtag_id, supertag_id, relation).await {
         Ok(Some(operation)) => Ok(Json(Data { operation: Some(operation as u8) })),
         Ok(None) => Ok(Json(Data { operation: None })),
@@ -46,12 +47,18 @@ pub async fn handler(
                 "タグ関係の提案への評価の取得に失敗しました"
             );
 
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         },
     }
 }
 
+impl IntoResponse for GetTagRelationProposalOperationError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct Data {
     operation: Option<u8>,
-}
+}
\ No newline at end of file
@@ -1,20 +1,22 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::put, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::put, Extension, Json, Router};
 use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{profile::account_id::AccountId, rating::Rating, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{profile::account_id::AccountId, rating::Rating, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::{dsl::{RateTagRelation, RateTagRelationError}, interpreter::RateTagRelationImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<RateTagRelationImpl>> {
     let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
     .layer(rate_limiter(db.clone(), cache.clone(), "rttrl", 150, 15, TimeUnit::MINS).await?)
-    .layer(session_manager(db.clone(), cache).await?);
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "rttrl", 150, 15, TimeUnit::MINS).await?);
 
     let interpreter = RateTagRelationImpl::try_new(db).await?;
 
@@ -30,10 +32,10 @@ pub async fn handler(
     State(routine): State<Arc<RateTagRelationImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>
-) -> StatusCode {
+) -> Result<StatusCode, RateTagRelationError> {
     match routine.rate_tag_relation(account_id, payload.subtag_id, payload.supertag_id, payload.relation, payload.rating).await {
-        Ok(()) => StatusCode::OK,
-        Err(RateTagRelationError::RateTagRelationFailed(e)) => {
+        Ok(()) => Ok(StatusCode::OK),
+        Err(e @ RateTagRelationError::RateTagRelationFailed(_)) => {
             error!(
                 error = %e,
                 account_id = %account_id,
@@ -43,9 +45,18 @@ pub async fn handler(
                 rating = ?payload.rating,
                 "タグ関係の評価に失敗しました"
             );
-            StatusCode::INTERNAL_SERVER_ERROR
+            Err(e)
         },
-        Err(_) => StatusCode::BAD_REQUEST,
+        Err(e) => Err(e),
+    }
+}
+
+impl IntoResponse for RateTagRelationError {
+    fn into_response(self) -> Response {
+        match self {
+            RateTagRelationError::NonProposedTagRelation => ApiError::NON_PROPOSED_TAG_RELATION,
+            _ => ApiError::INTERNAL,
+        }.into_response()
     }
 }
 
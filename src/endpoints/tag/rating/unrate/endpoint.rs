@@ -1,20 +1,22 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::delete, Extension, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::delete, Extension, Json, Router};
 use http::StatusCode;
 use scylla::Session;
 use serde::Deserialize;
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{error::InitError, middleware::{rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::{profile::account_id::AccountId, tag::{non_top_tag::NonTopTagId, relation::TagRelation}}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, account_rate_limiter, rate_limiter, session_manager}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
 use super::{dsl::{UnrateTagRelation, UnrateTagRelationError}, interpreter::UnrateTagRelationImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>) -> Result<Router, InitError<UnrateTagRelationImpl>> {
     let services = ServiceBuilder::new()
+    .layer(error_localizer(db.clone()).await?)
     .layer(rate_limiter(db.clone(), cache.clone(), "urtrl", 150, 15, TimeUnit::MINS).await?)
-    .layer(session_manager(db.clone(), cache).await?);
+    .layer(session_manager(db.clone(), cache.clone()).await?)
+    .layer(account_rate_limiter(db.clone(), cache, "urtrl", 150, 15, TimeUnit::MINS).await?);
 
     let interpreter = UnrateTagRelationImpl::try_new(db).await?;
 
@@ -30,10 +32,10 @@ pub async fn handler(
     State(routine): State<Arc<UnrateTagRelationImpl>>,
     Extension(account_id): Extension<AccountId>,
     Json(payload): Json<Payload>
-) -> StatusCode {
+) -> Result<StatusCode, UnrateTagRelationError> {
     match routine.unrate_tag_relation(account_id, payload.subtag_id, payload.supertag_id, payload.relation).await {
-        Ok(()) => StatusCode::OK,
-        Err(UnrateTagRelationError::UnrateTagRelationFailed(e)) => {
+        Ok(()) => Ok(StatusCode::OK),
+        Err(e @ UnrateTagRelationError::UnrateTagRelationFailed(_)) => {
             error!(
                 error = %e,
                 account_id = %account_id,
@@ -42,9 +44,18 @@ pub async fn handler(
                 inclusion_or_equivalence = ?payload.relation,
                 "タグ関係の評価の取り消しに失敗しました"
             );
-            StatusCode::INTERNAL_SERVER_ERROR
+            Err(e)
         },
-        Err(_) => StatusCode::BAD_REQUEST,
+        Err(e) => Err(e),
+    }
+}
+
+impl IntoResponse for UnrateTagRelationError {
+    fn into_response(self) -> Response {
+        match self {
+            UnrateTagRelationError::NonProposedTagRelation => ApiError::NON_PROPOSED_TAG_RELATION,
+            _ => ApiError::INTERNAL,
+        }.into_response()
     }
 }
 
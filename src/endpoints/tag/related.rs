@@ -0,0 +1,50 @@
+use std::{str::FromStr, sync::Arc};
+
+use anyhow::anyhow;
+use redis::cmd;
+use uuid::Uuid;
+
+use crate::{common::{tag::{non_top_tag::NonTopTagId, redis_tag_info::RedisTagInfo, tag_id::TagId}, uuid::uuid4::Uuid4}, helper::redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}, namespaces::{EQUIVALENT, SUB, TAG_LIST}}};
+
+// フィードなどで共有する、安定した下位タグと同値タグの取得
+pub struct StableRelatedTagFetcher {
+    cache: Arc<Pool>,
+}
+
+impl StableRelatedTagFetcher {
+    pub fn new(cache: Arc<Pool>) -> Self {
+        Self { cache }
+    }
+
+    // 同値タグ、下位タグの順に返す
+    pub async fn fetch(&self, tag_id: NonTopTagId) -> anyhow::Result<Vec<NonTopTagId>> {
+        let mut tag_ids = self.fetch_stable_tags(tag_id, EQUIVALENT).await?;
+        tag_ids.extend(self.fetch_stable_tags(tag_id, SUB).await?);
+
+        Ok(tag_ids)
+    }
+
+    async fn fetch_stable_tags(&self, tag_id: NonTopTagId, namespace: Namespace) -> anyhow::Result<Vec<NonTopTagId>> {
+        let mut conn = conn(&self.cache, anyhow::Error::from).await?;
+
+        // タグリストには推移閉包と未安定の提案の両方が含まれる
+        cmd("ZRANGE")
+            .arg(format!("{}{}{}{}{}", TAG_LIST, NAMESPACE_SEPARATOR, tag_id, NAMESPACE_SEPARATOR, namespace))
+            .arg(0)
+            .arg(-1)
+            .arg("WITHSCORES")
+            .query_async::<Vec<(String, RedisTagInfo)>>(&mut *conn) // メンバー, スコア の順で返される
+            .await?
+            .into_iter()
+            .filter(|(_, info)| info.is_stable())
+            .map(|(id_and_name, _)| {
+                id_and_name.split('$')
+                    .next()
+                    .and_then(|s| Uuid::from_str(s).ok())
+                    .and_then(|uuid| Uuid4::try_from(uuid).ok())
+                    .and_then(|uuid| NonTopTagId::try_from(TagId::of(uuid)).ok())
+                    .ok_or_else(|| anyhow!("タグリストの要素の解析に失敗しました: {}", id_and_name))
+            })
+            .collect()
+    }
+}
\ No newline at end of file
@@ -1,19 +1,19 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{extract::State, response::{IntoResponse, Response}, routing::post, Json, Router};
 use elasticsearch::Elasticsearch;
-use http::StatusCode;
 use scylla::Session;
 use serde::{Deserialize, Serialize};
 use tower::ServiceBuilder;
 use tracing::error;
 
-use crate::{common::tag::{hierarchy::TagHierarchy, language_group::LanguageGroup, tag_id::TagId, tag_name::TagName}, helper::{error::InitError, middleware::rate_limiter, redis::connection::Pool}, middlewares::limit::TimeUnit};
+use crate::{common::tag::{hierarchy::TagHierarchy, language_group::LanguageGroup, tag_id::TagId, tag_name::TagName}, helper::{api_error::ApiError, error::InitError, middleware::{error_localizer, rate_limiter}, redis::connection::Pool}, middlewares::limit::TimeUnit};
 
-use super::{dsl::{SearchWithinHierarchicalTagList, TagInfo}, interpreter::SearchWithinHierarchicalTagListImpl};
+use super::{dsl::{SearchWithinHierarchicalTagList, SearchWithinHierarchicalTagListError, TagInfo}, interpreter::SearchWithinHierarchicalTagListImpl};
 
 pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, client: Arc<Elasticsearch>) -> Result<Router, InitError<SearchWithinHierarchicalTagListImpl>> {
     let services = ServiceBuilder::new()
+        .layer(error_localizer(db.clone()).await?)
         .layer(rate_limiter(db.clone(), cache, "srtrl", 300, 15, TimeUnit::MINS).await?);
 
     let interpreter = SearchWithinHierarchicalTagListImpl::try_new(db, client).await?;
@@ -29,7 +29,7 @@ pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, client: Arc<Elasticsea
 pub async fn handler(
     State(routine): State<Arc<SearchWithinHierarchicalTagListImpl>>,
     Json(payload): Json<Payload>
-) -> Result<Json<Data>, StatusCode> {
+) -> Result<Json<Data>, SearchWithinHierarchicalTagListError> {
     match routine.search_within_hierarchical_tag_list(&payload.query, payload.language_group, &payload.search_after, payload.tag_id, payload.hierarchy).await {
         Ok(tag_infos) => Ok(Json(Data { tags: tag_infos })),
         Err(e) => {
@@ -43,11 +43,17 @@ pub async fn handler(
                 "階層別タグ一覧内の検索に失敗しました"
             );
 
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
 
+impl IntoResponse for SearchWithinHierarchicalTagListError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Payload {
     query: TagName, // 部分的なタグ名
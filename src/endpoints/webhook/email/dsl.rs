@@ -0,0 +1,189 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::common::{email::{address::Email, suppression::SuppressionReason}, fallible::Fallible, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+pub(crate) trait HandleEmailEvent {
+    // 通知は再送され得るため、同じ通知を何度処理しても結果が変わらないようにする
+    async fn handle_email_event(&self, event: &EmailEvent, now: UnixtimeMillis) -> Fallible<(), HandleEmailEventError> {
+        let (recipients, reason) = match event {
+            EmailEvent::HardBounce(recipients) => (recipients, SuppressionReason::HardBounce),
+            EmailEvent::Complaint(recipients) => (recipients, SuppressionReason::Complaint),
+            EmailEvent::Ignored => return Ok(()),
+        };
+
+        for email in recipients {
+            self.suppress(email, reason, now).await?;
+
+            // 迷惑メール報告はアドレス自体は有効であるため、更新を促すのは不達の場合に限る
+            if reason == SuppressionReason::HardBounce {
+                if let Some(account_id) = self.fetch_account_id(email).await? {
+                    self.flag_bounced_email(account_id, now).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn suppress(&self, email: &Email, reason: SuppressionReason, suppressed_at: UnixtimeMillis) -> Fallible<(), HandleEmailEventError>;
+
+    async fn fetch_account_id(&self, email: &Email) -> Fallible<Option<AccountId>, HandleEmailEventError>;
+
+    async fn flag_bounced_email(&self, account_id: AccountId, bounced_at: UnixtimeMillis) -> Fallible<(), HandleEmailEventError>;
+}
+
+#[derive(Debug, Error)]
+pub enum HandleEmailEventError {
+    #[error("宛先の送信停止に失敗しました")]
+    SuppressFailed(#[source] anyhow::Error),
+    #[error("アカウントIDの取得に失敗しました")]
+    FetchAccountIdFailed(#[source] anyhow::Error),
+    #[error("メールアドレスの不達の記録に失敗しました")]
+    FlagBouncedEmailFailed(#[source] anyhow::Error),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EmailEvent {
+    HardBounce(Vec<Email>),
+    Complaint(Vec<Email>),
+    // 配信や開封などの通知は扱わない
+    Ignored,
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("メール配信の通知の形式を満たしませんでした")]
+pub struct ParseEmailEventError;
+
+#[derive(Deserialize)]
+struct RawEmailEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    data: RawEmailEventData,
+}
+
+#[derive(Deserialize)]
+struct RawEmailEventData {
+    #[serde(default)]
+    to: Vec<String>,
+    bounce: Option<RawBounce>,
+}
+
+#[derive(Deserialize)]
+struct RawBounce {
+    #[serde(rename = "type")]
+    bounce_type: String,
+}
+
+impl EmailEvent {
+    pub fn parse(payload: &[u8]) -> Result<Self, ParseEmailEventError> {
+        let raw = serde_json::from_slice::<RawEmailEvent>(payload).map_err(|_| ParseEmailEventError)?;
+
+        // 宛先の一部が解釈できなくても、残りの宛先は送信を停止する
+        let recipients = || raw.data.to.iter().filter_map(|to| Email::from_str(to).ok()).collect();
+
+        let event = match raw.event_type.as_str() {
+            // 一時的な不達は再送に任せる
+            "email.bounced" => match raw.data.bounce.as_ref().map(|bounce| bounce.bounce_type.as_str()) {
+                Some("Transient") => EmailEvent::Ignored,
+                _ => EmailEvent::HardBounce(recipients()),
+            },
+            "email.complained" => EmailEvent::Complaint(recipients()),
+            _ => EmailEvent::Ignored,
+        };
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::{LazyLock, Mutex}};
+
+    use crate::common::{email::{address::Email, suppression::SuppressionReason}, fallible::Fallible, profile::account_id::AccountId, unixtime::UnixtimeMillis};
+
+    use super::{EmailEvent, HandleEmailEvent, HandleEmailEventError, ParseEmailEventError};
+
+    static REGISTERED: LazyLock<Email> = LazyLock::new(|| Email::from_str("registered@example.com").unwrap());
+    static UNREGISTERED: LazyLock<Email> = LazyLock::new(|| Email::from_str("unregistered@example.com").unwrap());
+    static ACCOUNT_ID: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    #[derive(Default)]
+    struct MockHandleEmailEvent {
+        suppressed: Mutex<Vec<(Email, SuppressionReason)>>,
+        flagged: Mutex<Vec<AccountId>>,
+    }
+
+    impl HandleEmailEvent for MockHandleEmailEvent {
+        async fn suppress(&self, email: &Email, reason: SuppressionReason, _: UnixtimeMillis) -> Fallible<(), HandleEmailEventError> {
+            self.suppressed.lock().unwrap().push((email.clone(), reason));
+            Ok(())
+        }
+
+        async fn fetch_account_id(&self, email: &Email) -> Fallible<Option<AccountId>, HandleEmailEventError> {
+            Ok((*email == *REGISTERED).then_some(*ACCOUNT_ID))
+        }
+
+        async fn flag_bounced_email(&self, account_id: AccountId, _: UnixtimeMillis) -> Fallible<(), HandleEmailEventError> {
+            self.flagged.lock().unwrap().push(account_id);
+            Ok(())
+        }
+    }
+
+    async fn handle(event: EmailEvent) -> MockHandleEmailEvent {
+        let handler = MockHandleEmailEvent::default();
+        handler.handle_email_event(&event, UnixtimeMillis::of(0)).await.unwrap();
+        handler
+    }
+
+    #[tokio::test]
+    async fn hard_bounce_flags_account() {
+        let handler = handle(EmailEvent::HardBounce(vec![REGISTERED.clone(), UNREGISTERED.clone()])).await;
+        assert_eq!(*handler.suppressed.lock().unwrap(), vec![(REGISTERED.clone(), SuppressionReason::HardBounce), (UNREGISTERED.clone(), SuppressionReason::HardBounce)]);
+        assert_eq!(*handler.flagged.lock().unwrap(), vec![*ACCOUNT_ID]);
+    }
+
+    #[tokio::test]
+    async fn complaint_does_not_flag_account() {
+        let handler = handle(EmailEvent::Complaint(vec![REGISTERED.clone()])).await;
+        assert_eq!(*handler.suppressed.lock().unwrap(), vec![(REGISTERED.clone(), SuppressionReason::Complaint)]);
+        assert!(handler.flagged.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn ignored() {
+        let handler = handle(EmailEvent::Ignored).await;
+        assert!(handler.suppressed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_hard_bounce() {
+        let payload = br#"{"type":"email.bounced","created_at":"2024-11-22T23:41:12.126Z","data":{"email_id":"id","to":["registered@example.com"],"bounce":{"message":"","subType":"General","type":"Permanent"}}}"#;
+        assert_eq!(EmailEvent::parse(payload), Ok(EmailEvent::HardBounce(vec![REGISTERED.clone()])));
+    }
+
+    #[test]
+    fn parse_soft_bounce() {
+        let payload = br#"{"type":"email.bounced","data":{"to":["registered@example.com"],"bounce":{"type":"Transient"}}}"#;
+        assert_eq!(EmailEvent::parse(payload), Ok(EmailEvent::Ignored));
+    }
+
+    #[test]
+    fn parse_complaint() {
+        let payload = br#"{"type":"email.complained","data":{"to":["registered@example.com","invalid"]}}"#;
+        assert_eq!(EmailEvent::parse(payload), Ok(EmailEvent::Complaint(vec![REGISTERED.clone()])));
+    }
+
+    #[test]
+    fn parse_other_event() {
+        let payload = br#"{"type":"email.delivered","data":{"to":["registered@example.com"]}}"#;
+        assert_eq!(EmailEvent::parse(payload), Ok(EmailEvent::Ignored));
+    }
+
+    #[test]
+    fn parse_invalid_payload() {
+        assert_eq!(EmailEvent::parse(b"{}"), Err(ParseEmailEventError));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use axum::{body::Bytes, extract::State, response::{IntoResponse, Response}, routing::post, Router};
+use http::{HeaderMap, HeaderName, StatusCode};
+use scylla::Session;
+use tower::ServiceBuilder;
+use tracing::{error, warn};
+
+use crate::{common::{email::webhook::WebhookSecret, unixtime::UnixtimeMillis}, helper::{api_error::ApiError, error::InitError, middleware::rate_limiter_by, redis::connection::Pool}, middlewares::{limit::TimeUnit, rate_limit::dsl::rate_limit::RateLimitKeyStrategy}};
+
+use super::{dsl::{EmailEvent, HandleEmailEvent, HandleEmailEventError}, interpreter::HandleEmailEventImpl};
+
+const SVIX_ID: HeaderName = HeaderName::from_static("svix-id");
+const SVIX_TIMESTAMP: HeaderName = HeaderName::from_static("svix-timestamp");
+const SVIX_SIGNATURE: HeaderName = HeaderName::from_static("svix-signature");
+
+struct WebhookState {
+    routine: HandleEmailEventImpl,
+    secret: WebhookSecret,
+}
+
+// 送信プロバイダからの通知はAPIキーを持たないため、IPアドレス単位で制限する
+pub async fn endpoint(db: Arc<Session>, cache: Arc<Pool>, secret: WebhookSecret) -> Result<Router, InitError<HandleEmailEventImpl>> {
+    let services = ServiceBuilder::new()
+        .layer(rate_limiter_by(db.clone(), cache, "emwhk", 600, 1, TimeUnit::MINS, RateLimitKeyStrategy::IP_ADDRESS).await?);
+
+    let routine = HandleEmailEventImpl::try_new(db).await?;
+
+    let router = Router::new()
+        .route("/webhooks/email", post(handler))
+        .layer(services)
+        .with_state(Arc::new(WebhookState { routine, secret }));
+
+    Ok(router)
+}
+
+async fn handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    payload: Bytes,
+) -> Result<StatusCode, Response> {
+    let header = |name: &HeaderName| headers.get(name).and_then(|value| value.to_str().ok()).unwrap_or_default();
+
+    // 署名の検証前に本文を解釈しない
+    if let Err(e) = state.secret.verify(header(&SVIX_ID), header(&SVIX_TIMESTAMP), header(&SVIX_SIGNATURE), &payload, UnixtimeMillis::now()) {
+        warn!(error = %e, "メール配信の通知の署名の検証に失敗しました");
+        return Err(ApiError::INVALID_WEBHOOK_SIGNATURE.into_response());
+    }
+
+    let event = EmailEvent::parse(&payload).map_err(|_| ApiError::INVALID_WEBHOOK_PAYLOAD.into_response())?;
+
+    match state.routine.handle_email_event(&event, UnixtimeMillis::now()).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!(
+                error = %e,
+                svix_id = %header(&SVIX_ID),
+                "メール配信の通知の処理に失敗しました"
+            );
+
+            // 失敗を返すと送信プロバイダが再送する
+            Err(e.into_response())
+        }
+    }
+}
+
+impl IntoResponse for HandleEmailEventError {
+    fn into_response(self) -> Response {
+        ApiError::INTERNAL.into_response()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{email::{address::Email, suppression::SuppressionReason}, fallible::Fallible, profile::account_id::AccountId, unixtime::UnixtimeMillis}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{HandleEmailEvent, HandleEmailEventError};
+
+pub struct HandleEmailEventImpl {
+    db: Arc<Session>,
+    insert_suppression: Arc<PreparedStatement>,
+    select_account_id: Arc<PreparedStatement>,
+    update_email_bounced_at: Arc<PreparedStatement>,
+}
+
+impl HandleEmailEventImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let insert_suppression = prepare(&db, "INSERT INTO email_suppressions (email, reason, suppressed_at) VALUES (?, ?, ?)").await?;
+
+        let select_account_id = prepare(&db, "SELECT id FROM accounts WHERE email = ? LIMIT 1").await?;
+
+        // アカウントの主アドレスが不達となったことを記録し、アドレスの更新を促す際に参照する
+        let update_email_bounced_at = prepare(&db, "UPDATE accounts SET email_bounced_at = ? WHERE id = ?").await?;
+
+        Ok(Self { db, insert_suppression, select_account_id, update_email_bounced_at })
+    }
+}
+
+impl HandleEmailEvent for HandleEmailEventImpl {
+    async fn suppress(&self, email: &Email, reason: SuppressionReason, suppressed_at: UnixtimeMillis) -> Fallible<(), HandleEmailEventError> {
+        self.db
+            .execute_unpaged(&self.insert_suppression, (email, reason, suppressed_at))
+            .await
+            .map(|_| ())
+            .map_err(|e| HandleEmailEventError::SuppressFailed(e.into()))
+    }
+
+    async fn fetch_account_id(&self, email: &Email) -> Fallible<Option<AccountId>, HandleEmailEventError> {
+        self.db
+            .execute_unpaged(&self.select_account_id, (email, ))
+            .await
+            .map_err(|e| HandleEmailEventError::FetchAccountIdFailed(e.into()))?
+            .maybe_first_row_typed::<(AccountId, )>()
+            .map(|row| row.map(|(account_id, )| account_id))
+            .map_err(|e| HandleEmailEventError::FetchAccountIdFailed(e.into()))
+    }
+
+    async fn flag_bounced_email(&self, account_id: AccountId, bounced_at: UnixtimeMillis) -> Fallible<(), HandleEmailEventError> {
+        self.db
+            .execute_unpaged(&self.update_email_bounced_at, (bounced_at, account_id))
+            .await
+            .map(|_| ())
+            .map_err(|e| HandleEmailEventError::FlagBouncedEmailFailed(e.into()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod email;
\ No newline at end of file
@@ -0,0 +1,251 @@
+use axum::{response::{IntoResponse, Response}, Json};
+use http::{header::CONTENT_TYPE, HeaderValue, StatusCode};
+use serde::Serialize;
+
+use crate::{common::profile::language::Language, translation::{ja, ko, us_en, zh_tw}};
+
+// クライアントに返すエラー
+// コードは表示文言が変わっても安定するよう、ドット区切りの英小文字で表す
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+}
+
+impl ApiError {
+    pub const INTERNAL: ApiError = ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal");
+
+    pub const INSUFFICIENT_ROLE: ApiError = ApiError::new(StatusCode::FORBIDDEN, "auth.insufficient_role");
+    pub const SESSION_REQUIRED: ApiError = ApiError::new(StatusCode::UNAUTHORIZED, "auth.session_required");
+    pub const NO_API_KEY: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "api_key.missing");
+    pub const INVALID_API_KEY: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "api_key.invalid");
+    pub const RATE_LIMITED: ApiError = ApiError::new(StatusCode::TOO_MANY_REQUESTS, "rate_limit.exceeded");
+    pub const QUOTA_EXCEEDED: ApiError = ApiError::new(StatusCode::TOO_MANY_REQUESTS, "quota.exceeded");
+
+    pub const SIGN_IN_FAILED: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "auth.sign_in.failed");
+    pub const SIGN_IN_THROTTLED: ApiError = ApiError::new(StatusCode::TOO_MANY_REQUESTS, "auth.sign_in.throttled");
+    pub const SIGN_IN_CHALLENGE_REQUIRED: ApiError = ApiError::new(StatusCode::PRECONDITION_REQUIRED, "auth.sign_in.challenge_required");
+
+    pub const BREACHED_PASSWORD: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "auth.sign_up.breached_password");
+
+    pub const INVALID_VERIFICATION_TOKEN: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "auth.verify_email.invalid_token");
+    pub const ACCOUNT_ALREADY_EXISTS: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "auth.verify_email.account_already_exists");
+
+    pub const INVALID_HUMAN_VERIFICATION_TOKEN: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "api_key.invalid_token");
+
+    pub const HANDLE_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "handle.not_found");
+    pub const ANONYMOUS_HANDLE: ApiError = ApiError::new(StatusCode::CONFLICT, "handle.delete.anonymous_handle");
+    pub const ANONYMOUS_HANDLE_RENAME: ApiError = ApiError::new(StatusCode::CONFLICT, "handle.rename.anonymous_handle");
+    pub const HANDLE_RENAME_CONFLICT: ApiError = ApiError::new(StatusCode::CONFLICT, "handle.rename.conflict");
+    pub const RESERVED_HANDLE_NAME: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "handle.reserved_name");
+    pub const PROFANE_HANDLE_NAME: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "handle.profane_name");
+    pub const HANDLE_LIMIT_REACHED: ApiError = ApiError::new(StatusCode::FORBIDDEN, "handle.create.limit_reached");
+
+    pub const NON_PROPOSED_TAG_RELATION: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "tag.rating.non_proposed_relation");
+
+    pub const CYCLIC_TAG_RELATION: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "tag.proposal.cyclic");
+    pub const NOT_EQUIVALENT_TAGS: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "tag.proposal.not_equivalent");
+    pub const ALREADY_PROPOSED: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "tag.proposal.already_proposed");
+    pub const NON_EXISTENT_TAG: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "tag.proposal.non_existent_tag");
+    pub const DIFFERENT_LANGUAGE_GROUPS: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "tag.proposal.different_language_groups");
+    pub const NOT_PROPOSER: ApiError = ApiError::new(StatusCode::FORBIDDEN, "tag.proposal.not_proposer");
+    pub const CANNOT_WITHDRAW: ApiError = ApiError::new(StatusCode::FORBIDDEN, "tag.proposal.cannot_withdraw");
+
+    pub const NOT_HANDLE_OWNER: ApiError = ApiError::new(StatusCode::FORBIDDEN, "post.not_handle_owner");
+    pub const POST_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "post.not_found");
+    pub const POST_NON_EXISTENT_TAG: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "post.non_existent_tag");
+    pub const POST_DIFFERENT_LANGUAGE_GROUP: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "post.different_language_group");
+
+    pub const FOLLOWEE_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "follow.followee_not_found");
+    pub const CANNOT_FOLLOW_OWN_HANDLE: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "follow.own_handle");
+    pub const FOLLOW_LIMIT_REACHED: ApiError = ApiError::new(StatusCode::FORBIDDEN, "follow.limit_reached");
+    pub const FOLLOW_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "follow.not_found");
+
+    pub const CANNOT_FILTER_OWN_HANDLE: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "handle_filter.own_handle");
+    pub const HANDLE_FILTER_LIMIT_REACHED: ApiError = ApiError::new(StatusCode::FORBIDDEN, "handle_filter.limit_reached");
+    pub const HANDLE_FILTER_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "handle_filter.not_found");
+
+    pub const ACCOUNT_SUSPENDED: ApiError = ApiError::new(StatusCode::FORBIDDEN, "auth.sign_in.account_suspended");
+    pub const REPORT_TARGET_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "report.target_not_found");
+    pub const ALREADY_REPORTED: ApiError = ApiError::new(StatusCode::CONFLICT, "report.already_reported");
+    pub const REPORT_NOT_FOUND: ApiError = ApiError::new(StatusCode::NOT_FOUND, "moderation.report_not_found");
+    pub const REPORT_ALREADY_RESOLVED: ApiError = ApiError::new(StatusCode::CONFLICT, "moderation.report_already_resolved");
+    pub const MODERATION_ACTION_NOT_APPLICABLE: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "moderation.action_not_applicable");
+
+    pub const INVALID_WEBHOOK_SIGNATURE: ApiError = ApiError::new(StatusCode::UNAUTHORIZED, "webhook.invalid_signature");
+    pub const INVALID_WEBHOOK_PAYLOAD: ApiError = ApiError::new(StatusCode::BAD_REQUEST, "webhook.invalid_payload");
+
+    // 翻訳の網羅性の検証に用いるため、エラーを追加した際は必ず追記する
+    pub const ALL: [ApiError; 48] = [
+        Self::INTERNAL,
+        Self::INSUFFICIENT_ROLE,
+        Self::SESSION_REQUIRED,
+        Self::NO_API_KEY,
+        Self::INVALID_API_KEY,
+        Self::RATE_LIMITED,
+        Self::QUOTA_EXCEEDED,
+        Self::SIGN_IN_FAILED,
+        Self::SIGN_IN_THROTTLED,
+        Self::SIGN_IN_CHALLENGE_REQUIRED,
+        Self::BREACHED_PASSWORD,
+        Self::INVALID_VERIFICATION_TOKEN,
+        Self::ACCOUNT_ALREADY_EXISTS,
+        Self::INVALID_HUMAN_VERIFICATION_TOKEN,
+        Self::HANDLE_NOT_FOUND,
+        Self::ANONYMOUS_HANDLE,
+        Self::ANONYMOUS_HANDLE_RENAME,
+        Self::HANDLE_RENAME_CONFLICT,
+        Self::RESERVED_HANDLE_NAME,
+        Self::PROFANE_HANDLE_NAME,
+        Self::HANDLE_LIMIT_REACHED,
+        Self::NON_PROPOSED_TAG_RELATION,
+        Self::CYCLIC_TAG_RELATION,
+        Self::NOT_EQUIVALENT_TAGS,
+        Self::ALREADY_PROPOSED,
+        Self::NON_EXISTENT_TAG,
+        Self::DIFFERENT_LANGUAGE_GROUPS,
+        Self::NOT_PROPOSER,
+        Self::CANNOT_WITHDRAW,
+        Self::NOT_HANDLE_OWNER,
+        Self::POST_NOT_FOUND,
+        Self::POST_NON_EXISTENT_TAG,
+        Self::POST_DIFFERENT_LANGUAGE_GROUP,
+        Self::FOLLOWEE_NOT_FOUND,
+        Self::CANNOT_FOLLOW_OWN_HANDLE,
+        Self::FOLLOW_LIMIT_REACHED,
+        Self::FOLLOW_NOT_FOUND,
+        Self::CANNOT_FILTER_OWN_HANDLE,
+        Self::HANDLE_FILTER_LIMIT_REACHED,
+        Self::HANDLE_FILTER_NOT_FOUND,
+        Self::ACCOUNT_SUSPENDED,
+        Self::REPORT_TARGET_NOT_FOUND,
+        Self::ALREADY_REPORTED,
+        Self::REPORT_NOT_FOUND,
+        Self::REPORT_ALREADY_RESOLVED,
+        Self::MODERATION_ACTION_NOT_APPLICABLE,
+        Self::INVALID_WEBHOOK_SIGNATURE,
+        Self::INVALID_WEBHOOK_PAYLOAD,
+    ];
+
+    const fn new(status: StatusCode, code: &'static str) -> Self {
+        Self { status, code }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self, language: Language) -> &'static str {
+        messages(language)
+            .iter()
+            .find(|(code, _)| *code == self.code)
+            .map(|(_, message)| *message)
+            // 翻訳の欠落はテストで検出するため、ここに到達した場合はコードをそのまま返す
+            .unwrap_or(self.code)
+    }
+
+    pub fn body(&self, language: Language) -> ErrorBody {
+        ErrorBody { code: self.code, status: self.status.as_u16(), message: self.message(language) }
+    }
+
+    // ミドルウェアは本文の型を限定できないため、`IntoResponse`の代わりに用いる
+    // `IntoResponse`と同様に英語で返し、`LocalizeErrorLayer`が翻訳し直す
+    pub fn to_response<B: From<String>>(&self) -> http::Response<B> {
+        // 本文は文字列とステータスコードのみで構成されるため、シリアライズは失敗しない
+        let body = serde_json::to_string(&self.body(Language::AmericanEnglish)).unwrap();
+
+        let mut response = http::Response::new(B::from(body));
+        *response.status_mut() = self.status;
+        response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response.extensions_mut().insert(*self);
+        response
+    }
+}
+
+// 言語を追加した際に翻訳の追加漏れがコンパイルエラーとなるよう、`_`を用いずに対応付ける
+fn messages(language: Language) -> &'static [(&'static str, &'static str)] {
+    match language {
+        Language::Japanese => ja::error::MESSAGES,
+        Language::Korean => ko::error::MESSAGES,
+        Language::TaiwaneseMandarin => zh_tw::error::MESSAGES,
+        Language::AmericanEnglish => us_en::error::MESSAGES,
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    code: &'static str,
+    status: u16,
+    message: &'static str,
+}
+
+// 言語はリクエストから決まるため、ここでは英語で返し、`LocalizeErrorLayer`が翻訳し直す
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut response = (self.status, Json(self.body(Language::AmericanEnglish))).into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use axum::response::IntoResponse;
+    use http::{header::CONTENT_TYPE, StatusCode};
+
+    use crate::common::profile::language::Language;
+
+    use super::{messages, ApiError};
+
+    #[test]
+    fn every_error_translated() {
+        for language in Language::ALL {
+            for error in ApiError::ALL {
+                assert!(messages(language).iter().any(|(code, _)| *code == error.code()), "{:?}に{}の翻訳がありません", language, error.code());
+            }
+        }
+    }
+
+    #[test]
+    fn no_unknown_translation() {
+        for language in Language::ALL {
+            for (code, _) in messages(language) {
+                assert!(ApiError::ALL.iter().any(|error| error.code() == *code), "{:?}に未登録のエラー{}の翻訳があります", language, code);
+            }
+        }
+    }
+
+    #[test]
+    fn unique_codes() {
+        let codes = ApiError::ALL.iter().map(ApiError::code).collect::<HashSet<_>>();
+        assert_eq!(codes.len(), ApiError::ALL.len());
+    }
+
+    #[test]
+    fn localized_message() {
+        assert_eq!(ApiError::DIFFERENT_LANGUAGE_GROUPS.message(Language::Japanese), "異なる言語グループのタグ間の関係は提案できません。");
+        assert_eq!(ApiError::DIFFERENT_LANGUAGE_GROUPS.message(Language::AmericanEnglish), "Tags in different language groups cannot be related.");
+    }
+
+    #[test]
+    fn into_response() {
+        let response = ApiError::NOT_PROPOSER.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(response.extensions().get::<ApiError>(), Some(&ApiError::NOT_PROPOSER));
+    }
+
+    #[test]
+    fn to_response() {
+        let response = ApiError::RATE_LIMITED.to_response::<String>();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+        assert_eq!(response.extensions().get::<ApiError>(), Some(&ApiError::RATE_LIMITED));
+        assert!(response.body().contains("\"code\":\"rate_limit.exceeded\""));
+    }
+}
\ No newline at end of file
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use scylla::Session;
 
-use crate::middlewares::{limit::{Count, EndpointName, InculsiveLimit, TimeUnit}, manage_session::middleware::ManageSessionLayer, quota_limit::middleware::QuotaLimitLayer, rate_limit::middleware::RateLimitLayer, start_session::middleware::StartSessionLayer};
+use crate::{common::profile::role::Role, middlewares::{limit::{Count, EndpointName, InculsiveLimit, TimeUnit}, localize_error::middleware::LocalizeErrorLayer, manage_session::middleware::ManageSessionLayer, quota_limit::{dsl::ChargedStatuses, middleware::QuotaLimitLayer, policy::ContributionQuotaPolicy}, rate_limit::{dsl::rate_limit::RateLimitKeyStrategy, middleware::RateLimitLayer}, require_role::middleware::RequireRoleLayer, start_session::middleware::StartSessionLayer}};
 
 use super::{error::InitError, redis::{namespace::Namespace, connection::Pool}};
 
@@ -13,10 +13,19 @@ pub async fn session_manager<T>(db: Arc<Session>, cache: Arc<Pool>) -> Result<Ma
 }
 
 pub async fn rate_limiter<T>(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: &'static str, limit: u32, time_window: u32, time_unit: TimeUnit) -> Result<RateLimitLayer, InitError<T>> {
+    rate_limiter_by(db, cache, endpoint_name, limit, time_window, time_unit, RateLimitKeyStrategy::API_KEY).await
+}
+
+// サインインが必要なエンドポイントでは、APIキーを取り替えて上限を回避されないよう`session_manager`の内側でアカウント単位でも制限する
+pub async fn account_rate_limiter<T>(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: &'static str, limit: u32, time_window: u32, time_unit: TimeUnit) -> Result<RateLimitLayer, InitError<T>> {
+    rate_limiter_by(db, cache, endpoint_name, limit, time_window, time_unit, RateLimitKeyStrategy::ACCOUNT_ID).await
+}
+
+pub async fn rate_limiter_by<T>(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: &'static str, limit: u32, time_window: u32, time_unit: TimeUnit, key_strategy: RateLimitKeyStrategy) -> Result<RateLimitLayer, InitError<T>> {
     let endpoint_name = EndpointName::new(Namespace::of(endpoint_name));
     let limit = InculsiveLimit::new(Count::new(limit));
 
-    RateLimitLayer::try_new(db, cache, endpoint_name, limit, time_unit.apply(time_window))
+    RateLimitLayer::try_new(db, cache, endpoint_name, limit, time_unit.apply(time_window), key_strategy)
         .await
         .map_err(|e| InitError::<T>::new(e.into()))
 }
@@ -27,11 +36,24 @@ pub async fn session_starter<T>(db: Arc<Session>, cache: Arc<Pool>) -> Result<St
         .map_err(|e| InitError::<T>::new(e.into()))
 }
 
-pub async fn quota_limiter<T>(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: &'static str, time_window: u32, time_unit: TimeUnit) -> Result<QuotaLimitLayer, InitError<T>> {
+pub async fn quota_limiter<T>(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: &'static str, time_window: u32, time_unit: TimeUnit, quota_policy: ContributionQuotaPolicy, charged_statuses: ChargedStatuses) -> Result<QuotaLimitLayer, InitError<T>> {
     let endpoint_name = EndpointName::new(Namespace::of(endpoint_name));
 
-    QuotaLimitLayer::try_new(db, cache, endpoint_name, time_unit.apply(time_window))
+    QuotaLimitLayer::try_new(db, cache, endpoint_name, time_unit.apply(time_window), quota_policy, charged_statuses)
+        .await
+        .map_err(|e| InitError::<T>::new(e.into()))
+}
+
+// アカウントIDを参照するため、`session_manager`の内側に配置する
+pub async fn role_requirer<T>(db: Arc<Session>, cache: Arc<Pool>, required_role: Role) -> Result<RequireRoleLayer, InitError<T>> {
+    RequireRoleLayer::try_new(db, cache, required_role)
         .await
         .map_err(|e| InitError::<T>::new(e.into()))
 }
 
+// エラーレスポンスを翻訳するため、各エンドポイントの最も外側に配置する
+pub async fn error_localizer<T>(db: Arc<Session>) -> Result<LocalizeErrorLayer, InitError<T>> {
+    LocalizeErrorLayer::try_new(db)
+        .await
+        .map_err(|e| InitError::<T>::new(e.into()))
+}
\ No newline at end of file
@@ -1,3 +1,4 @@
+pub mod api_error;
 pub mod cache;
 pub mod error;
 pub mod middleware;
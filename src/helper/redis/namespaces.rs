@@ -10,4 +10,10 @@ namespace!(TAG_LIST, "tls");
 namespace!(SUPER, "sup");
 namespace!(EQUIVALENT, "eq");
 namespace!(SUB, "sub");
-namespace!(API_KEY, "apkey");
\ No newline at end of file
+namespace!(API_KEY, "apkey");
+namespace!(SIGN_IN_FAILURE_BY_EMAIL, "sifem");
+namespace!(SIGN_IN_LOCK_BY_EMAIL, "silem");
+namespace!(SIGN_IN_FAILURE_BY_IP, "sifip");
+namespace!(SIGN_IN_FAILURE_BY_NETWORK, "sifnw");
+namespace!(SIGN_IN_FAILURE, "sifal");
+namespace!(SIGN_IN_BREAKER, "sibrk");
@@ -2,6 +2,7 @@ use std::fmt::{self, Display};
 
 use redis::{FromRedisValue, RedisResult, ToRedisArgs};
 use scylla::{cql_to_rust::{FromCqlVal, FromCqlValError}, frame::response::result::CqlValue};
+use serde::Serialize;
 
 use crate::helper::redis::namespace::Namespace;
 
@@ -57,7 +58,7 @@ impl ToRedisArgs for TimeWindow {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
 pub struct Count(u32);
 
 impl Count {
@@ -65,7 +66,7 @@ impl Count {
         Self(quota)
     }
 
-    pub fn value(&self) -> u32 {
+    pub const fn value(&self) -> u32 {
         self.0
     }
 }
@@ -82,13 +83,22 @@ impl FromRedisValue for Count {
     }
 }
 
+impl ToRedisArgs for Count {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + redis::RedisWrite
+    {
+        self.0.write_redis_args(out);
+    }
+}
+
 impl FromCqlVal<Option<CqlValue>> for Count {
     fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
         i32::from_cql(cql_val).map(Count::from)
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
 pub struct InculsiveLimit(Count);
 
 impl InculsiveLimit {
@@ -101,6 +111,21 @@ impl InculsiveLimit {
     }
 }
 
+impl FromRedisValue for InculsiveLimit {
+    fn from_redis_value(v: &redis::Value) -> RedisResult<Self> {
+        Count::from_redis_value(v).map(InculsiveLimit::new)
+    }
+}
+
+impl ToRedisArgs for InculsiveLimit {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + redis::RedisWrite
+    {
+        self.0.write_redis_args(out);
+    }
+}
+
 impl FromCqlVal<Option<CqlValue>> for InculsiveLimit {
     fn from_cql(cql_val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
         Count::from_cql(cql_val).map(InculsiveLimit::new)
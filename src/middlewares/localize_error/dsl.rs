@@ -0,0 +1,161 @@
+use std::convert::Infallible;
+
+use http::{header::ACCEPT_LANGUAGE, Request, Response};
+use thiserror::Error;
+use tower::Service;
+
+use crate::{common::{fallible::Fallible, profile::{account_id::AccountId, language::Language}}, helper::api_error::ApiError};
+
+pub(crate) trait LocalizeError {
+    // エラーレスポンスの本文を、アカウントの言語、Accept-Language、英語の優先順で選んだ言語に翻訳する
+    async fn localize_error<S, B>(&self, inner: &mut S, request: Request<B>) -> Response<B>
+    where
+        S: Service<Request<B>, Error = Infallible, Response = Response<B>>,
+        B: From<String>,
+    {
+        let accept_language = request.headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Language::from_accept_language);
+
+        // `Error`は`Infallible`であるため`unwrap()`で問題ない
+        let mut response = inner.call(request).await.unwrap();
+
+        let Some(error) = response.extensions().get::<ApiError>().copied() else {
+            return response;
+        };
+
+        // 言語の取得に失敗しても、エラーを返すこと自体は妨げない
+        let account_language = match response.extensions().get::<AccountId>().copied() {
+            Some(account_id) => self.fetch_account_language(account_id).await.ok(),
+            None => None,
+        };
+
+        let language = account_language
+            .or(accept_language)
+            .unwrap_or(Language::AmericanEnglish);
+
+        // 本文は文字列とステータスコードのみで構成されるため、シリアライズは失敗しない
+        *response.body_mut() = B::from(serde_json::to_string(&error.body(language)).unwrap());
+
+        response
+    }
+
+    async fn fetch_account_language(&self, account_id: AccountId) -> Fallible<Language, LocalizeErrorError>;
+}
+
+#[derive(Debug, Error)]
+pub enum LocalizeErrorError {
+    #[error("アカウントの言語の取得に失敗しました")]
+    FetchAccountLanguageFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, future::{ready, Ready}, sync::LazyLock, task::{Context, Poll}};
+
+    use http::{header::ACCEPT_LANGUAGE, Request, Response};
+    use thiserror::Error;
+    use tower::Service;
+
+    use crate::{common::{fallible::Fallible, profile::{account_id::AccountId, language::Language}}, helper::api_error::ApiError};
+
+    use super::{LocalizeError, LocalizeErrorError};
+
+    static KOREAN: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static UNFETCHABLE: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    struct MockLocalizeError;
+
+    #[derive(Debug, Error)]
+    #[error("疑似エラー")]
+    struct MockError;
+
+    impl LocalizeError for MockLocalizeError {
+        async fn fetch_account_language(&self, account_id: AccountId) -> Fallible<Language, LocalizeErrorError> {
+            if account_id == *KOREAN {
+                Ok(Language::Korean)
+            } else {
+                Err(LocalizeErrorError::FetchAccountLanguageFailed(MockError.into()))
+            }
+        }
+    }
+
+    struct MockService {
+        error: Option<ApiError>,
+        account_id: Option<AccountId>,
+    }
+
+    impl Service<Request<String>> for MockService {
+        type Response = Response<String>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: Request<String>) -> Self::Future {
+            let mut response = Response::new(String::from("本文"));
+
+            if let Some(error) = self.error {
+                *response.status_mut() = error.status();
+                response.extensions_mut().insert(error);
+            }
+
+            if let Some(account_id) = self.account_id {
+                response.extensions_mut().insert(account_id);
+            }
+
+            ready(Ok(response))
+        }
+    }
+
+    async fn localize(error: Option<ApiError>, account_id: Option<AccountId>, accept_language: Option<&str>) -> String {
+        let mut request = Request::builder();
+
+        if let Some(accept_language) = accept_language {
+            request = request.header(ACCEPT_LANGUAGE, accept_language);
+        }
+
+        let mut service = MockService { error, account_id };
+        let response = MockLocalizeError.localize_error(&mut service, request.body(String::new()).unwrap()).await;
+
+        response.into_body()
+    }
+
+    fn message_of(body: &str) -> String {
+        serde_json::from_str::<serde_json::Value>(body).unwrap()["message"].as_str().unwrap().to_owned()
+    }
+
+    #[tokio::test]
+    async fn account_language_preferred() {
+        let body = localize(Some(ApiError::NOT_PROPOSER), Some(*KOREAN), Some("ja")).await;
+        assert_eq!(message_of(&body), ApiError::NOT_PROPOSER.message(Language::Korean));
+    }
+
+    #[tokio::test]
+    async fn accept_language_fallback() {
+        let body = localize(Some(ApiError::NOT_PROPOSER), Some(*UNFETCHABLE), Some("ja-JP,ja;q=0.9")).await;
+        assert_eq!(message_of(&body), ApiError::NOT_PROPOSER.message(Language::Japanese));
+    }
+
+    #[tokio::test]
+    async fn english_by_default() {
+        let body = localize(Some(ApiError::NOT_PROPOSER), None, Some("fr")).await;
+        assert_eq!(message_of(&body), ApiError::NOT_PROPOSER.message(Language::AmericanEnglish));
+    }
+
+    #[tokio::test]
+    async fn machine_readable_body() {
+        let body = localize(Some(ApiError::DIFFERENT_LANGUAGE_GROUPS), None, None).await;
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["code"], "tag.proposal.different_language_groups");
+        assert_eq!(body["status"], 400);
+    }
+
+    #[tokio::test]
+    async fn non_error_untouched() {
+        assert_eq!(localize(None, Some(*KOREAN), Some("ja")).await, "本文");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, profile::{account_id::AccountId, language::Language}}, helper::{error::InitError, scylla::prepare}};
+
+use super::dsl::{LocalizeError, LocalizeErrorError};
+
+#[derive(Debug)]
+pub struct LocalizeErrorImpl {
+    db: Arc<Session>,
+    select_language: Arc<PreparedStatement>,
+}
+
+impl LocalizeErrorImpl {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<Self>> {
+        let select_language = prepare(&db, "SELECT language FROM accounts WHERE id = ?").await?;
+        Ok(Self { db, select_language })
+    }
+}
+
+impl LocalizeError for LocalizeErrorImpl {
+    async fn fetch_account_language(&self, account_id: AccountId) -> Fallible<Language, LocalizeErrorError> {
+        self.db
+            .execute_unpaged(&self.select_language, (account_id, ))
+            .await
+            .map_err(|e| LocalizeErrorError::FetchAccountLanguageFailed(e.into()))?
+            .first_row_typed::<(Language, )>()
+            .map(|(language, )| language)
+            .map_err(|e| LocalizeErrorError::FetchAccountLanguageFailed(e.into()))
+    }
+}
\ No newline at end of file
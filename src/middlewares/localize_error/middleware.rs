@@ -0,0 +1,65 @@
+use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc, task::{Context, Poll}};
+
+use http::{Request, Response};
+use scylla::Session;
+use tower::{Layer, Service};
+
+use crate::helper::error::InitError;
+
+use super::{dsl::LocalizeError, interpreter::LocalizeErrorImpl};
+
+// `ManageSessionLayer`がレスポンスに付与するアカウントIDを参照するため、その外側に配置する
+#[derive(Clone)]
+pub struct LocalizeErrorLayer {
+    localize_error: Arc<LocalizeErrorImpl>,
+}
+
+impl LocalizeErrorLayer {
+    pub async fn try_new(db: Arc<Session>) -> Result<Self, InitError<LocalizeErrorImpl>> {
+        let localize_error = LocalizeErrorImpl::try_new(db).await?;
+        Ok(Self { localize_error: Arc::new(localize_error) })
+    }
+}
+
+impl<S> Layer<S> for LocalizeErrorLayer {
+    type Service = LocalizeErrorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LocalizeErrorService {
+            inner,
+            localize_error: self.localize_error.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LocalizeErrorService<S> {
+    inner: S,
+    localize_error: Arc<LocalizeErrorImpl>,
+}
+
+impl <S, B> Service<Request<B>> for LocalizeErrorService<S>
+where
+    S: Service<Request<B>, Error = Infallible, Response = Response<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: From<String> + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        // `poll_ready`を呼んだインスタンスで`call`するため、複製と入れ替える
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let localize_error = self.localize_error.clone();
+
+        Box::pin(async move {
+            Ok(localize_error.localize_error(&mut inner, req).await)
+        })
+    }
+}
\ No newline at end of file
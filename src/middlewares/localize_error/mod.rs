@@ -0,0 +1,3 @@
+pub mod dsl;
+pub mod interpreter;
+pub mod middleware;
\ No newline at end of file
@@ -32,6 +32,9 @@ pub(crate) trait ManageSession {
                     // 同じセッションIDをセットすることで有効期限をリフレッシュ
                     set_session_cookie_with_expiration(&mut response, &session_id);
                 }
+
+                // 外側のミドルウェアがエラーメッセージをアカウントの言語で返せるよう、アカウントIDを渡す
+                response.extensions_mut().insert(account_id);
                 
                 return Ok(response)
             }
@@ -63,6 +66,8 @@ pub(crate) trait ManageSession {
                         }
                     }
 
+                    response.extensions_mut().insert(account_id);
+
                     return Ok(response);
                 },
                 Err(ReAuthenticateSessionError::PotentialSessionTheft(account_id)) => self.mitigate_session_theft(account_id).await,
@@ -155,7 +160,7 @@ mod tests {
             Ok((Email::from_str("email@example.com").unwrap(), Language::Japanese))
         }
 
-        async fn send_security_notification(&self, _: &Email, _: Language) -> Fallible<(), MitigateSessionTheftError> {
+        async fn send_security_notification(&self, _: AccountId, _: &Email, _: Language) -> Fallible<(), MitigateSessionTheftError> {
             Ok(())
         }
     
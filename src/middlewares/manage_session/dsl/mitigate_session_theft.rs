@@ -6,7 +6,7 @@ use crate::common::{email::address::Email, fallible::Fallible, profile::{account
 pub(crate) trait MitigateSessionTheft {
     async fn mitigate_session_theft(&self, account_id: AccountId) {
         let is_email_sent = match self.fetch_email_and_language(account_id).await {
-            Ok((email, language)) => self.send_security_notification(&email, language)
+            Ok((email, language)) => self.send_security_notification(account_id, &email, language)
                 .await
                 .is_ok(),
             _ => false
@@ -24,7 +24,7 @@ pub(crate) trait MitigateSessionTheft {
 
     async fn fetch_email_and_language(&self, account_id: AccountId) -> Fallible<(Email, Language), MitigateSessionTheftError>;
 
-    async fn send_security_notification(&self, email: &Email, language: Language) -> Fallible<(), MitigateSessionTheftError>;
+    async fn send_security_notification(&self, account_id: AccountId, email: &Email, language: Language) -> Fallible<(), MitigateSessionTheftError>;
 
     async fn purge_all_session_series(&self, account_id: AccountId) -> Fallible<(), MitigateSessionTheftError>;
 }
@@ -2,12 +2,11 @@ use std::{str::FromStr, sync::LazyLock};
 
 use redis::cmd;
 
-use crate::{common::{email::{address::Email, resend::ResendEmailSender, send::{Body, EmailSender, HtmlContent, NetmateEmail, PlainText, SenderName, Subject}}, fallible::Fallible, profile::{account_id::AccountId, language::Language}, session::session_series::SessionSeries}, helper::redis::connection::conn, middlewares::{manage_session::dsl::mitigate_session_theft::{MitigateSessionTheft, MitigateSessionTheftError}, session::RefreshPairKey}, translation::ja};
+use crate::{common::{email::{address::Email, outbox::{enqueue::EnqueueEmail, message::{IdempotencyKey, OutboxMessage}}, send::{NetmateEmail, SenderName}, template::{kind::EmailTemplateKind, variable::TemplateVariables}}, fallible::Fallible, profile::{account_id::AccountId, language::Language}, session::session_series::SessionSeries, unixtime::UnixtimeMillis}, helper::redis::connection::conn, middlewares::{manage_session::dsl::mitigate_session_theft::{MitigateSessionTheft, MitigateSessionTheftError}, session::RefreshPairKey}};
 
 use super::ManageSessionImpl;
 
 static SECURITY_EMAIL_ADDRESS: LazyLock<NetmateEmail> = LazyLock::new(|| NetmateEmail::try_from(Email::from_str("security@account.netmate.app").unwrap()).unwrap());
-static SECURITY_NOTIFICATION_SUBJECT: LazyLock<Subject> = LazyLock::new(|| Subject::from_str(ja::session::SECURITY_NOTIFICATION_SUBJECT).unwrap());
 
 impl MitigateSessionTheft for ManageSessionImpl {
     async fn fetch_email_and_language(&self, account_id: AccountId) -> Fallible<(Email, Language), MitigateSessionTheftError> {
@@ -19,14 +18,26 @@ impl MitigateSessionTheft for ManageSessionImpl {
             .map_err(|e| MitigateSessionTheftError::FetchEmailAndLanguageFailed(e.into()))
     }
 
-    async fn send_security_notification(&self, email: &Email, language: Language) -> Fallible<(), MitigateSessionTheftError> {
-        let (subject, html_content, plain_text) = match language {
-            _ => (&*SECURITY_NOTIFICATION_SUBJECT, ja::session::SECURITY_NOTIFICATION_BODY_HTML, ja::session::SECURITY_NOTIFICATION_BODY_PLAIN)
-        };
+    async fn send_security_notification(&self, account_id: AccountId, email: &Email, language: Language) -> Fallible<(), MitigateSessionTheftError> {
+        let detected_at = UnixtimeMillis::now();
 
-        let body = Body::new(HtmlContent::new(html_content), PlainText::new(plain_text));
+        let variables = TemplateVariables::new()
+            .text("email", email.value())
+            .datetime("detected_at", detected_at)
+            .text("recovery_url", "https://netmate.app/recovery-account");
 
-        ResendEmailSender::send(&SECURITY_EMAIL_ADDRESS, email, &SenderName::by(language), subject, &body)
+        let (subject, body) = self.templates
+            .render(EmailTemplateKind::SecurityNotification, language, &variables)
+            .map_err(|e| MitigateSessionTheftError::SendSecurityNotificationFailed(e.into()))?;
+
+        // 盗用の検出が短時間に繰り返されても、通知は1時間に1通までとする
+        let hour = detected_at.value() / (60 * 60 * 1000);
+        let idempotency_key = IdempotencyKey::new(format!("security_notification:{}:{}", account_id, hour));
+
+        let message = OutboxMessage::compose(idempotency_key, SECURITY_EMAIL_ADDRESS.clone(), email.clone(), SenderName::by(language), subject, body);
+
+        self.outbox
+            .enqueue(message)
             .await
             .map_err(|e| MitigateSessionTheftError::SendSecurityNotificationFailed(e.into()))
     }
@@ -63,4 +74,4 @@ impl MitigateSessionTheft for ManageSessionImpl {
             .map(|_| ())
             .map_err(handle_error)
     }
-}
+}
\ No newline at end of file
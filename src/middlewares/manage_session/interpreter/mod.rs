@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::helper::{error::InitError, redis::connection::Pool, scylla::prepare};
+use crate::{common::email::{outbox::interpreter::EmailOutboxImpl, template::registry::EmailTemplates}, helper::{error::InitError, redis::connection::Pool, scylla::prepare}};
 
 use super::dsl::{extract_session_info::ExtractSessionInformation, manage_session::ManageSession};
 
@@ -22,6 +22,8 @@ pub struct ManageSessionImpl {
     select_email_and_language: Arc<PreparedStatement>,
     select_all_session_series: Arc<PreparedStatement>,
     delete_all_session_series: Arc<PreparedStatement>,
+    outbox: EmailOutboxImpl,
+    templates: EmailTemplates,
 }
 
 impl ManageSessionImpl {
@@ -36,7 +38,13 @@ impl ManageSessionImpl {
 
         let delete_all_session_series = prepare(&db, "DELETE FROM session_series WHERE account_id = ?").await?;
 
-        Ok(Self { db, cache, select_last_session_series_refreshed_at, update_session_series_ttl, select_email_and_language, select_all_session_series, delete_all_session_series })
+        let outbox = EmailOutboxImpl::try_new(db.clone())
+            .await
+            .map_err(|e| InitError::new(e.into()))?;
+
+        let templates = EmailTemplates::load().map_err(|e| InitError::new(e.into()))?;
+
+        Ok(Self { db, cache, select_last_session_series_refreshed_at, update_session_series_ttl, select_email_and_language, select_all_session_series, delete_all_session_series, outbox, templates })
     }
 }
 
@@ -1,12 +1,12 @@
 use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc, task::{ready, Context, Poll}};
 
-use http::{Request, Response, StatusCode};
+use http::{Request, Response};
 use pin_project::pin_project;
 use scylla::Session;
 use tokio::pin;
 use tower::{Layer, Service};
 
-use crate::{helper::{error::InitError, redis::connection::Pool}, middlewares::manage_session::dsl::manage_session::{ManageSession, ManageSessionError}};
+use crate::{helper::{api_error::ApiError, error::InitError, redis::connection::Pool}, middlewares::manage_session::dsl::manage_session::{ManageSession, ManageSessionError}};
 
 use super::interpreter::ManageSessionImpl;
 
@@ -43,7 +43,7 @@ impl <S, B> Service<Request<B>> for ManageSessionService<S>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>> + Clone,
     S::Future: Future<Output = Result<S::Response, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Response = S::Response;
     type Error = S::Error;
@@ -66,7 +66,7 @@ where
 pub struct ManageSessionFuture<S, B>
 where
     S: Service<Request<B>>,
-    B: Default,
+    B: From<String>,
 {
     inner: S,
     request: Option<Request<B>>,
@@ -77,7 +77,7 @@ impl<S, B> Future for ManageSessionFuture<S, B>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>>,
     S::Future: Future<Output = Result<Response<B>, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Output = Result<S::Response, S::Error>;
 
@@ -92,16 +92,12 @@ where
         match ready!(response_future.poll(cx)) {
             Ok(response) => Poll::Ready(Ok(response)),
             Err(e) => {
-                let status_code = match e {
-                    ManageSessionError::NoSession => StatusCode::UNAUTHORIZED,
-                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                let error = match e {
+                    ManageSessionError::NoSession => ApiError::SESSION_REQUIRED,
+                    _ => ApiError::INTERNAL,
                 };
 
-                let response = Response::builder()
-                    .status(status_code)
-                    .body(B::default())
-                    .unwrap();
-                Poll::Ready(Ok(response))
+                Poll::Ready(Ok(error.to_response()))
             }
         }
     }
@@ -1,6 +1,8 @@
+pub mod localize_error;
 pub mod manage_session;
 pub mod quota_limit;
 pub mod rate_limit;
+pub mod require_role;
 pub mod start_session;
 pub mod limit;
 pub mod session;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- 有効期限切れ後に払い戻すと、有効期限の無い負の値が残るため、キーが存在する場合のみ減算する
+if redis.call("exists", KEYS[1]) == 1 then
+    return redis.call("decr", KEYS[1])
+end
+return 0
@@ -1,13 +1,34 @@
 use std::convert::Infallible;
 
-use http::{Request, Response, StatusCode};
+use http::{HeaderMap, HeaderName, HeaderValue, Request, Response, StatusCode};
 use thiserror::Error;
 use tower::Service;
 
-use crate::{common::{fallible::Fallible, profile::account_id::AccountId}, middlewares::limit::{Count, InculsiveLimit, TimeWindow}};
+use crate::{common::{contribution::Contribution, fallible::Fallible, profile::account_id::AccountId}, middlewares::limit::{Count, InculsiveLimit, TimeWindow}};
+
+use super::policy::ContributionQuotaPolicy;
 
 pub type ConsumedQuota = Count;
 
+pub const QUOTA_LIMIT_HEADER: HeaderName = HeaderName::from_static("x-quota-limit");
+pub const QUOTA_REMAINING_HEADER: HeaderName = HeaderName::from_static("x-quota-remaining");
+
+// クォータを消費するレスポンスのステータスコード
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ChargedStatuses(&'static [StatusCode]);
+
+impl ChargedStatuses {
+    pub const ONLY_OK: ChargedStatuses = ChargedStatuses::new(&[StatusCode::OK]);
+
+    pub const fn new(statuses: &'static [StatusCode]) -> Self {
+        Self(statuses)
+    }
+
+    pub fn contains(&self, status: StatusCode) -> bool {
+        self.0.contains(&status)
+    }
+}
+
 pub(crate) trait QuotaLimit {
     async fn quota_limit<S, B>(&self, inner: &mut S, request: Request<B>) -> Fallible<S::Response, QuotaLimitError>
     where
@@ -18,56 +39,94 @@ pub(crate) trait QuotaLimit {
             .cloned()
             .ok_or_else(|| QuotaLimitError::QuotaLimitFailed)?;
 
-        if self.is_limit_over(account_id).await? {
-            Err(QuotaLimitError::QuotaLimitOver)
-        } else {
-            // `Error`は`Infallible`であるため`unwrap()`で問題ない
-            let response = inner.call(request).await.unwrap();
-
-            match response.status() {
-                StatusCode::OK => {
-                    // 失敗しても続行
-                    let _ = self.increment_consumed_quota(account_id, self.time_window()).await;
-                    
-                    Ok(response)
-                },
-                _ => Err(QuotaLimitError::QuotaLimitFailed)
-            }
+        let personal_limit = self.fetch_personal_limit(account_id).await?;
+
+        // 上限の確認より先に予約することで、並行するリクエストが揃って上限を通過することを防ぐ
+        let consumed_quota = self.reserve_quota(account_id, self.time_window()).await?;
+
+        if personal_limit.value() < consumed_quota {
+            // 失敗しても上限を超えている状態が続くだけであるため続行
+            let _ = self.refund_quota(account_id).await;
+
+            return Err(QuotaLimitError::QuotaLimitOver(personal_limit));
         }
+
+        // `Error`は`Infallible`であるため`unwrap()`で問題ない
+        let mut response = inner.call(request).await.unwrap();
+
+        // 内部サービスのレスポンスはステータスコードに関わらずそのまま返す
+        let consumed_quota = if self.charged_statuses().contains(response.status()) {
+            consumed_quota
+        } else {
+            // 失敗しても続行
+            let _ = self.refund_quota(account_id).await;
+
+            ConsumedQuota::new(consumed_quota.value() - 1)
+        };
+
+        insert_quota_headers(response.headers_mut(), personal_limit, consumed_quota);
+
+        Ok(response)
     }
 
-    async fn is_limit_over(&self, account_id: AccountId) -> Fallible<bool, QuotaLimitError> {
-        match self.fetch_personal_limit(account_id).await? {
-            Some(personal_limit) => {
-                let consumed_quota = self.fetch_consumed_quota(account_id)
-                    .await?
-                    .unwrap_or_else(|| ConsumedQuota::new(0));
-    
-                Ok(personal_limit.value() <= consumed_quota)
-            },
-            None => Ok(true)
+    async fn fetch_personal_limit(&self, account_id: AccountId) -> Fallible<InculsiveLimit, QuotaLimitError> {
+        if let Some(personal_limit) = self.fetch_cached_personal_limit(account_id).await? {
+            return Ok(personal_limit);
         }
+
+        let personal_limit = self.quota_policy().limit_for(&self.fetch_contribution(account_id).await?);
+
+        // 失敗しても次回に再計算されるだけであるため続行
+        let _ = self.cache_personal_limit(account_id, personal_limit).await;
+
+        Ok(personal_limit)
     }
 
-    async fn fetch_personal_limit(&self, account_id: AccountId) -> Fallible<Option<InculsiveLimit>, QuotaLimitError>;
+    async fn fetch_cached_personal_limit(&self, account_id: AccountId) -> Fallible<Option<InculsiveLimit>, QuotaLimitError>;
+
+    // 貢献が記録されていないアカウントは、貢献が無いものとして扱う
+    async fn fetch_contribution(&self, account_id: AccountId) -> Fallible<Contribution, QuotaLimitError>;
+
+    async fn cache_personal_limit(&self, account_id: AccountId, personal_limit: InculsiveLimit) -> Fallible<(), QuotaLimitError>;
+
+    fn quota_policy(&self) -> &ContributionQuotaPolicy;
 
     async fn fetch_consumed_quota(&self, account_id: AccountId) -> Fallible<Option<ConsumedQuota>, QuotaLimitError>;
 
-    async fn increment_consumed_quota(&self, account_id: AccountId, time_window: TimeWindow) -> Fallible<(), QuotaLimitError>;
+    // クォータを1つ消費し、消費後の消費クォータを返す
+    async fn reserve_quota(&self, account_id: AccountId, time_window: TimeWindow) -> Fallible<ConsumedQuota, QuotaLimitError>;
+
+    async fn refund_quota(&self, account_id: AccountId) -> Fallible<(), QuotaLimitError>;
+
+    fn charged_statuses(&self) -> ChargedStatuses;
 
     fn time_window(&self) -> TimeWindow;
 }
 
+pub fn insert_quota_headers(headers: &mut HeaderMap, personal_limit: InculsiveLimit, consumed_quota: ConsumedQuota) {
+    let limit = personal_limit.value().value();
+    let remaining = limit.saturating_sub(consumed_quota.value());
+
+    headers.insert(QUOTA_LIMIT_HEADER, HeaderValue::from(limit));
+    headers.insert(QUOTA_REMAINING_HEADER, HeaderValue::from(remaining));
+}
+
 #[derive(Debug, Error)]
 pub enum QuotaLimitError {
     #[error("個人のクォータ上限の取得に失敗しました")]
     FetchPersonalLimitFailed(#[source] anyhow::Error),
+    #[error("貢献の取得に失敗しました")]
+    FetchContributionFailed(#[source] anyhow::Error),
+    #[error("個人のクォータ上限のキャッシュに失敗しました")]
+    CachePersonalLimitFailed(#[source] anyhow::Error),
     #[error("消費クォータの取得に失敗しました")]
     FetchConsumedQuotaFailed(#[source] anyhow::Error),
     #[error("クォータ上限に達しています")]
-    QuotaLimitOver,
-    #[error("消費クォータのインクリメントに失敗しました")]
-    IncrementConsumedQuotaFailed(#[source] anyhow::Error),
+    QuotaLimitOver(InculsiveLimit),
+    #[error("クォータの予約に失敗しました")]
+    ReserveQuotaFailed(#[source] anyhow::Error),
+    #[error("クォータの払い戻しに失敗しました")]
+    RefundQuotaFailed(#[source] anyhow::Error),
     #[error("クォータ制限に失敗しました")]
     QuotaLimitFailed,
 }
@@ -76,22 +135,24 @@ pub enum QuotaLimitError {
 mod tests {
     use std::{convert::Infallible, future::{ready, Ready}, sync::LazyLock, task::{Context, Poll}};
 
-    use http::{Request, Response};
+    use http::{Request, Response, StatusCode};
     use thiserror::Error;
     use tower::Service;
 
 
-    use crate::{common::{fallible::Fallible, profile::account_id::AccountId}, middlewares::limit::{InculsiveLimit, TimeWindow}};
+    use crate::{common::{contribution::Contribution, fallible::Fallible, profile::account_id::AccountId}, middlewares::{limit::{Count, InculsiveLimit, TimeWindow}, quota_limit::policy::ContributionQuotaPolicy}};
 
-    use super::{ConsumedQuota, QuotaLimit, QuotaLimitError};
+    use super::{ChargedStatuses, ConsumedQuota, QuotaLimit, QuotaLimitError, QUOTA_LIMIT_HEADER, QUOTA_REMAINING_HEADER};
 
     struct MockQuotaLimit;
 
+    const POLICY: ContributionQuotaPolicy = ContributionQuotaPolicy::new(Count::new(1), 1, 1, 0, Count::new(2));
+
     #[derive(Debug, Error)]
     #[error("疑似エラー")]
     struct MockError;
 
-    struct MockService;
+    struct MockService(StatusCode);
 
     impl Service<Request<()>> for MockService {
         type Response = Response<()>;
@@ -103,23 +164,43 @@ mod tests {
         }
 
         fn call(&mut self, _: Request<()>) -> Self::Future {
-            ready(Ok(Response::new(())))
+            let mut response = Response::new(());
+            *response.status_mut() = self.0;
+            ready(Ok(response))
         }
     }
 
     static UNCONSUMED: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
     static WITHIN_LIMIT: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
     static LIMIT_OVER: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static CACHED: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static NO_CONTRIBUTION: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
 
     impl QuotaLimit for MockQuotaLimit {
-        async fn fetch_personal_limit(&self, account_id: AccountId) -> Fallible<Option<InculsiveLimit>, QuotaLimitError> {
-            if account_id == *UNCONSUMED || account_id == *WITHIN_LIMIT || account_id == *LIMIT_OVER {
+        async fn fetch_cached_personal_limit(&self, account_id: AccountId) -> Fallible<Option<InculsiveLimit>, QuotaLimitError> {
+            if account_id == *CACHED {
                 Ok(Some(InculsiveLimit::new(ConsumedQuota::new(2))))
             } else {
                 Ok(None)
             }
         }
 
+        async fn fetch_contribution(&self, account_id: AccountId) -> Fallible<Contribution, QuotaLimitError> {
+            if account_id == *UNCONSUMED || account_id == *WITHIN_LIMIT || account_id == *LIMIT_OVER {
+                Ok(Contribution::new(1, 0, 0))
+            } else {
+                Ok(Contribution::default())
+            }
+        }
+
+        async fn cache_personal_limit(&self, _: AccountId, _: InculsiveLimit) -> Fallible<(), QuotaLimitError> {
+            Ok(())
+        }
+
+        fn quota_policy(&self) -> &ContributionQuotaPolicy {
+            &POLICY
+        }
+
         async fn fetch_consumed_quota(&self, account_id: AccountId) -> Fallible<Option<ConsumedQuota>, QuotaLimitError> {
             if account_id == *UNCONSUMED {
                 Ok(None)
@@ -127,21 +208,35 @@ mod tests {
                 Ok(Some(ConsumedQuota::new(1)))
             } else if account_id == *LIMIT_OVER {
                 Ok(Some(ConsumedQuota::new(2)))
+            } else if account_id == *NO_CONTRIBUTION || account_id == *CACHED {
+                Ok(Some(ConsumedQuota::new(1)))
             } else {
                 Err(QuotaLimitError::FetchConsumedQuotaFailed(MockError.into()))
             }
         }
-    
-        async fn increment_consumed_quota(&self, _: AccountId, _: TimeWindow) -> Fallible<(), QuotaLimitError> {
+
+        async fn reserve_quota(&self, account_id: AccountId, _: TimeWindow) -> Fallible<ConsumedQuota, QuotaLimitError> {
+            let consumed_quota = self.fetch_consumed_quota(account_id)
+                .await?
+                .unwrap_or_else(|| ConsumedQuota::new(0));
+
+            Ok(ConsumedQuota::new(consumed_quota.value() + 1))
+        }
+
+        async fn refund_quota(&self, _: AccountId) -> Fallible<(), QuotaLimitError> {
             Ok(())
         }
-    
+
+        fn charged_statuses(&self) -> ChargedStatuses {
+            ChargedStatuses::ONLY_OK
+        }
+
         fn time_window(&self) -> TimeWindow {
             TimeWindow::seconds(60)
         }
     }
 
-    async fn test_quota_limit(account_id: AccountId) -> Fallible<Response<()>, QuotaLimitError> {
+    async fn test_quota_limit(account_id: AccountId, status: StatusCode) -> Fallible<Response<()>, QuotaLimitError> {
         let mut request = Request::builder()
             .body(())
             .unwrap();
@@ -149,30 +244,54 @@ mod tests {
         // セッション管理ミドルウェアを模倣
         request.extensions_mut().insert(account_id);
 
-        MockQuotaLimit.quota_limit(&mut MockService, request).await
+        MockQuotaLimit.quota_limit(&mut MockService(status), request).await
+    }
+
+    fn remaining(response: &Response<()>) -> &str {
+        response.headers()
+            .get(QUOTA_REMAINING_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap()
     }
 
     #[tokio::test]
-    async fn no_personal_limit() {
-        let res = test_quota_limit(AccountId::gen()).await;
-        assert!(matches!(res.err().unwrap(), QuotaLimitError::QuotaLimitOver));
+    async fn no_contribution() {
+        let res = test_quota_limit(*NO_CONTRIBUTION, StatusCode::OK).await;
+        assert!(matches!(res.err().unwrap(), QuotaLimitError::QuotaLimitOver(_)));
     }
 
     #[tokio::test]
-    async fn unconsumed() {
-        let res = test_quota_limit(*UNCONSUMED).await;
+    async fn cached_personal_limit() {
+        // 貢献から導出される上限(1)ではなく、キャッシュされた上限(2)が使われる
+        let res = test_quota_limit(*CACHED, StatusCode::OK).await;
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn unconsumed() {
+        let res = test_quota_limit(*UNCONSUMED, StatusCode::OK).await.unwrap();
+        assert_eq!(res.headers().get(QUOTA_LIMIT_HEADER).unwrap(), "2");
+        assert_eq!(remaining(&res), "1");
+    }
+
     #[tokio::test]
     async fn within_limit() {
-        let res = test_quota_limit(*WITHIN_LIMIT).await;
-        assert!(res.is_ok());
+        let res = test_quota_limit(*WITHIN_LIMIT, StatusCode::OK).await.unwrap();
+        assert_eq!(remaining(&res), "0");
     }
 
     #[tokio::test]
     async fn limit_over() {
-        let res = test_quota_limit(*LIMIT_OVER).await;
-        assert!(matches!(res.err().unwrap(), QuotaLimitError::QuotaLimitOver));
+        let res = test_quota_limit(*LIMIT_OVER, StatusCode::OK).await;
+        assert!(matches!(res.err().unwrap(), QuotaLimitError::QuotaLimitOver(_)));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn uncharged_status() {
+        // 内部サービスのレスポンスはそのまま返され、クォータは払い戻される
+        let res = test_quota_limit(*WITHIN_LIMIT, StatusCode::BAD_REQUEST).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(remaining(&res), "1");
+    }
+}
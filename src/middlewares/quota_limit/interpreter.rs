@@ -3,62 +3,123 @@ use std::sync::Arc;
 use redis::{cmd, Script};
 use scylla::{prepared_statement::PreparedStatement, Session};
 
-use crate::{common::{fallible::Fallible, profile::account_id::AccountId}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}}, scylla::prepare}, middlewares::limit::{EndpointName, InculsiveLimit, TimeWindow}};
+use crate::{common::{contribution::Contribution, fallible::Fallible, profile::account_id::AccountId}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}}, scylla::prepare}, middlewares::limit::{EndpointName, InculsiveLimit, TimeWindow}};
 
-use super::dsl::{ConsumedQuota, QuotaLimit, QuotaLimitError};
+use super::{dsl::{ChargedStatuses, ConsumedQuota, QuotaLimit, QuotaLimitError}, policy::ContributionQuotaPolicy};
 
 const QUOTA_LIMIT_NAMESPACE: Namespace = Namespace::of("qtlim");
 
+const PERSONAL_LIMIT_NAMESPACE: Namespace = Namespace::of("qtcap");
+
+// 貢献は1サイクル(1時間)ごとに集計されるため、それより長くキャッシュする意味はない
+const PERSONAL_LIMIT_CACHE_EXPIRATION: TimeWindow = TimeWindow::hours(1);
+
 #[derive(Debug)]
 pub struct QuotaLimitImpl {
     db: Arc<Session>,
     cache: Arc<Pool>,
     endpoint_name: EndpointName,
     time_window: TimeWindow,
-    select_quota_limit: Arc<PreparedStatement>,
+    quota_policy: ContributionQuotaPolicy,
+    charged_statuses: ChargedStatuses,
+    select_contribution: Arc<PreparedStatement>,
     incr_and_expire_if_first: Arc<Script>,
+    decr_if_exists: Arc<Script>,
 }
 
 impl QuotaLimitImpl {
-    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: EndpointName, time_window: TimeWindow) -> Result<Self, InitError<Self>> {
-        let select_quota_limit = prepare(&db, "").await?;
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: EndpointName, time_window: TimeWindow, quota_policy: ContributionQuotaPolicy, charged_statuses: ChargedStatuses) -> Result<Self, InitError<Self>> {
+        let select_contribution = prepare(&db, "SELECT accepted_proposals, agreed_ratings, disagreed_ratings FROM account_contributions WHERE account_id = ?").await?;
 
         let incr_and_expire_if_first = Arc::new(Script::new(include_str!("incr_and_expire_if_first.lua")));
 
-        Ok(Self { db, cache, endpoint_name, time_window, select_quota_limit, incr_and_expire_if_first })
+        let decr_if_exists = Arc::new(Script::new(include_str!("decr_if_exists.lua")));
+
+        Ok(Self { db, cache, endpoint_name, time_window, quota_policy, charged_statuses, select_contribution, incr_and_expire_if_first, decr_if_exists })
+    }
+
+    fn consumed_quota_key(&self, account_id: AccountId) -> String {
+        format!("{}{}{}{}{}", QUOTA_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, self.endpoint_name, NAMESPACE_SEPARATOR, account_id)
+    }
+
+    fn personal_limit_key(&self, account_id: AccountId) -> String {
+        format!("{}{}{}{}{}", PERSONAL_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, self.endpoint_name, NAMESPACE_SEPARATOR, account_id)
     }
 }
 
 impl QuotaLimit for QuotaLimitImpl {
-    async fn fetch_personal_limit(&self, account_id: AccountId) -> Fallible<Option<InculsiveLimit>, QuotaLimitError> {
-        self.db
-            .execute_unpaged(&self.select_quota_limit, (account_id, ))
+    async fn fetch_cached_personal_limit(&self, account_id: AccountId) -> Fallible<Option<InculsiveLimit>, QuotaLimitError> {
+        let mut conn = conn(&self.cache, |e| QuotaLimitError::FetchPersonalLimitFailed(e.into())).await?;
+
+        cmd("GET")
+            .arg(self.personal_limit_key(account_id))
+            .query_async::<Option<InculsiveLimit>>(&mut *conn)
             .await
-            .map_err(|e| QuotaLimitError::FetchPersonalLimitFailed(e.into()))?
-            .maybe_first_row_typed::<(InculsiveLimit, )>()
             .map_err(|e| QuotaLimitError::FetchPersonalLimitFailed(e.into()))
-            .map(|o| o.map(|(personal_limit, )| personal_limit))
+    }
+
+    async fn fetch_contribution(&self, account_id: AccountId) -> Fallible<Contribution, QuotaLimitError> {
+        self.db
+            .execute_unpaged(&self.select_contribution, (account_id, ))
+            .await
+            .map_err(|e| QuotaLimitError::FetchContributionFailed(e.into()))?
+            .maybe_first_row_typed::<(i32, i32, i32)>()
+            .map_err(|e| QuotaLimitError::FetchContributionFailed(e.into()))
+            .map(|o| o.map(Contribution::from).unwrap_or_default())
+    }
+
+    async fn cache_personal_limit(&self, account_id: AccountId, personal_limit: InculsiveLimit) -> Fallible<(), QuotaLimitError> {
+        let mut conn = conn(&self.cache, |e| QuotaLimitError::CachePersonalLimitFailed(e.into())).await?;
+
+        cmd("SET")
+            .arg(self.personal_limit_key(account_id))
+            .arg(personal_limit)
+            .arg("EX")
+            .arg(PERSONAL_LIMIT_CACHE_EXPIRATION)
+            .exec_async(&mut *conn)
+            .await
+            .map_err(|e| QuotaLimitError::CachePersonalLimitFailed(e.into()))
     }
 
     async fn fetch_consumed_quota(&self, account_id: AccountId) -> Fallible<Option<ConsumedQuota>, QuotaLimitError> {
         let mut conn = conn(&self.cache, |e| QuotaLimitError::FetchConsumedQuotaFailed(e.into())).await?;
         
         cmd("GET")
-            .arg(format!("{}{}{}{}{}", QUOTA_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, self.endpoint_name, NAMESPACE_SEPARATOR, account_id))
+            .arg(self.consumed_quota_key(account_id))
             .query_async::<Option<ConsumedQuota>>(&mut *conn)
             .await
             .map_err(|e| QuotaLimitError::FetchConsumedQuotaFailed(e.into()))
     }
 
-    async fn increment_consumed_quota(&self, account_id: AccountId, time_window: TimeWindow) -> Fallible<(), QuotaLimitError> {
-        let mut conn = conn(&self.cache, |e| QuotaLimitError::IncrementConsumedQuotaFailed(e.into())).await?;
+    async fn reserve_quota(&self, account_id: AccountId, time_window: TimeWindow) -> Fallible<ConsumedQuota, QuotaLimitError> {
+        let mut conn = conn(&self.cache, |e| QuotaLimitError::ReserveQuotaFailed(e.into())).await?;
         
         self.incr_and_expire_if_first
-            .key(format!("{}{}{}{}{}", QUOTA_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, self.endpoint_name, NAMESPACE_SEPARATOR, account_id))
+            .key(self.consumed_quota_key(account_id))
             .arg(time_window)
-            .invoke_async(&mut *conn)
+            .invoke_async::<ConsumedQuota>(&mut *conn)
             .await
-            .map_err(|e| QuotaLimitError::IncrementConsumedQuotaFailed(e.into()))
+            .map_err(|e| QuotaLimitError::ReserveQuotaFailed(e.into()))
+    }
+
+    async fn refund_quota(&self, account_id: AccountId) -> Fallible<(), QuotaLimitError> {
+        let mut conn = conn(&self.cache, |e| QuotaLimitError::RefundQuotaFailed(e.into())).await?;
+
+        self.decr_if_exists
+            .key(self.consumed_quota_key(account_id))
+            .invoke_async::<i64>(&mut *conn)
+            .await
+            .map_err(|e| QuotaLimitError::RefundQuotaFailed(e.into()))?;
+
+        Ok(())
+    }
+
+    fn quota_policy(&self) -> &ContributionQuotaPolicy {
+        &self.quota_policy
+    }
+
+    fn charged_statuses(&self) -> ChargedStatuses {
+        self.charged_statuses
     }
 
     fn time_window(&self) -> TimeWindow {
@@ -1,12 +1,12 @@
 use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc, task::{ready, Context, Poll}};
 
-use http::{Request, Response, StatusCode};
+use http::{Request, Response};
 use pin_project::pin_project;
 use scylla::Session;
 use tokio::pin;
 use tower::{Layer, Service};
 
-use crate::{helper::{error::InitError, redis::connection::Pool}, middlewares::{limit::{EndpointName, TimeWindow}, quota_limit::dsl::{QuotaLimit, QuotaLimitError}}};
+use crate::{helper::{api_error::ApiError, error::InitError, redis::connection::Pool}, middlewares::{limit::{EndpointName, TimeWindow}, quota_limit::{dsl::{insert_quota_headers, ChargedStatuses, QuotaLimit, QuotaLimitError}, policy::ContributionQuotaPolicy}}};
 
 use super::interpreter::QuotaLimitImpl;
 
@@ -16,8 +16,8 @@ pub struct QuotaLimitLayer {
 }
 
 impl QuotaLimitLayer {
-    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: EndpointName, time_window: TimeWindow) -> Result<Self, InitError<QuotaLimitImpl>> {
-        let quota_limit = QuotaLimitImpl::try_new(db, cache, endpoint_name, time_window).await?;
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: EndpointName, time_window: TimeWindow, quota_policy: ContributionQuotaPolicy, charged_statuses: ChargedStatuses) -> Result<Self, InitError<QuotaLimitImpl>> {
+        let quota_limit = QuotaLimitImpl::try_new(db, cache, endpoint_name, time_window, quota_policy, charged_statuses).await?;
         Ok(Self { quota_limit: Arc::new(quota_limit) })
     }
 }
@@ -43,7 +43,7 @@ impl <S, B> Service<Request<B>> for RateLimitService<S>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>> + Clone,
     S::Future: Future<Output = Result<S::Response, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Response = S::Response;
     type Error = S::Error;
@@ -66,7 +66,7 @@ where
 pub struct SessionFuture<S, B>
 where
     S: Service<Request<B>>,
-    B: Default,
+    B: From<String>,
 {
     inner: S,
     request: Option<Request<B>>,
@@ -77,7 +77,7 @@ impl<S, B> Future for SessionFuture<S, B>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>>,
     S::Future: Future<Output = Result<S::Response, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Output = Result<S::Response, S::Error>;
 
@@ -92,16 +92,17 @@ where
         match ready!(response_future.poll(cx)) {
             Ok(response) => Poll::Ready(Ok(response)),
             Err(e) => {
-                let status_code = match e {
-                    QuotaLimitError::QuotaLimitOver => StatusCode::TOO_MANY_REQUESTS,
-                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                let response = match e {
+                    QuotaLimitError::QuotaLimitOver(personal_limit) => {
+                        let mut response = ApiError::QUOTA_EXCEEDED.to_response();
+
+                        // 残りのクォータが無いことを伝える
+                        insert_quota_headers(response.headers_mut(), personal_limit, personal_limit.value());
+                        response
+                    },
+                    _ => ApiError::INTERNAL.to_response(),
                 };
 
-                let response = Response::builder()
-                    .status(status_code)
-                    .body(B::default())
-                    .unwrap();
-
                 Poll::Ready(Ok(response))
             }
         }
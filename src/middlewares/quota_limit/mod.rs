@@ -1,3 +1,4 @@
 pub mod dsl;
 pub mod interpreter;
-pub mod middleware;
\ No newline at end of file
+pub mod middleware;
+pub mod policy;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use crate::{common::contribution::Contribution, middlewares::limit::{Count, InculsiveLimit}};
+
+// 貢献から個人のクォータ上限を導出する
+// 上限 = 基本上限 + 採用された提案による加算 + 評価の一致率による加算 (最大上限で頭打ち)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ContributionQuotaPolicy {
+    base_limit: Count,
+    accepted_proposals_per_bonus: u32,
+    min_judged_ratings: u32,
+    max_agreement_bonus: u32,
+    max_limit: Count,
+}
+
+impl ContributionQuotaPolicy {
+    pub const fn new(base_limit: Count, accepted_proposals_per_bonus: u32, min_judged_ratings: u32, max_agreement_bonus: u32, max_limit: Count) -> Self {
+        if accepted_proposals_per_bonus == 0 {
+            panic!("`accepted_proposals_per_bonus`は1以上である必要があります");
+        } else if base_limit.value() > max_limit.value() {
+            panic!("`base_limit`が`max_limit`を超えています");
+        }
+
+        Self { base_limit, accepted_proposals_per_bonus, min_judged_ratings, max_agreement_bonus, max_limit }
+    }
+
+    pub fn limit_for(&self, contribution: &Contribution) -> InculsiveLimit {
+        let limit = self.base_limit.value()
+            .saturating_add(self.accepted_proposal_bonus(contribution))
+            .saturating_add(self.agreement_bonus(contribution))
+            .min(self.max_limit.value());
+
+        InculsiveLimit::new(Count::new(limit))
+    }
+
+    fn accepted_proposal_bonus(&self, contribution: &Contribution) -> u32 {
+        contribution.accepted_proposals() / self.accepted_proposals_per_bonus
+    }
+
+    // 少数の評価による偶然の一致を除外するため、一定数以上の評価が判定されるまで加算しない
+    fn agreement_bonus(&self, contribution: &Contribution) -> u32 {
+        let judged_ratings = contribution.judged_ratings();
+
+        if judged_ratings == 0 || judged_ratings < self.min_judged_ratings {
+            0
+        } else {
+            (contribution.agreed_ratings() as u64 * self.max_agreement_bonus as u64 / judged_ratings as u64) as u32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{common::contribution::Contribution, middlewares::limit::{Count, InculsiveLimit}};
+
+    use super::ContributionQuotaPolicy;
+
+    const POLICY: ContributionQuotaPolicy = ContributionQuotaPolicy::new(Count::new(1), 5, 20, 4, Count::new(10));
+
+    fn limit(accepted_proposals: u32, agreed_ratings: u32, disagreed_ratings: u32) -> InculsiveLimit {
+        POLICY.limit_for(&Contribution::new(accepted_proposals, agreed_ratings, disagreed_ratings))
+    }
+
+    #[test]
+    fn no_contribution() {
+        assert_eq!(limit(0, 0, 0), InculsiveLimit::new(Count::new(1)));
+    }
+
+    #[test]
+    fn accepted_proposals() {
+        assert_eq!(limit(4, 0, 0), InculsiveLimit::new(Count::new(1)));
+        assert_eq!(limit(10, 0, 0), InculsiveLimit::new(Count::new(3)));
+    }
+
+    #[test]
+    fn too_few_judged_ratings() {
+        assert_eq!(limit(0, 19, 0), InculsiveLimit::new(Count::new(1)));
+    }
+
+    #[test]
+    fn agreement() {
+        assert_eq!(limit(0, 20, 0), InculsiveLimit::new(Count::new(5)));
+        assert_eq!(limit(0, 10, 10), InculsiveLimit::new(Count::new(3)));
+        assert_eq!(limit(0, 0, 20), InculsiveLimit::new(Count::new(1)));
+    }
+
+    #[test]
+    fn capped() {
+        assert_eq!(limit(u32::MAX, u32::MAX, 0), InculsiveLimit::new(Count::new(10)));
+    }
+}
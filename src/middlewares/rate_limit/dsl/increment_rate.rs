@@ -1,19 +1,54 @@
+use std::{fmt::{self, Display}, net::IpAddr};
+
 use thiserror::Error;
 
-use crate::{common::{api_key::key::ApiKey, fallible::Fallible}, middlewares::limit::{Count, InculsiveLimit, TimeWindow}};
+use crate::{common::{api_key::key::ApiKey, fallible::Fallible, ip_address::host_prefix, profile::account_id::AccountId}, middlewares::limit::{Count, InculsiveLimit, TimeWindow}};
 
 pub type Rate = Count;
 
+// レートを数える単位
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitSubject<'a> {
+    ApiKey(&'a ApiKey),
+    AccountId(AccountId),
+    IpAddress(IpAddr),
+}
+
+impl RateLimitSubject<'_> {
+    pub fn ip_address(ip_address: IpAddr) -> Self {
+        Self::IpAddress(host_prefix(ip_address))
+    }
+
+    // 同じエンドポイントで単位ごとのレートを区別するために用いる
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ApiKey(_) => "ak",
+            Self::AccountId(_) => "ac",
+            Self::IpAddress(_) => "ip",
+        }
+    }
+}
+
+impl Display for RateLimitSubject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ApiKey(api_key) => api_key.fmt(f),
+            Self::AccountId(account_id) => account_id.fmt(f),
+            Self::IpAddress(ip_address) => ip_address.fmt(f),
+        }
+    }
+}
+
 pub(crate) trait IncrementRate {
-    async fn try_increment_rate(&self, api_key: &ApiKey) -> Fallible<(), IncrementRateError> {
-        let rate = self.increment_rate_within_window(api_key, self.time_window()).await?;
+    async fn try_increment_rate(&self, subject: &RateLimitSubject<'_>) -> Fallible<(), IncrementRateError> {
+        let rate = self.increment_rate_within_window(subject, self.time_window()).await?;
         if self.is_limit_over(rate) {
             return Err(IncrementRateError::RateLimitOver)
         }
         Ok(())
     }
 
-    async fn increment_rate_within_window(&self, api_key: &ApiKey, time_window: TimeWindow) -> Fallible<Rate, IncrementRateError>;
+    async fn increment_rate_within_window(&self, subject: &RateLimitSubject<'_>, time_window: TimeWindow) -> Fallible<Rate, IncrementRateError>;
 
     fn time_window(&self) -> TimeWindow;
 
@@ -34,11 +69,11 @@ pub enum IncrementRateError {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::LazyLock;
+    use std::{net::IpAddr, sync::LazyLock};
 
     use crate::{common::{api_key::key::ApiKey, fallible::Fallible}, middlewares::limit::{Count, InculsiveLimit, TimeWindow}};
 
-    use super::{IncrementRate, IncrementRateError, Rate};
+    use super::{IncrementRate, IncrementRateError, Rate, RateLimitSubject};
 
     static WITHIN_LIMIT: LazyLock<ApiKey> = LazyLock::new(ApiKey::gen);
 
@@ -48,8 +83,8 @@ mod tests {
     struct MockIncrementRate;
 
     impl IncrementRate for MockIncrementRate {
-        async fn increment_rate_within_window(&self, api_key: &ApiKey, _: TimeWindow) -> Fallible<Rate, IncrementRateError> {
-            if api_key == &*WITHIN_LIMIT {
+        async fn increment_rate_within_window(&self, subject: &RateLimitSubject<'_>, _: TimeWindow) -> Fallible<Rate, IncrementRateError> {
+            if subject == &RateLimitSubject::ApiKey(&WITHIN_LIMIT) {
                 Ok(Rate::new(INCLUSIVE_LIMIT.value().value()))
             } else {
                 Ok(Rate::new(INCLUSIVE_LIMIT.value().value() + 1))
@@ -67,18 +102,40 @@ mod tests {
 
     #[tokio::test]
     async fn within_limit() {
-        let api_key = &*WITHIN_LIMIT;
-        let result = MockIncrementRate.try_increment_rate(api_key).await;
+        let subject = RateLimitSubject::ApiKey(&WITHIN_LIMIT);
+        let result = MockIncrementRate.try_increment_rate(&subject).await;
         assert!(result.is_ok());
     }
 
     #[tokio::test]
     async fn over_limit() {
         let api_key = ApiKey::gen();
-        let result = MockIncrementRate.try_increment_rate(&api_key).await;
+        let result = MockIncrementRate.try_increment_rate(&RateLimitSubject::ApiKey(&api_key)).await;
         match result {
             Err(IncrementRateError::RateLimitOver) => (),
             _ => panic!(),
         }
     }
+
+    fn ip_address(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn ipv4_address() {
+        let subject = RateLimitSubject::ip_address(ip_address("192.0.2.1"));
+        assert_eq!(subject, RateLimitSubject::IpAddress(ip_address("192.0.2.1")));
+    }
+
+    #[test]
+    fn ipv6_address_grouped_by_prefix() {
+        let subject = RateLimitSubject::ip_address(ip_address("2001:db8:1:2:3:4:5:6"));
+        assert_eq!(subject, RateLimitSubject::IpAddress(ip_address("2001:db8:1:2::")));
+    }
+
+    #[test]
+    fn ipv4_mapped_ipv6_address() {
+        let subject = RateLimitSubject::ip_address(ip_address("::ffff:192.0.2.1"));
+        assert_eq!(subject, RateLimitSubject::IpAddress(ip_address("192.0.2.1")));
+    }
 }
\ No newline at end of file
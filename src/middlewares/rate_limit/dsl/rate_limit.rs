@@ -1,12 +1,50 @@
-use std::{convert::Infallible, str::FromStr};
+use std::{convert::Infallible, net::SocketAddr, str::FromStr};
 
+use axum::extract::ConnectInfo;
 use http::{HeaderMap, Request, Response};
 use thiserror::Error;
 use tower::Service;
 
-use crate::common::{api_key::{key::ApiKey, refreshed_at::LastApiKeyRefreshedAt}, fallible::Fallible};
+use crate::common::{api_key::{key::ApiKey, refreshed_at::LastApiKeyRefreshedAt}, fallible::Fallible, profile::account_id::AccountId};
 
-use super::{increment_rate::{IncrementRate, IncrementRateError}, refresh_api_key::RefreshApiKey};
+use super::{increment_rate::{IncrementRate, IncrementRateError, RateLimitSubject}, refresh_api_key::RefreshApiKey};
+
+// どの単位でレートを数えるか
+// 複数を組み合わせた場合は単位ごとに数え、いずれかが上限を超えた時点で制限する
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RateLimitKeyStrategy {
+    by_api_key: bool,
+    by_account_id: bool,
+    by_ip_address: bool,
+}
+
+impl RateLimitKeyStrategy {
+    pub const API_KEY: Self = Self { by_api_key: true, by_account_id: false, by_ip_address: false };
+    // セッション管理ミドルウェアより内側で用いる必要がある
+    pub const ACCOUNT_ID: Self = Self { by_api_key: false, by_account_id: true, by_ip_address: false };
+    // `ConnectInfo`を伴うサーバーで用いる必要がある
+    pub const IP_ADDRESS: Self = Self { by_api_key: false, by_account_id: false, by_ip_address: true };
+
+    pub const fn and(self, other: Self) -> Self {
+        Self {
+            by_api_key: self.by_api_key || other.by_api_key,
+            by_account_id: self.by_account_id || other.by_account_id,
+            by_ip_address: self.by_ip_address || other.by_ip_address,
+        }
+    }
+
+    pub fn by_api_key(&self) -> bool {
+        self.by_api_key
+    }
+
+    pub fn by_account_id(&self) -> bool {
+        self.by_account_id
+    }
+
+    pub fn by_ip_address(&self) -> bool {
+        self.by_ip_address
+    }
+}
 
 pub(crate) trait RateLimit {
     async fn rate_limit<S, B>(&self, inner: &mut S, request: Request<B>) -> Fallible<S::Response, RateLimitError>
@@ -14,26 +52,69 @@ pub(crate) trait RateLimit {
         Self: IncrementRate + RefreshApiKey,
         S: Service<Request<B>, Error = Infallible, Response = Response<B>>
     {
-        let api_key = Self::extract_no_account_user_api_key(request.headers())
-            .ok_or(RateLimitError::NoApiKey)?;
-
-        let last_api_key_refreshed_at = self.fetch_last_api_key_refreshed_at(&api_key)
-            .await?
-            .ok_or(RateLimitError::InvalidApiKey)?;
-
-        match self.try_increment_rate(&api_key).await {
-            Ok(_) => {
-                // `Error`は`Infallible`であるため`unwrap()`で問題ない
-                let response = inner.call(request).await.unwrap();
-
-                // 失敗しても続行
-                let _ = self.try_refresh_api_key(last_api_key_refreshed_at, &api_key).await;
-                
-                Ok(response)
+        let key_strategy = self.key_strategy();
+
+        // APIキーはレートを数える単位に含まれる場合のみ検証する
+        let api_key = match key_strategy.by_api_key() {
+            true => {
+                let api_key = Self::extract_no_account_user_api_key(request.headers())
+                    .ok_or(RateLimitError::NoApiKey)?;
+
+                let last_api_key_refreshed_at = self.fetch_last_api_key_refreshed_at(&api_key)
+                    .await?
+                    .ok_or(RateLimitError::InvalidApiKey)?;
+
+                Some((api_key, last_api_key_refreshed_at))
             },
-            Err(IncrementRateError::RateLimitOver) => Err(RateLimitError::RateLimitOver),
-            _ => Err(RateLimitError::RateLimitFailed),
+            false => None,
+        };
+
+        let subjects = Self::extract_subjects(key_strategy, &request, api_key.as_ref().map(|(api_key, _)| api_key))?;
+
+        for subject in subjects.iter() {
+            match self.try_increment_rate(subject).await {
+                Ok(_) => (),
+                Err(IncrementRateError::RateLimitOver) => return Err(RateLimitError::RateLimitOver),
+                _ => return Err(RateLimitError::RateLimitFailed),
+            }
         }
+
+        // `Error`は`Infallible`であるため`unwrap()`で問題ない
+        let response = inner.call(request).await.unwrap();
+
+        if let Some((api_key, last_api_key_refreshed_at)) = api_key {
+            // 失敗しても続行
+            let _ = self.try_refresh_api_key(last_api_key_refreshed_at, &api_key).await;
+        }
+
+        Ok(response)
+    }
+
+    fn extract_subjects<'a, B>(key_strategy: RateLimitKeyStrategy, request: &Request<B>, api_key: Option<&'a ApiKey>) -> Fallible<Vec<RateLimitSubject<'a>>, RateLimitError> {
+        let mut subjects = Vec::new();
+
+        if let Some(api_key) = api_key {
+            subjects.push(RateLimitSubject::ApiKey(api_key));
+        }
+
+        if key_strategy.by_account_id() {
+            let account_id = request.extensions()
+                .get::<AccountId>()
+                .cloned()
+                .ok_or(RateLimitError::NoAccountId)?;
+
+            subjects.push(RateLimitSubject::AccountId(account_id));
+        }
+
+        if key_strategy.by_ip_address() {
+            let ConnectInfo(addr) = request.extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .ok_or(RateLimitError::NoIpAddress)?;
+
+            subjects.push(RateLimitSubject::ip_address(addr.ip()));
+        }
+
+        Ok(subjects)
     }
 
     fn extract_no_account_user_api_key(headers: &HeaderMap) -> Option<ApiKey> {
@@ -44,6 +125,8 @@ pub(crate) trait RateLimit {
     }
 
     async fn fetch_last_api_key_refreshed_at(&self, api_key: &ApiKey) -> Fallible<Option<LastApiKeyRefreshedAt>, RateLimitError>;
+
+    fn key_strategy(&self) -> RateLimitKeyStrategy;
 }
 
 #[derive(Debug, Error)]
@@ -52,6 +135,10 @@ pub enum RateLimitError {
     NoApiKey,
     #[error("無効なAPIキーです")]
     InvalidApiKey,
+    #[error("アカウントIDがありません")]
+    NoAccountId,
+    #[error("IPアドレスがありません")]
+    NoIpAddress,
     #[error("APIキーの存在確認に失敗しました")]
     FetchLastApiKeyRefreshedAt(#[source] anyhow::Error),
     #[error("レート上限に達しています")]
@@ -60,20 +147,26 @@ pub enum RateLimitError {
     RateLimitFailed,
 }
 
+#[cfg(test)]
 mod tests {
-    use std::{convert::Infallible, future::{ready, Ready}, sync::LazyLock, task::{Context, Poll}};
+    use std::{convert::Infallible, future::{ready, Ready}, net::SocketAddr, sync::LazyLock, task::{Context, Poll}};
 
+    use axum::extract::ConnectInfo;
     use http::{Request, Response};
     use thiserror::Error;
     use tower::Service;
 
-    use crate::{common::{api_key::{expiration::ApiKeyExpirationSeconds, key::ApiKey, refreshed_at::LastApiKeyRefreshedAt}, fallible::Fallible, unixtime::UnixtimeMillis}, middlewares::{limit::{InculsiveLimit, TimeWindow}, rate_limit::dsl::{increment_rate::{IncrementRate, IncrementRateError, Rate}, refresh_api_key::{ApiKeyRefreshThereshold, RefreshApiKey, RefreshApiKeyError}}}};
+    use crate::{common::{api_key::{expiration::ApiKeyExpirationSeconds, key::ApiKey, refreshed_at::LastApiKeyRefreshedAt}, fallible::Fallible, profile::account_id::AccountId, unixtime::UnixtimeMillis}, middlewares::{limit::{InculsiveLimit, TimeWindow}, rate_limit::dsl::{increment_rate::{IncrementRate, IncrementRateError, Rate, RateLimitSubject}, refresh_api_key::{ApiKeyRefreshThereshold, RefreshApiKey, RefreshApiKeyError}}}};
 
-    use super::{RateLimit, RateLimitError};
+    use super::{RateLimit, RateLimitError, RateLimitKeyStrategy};
 
     static VALID_API_KEY: LazyLock<ApiKey> = LazyLock::new(ApiKey::gen);
+    static WITHIN_LIMIT: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static LIMIT_OVER: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    const IP_ADDRESS: &str = "192.0.2.1:443";
 
-    struct MockRateLimit;
+    struct MockRateLimit(RateLimitKeyStrategy);
 
     impl RateLimit for MockRateLimit {
         async fn fetch_last_api_key_refreshed_at(&self, api_key: &ApiKey) -> Fallible<Option<LastApiKeyRefreshedAt>, RateLimitError> {
@@ -83,14 +176,24 @@ mod tests {
                 Ok(None)
             }
         }
+
+        fn key_strategy(&self) -> RateLimitKeyStrategy {
+            self.0
+        }
     }
 
     const TIME_WINDOW: TimeWindow = TimeWindow::seconds(60);
     const INCLUSIVE_LIMIT: InculsiveLimit = InculsiveLimit::new(Rate::new(100));
 
     impl IncrementRate for MockRateLimit {
-        async fn increment_rate_within_window(&self, api_key: &ApiKey, _: TimeWindow) -> Fallible<Rate, IncrementRateError> {
-            if api_key == &*VALID_API_KEY {
+        async fn increment_rate_within_window(&self, subject: &RateLimitSubject<'_>, _: TimeWindow) -> Fallible<Rate, IncrementRateError> {
+            let is_within_limit = match subject {
+                RateLimitSubject::ApiKey(api_key) => *api_key == &*VALID_API_KEY,
+                RateLimitSubject::AccountId(account_id) => *account_id == *WITHIN_LIMIT,
+                RateLimitSubject::IpAddress(_) => true,
+            };
+
+            if is_within_limit {
                 Ok(Rate::new(0))
             } else {
                 Err(IncrementRateError::RateLimitOver)
@@ -147,12 +250,27 @@ mod tests {
         }
     }
 
-    async fn test_rate_limit(api_key: &ApiKey) -> Fallible<Response<()>, RateLimitError> {
-        let request = Request::builder()
+    fn request(api_key: &ApiKey) -> Request<()> {
+        Request::builder()
             .header("Authorization", format!("Bearer {}", api_key))
             .body(())
-            .unwrap();
-        MockRateLimit.rate_limit(&mut MockService, request).await
+            .unwrap()
+    }
+
+    async fn test_rate_limit(api_key: &ApiKey) -> Fallible<Response<()>, RateLimitError> {
+        MockRateLimit(RateLimitKeyStrategy::API_KEY).rate_limit(&mut MockService, request(api_key)).await
+    }
+
+    async fn test_rate_limit_by_account(account_id: Option<AccountId>) -> Fallible<Response<()>, RateLimitError> {
+        let mut request = request(&VALID_API_KEY);
+
+        // セッション管理ミドルウェアを模倣
+        if let Some(account_id) = account_id {
+            request.extensions_mut().insert(account_id);
+        }
+
+        let key_strategy = RateLimitKeyStrategy::API_KEY.and(RateLimitKeyStrategy::ACCOUNT_ID);
+        MockRateLimit(key_strategy).rate_limit(&mut MockService, request).await
     }
 
     #[tokio::test]
@@ -166,4 +284,42 @@ mod tests {
         let result = test_rate_limit(&ApiKey::gen()).await;
         assert!(matches!(result, Err(RateLimitError::InvalidApiKey)));
     }
+
+    #[tokio::test]
+    async fn account_within_limit() {
+        let result = test_rate_limit_by_account(Some(*WITHIN_LIMIT)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn account_limit_over() {
+        // APIキーが上限に達していなくても、アカウントが上限に達していれば制限する
+        let result = test_rate_limit_by_account(Some(*LIMIT_OVER)).await;
+        assert!(matches!(result, Err(RateLimitError::RateLimitOver)));
+    }
+
+    #[tokio::test]
+    async fn no_account_id() {
+        let result = test_rate_limit_by_account(None).await;
+        assert!(matches!(result, Err(RateLimitError::NoAccountId)));
+    }
+
+    #[tokio::test]
+    async fn without_api_key() {
+        // APIキーを単位に含まない場合は検証しない
+        let mut request = Request::builder()
+            .body(())
+            .unwrap();
+
+        request.extensions_mut().insert(ConnectInfo(IP_ADDRESS.parse::<SocketAddr>().unwrap()));
+
+        let result = MockRateLimit(RateLimitKeyStrategy::IP_ADDRESS).rate_limit(&mut MockService, request).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn no_ip_address() {
+        let result = MockRateLimit(RateLimitKeyStrategy::IP_ADDRESS).rate_limit(&mut MockService, request(&VALID_API_KEY)).await;
+        assert!(matches!(result, Err(RateLimitError::NoIpAddress)));
+    }
 }
\ No newline at end of file
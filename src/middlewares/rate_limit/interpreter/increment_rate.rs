@@ -1,16 +1,16 @@
 
 use redis::{RedisWrite, ToRedisArgs};
 
-use crate::{common::{api_key::key::ApiKey, fallible::Fallible}, helper::redis::{connection::conn, namespace::NAMESPACE_SEPARATOR}, middlewares::{limit::{InculsiveLimit, TimeWindow}, rate_limit::{dsl::increment_rate::{IncrementRate, IncrementRateError, Rate}, interpreter::RATE_LIMIT_NAMESPACE}}};
+use crate::{common::fallible::Fallible, helper::redis::{connection::conn, namespace::NAMESPACE_SEPARATOR}, middlewares::{limit::{InculsiveLimit, TimeWindow}, rate_limit::{dsl::increment_rate::{IncrementRate, IncrementRateError, Rate, RateLimitSubject}, interpreter::RATE_LIMIT_NAMESPACE}}};
 
 use super::{EndpointName, RateLimitImpl};
 
 impl IncrementRate for RateLimitImpl {
-    async fn increment_rate_within_window(&self, api_key: &ApiKey, time_window: TimeWindow) -> Fallible<Rate, IncrementRateError> {
+    async fn increment_rate_within_window(&self, subject: &RateLimitSubject<'_>, time_window: TimeWindow) -> Fallible<Rate, IncrementRateError> {
         let mut conn = conn(&self.cache, |e| IncrementRateError::IncrementRateFailed(e.into())).await?;
         
         self.incr_and_expire_if_first
-            .key(RateKey::new(&self.endpoint_name, api_key))
+            .key(RateKey::new(&self.endpoint_name, subject))
             .arg(time_window)
             .invoke_async::<Rate>(&mut *conn)
             .await
@@ -29,8 +29,8 @@ impl IncrementRate for RateLimitImpl {
 struct RateKey(String);
 
 impl RateKey {
-    pub fn new(endpoint_name: &EndpointName, api_key: &ApiKey) -> Self {
-        Self(format!("{}{}{}{}{}", RATE_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, endpoint_name, NAMESPACE_SEPARATOR, api_key))
+    pub fn new(endpoint_name: &EndpointName, subject: &RateLimitSubject<'_>) -> Self {
+        Self(format!("{}{}{}{}{}{}{}", RATE_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, endpoint_name, NAMESPACE_SEPARATOR, subject.kind(), NAMESPACE_SEPARATOR, subject))
     }
 }
 
@@ -42,14 +42,14 @@ impl ToRedisArgs for RateKey {
 
 #[cfg(test)]
 mod tests {
-    use crate::{common::api_key::key::ApiKey, helper::redis::namespace::{Namespace, NAMESPACE_SEPARATOR}, middlewares::rate_limit::interpreter::{increment_rate::RateKey, EndpointName, RATE_LIMIT_NAMESPACE}};
+    use crate::{common::api_key::key::ApiKey, helper::redis::namespace::{Namespace, NAMESPACE_SEPARATOR}, middlewares::rate_limit::{dsl::increment_rate::RateLimitSubject, interpreter::{increment_rate::RateKey, EndpointName, RATE_LIMIT_NAMESPACE}}};
 
     #[test]
     fn test_format_key() {
         let endpoint_name = EndpointName::new(Namespace::new("test").unwrap());
         let api_key = ApiKey::gen();
-        let key = RateKey::new(&endpoint_name, &api_key);
-        let expected = format!("{}{}{}{}{}", RATE_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, endpoint_name, NAMESPACE_SEPARATOR, api_key);
+        let key = RateKey::new(&endpoint_name, &RateLimitSubject::ApiKey(&api_key));
+        let expected = format!("{}{}{}{}ak{}{}", RATE_LIMIT_NAMESPACE, NAMESPACE_SEPARATOR, endpoint_name, NAMESPACE_SEPARATOR, NAMESPACE_SEPARATOR, api_key);
         assert_eq!(key.0, expected);
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use redis::cmd;
 
-use crate::{common::{api_key::{key::ApiKey, refreshed_at::LastApiKeyRefreshedAt}, fallible::Fallible}, helper::redis::{connection::conn, namespace::NAMESPACE_SEPARATOR, namespaces::API_KEY}, middlewares::rate_limit::dsl::rate_limit::{RateLimit, RateLimitError}};
+use crate::{common::{api_key::{key::ApiKey, refreshed_at::LastApiKeyRefreshedAt}, fallible::Fallible}, helper::redis::{connection::conn, namespace::NAMESPACE_SEPARATOR, namespaces::API_KEY}, middlewares::rate_limit::dsl::rate_limit::{RateLimit, RateLimitError, RateLimitKeyStrategy}};
 
 use super::RateLimitImpl;
 
@@ -16,4 +16,8 @@ impl RateLimit for RateLimitImpl {
             .await
             .map_err(|e| RateLimitError::FetchLastApiKeyRefreshedAt(e.into()))
     }
+
+    fn key_strategy(&self) -> RateLimitKeyStrategy {
+        self.key_strategy
+    }
 }
\ No newline at end of file
@@ -1,12 +1,12 @@
 use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc, task::{ready, Context, Poll}};
 
-use http::{Request, Response, StatusCode};
+use http::{Request, Response};
 use pin_project::pin_project;
 use scylla::Session;
 use tokio::pin;
 use tower::{Layer, Service};
 
-use crate::{helper::{error::InitError, redis::connection::Pool}, middlewares::{limit::{EndpointName, InculsiveLimit, TimeWindow}, rate_limit::dsl::rate_limit::{RateLimit, RateLimitError}}};
+use crate::{helper::{api_error::ApiError, error::InitError, redis::connection::Pool}, middlewares::{limit::{EndpointName, InculsiveLimit, TimeWindow}, rate_limit::dsl::rate_limit::{RateLimit, RateLimitError, RateLimitKeyStrategy}}};
 
 use super::interpreter::RateLimitImpl;
 
@@ -16,8 +16,8 @@ pub struct RateLimitLayer {
 }
 
 impl RateLimitLayer {
-    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: EndpointName, limit: InculsiveLimit, time_window: TimeWindow) -> Result<Self, InitError<RateLimitImpl>> {
-        let rate_limit = RateLimitImpl::try_new(db, cache, endpoint_name, limit, time_window).await?;
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, endpoint_name: EndpointName, limit: InculsiveLimit, time_window: TimeWindow, key_strategy: RateLimitKeyStrategy) -> Result<Self, InitError<RateLimitImpl>> {
+        let rate_limit = RateLimitImpl::try_new(db, cache, endpoint_name, limit, time_window, key_strategy).await?;
         Ok(Self { rate_limit: Arc::new(rate_limit) })
     }
 }
@@ -43,7 +43,7 @@ impl <S, B> Service<Request<B>> for RateLimitService<S>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>> + Clone,
     S::Future: Future<Output = Result<S::Response, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Response = S::Response;
     type Error = S::Error;
@@ -66,7 +66,7 @@ where
 pub struct SessionFuture<S, B>
 where
     S: Service<Request<B>>,
-    B: Default,
+    B: From<String>,
 {
     inner: S,
     request: Option<Request<B>>,
@@ -77,7 +77,7 @@ impl<S, B> Future for SessionFuture<S, B>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>>,
     S::Future: Future<Output = Result<Response<B>, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Output = Result<S::Response, S::Error>;
 
@@ -92,18 +92,15 @@ where
         match ready!(response_future.poll(cx)) {
             Ok(response) => Poll::Ready(Ok(response)),
             Err(e) => {
-                let status_code = match e {
-                    RateLimitError::RateLimitOver => StatusCode::TOO_MANY_REQUESTS,
-                    RateLimitError::NoApiKey | RateLimitError::InvalidApiKey => StatusCode::BAD_REQUEST,
-                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                let error = match e {
+                    RateLimitError::RateLimitOver => ApiError::RATE_LIMITED,
+                    RateLimitError::NoApiKey => ApiError::NO_API_KEY,
+                    RateLimitError::InvalidApiKey => ApiError::INVALID_API_KEY,
+                    // `NoAccountId`と`NoIpAddress`はミドルウェアの構成の誤りであるため、内部エラーとして扱う
+                    _ => ApiError::INTERNAL,
                 };
 
-                let response = Response::builder()
-                    .status(status_code)
-                    .body(B::default())
-                    .unwrap();
-
-                Poll::Ready(Ok(response))
+                Poll::Ready(Ok(error.to_response()))
             }
         }
     }
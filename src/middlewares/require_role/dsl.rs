@@ -0,0 +1,180 @@
+use std::convert::Infallible;
+
+use http::{Request, Response};
+use thiserror::Error;
+use tower::Service;
+
+use crate::common::{fallible::Fallible, profile::{account_id::AccountId, role::{Role, Roles}}};
+
+pub(crate) trait RequireRole {
+    async fn require_role<S, B>(&self, inner: &mut S, request: Request<B>) -> Fallible<S::Response, RequireRoleError>
+    where
+        S: Service<Request<B>, Error = Infallible, Response = Response<B>>
+    {
+        // `ManageSessionLayer`の内側に置くことを前提とする
+        let account_id = request.extensions()
+            .get::<AccountId>()
+            .cloned()
+            .ok_or_else(|| RequireRoleError::NoAccountId)?;
+
+        let required_role = self.required_role();
+
+        if !self.fetch_roles(account_id).await?.satisfies(required_role) {
+            return Err(RequireRoleError::InsufficientRole(account_id, required_role));
+        }
+
+        // `Error`は`Infallible`であるため`unwrap()`で問題ない
+        Ok(inner.call(request).await.unwrap())
+    }
+
+    async fn fetch_roles(&self, account_id: AccountId) -> Fallible<Roles, RequireRoleError> {
+        if let Some(roles) = self.fetch_cached_roles(account_id).await? {
+            return Ok(roles);
+        }
+
+        let roles = self.fetch_stored_roles(account_id).await?;
+
+        // 失敗しても次回に再取得されるだけであるため続行
+        let _ = self.cache_roles(account_id, roles).await;
+
+        Ok(roles)
+    }
+
+    fn required_role(&self) -> Role;
+
+    async fn fetch_cached_roles(&self, account_id: AccountId) -> Fallible<Option<Roles>, RequireRoleError>;
+
+    // 権限が記録されていないアカウントは、一般の権限のみを持つものとして扱う
+    async fn fetch_stored_roles(&self, account_id: AccountId) -> Fallible<Roles, RequireRoleError>;
+
+    async fn cache_roles(&self, account_id: AccountId, roles: Roles) -> Fallible<(), RequireRoleError>;
+}
+
+#[derive(Debug, Error)]
+pub enum RequireRoleError {
+    #[error("アカウントIDがありません")]
+    NoAccountId,
+    #[error("キャッシュされた権限の取得に失敗しました")]
+    FetchCachedRolesFailed(#[source] anyhow::Error),
+    #[error("権限の取得に失敗しました")]
+    FetchStoredRolesFailed(#[source] anyhow::Error),
+    #[error("権限のキャッシュに失敗しました")]
+    CacheRolesFailed(#[source] anyhow::Error),
+    #[error("{1}の権限がありません")]
+    InsufficientRole(AccountId, Role),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, convert::Infallible, future::{ready, Ready}, sync::{LazyLock, Mutex}, task::{Context, Poll}};
+
+    use http::{Request, Response, StatusCode};
+    use tower::Service;
+
+    use crate::common::{fallible::Fallible, profile::{account_id::AccountId, role::{Role, Roles}}};
+
+    use super::{RequireRole, RequireRoleError};
+
+    static ADMIN: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static MODERATOR: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+    static CACHED_MODERATOR: LazyLock<AccountId> = LazyLock::new(AccountId::gen);
+
+    struct MockRequireRole {
+        required_role: Role,
+        cached: Mutex<HashMap<AccountId, Roles>>,
+    }
+
+    impl MockRequireRole {
+        fn new(required_role: Role) -> Self {
+            let cached = HashMap::from([(*CACHED_MODERATOR, Roles::from_iter([Role::Moderator]))]);
+            Self { required_role, cached: Mutex::new(cached) }
+        }
+    }
+
+    impl RequireRole for MockRequireRole {
+        fn required_role(&self) -> Role {
+            self.required_role
+        }
+
+        async fn fetch_cached_roles(&self, account_id: AccountId) -> Fallible<Option<Roles>, RequireRoleError> {
+            Ok(self.cached.lock().unwrap().get(&account_id).copied())
+        }
+
+        async fn fetch_stored_roles(&self, account_id: AccountId) -> Fallible<Roles, RequireRoleError> {
+            if account_id == *ADMIN {
+                Ok(Roles::from_iter([Role::Admin]))
+            } else if account_id == *MODERATOR {
+                Ok(Roles::from_iter([Role::Moderator]))
+            } else {
+                Ok(Roles::user())
+            }
+        }
+
+        async fn cache_roles(&self, account_id: AccountId, roles: Roles) -> Fallible<(), RequireRoleError> {
+            self.cached.lock().unwrap().insert(account_id, roles);
+            Ok(())
+        }
+    }
+
+    struct MockService;
+
+    impl Service<Request<()>> for MockService {
+        type Response = Response<()>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: Request<()>) -> Self::Future {
+            ready(Ok(Response::new(())))
+        }
+    }
+
+    fn request(account_id: Option<AccountId>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(account_id) = account_id {
+            request.extensions_mut().insert(account_id);
+        }
+        request
+    }
+
+    async fn test_require_role(required_role: Role, account_id: Option<AccountId>) -> Fallible<Response<()>, RequireRoleError> {
+        MockRequireRole::new(required_role).require_role(&mut MockService, request(account_id)).await
+    }
+
+    #[tokio::test]
+    async fn sufficient_role() {
+        let response = test_require_role(Role::Moderator, Some(*MODERATOR)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 管理者はモデレーターの権限も持つ
+        assert!(test_require_role(Role::Moderator, Some(*ADMIN)).await.is_ok());
+        assert!(test_require_role(Role::User, Some(AccountId::gen())).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn insufficient_role() {
+        let result = test_require_role(Role::Admin, Some(*MODERATOR)).await;
+        assert!(matches!(result.err(), Some(RequireRoleError::InsufficientRole(account_id, Role::Admin)) if account_id == *MODERATOR));
+
+        let result = test_require_role(Role::Moderator, Some(AccountId::gen())).await;
+        assert!(matches!(result.err(), Some(RequireRoleError::InsufficientRole(_, Role::Moderator))));
+    }
+
+    #[tokio::test]
+    async fn cached_roles() {
+        assert!(test_require_role(Role::Moderator, Some(*CACHED_MODERATOR)).await.is_ok());
+
+        let require_role = MockRequireRole::new(Role::Admin);
+        assert!(require_role.require_role(&mut MockService, request(Some(*ADMIN))).await.is_ok());
+        assert_eq!(require_role.cached.lock().unwrap().get(&*ADMIN), Some(&Roles::from_iter([Role::Admin])));
+    }
+
+    #[tokio::test]
+    async fn no_account_id() {
+        let result = test_require_role(Role::User, None).await;
+        assert!(matches!(result.err(), Some(RequireRoleError::NoAccountId)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use redis::cmd;
+use scylla::{prepared_statement::PreparedStatement, Session};
+
+use crate::{common::{fallible::Fallible, profile::{account_id::AccountId, role::{Role, Roles}}}, helper::{error::InitError, redis::{connection::{conn, Pool}, namespace::{Namespace, NAMESPACE_SEPARATOR}}, scylla::prepare}, middlewares::limit::TimeWindow};
+
+use super::dsl::{RequireRole, RequireRoleError};
+
+const ROLES_NAMESPACE: Namespace = Namespace::of("acrol");
+
+// 権限の剥奪がこの時間以内に反映されるよう、短めにキャッシュする
+const ROLES_CACHE_EXPIRATION: TimeWindow = TimeWindow::minutes(5);
+
+#[derive(Debug)]
+pub struct RequireRoleImpl {
+    db: Arc<Session>,
+    cache: Arc<Pool>,
+    required_role: Role,
+    select_roles: Arc<PreparedStatement>,
+}
+
+impl RequireRoleImpl {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, required_role: Role) -> Result<Self, InitError<Self>> {
+        let select_roles = prepare(&db, "SELECT role FROM account_roles WHERE account_id = ?").await?;
+
+        Ok(Self { db, cache, required_role, select_roles })
+    }
+
+    fn roles_key(account_id: AccountId) -> String {
+        format!("{}{}{}", ROLES_NAMESPACE, NAMESPACE_SEPARATOR, account_id)
+    }
+}
+
+impl RequireRole for RequireRoleImpl {
+    fn required_role(&self) -> Role {
+        self.required_role
+    }
+
+    async fn fetch_cached_roles(&self, account_id: AccountId) -> Fallible<Option<Roles>, RequireRoleError> {
+        let mut conn = conn(&self.cache, |e| RequireRoleError::FetchCachedRolesFailed(e.into())).await?;
+
+        cmd("GET")
+            .arg(Self::roles_key(account_id))
+            .query_async::<Option<Roles>>(&mut *conn)
+            .await
+            .map_err(|e| RequireRoleError::FetchCachedRolesFailed(e.into()))
+    }
+
+    async fn fetch_stored_roles(&self, account_id: AccountId) -> Fallible<Roles, RequireRoleError> {
+        fn handle_error<E: Into<anyhow::Error>>(e: E) -> RequireRoleError {
+            RequireRoleError::FetchStoredRolesFailed(e.into())
+        }
+
+        self.db
+            .execute_unpaged(&self.select_roles, (account_id, ))
+            .await
+            .map_err(handle_error)?
+            .rows_typed::<(Role, )>()
+            .map_err(handle_error)?
+            .map(|row| row.map(|(role, )| role))
+            .collect::<Result<Roles, _>>()
+            .map_err(handle_error)
+    }
+
+    async fn cache_roles(&self, account_id: AccountId, roles: Roles) -> Fallible<(), RequireRoleError> {
+        let mut conn = conn(&self.cache, |e| RequireRoleError::CacheRolesFailed(e.into())).await?;
+
+        cmd("SET")
+            .arg(Self::roles_key(account_id))
+            .arg(roles)
+            .arg("EX")
+            .arg(ROLES_CACHE_EXPIRATION)
+            .exec_async(&mut *conn)
+            .await
+            .map_err(|e| RequireRoleError::CacheRolesFailed(e.into()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc, task::{ready, Context, Poll}};
+
+use http::{Request, Response};
+use pin_project::pin_project;
+use scylla::Session;
+use tokio::pin;
+use tower::{Layer, Service};
+
+use crate::{common::profile::role::Role, helper::{api_error::ApiError, error::InitError, redis::connection::Pool}, middlewares::require_role::dsl::{RequireRole, RequireRoleError}};
+
+use super::interpreter::RequireRoleImpl;
+
+#[derive(Clone)]
+pub struct RequireRoleLayer {
+    require_role: Arc<RequireRoleImpl>,
+}
+
+impl RequireRoleLayer {
+    pub async fn try_new(db: Arc<Session>, cache: Arc<Pool>, required_role: Role) -> Result<Self, InitError<RequireRoleImpl>> {
+        let require_role = RequireRoleImpl::try_new(db, cache, required_role).await?;
+        Ok(Self { require_role: Arc::new(require_role) })
+    }
+}
+
+impl<S> Layer<S> for RequireRoleLayer {
+    type Service = RequireRoleService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireRoleService {
+            inner,
+            require_role: self.require_role.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RequireRoleService<S> {
+    inner: S,
+    require_role: Arc<RequireRoleImpl>,
+}
+
+impl <S, B> Service<Request<B>> for RequireRoleService<S>
+where
+    S: Service<Request<B>, Error = Infallible, Response = Response<B>> + Clone,
+    S::Future: Future<Output = Result<S::Response, S::Error>>,
+    B: From<String>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = RequireRoleFuture<S, B>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        RequireRoleFuture {
+            inner: self.inner.clone(), // 下記の都合で`Future::poll`内で`inner.call(req)`を呼ぶ必要があるため複製して渡す
+            request: Some(req), // `inner.call(req)`が`req`の所有権を必要とするため渡す必要がある
+            require_role: self.require_role.clone(),
+        }
+    }
+}
+
+#[pin_project]
+pub struct RequireRoleFuture<S, B>
+where
+    S: Service<Request<B>>,
+    B: From<String>,
+{
+    inner: S,
+    request: Option<Request<B>>,
+    require_role: Arc<RequireRoleImpl>,
+}
+
+impl<S, B> Future for RequireRoleFuture<S, B>
+where
+    S: Service<Request<B>, Error = Infallible, Response = Response<B>>,
+    S::Future: Future<Output = Result<Response<B>, S::Error>>,
+    B: From<String>,
+{
+    type Output = Result<S::Response, S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let response_future = this.require_role
+            .require_role::<S, B>(this.inner, this.request.take().unwrap());
+        pin!(response_future);
+
+        // エラーもレスポンスに変換して返す
+        match ready!(response_future.poll(cx)) {
+            Ok(response) => Poll::Ready(Ok(response)),
+            Err(e) => {
+                let response = match e {
+                    // `LocalizeErrorLayer`がアカウントの言語で翻訳し直す
+                    RequireRoleError::InsufficientRole(account_id, _) => {
+                        let mut response = ApiError::INSUFFICIENT_ROLE.to_response();
+                        response.extensions_mut().insert(account_id);
+                        response
+                    },
+                    _ => ApiError::INTERNAL.to_response(),
+                };
+
+                Poll::Ready(Ok(response))
+            }
+        }
+    }
+}
\ No newline at end of file
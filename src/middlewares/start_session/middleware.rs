@@ -1,12 +1,12 @@
 use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc, task::{ready, Context, Poll}};
 
-use http::{Request, Response, StatusCode};
+use http::{Request, Response};
 use pin_project::pin_project;
 use scylla::Session;
 use tokio::pin;
 use tower::{Layer, Service};
 
-use crate::{helper::{error::InitError, redis::connection::Pool}, middlewares::start_session::dsl::start_session::StartSession};
+use crate::{helper::{api_error::ApiError, error::InitError, redis::connection::Pool}, middlewares::start_session::dsl::start_session::StartSession};
 
 use super::interpreter::StartSessionImpl;
 
@@ -43,7 +43,7 @@ impl <S, B> Service<Request<B>> for StartSessionService<S>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>> + Clone,
     S::Future: Future<Output = Result<S::Response, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Response = S::Response;
     type Error = S::Error;
@@ -66,7 +66,7 @@ where
 pub struct StartSessionFuture<S, B>
 where
     S: Service<Request<B>>,
-    B: Default,
+    B: From<String>,
 {
     inner: S,
     request: Option<Request<B>>,
@@ -77,7 +77,7 @@ impl<S, B> Future for StartSessionFuture<S, B>
 where
     S: Service<Request<B>, Error = Infallible, Response = Response<B>>,
     S::Future: Future<Output = Result<Response<B>, S::Error>>,
-    B: Default,
+    B: From<String>,
 {
     type Output = Result<S::Response, S::Error>;
 
@@ -91,13 +91,7 @@ where
         // エラーもレスポンスに変換して返す
         match ready!(response_future.poll(cx)) {
             Ok(response) => Poll::Ready(Ok(response)),
-            Err(_) => {
-                let response = Response::builder()
-                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(B::default())
-                    .unwrap();
-                Poll::Ready(Ok(response))
-            }
+            Err(_) => Poll::Ready(Ok(ApiError::INTERNAL.to_response())),
         }
     }
 }
\ No newline at end of file
@@ -2,28 +2,56 @@ pub mod email {
     pub const SENDER_NAME: &str = "ネットメイト";
 }
 
-pub mod session {
-    pub const SECURITY_NOTIFICATION_SUBJECT: &str = "アカウントが不正に利用されている恐れがあります";
-    pub const SECURITY_NOTIFICATION_BODY_HTML: &str = concat!(
-        "<p>アカウントが不正に利用されている恐れがあります。</p>",
-        "<p>応急措置として全ての端末がログアウトされました。</p>",
-        "<p>パスワードを再設定し、アカウントの状態を確認してください。</p>",
-        "<p>https://netmate.app/recovery-account</p>",
-    );
-    pub const SECURITY_NOTIFICATION_BODY_PLAIN: &str = concat!(
-        "アカウントが不正に利用されている恐れがあるため、アカウントのセキュリティを確認してください。",
-        "https://netmate.app/security",
-    );
-}
-
-pub mod sign_up {
-    pub const AUTHENTICATION_EMAIL_SUBJECT: &str = "メールアドレスの認証をしてください";
-    pub const ATUHENTICATION_EMAIL_BODY_HTML: &str = concat!(
-        "<p>次のリンクをクリックし、メールアドレスを認証を完了してください。</p>",
-        "<p><a href=\"https://netmate.app/verify-email/{token}\">https://netmate.app/verify-email/{token}</a></p>",
-    );
-    pub const AUTHENTICATION_EMAIL_BODY_PLAIN: &str = concat!(
-        "次のリンクをクリックし、メールアドレスを認証を完了してください。",
-        "https://netmate.app/verify-email/{token}",
-    );
+pub mod error {
+    // エラーコードと、利用者に表示するエラーメッセージの対応
+    pub const MESSAGES: &[(&str, &str)] = &[
+        ("internal", "サーバーでエラーが発生しました。時間をおいて再度お試しください。"),
+        ("auth.insufficient_role", "この機能を利用する権限がありません。"),
+        ("auth.session_required", "サインインが必要です。"),
+        ("api_key.missing", "APIキーがありません。"),
+        ("api_key.invalid", "APIキーが無効です。"),
+        ("rate_limit.exceeded", "リクエストが多すぎます。時間をおいて再度お試しください。"),
+        ("quota.exceeded", "利用できる回数の上限に達しました。時間をおいて再度お試しください。"),
+        ("auth.sign_in.failed", "メールアドレスまたはパスワードが正しくありません。"),
+        ("auth.sign_in.throttled", "ログインの試行回数が上限に達しました。時間をおいて再度お試しください。"),
+        ("auth.sign_in.challenge_required", "ロボットではないことを確認してください。"),
+        ("auth.sign_up.breached_password", "このパスワードは過去に漏洩しているため利用できません。別のパスワードを設定してください。"),
+        ("auth.verify_email.invalid_token", "認証リンクが無効か、有効期限が切れています。"),
+        ("auth.verify_email.account_already_exists", "このメールアドレスのアカウントは既に存在します。"),
+        ("api_key.invalid_token", "ロボットではないことを確認できませんでした。"),
+        ("handle.not_found", "名義が見つかりません。"),
+        ("handle.delete.anonymous_handle", "匿名の名義は削除できません。"),
+        ("handle.rename.anonymous_handle", "匿名の名義は変更できません。"),
+        ("handle.rename.conflict", "名義が同時に変更されました。再度お試しください。"),
+        ("handle.reserved_name", "この名義は予約されているため利用できません。"),
+        ("handle.profane_name", "不適切な語を含む名義は利用できません。"),
+        ("handle.create.limit_reached", "名義の数が上限に達しています。"),
+        ("tag.rating.non_proposed_relation", "このタグ関係は提案されていません。"),
+        ("tag.proposal.cyclic", "タグ関係が循環するため提案できません。"),
+        ("tag.proposal.not_equivalent", "同値関係を提案するには、両方向の包含関係が必要です。"),
+        ("tag.proposal.already_proposed", "このタグ関係は既に提案されています。"),
+        ("tag.proposal.non_existent_tag", "存在しないタグです。"),
+        ("tag.proposal.different_language_groups", "異なる言語グループのタグ間の関係は提案できません。"),
+        ("tag.proposal.not_proposer", "提案者のみが提案を撤回できます。"),
+        ("tag.proposal.cannot_withdraw", "この提案は撤回できません。"),
+        ("post.not_handle_owner", "自分の名義でのみ投稿できます。"),
+        ("post.not_found", "投稿が見つかりません。"),
+        ("post.non_existent_tag", "存在しないタグが含まれています。"),
+        ("post.different_language_group", "投稿者の言語グループに属さないタグは付けられません。"),
+        ("follow.followee_not_found", "フォロー対象が見つかりません。"),
+        ("follow.own_handle", "自分の名義はフォローできません。"),
+        ("follow.limit_reached", "フォロー数が上限に達しています。"),
+        ("follow.not_found", "フォローしていません。"),
+        ("handle_filter.own_handle", "自分の名義はブロック・ミュートできません。"),
+        ("handle_filter.limit_reached", "ブロック・ミュートできる名義の数が上限に達しています。"),
+        ("handle_filter.not_found", "この名義はブロック・ミュートされていません。"),
+        ("auth.sign_in.account_suspended", "このアカウントは利用規約違反により停止されています。"),
+        ("report.target_not_found", "通報の対象が見つかりません。"),
+        ("report.already_reported", "この内容は既に通報済みです。"),
+        ("moderation.report_not_found", "通報が見つかりません。"),
+        ("moderation.report_already_resolved", "この通報は既に対応済みです。"),
+        ("moderation.action_not_applicable", "この通報の対象には適用できない対応です。"),
+        ("webhook.invalid_signature", "署名が不正です。"),
+        ("webhook.invalid_payload", "通知の形式が不正です。"),
+    ];
 }
\ No newline at end of file
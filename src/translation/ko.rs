@@ -0,0 +1,56 @@
+pub mod email {
+    pub const SENDER_NAME: &str = "넷메이트";
+}
+
+pub mod error {
+    pub const MESSAGES: &[(&str, &str)] = &[
+        ("internal", "서버에서 오류가 발생했습니다. 잠시 후 다시 시도해 주세요."),
+        ("auth.insufficient_role", "이 기능을 사용할 권한이 없습니다."),
+        ("auth.session_required", "로그인이 필요합니다."),
+        ("api_key.missing", "API 키가 없습니다."),
+        ("api_key.invalid", "API 키가 유효하지 않습니다."),
+        ("rate_limit.exceeded", "요청이 너무 많습니다. 잠시 후 다시 시도해 주세요."),
+        ("quota.exceeded", "사용 가능한 횟수의 한도에 도달했습니다. 잠시 후 다시 시도해 주세요."),
+        ("auth.sign_in.failed", "이메일 주소 또는 비밀번호가 올바르지 않습니다."),
+        ("auth.sign_in.throttled", "로그인 시도 횟수가 한도에 도달했습니다. 잠시 후 다시 시도해 주세요."),
+        ("auth.sign_in.challenge_required", "로봇이 아님을 확인해 주세요."),
+        ("auth.sign_up.breached_password", "이 비밀번호는 과거에 유출된 적이 있어 사용할 수 없습니다. 다른 비밀번호를 설정해 주세요."),
+        ("auth.verify_email.invalid_token", "인증 링크가 유효하지 않거나 만료되었습니다."),
+        ("auth.verify_email.account_already_exists", "이 이메일 주소의 계정이 이미 존재합니다."),
+        ("api_key.invalid_token", "로봇이 아님을 확인하지 못했습니다."),
+        ("handle.not_found", "명의를 찾을 수 없습니다."),
+        ("handle.delete.anonymous_handle", "익명 명의는 삭제할 수 없습니다."),
+        ("handle.rename.anonymous_handle", "익명 명의는 변경할 수 없습니다."),
+        ("handle.rename.conflict", "명의가 동시에 변경되었습니다. 다시 시도해 주세요."),
+        ("handle.reserved_name", "이 명의는 예약되어 있어 사용할 수 없습니다."),
+        ("handle.profane_name", "부적절한 단어가 포함된 명의는 사용할 수 없습니다."),
+        ("handle.create.limit_reached", "명의 수가 상한에 도달했습니다."),
+        ("tag.rating.non_proposed_relation", "제안되지 않은 태그 관계입니다."),
+        ("tag.proposal.cyclic", "태그 관계가 순환하므로 제안할 수 없습니다."),
+        ("tag.proposal.not_equivalent", "동치 관계를 제안하려면 양방향의 포함 관계가 필요합니다."),
+        ("tag.proposal.already_proposed", "이 태그 관계는 이미 제안되었습니다."),
+        ("tag.proposal.non_existent_tag", "존재하지 않는 태그입니다."),
+        ("tag.proposal.different_language_groups", "서로 다른 언어 그룹의 태그 간 관계는 제안할 수 없습니다."),
+        ("tag.proposal.not_proposer", "제안자만 제안을 철회할 수 있습니다."),
+        ("tag.proposal.cannot_withdraw", "이 제안은 철회할 수 없습니다."),
+        ("post.not_handle_owner", "자신의 명의로만 게시할 수 있습니다."),
+        ("post.not_found", "게시물을 찾을 수 없습니다."),
+        ("post.non_existent_tag", "존재하지 않는 태그가 포함되어 있습니다."),
+        ("post.different_language_group", "게시자의 언어 그룹에 속하지 않는 태그는 붙일 수 없습니다."),
+        ("follow.followee_not_found", "팔로우 대상을 찾을 수 없습니다."),
+        ("follow.own_handle", "자신의 명의는 팔로우할 수 없습니다."),
+        ("follow.limit_reached", "팔로우 수가 상한에 도달했습니다."),
+        ("follow.not_found", "팔로우하고 있지 않습니다."),
+        ("handle_filter.own_handle", "자신의 명의는 차단하거나 뮤트할 수 없습니다."),
+        ("handle_filter.limit_reached", "차단 또는 뮤트할 수 있는 명의 수가 상한에 도달했습니다."),
+        ("handle_filter.not_found", "이 명의는 차단 또는 뮤트되어 있지 않습니다."),
+        ("auth.sign_in.account_suspended", "이 계정은 이용약관 위반으로 정지되었습니다."),
+        ("report.target_not_found", "신고 대상을 찾을 수 없습니다."),
+        ("report.already_reported", "이 내용은 이미 신고했습니다."),
+        ("moderation.report_not_found", "신고를 찾을 수 없습니다."),
+        ("moderation.report_already_resolved", "이 신고는 이미 처리되었습니다."),
+        ("moderation.action_not_applicable", "이 신고 대상에는 적용할 수 없는 조치입니다."),
+        ("webhook.invalid_signature", "서명이 올바르지 않습니다."),
+        ("webhook.invalid_payload", "알림 형식이 올바르지 않습니다."),
+    ];
+}
\ No newline at end of file
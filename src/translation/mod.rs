@@ -1,2 +1,4 @@
 pub mod ja;
-pub mod us_en;
\ No newline at end of file
+pub mod ko;
+pub mod us_en;
+pub mod zh_tw;
\ No newline at end of file
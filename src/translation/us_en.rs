@@ -2,14 +2,55 @@ pub mod email {
     pub const SENDER_NAME: &str = "Netmate";
 }
 
-pub mod sign_up {
-    pub const AUTHENTICATION_EMAIL_SUBJECT: &str = "Please verify your email address.";
-    pub const ATUHENTICATION_EMAIL_BODY_HTML: &str = concat!(
-        "<p>Please click the following link to complete the verification of your email address.</p>",
-        "<p><a href=\"https://netmate.app/verify-email/{token}\">https://netmate.app/verify-email/{token}</a></p>"
-    );
-    pub const AUTHENTICATION_EMAIL_BODY_PLAIN: &str = concat!(
-        "Please click the following link to complete the verification of your email address.",
-        "https://netmate.app/verify-email/{token}",
-    );
+pub mod error {
+    pub const MESSAGES: &[(&str, &str)] = &[
+        ("internal", "Something went wrong on our end. Please try again later."),
+        ("auth.insufficient_role", "You do not have permission to use this feature."),
+        ("auth.session_required", "You need to sign in."),
+        ("api_key.missing", "An API key is required."),
+        ("api_key.invalid", "The API key is invalid."),
+        ("rate_limit.exceeded", "Too many requests. Please try again later."),
+        ("quota.exceeded", "You have reached your usage quota. Please try again later."),
+        ("auth.sign_in.failed", "The email address or password is incorrect."),
+        ("auth.sign_in.throttled", "Too many sign-in attempts. Please try again later."),
+        ("auth.sign_in.challenge_required", "Please confirm that you are not a robot."),
+        ("auth.sign_up.breached_password", "This password has appeared in a data breach. Please choose a different password."),
+        ("auth.verify_email.invalid_token", "The verification link is invalid or has expired."),
+        ("auth.verify_email.account_already_exists", "An account with this email address already exists."),
+        ("api_key.invalid_token", "We could not confirm that you are not a robot."),
+        ("handle.not_found", "The handle was not found."),
+        ("handle.delete.anonymous_handle", "Anonymous handles cannot be deleted."),
+        ("handle.rename.anonymous_handle", "Anonymous handles cannot be renamed."),
+        ("handle.rename.conflict", "The handle was changed at the same time. Please try again."),
+        ("handle.reserved_name", "This handle name is reserved."),
+        ("handle.profane_name", "Handle names cannot contain inappropriate words."),
+        ("handle.create.limit_reached", "You have reached the maximum number of handles."),
+        ("tag.rating.non_proposed_relation", "This tag relation has not been proposed."),
+        ("tag.proposal.cyclic", "This tag relation would create a cycle."),
+        ("tag.proposal.not_equivalent", "Equivalence requires inclusion in both directions."),
+        ("tag.proposal.already_proposed", "This tag relation has already been proposed."),
+        ("tag.proposal.non_existent_tag", "The tag does not exist."),
+        ("tag.proposal.different_language_groups", "Tags in different language groups cannot be related."),
+        ("tag.proposal.not_proposer", "Only the proposer can withdraw this proposal."),
+        ("tag.proposal.cannot_withdraw", "This proposal can no longer be withdrawn."),
+        ("post.not_handle_owner", "You can only post under your own handles."),
+        ("post.not_found", "The post was not found."),
+        ("post.non_existent_tag", "One or more tags do not exist."),
+        ("post.different_language_group", "Tags must belong to your language group."),
+        ("follow.followee_not_found", "The handle or tag to follow was not found."),
+        ("follow.own_handle", "You cannot follow your own handle."),
+        ("follow.limit_reached", "You have reached the maximum number of follows."),
+        ("follow.not_found", "You are not following this handle or tag."),
+        ("handle_filter.own_handle", "You cannot block or mute your own handle."),
+        ("handle_filter.limit_reached", "You have reached the maximum number of blocked or muted handles."),
+        ("handle_filter.not_found", "This handle is not blocked or muted."),
+        ("auth.sign_in.account_suspended", "This account has been suspended for violating the terms of service."),
+        ("report.target_not_found", "The reported content could not be found."),
+        ("report.already_reported", "You have already reported this content."),
+        ("moderation.report_not_found", "The report could not be found."),
+        ("moderation.report_already_resolved", "This report has already been resolved."),
+        ("moderation.action_not_applicable", "This action cannot be applied to the reported content."),
+        ("webhook.invalid_signature", "The signature is invalid."),
+        ("webhook.invalid_payload", "The notification payload is invalid."),
+    ];
 }
\ No newline at end of file
@@ -0,0 +1,56 @@
+pub mod email {
+    pub const SENDER_NAME: &str = "Netmate";
+}
+
+pub mod error {
+    pub const MESSAGES: &[(&str, &str)] = &[
+        ("internal", "伺服器發生錯誤，請稍後再試。"),
+        ("auth.insufficient_role", "您沒有使用此功能的權限。"),
+        ("auth.session_required", "需要登入。"),
+        ("api_key.missing", "缺少 API 金鑰。"),
+        ("api_key.invalid", "API 金鑰無效。"),
+        ("rate_limit.exceeded", "請求次數過多，請稍後再試。"),
+        ("quota.exceeded", "已達可使用次數的上限，請稍後再試。"),
+        ("auth.sign_in.failed", "電子郵件地址或密碼不正確。"),
+        ("auth.sign_in.throttled", "登入嘗試次數已達上限，請稍後再試。"),
+        ("auth.sign_in.challenge_required", "請確認您不是機器人。"),
+        ("auth.sign_up.breached_password", "此密碼曾經外洩，無法使用。請設定其他密碼。"),
+        ("auth.verify_email.invalid_token", "驗證連結無效或已過期。"),
+        ("auth.verify_email.account_already_exists", "此電子郵件地址的帳號已存在。"),
+        ("api_key.invalid_token", "無法確認您不是機器人。"),
+        ("handle.not_found", "找不到名義。"),
+        ("handle.delete.anonymous_handle", "無法刪除匿名名義。"),
+        ("handle.rename.anonymous_handle", "無法變更匿名名義。"),
+        ("handle.rename.conflict", "名義已同時被變更，請再試一次。"),
+        ("handle.reserved_name", "此名義已被保留，無法使用。"),
+        ("handle.profane_name", "無法使用含有不當字詞的名義。"),
+        ("handle.create.limit_reached", "名義數量已達上限。"),
+        ("tag.rating.non_proposed_relation", "此標籤關係尚未被提議。"),
+        ("tag.proposal.cyclic", "標籤關係會形成循環，因此無法提議。"),
+        ("tag.proposal.not_equivalent", "提議等價關係時，兩個方向的包含關係皆須存在。"),
+        ("tag.proposal.already_proposed", "此標籤關係已被提議。"),
+        ("tag.proposal.non_existent_tag", "標籤不存在。"),
+        ("tag.proposal.different_language_groups", "無法提議不同語言群組標籤之間的關係。"),
+        ("tag.proposal.not_proposer", "只有提議者可以撤回提議。"),
+        ("tag.proposal.cannot_withdraw", "此提議無法撤回。"),
+        ("post.not_handle_owner", "只能以自己的名義發文。"),
+        ("post.not_found", "找不到貼文。"),
+        ("post.non_existent_tag", "包含不存在的標籤。"),
+        ("post.different_language_group", "無法附加不屬於發文者語言群組的標籤。"),
+        ("follow.followee_not_found", "找不到追蹤對象。"),
+        ("follow.own_handle", "無法追蹤自己的名義。"),
+        ("follow.limit_reached", "追蹤數已達上限。"),
+        ("follow.not_found", "尚未追蹤。"),
+        ("handle_filter.own_handle", "無法封鎖或靜音自己的名義。"),
+        ("handle_filter.limit_reached", "可封鎖或靜音的名義數已達上限。"),
+        ("handle_filter.not_found", "此名義未被封鎖或靜音。"),
+        ("auth.sign_in.account_suspended", "此帳號因違反使用條款已被停權。"),
+        ("report.target_not_found", "找不到檢舉的對象。"),
+        ("report.already_reported", "您已檢舉過此內容。"),
+        ("moderation.report_not_found", "找不到該檢舉。"),
+        ("moderation.report_already_resolved", "此檢舉已處理完畢。"),
+        ("moderation.action_not_applicable", "此處置無法套用於該檢舉對象。"),
+        ("webhook.invalid_signature", "簽章無效。"),
+        ("webhook.invalid_payload", "通知格式無效。"),
+    ];
+}
\ No newline at end of file